@@ -0,0 +1,79 @@
+//! Window setup via `sciter::HostBuilder`: a custom resource loader, debug output and a native behavior.
+
+// Specify the Windows subsystem to eliminate console window.
+// Requires Rust 1.18.
+#![windows_subsystem="windows"]
+
+extern crate sciter;
+
+use sciter::dom::event::{BEHAVIOR_EVENTS, EventReason, PHASE_MASK};
+use sciter::dom::HELEMENT;
+use sciter::host::{self, HostHandler, LOAD_RESULT, OUTPUT_SEVERITY, OUTPUT_SUBSYTEMS};
+
+const HTML: &[u8] = br#"<html>
+<head>
+	<title>HostBuilder</title>
+	<link rel="stylesheet" href="app://main.css">
+</head>
+<body>
+	<button style="behavior: counter">Click me</button>
+	<script type="text/tiscript">
+		stdout.println("document is loaded");
+	</script>
+</body>
+</html>"#;
+
+const CSS: &[u8] = b"body { font: system; } button { padding: 1em; }";
+
+
+/// Serves the `app://` resources from memory and prints the debug output.
+struct Loader;
+
+impl HostHandler for Loader {
+	fn on_data_load(&mut self, pnm: &mut host::SCN_LOAD_DATA) -> Option<LOAD_RESULT> {
+		let uri = sciter::utf::w2s(pnm.uri);
+		if uri == "app://main.css" {
+			self.data_ready(pnm.hwnd, &uri, CSS, None);
+		}
+		None
+	}
+
+	fn on_debug_output(&mut self, subsystem: OUTPUT_SUBSYTEMS, severity: OUTPUT_SEVERITY, message: &str) {
+		println!("[{:?} {:?}] {}", subsystem, severity, message.trim_end());
+	}
+}
+
+
+/// Counts button clicks.
+#[derive(Default)]
+struct Counter {
+	clicks: u32,
+}
+
+impl sciter::EventHandler for Counter {
+	fn on_event(&mut self, root: HELEMENT, _source: HELEMENT, _target: HELEMENT, code: BEHAVIOR_EVENTS, phase: PHASE_MASK, _reason: EventReason) -> bool {
+		if phase == PHASE_MASK::SINKING && code == BEHAVIOR_EVENTS::BUTTON_CLICK {
+			self.clicks += 1;
+			let mut button = sciter::Element::from(root);
+			button.set_text(&format!("Clicked {} times", self.clicks)).ok();
+		}
+		false
+	}
+}
+
+
+fn main() {
+	let mut frame = sciter::HostBuilder::new()
+		.with_window(sciter::WindowBuilder::main_window().with_size((400, 300)))
+		// global options go before the window creation
+		.global_option(sciter::RuntimeOptions::DebugMode(true))
+		// and per-window ones before the document loading
+		.window_option(sciter::window::Options::DebugMode(true))
+		.handler(Loader)
+		.behavior("counter", || Box::new(Counter::default()))
+		.build()
+		.unwrap_or_else(|error| panic!("{}", error));
+
+	frame.load_html(HTML, Some("app://index.htm"));
+	frame.run_app();
+}
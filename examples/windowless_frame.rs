@@ -0,0 +1,63 @@
+//! Driving Sciter.Lite from a host render loop: input forwarding and rendering frames into an offscreen image.
+//!
+//! Run it as `cargo run --example windowless_frame --features windowless -- sciter-sdk/bin.win/x64lite/sciter.dll`.
+
+extern crate sciter;
+
+use sciter::graphics::{Image, SaveImageEncoding};
+use sciter::types::{GFX_LAYER, HWINDOW, POINT, RECT};
+use sciter::windowless::{handle_input, handle_message, render_frame, InputEvent, Message, MouseEvent};
+use sciter::windowless::{KEYBOARD_STATES, MOUSE_BUTTONS, MOUSE_EVENTS};
+
+const HTML: &[u8] = br#"<html>
+<body style="background: gold">
+	<button style="margin: 20px">Hover me</button>
+</body>
+</html>"#;
+
+fn main() {
+	if let Some(arg) = std::env::args().nth(1) {
+		sciter::set_options(sciter::RuntimeOptions::LibraryPath(&arg)).expect("Invalid sciter-lite library specified.");
+	} else {
+		panic!("usage: cargo run --example windowless_frame --features windowless -- sciter-sdk/bin.win/x64lite/sciter.dll")
+	}
+
+	// any unique pointer identifies the engine instance
+	let engine = Box::new(0u8);
+	let scwnd = &*engine as *const _ as HWINDOW;
+
+	let (width, height) = (320, 240);
+	handle_message(scwnd, Message::Create { backend: GFX_LAYER::SKIA_CPU, transparent: false });
+	handle_message(scwnd, Message::Size { width, height });
+
+	let instance = sciter::Host::attach(scwnd);
+	instance.load_html(HTML, Some("example://frame.htm"));
+
+	let startup = std::time::Instant::now();
+	let mut image = Image::create((width, height), false).expect("Can't create an offscreen image");
+
+	// a few frames of the host loop
+	for frame in 0..3 {
+		handle_message(scwnd, Message::Heartbit {
+			milliseconds: startup.elapsed().as_millis() as u32,
+		});
+
+		// move the mouse over the button on the second frame
+		let pos = if frame == 0 { POINT { x: 0, y: 0 } } else { POINT { x: 40, y: 30 } };
+		handle_input(scwnd, InputEvent::Mouse(MouseEvent {
+			event: MOUSE_EVENTS::MOUSE_MOVE,
+			button: MOUSE_BUTTONS::NONE,
+			modifiers: KEYBOARD_STATES::from(0),
+			pos,
+		}));
+
+		let area = RECT { left: 0, top: 0, right: width as i32, bottom: height as i32 };
+		image.paint(|gfx, _| render_frame(scwnd, gfx, area)).expect("Can't render a frame");
+	}
+
+	let png = image.save(SaveImageEncoding::Png).expect("Can't encode the frame");
+	std::fs::write("frame.png", png).expect("Can't save the frame");
+	println!("saved the last frame to frame.png");
+
+	handle_message(scwnd, Message::Destroy);
+}
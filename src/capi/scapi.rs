@@ -298,7 +298,7 @@ pub struct ISciterAPI
     pub SciterReleaseGlobalAsset: extern "system" fn(pass: *mut som_asset_t) -> BOOL,
 
     pub SciterExec: extern "system" fn(appCmd: UINT, p1: UINT_PTR, p2: UINT_PTR) -> INT_PTR,
-    pub SciterWindowExec: extern "system" fn(hwnd: HWINDOW, windowCmd: UINT, p1: UINT, p2: UINT) -> INT_PTR,
+    pub SciterWindowExec: extern "system" fn(hwnd: HWINDOW, windowCmd: UINT, p1: UINT_PTR, p2: UINT_PTR) -> INT_PTR,
 
     pub SciterEGLGetProcAddress: extern "system" fn(procName: *const CHAR) -> *const VOID, // Should be `proc_ptr_t` if defined
     pub SciterEGLSendEvent: extern "system" fn(he: HELEMENT, eventCode: UINT, reason: UINT_PTR) -> SCDOM_RESULT,
@@ -86,6 +86,9 @@ pub enum BEHAVIOR_METHOD_IDENTIFIERS {
   /// `VALUE_PARAMS`
   SET_VALUE = 0xFE,
 
+  /// `XCALL_PARAMS`, call a behavior method by its name.
+  XCALL = 0xFF,
+
   /// User method identifier used in custom behaviors.
   ///
   /// All custom event codes shall be greater than this number.
@@ -114,6 +117,24 @@ pub struct VALUE_PARAMS {
   pub value: VALUE,
 }
 
+/// Parameters of the `XCALL` method: a named method of a behavior, e.g. of a built-in control.
+///
+/// Behaves like a script call of `element.method_name(argv...)`
+/// but is dispatched to behaviors only (natively implemented methods).
+#[repr(C)]
+pub struct XCALL_PARAMS {
+  /// Always `BEHAVIOR_METHOD_IDENTIFIERS::XCALL`.
+  pub method: UINT,
+  /// UTF-8 method name.
+  pub method_name: LPCSTR,
+  /// Number of arguments.
+  pub argc: UINT,
+  /// Arguments.
+  pub argv: *const VALUE,
+  /// Method result, filled by the behavior.
+  pub retval: VALUE,
+}
+
 #[repr(C)]
 pub struct SCRIPTING_METHOD_PARAMS
 {
@@ -175,6 +175,22 @@ pub use dom::event::{EventHandler, EventReason};
 pub type Result<T> = ::std::result::Result<T, SCDOM_RESULT>;
 
 
+/// Coordinate space for [`Element::rect_in`](struct.Element.html#method.rect_in).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CoordSpace {
+	/// Relative to the element itself, i.e. `(0, 0, width, height)`.
+	Element,
+	/// Relative to the immediate container.
+	Parent,
+	/// Relative to the root element of the document (or of the nearest windowed container, e.g. popup).
+	Document,
+	/// Relative to the client area of the Sciter window.
+	Window,
+	/// Screen coordinates.
+	Screen,
+}
+
+
 /// Initialize HELEMENT by nullptr.
 macro_rules! HELEMENT {
 	() => { ::std::ptr::null_mut() }
@@ -529,6 +545,54 @@ impl Element {
 		ok_or!(rc, ok)
 	}
 
+	/// Get the border box of the element in the given coordinate space.
+	///
+	/// `CoordSpace::Screen` is meant for positioning native windows (tooltips, popups, etc.) over the element:
+	///
+	/// ```rust,no_run
+	/// # use sciter::dom::{Element, CoordSpace};
+	/// # let button = Element::from_window(::std::ptr::null_mut()).unwrap();
+	/// let rc = button.rect_in(CoordSpace::Screen).unwrap();
+	/// let popup = sciter::WindowBuilder::popup_window()
+	///   .with_pos((rc.left, rc.bottom))
+	///   .with_size((rc.right - rc.left, 100))
+	///   .create();
+	/// ```
+	///
+	/// Element coordinates are in physical pixels. On Windows and Linux screen coordinates are in physical pixels too,
+	/// so no scaling is applied. On macOS screen coordinates are in points
+	/// and the rectangle is divided by the window backing scale factor.
+	pub fn rect_in(&self, space: CoordSpace) -> Result<RECT> {
+		let relative = match space {
+			CoordSpace::Element => ELEMENT_AREAS::SELF_RELATIVE,
+			CoordSpace::Parent => ELEMENT_AREAS::CONTAINER_RELATIVE,
+			CoordSpace::Document => ELEMENT_AREAS::ROOT_RELATIVE,
+			CoordSpace::Window | CoordSpace::Screen => ELEMENT_AREAS::VIEW_RELATIVE,
+		};
+		let rc = self.get_location(relative as u32 | ELEMENT_AREAS::BORDER_BOX as u32)?;
+		if space != CoordSpace::Screen {
+			return Ok(rc);
+		}
+
+		use platform::{BaseWindow, OsWindow};
+		let wnd = OsWindow::from(self.get_hwnd(true));
+		let lt = wnd.client_to_screen(POINT { x: rc.left, y: rc.top });
+		let rb = wnd.client_to_screen(POINT { x: rc.right, y: rc.bottom });
+		Ok(RECT { left: lt.x, top: lt.y, right: rb.x, bottom: rb.y })
+	}
+
+	/// Get scroll information of the element: `(scroll position, view rectangle, content size)`.
+	///
+	/// The scroll position is the offset of the element content, so a point of the content
+	/// is shown at `point - scroll_pos` relative to the element.
+	pub fn scroll_info(&self) -> Result<(POINT, RECT, SIZE)> {
+		let mut pos = POINT::default();
+		let mut view = RECT::default();
+		let mut size = SIZE::default();
+		let ok = (_API.SciterGetScrollInfo)(self.he, &mut pos, &mut view, &mut size);
+		ok_or!((pos, view, size), ok)
+	}
+
 	/// Request data download for this element.
 	pub fn request_data(&self, url: &str, data_type: RESOURCE_TYPE, initiator: Option<HELEMENT>) -> Result<()> {
 		let url = s2w!(url);
@@ -704,7 +768,7 @@ impl Element {
       },
       event::MethodParams::GetValue(retv) => {
         let mut p = VALUE_PARAMS {
-          method: SET_VALUE as u32,
+          method: GET_VALUE as u32,
           value: Default::default(),
         };
         let ok = call(&mut p as *mut _ as *mut METHOD_PARAMS);
@@ -735,6 +799,85 @@ impl Element {
     ok_or!((), ok)
   }
 
+	/// Call a behavior method by its identifier.
+	///
+	/// The `params` value is passed in the `VALUE_PARAMS` structure (`{ method, value }`)
+	/// and its content on return (possibly modified by the behavior) is the result of the call.
+	/// It works for the `GET_VALUE` and `SET_VALUE` methods of built-in behaviors
+	/// as well as for the application ones (starting from `FIRST_APPLICATION_METHOD_ID`)
+	/// handled in [`EventHandler::on_method_call`](event/trait.EventHandler.html#method.on_method_call).
+	///
+	/// See `capi::scbehavior::BEHAVIOR_METHOD_IDENTIFIERS` for the known identifiers.
+	/// Unlike [`call_method`](#method.call_method), script methods are not involved here.
+	pub fn behavior_call(&self, method_id: u32, params: &Value) -> Result<Value> {
+		use capi::scbehavior::{METHOD_PARAMS, VALUE_PARAMS};
+		let mut p = VALUE_PARAMS {
+			method: method_id,
+			value: Default::default(),
+		};
+		params.pack_to(&mut p.value);
+		let ok = (_API.SciterCallBehaviorMethod)(self.he, &mut p as *mut _ as *mut METHOD_PARAMS);
+		let rv = Value::from(&p.value);
+		(_API.ValueClear)(&mut p.value);
+		ok_or!(rv, ok)
+	}
+
+	/// Call a named method of a behavior (`XCALL`), e.g. of a built-in control.
+	///
+	/// The call is handled by the native behaviors of the element only, for example:
+	///
+	/// ```rust,no_run
+	/// # #[macro_use] extern crate sciter;
+	/// # fn main() {
+	/// # let grid = sciter::Element::from(::std::ptr::null_mut());
+	/// // tell the virtual list that the rows have been changed
+	/// grid.behavior_xcall("rowsChanged", &make_args!(10, 20)).unwrap();
+	/// # }
+	/// ```
+	///
+	/// The arguments and the result are passed in the `XCALL_PARAMS` structure:
+	/// `{ method: XCALL, method_name: LPCSTR, argc: UINT, argv: *const VALUE, retval: VALUE }`.
+	pub fn behavior_xcall(&self, name: &str, args: &[Value]) -> Result<Value> {
+		use capi::scbehavior::{METHOD_PARAMS, XCALL_PARAMS, BEHAVIOR_METHOD_IDENTIFIERS};
+		let name = s2u!(name);
+		let argv = Value::pack_args(args);
+		let mut p = XCALL_PARAMS {
+			method: BEHAVIOR_METHOD_IDENTIFIERS::XCALL as u32,
+			method_name: name.as_ptr(),
+			argc: argv.len() as UINT,
+			argv: argv.as_ptr(),
+			retval: Default::default(),
+		};
+		let ok = (_API.SciterCallBehaviorMethod)(self.he, &mut p as *mut _ as *mut METHOD_PARAMS);
+		let rv = Value::from(&p.retval);
+		(_API.ValueClear)(&mut p.retval);
+		ok_or!(rv, ok)
+	}
+
+	/// Simulate a click on the element via its behavior (e.g. of a `<button>`).
+	pub fn behavior_click(&self) -> Result<()> {
+		self.call_behavior_method(event::MethodParams::Click)
+	}
+
+	/// Get the value of the element behavior (e.g. of a built-in `<input>` or `<select>` control).
+	pub fn behavior_value(&self) -> Result<Value> {
+		let mut rv = Value::new();
+		self.call_behavior_method(event::MethodParams::GetValue(&mut rv))?;
+		Ok(rv)
+	}
+
+	/// Set the value of the element behavior (e.g. of a built-in `<input>` or `<select>` control).
+	pub fn set_behavior_value<T: Into<Value>>(&self, value: T) -> Result<()> {
+		self.call_behavior_method(event::MethodParams::SetValue(value.into()))
+	}
+
+	/// Get the `:empty` state of the element behavior, i.e. if it has no children and no text.
+	pub fn is_behavior_empty(&self) -> Result<bool> {
+		let mut rv = false;
+		self.call_behavior_method(event::MethodParams::IsEmpty(&mut rv))?;
+		Ok(rv)
+	}
+
 
 	//\name Attributes
 	/// Get number of the attributes.
@@ -1017,11 +1160,64 @@ impl Element {
 	}
 
 	/// Swap element positions.
+	///
+	/// The elements may be in different containers.
 	pub fn swap(&mut self, other: &mut Element) -> Result<()> {
 		let ok = (_API.SciterSwapElements)(self.he, other.he);
 		ok_or!((), ok)
 	}
 
+	/// Move the element right before the `reference` element (into the `reference` container).
+	///
+	/// The element is moved, not recreated, so its behaviors, state and value are preserved.
+	/// If the element (or one of its children) had focus, the focus is restored after the move.
+	pub fn move_before(&mut self, reference: &Element) -> Result<()> {
+		self.move_near(reference, false)
+	}
+
+	/// Move the element right after the `reference` element (into the `reference` container).
+	///
+	/// See [`move_before`](#method.move_before) for details.
+	pub fn move_after(&mut self, reference: &Element) -> Result<()> {
+		self.move_near(reference, true)
+	}
+
+	fn move_near(&mut self, reference: &Element, after: bool) -> Result<()> {
+		if self.he == reference.he {
+			return Ok(());
+		}
+		let inside = |el: &Element| {
+			let mut it = Some(el.clone());
+			while let Some(el) = it {
+				if el.he == self.he {
+					return true;
+				}
+				it = el.parent();
+			}
+			false
+		};
+
+		// the element can not be moved into its own subtree
+		if inside(reference) {
+			return Err(SCDOM_RESULT::INVALID_PARAMETER);
+		}
+		let mut parent = reference.parent().ok_or(SCDOM_RESULT::INVALID_PARAMETER)?;
+
+		// detaching the element drops its focus, so check if it is inside
+		let focus = Element::from_focus(self.get_hwnd(true)).ok().filter(|focus| inside(focus));
+
+		self.detach()?;
+		let index = reference.index() + after as usize;
+		parent.insert(index, self)?;
+
+		if let Some(mut focus) = focus {
+			if !focus.get_state().contains(ELEMENT_STATE_BITS::STATE_FOCUS) {
+				focus.set_state(ELEMENT_STATE_BITS::STATE_FOCUS, None, true).ok();
+			}
+		}
+		Ok(())
+	}
+
 	//\name Selectors
 
 	/// Test this element against CSS selector(s).
@@ -1310,7 +1506,6 @@ SciterGetElementNamespace
 SciterGetElementType
 SciterGetExpando
 SciterGetObject
-SciterGetScrollInfo
 SciterHidePopup
 SciterHttpRequest
 SciterIsElementEnabled
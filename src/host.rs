@@ -8,6 +8,8 @@ use capi::schandler::NativeHandler;
 use dom::{self, event::EventHandler};
 use eventhandler::*;
 use value::{Value};
+use window::{self, Window};
+use RuntimeOptions;
 
 pub use capi::scdef::{LOAD_RESULT, OUTPUT_SUBSYTEMS, OUTPUT_SEVERITY};
 pub use capi::scdef::{SCN_LOAD_DATA, SCN_DATA_LOADED, SCN_ATTACH_BEHAVIOR, SCN_INVALIDATE_RECT};
@@ -147,6 +149,7 @@ use std::cell::RefCell;
 type BehaviorList = Vec<(String, Box<dyn Fn() -> Box<dyn EventHandler>>)>;
 type SharedBehaviorList = Rc<RefCell<BehaviorList>>;
 type SharedArchive = Rc<RefCell<Option<Archive>>>;
+type HandlerSetup = Box<dyn FnOnce(&mut Window)>;
 
 #[repr(C)]
 struct HostCallback<Callback> {
@@ -410,6 +413,169 @@ impl Host {
 }
 
 
+/** Builder for a Sciter window with its host setup in one place.
+
+The settings are applied in the right order regardless of the order of the builder calls:
+
+1. [global options](#method.global_option) which are applied _before_ the window creation
+   (the library path goes first, then the Sciter library availability is checked);
+2. the window is created by the [window builder](#method.with_window);
+3. the [host handler](#method.handler) (resource loader, debug output, etc.) is installed;
+4. [window options](#method.window_option), [behaviors](#method.behavior) and [archive](#method.archive) are set up.
+
+After that the window is ready to load a document. Options which must be set before loading
+(e.g. `window::Options::DebugMode`) are safe to use here.
+
+Note that some global options affect only the windows created after them
+(e.g. `RuntimeOptions::DebugMode`, `RuntimeOptions::GfxLayer`),
+while other ones can be changed at runtime as well via [`sciter::set_options`](../fn.set_options.html).
+
+## Example:
+
+```rust,no_run
+use sciter::host::{self, HostHandler, LOAD_RESULT, OUTPUT_SEVERITY, OUTPUT_SUBSYTEMS};
+
+struct Loader;
+
+impl HostHandler for Loader {
+  fn on_data_load(&mut self, pnm: &mut host::SCN_LOAD_DATA) -> Option<LOAD_RESULT> {
+    let uri = sciter::utf::w2s(pnm.uri);
+    if uri == "app://main.css" {
+      self.data_ready(pnm.hwnd, &uri, b"body { color: green; }", None);
+    }
+    None
+  }
+
+  fn on_debug_output(&mut self, _: OUTPUT_SUBSYTEMS, severity: OUTPUT_SEVERITY, message: &str) {
+    eprintln!("[{:?}] {}", severity, message);
+  }
+}
+
+struct Button;
+impl sciter::EventHandler for Button {}
+
+let mut frame = sciter::HostBuilder::new()
+  .with_window(sciter::WindowBuilder::main_window().with_size((800, 600)))
+  .global_option(sciter::RuntimeOptions::DebugMode(true))
+  .handler(Loader)
+  .behavior("custom-button", || Box::new(Button))
+  .build()
+  .expect("Sciter is not available");
+
+frame.load_html(b"<html><link rel=stylesheet href='app://main.css'><button style='behavior: custom-button'/></html>", None);
+frame.run_app();
+```
+
+*/
+pub struct HostBuilder<'a> {
+	window: window::Builder,
+	globals: Vec<RuntimeOptions<'a>>,
+	options: Vec<window::Options>,
+	handler: Option<HandlerSetup>,
+	behaviors: BehaviorList,
+	archive: Option<&'a [u8]>,
+}
+
+impl<'a> Default for HostBuilder<'a> {
+	fn default() -> Self {
+		HostBuilder {
+			window: window::Builder::main_window(),
+			globals: Vec::new(),
+			options: Vec::new(),
+			handler: None,
+			behaviors: Vec::new(),
+			archive: None,
+		}
+	}
+}
+
+impl<'a> HostBuilder<'a> {
+
+	/// A new builder for the main window with the default host handler.
+	pub fn new() -> Self {
+		Self::default()
+	}
+
+	/// Use the specified [window builder](../window/struct.Builder.html) instead of the main window one.
+	pub fn with_window(mut self, builder: window::Builder) -> Self {
+		self.window = builder;
+		self
+	}
+
+	/// Set a [global option](../enum.RuntimeOptions.html), applied before the window creation.
+	pub fn global_option(mut self, option: RuntimeOptions<'a>) -> Self {
+		self.globals.push(option);
+		self
+	}
+
+	/// Set a [per-window option](../window/enum.Options.html), applied after the window creation
+	/// but before any document is loaded.
+	pub fn window_option(mut self, option: window::Options) -> Self {
+		self.options.push(option);
+		self
+	}
+
+	/// Set a [callback](trait.HostHandler.html) for Sciter engine events (resource loading, debug output, etc.).
+	pub fn handler<Handler: HostHandler + 'static>(mut self, handler: Handler) -> Self {
+		self.handler = Some(Box::new(move |wnd: &mut Window| wnd.sciter_handler(handler)));
+		self
+	}
+
+	/// Register a native event handler for the specified behavior name,
+	/// see [`Window::register_behavior`](../window/struct.Window.html#method.register_behavior).
+	pub fn behavior<Factory>(mut self, name: &str, factory: Factory) -> Self
+	where
+		Factory: Fn() -> Box<dyn EventHandler> + 'static
+	{
+		self.behaviors.push((name.to_owned(), Box::new(factory)));
+		self
+	}
+
+	/// Register an archive produced by `packfolder`, see [`Window::archive_handler`](../window/struct.Window.html#method.archive_handler).
+	pub fn archive(mut self, resource: &'a [u8]) -> Self {
+		self.archive = Some(resource);
+		self
+	}
+
+	/// Consume the builder and create a configured window.
+	///
+	/// Returns an error if the Sciter library is not available or if some option can not be set.
+	#[cfg_attr(feature = "windowless", deprecated = "Sciter.Lite doesn't have OS windows in windowless mode.")]
+	#[cfg_attr(feature = "windowless", allow(deprecated))]
+	pub fn build(self) -> ::std::result::Result<Window, String> {
+		// the library path must be set before any other call
+		let (paths, globals): (Vec<_>, Vec<_>) = self.globals.into_iter().partition(|option| matches!(option, RuntimeOptions::LibraryPath(_)));
+		for option in paths {
+			if let RuntimeOptions::LibraryPath(path) = option {
+				::set_library(path)?;
+			}
+		}
+
+		::check_api()?;
+
+		for option in globals {
+			::set_options(option).map_err(|_| "Could not set a global option.".to_owned())?;
+		}
+
+		let mut wnd = self.window.try_create().map_err(|error| error.to_string())?;
+
+		if let Some(setup) = self.handler {
+			setup(&mut wnd);
+		}
+		for option in self.options {
+			wnd.set_options(option).map_err(|_| "Could not set a window option.".to_owned())?;
+		}
+		for (name, factory) in self.behaviors {
+			wnd.register_behavior(&name, factory);
+		}
+		if let Some(archive) = self.archive {
+			wnd.archive_handler(archive).map_err(|_| "Invalid archive.".to_owned())?;
+		}
+		Ok(wnd)
+	}
+}
+
+
 // Sciter notification handler.
 // This comes as free function due to https://github.com/rust-lang/rust/issues/32364
 extern "system" fn _on_handle_notification<T: HostHandler>(pnm: *mut ::capi::scdef::SCITER_CALLBACK_NOTIFICATION, param: LPVOID) -> UINT
@@ -487,8 +653,10 @@ extern "system" fn _on_handle_notification<T: HostHandler>(pnm: *mut ::capi::scd
 		},
 
 		SCITER_NOTIFICATION::SC_INVALIDATE_RECT => {
-			let scnm = pnm as *const SCN_INVALIDATE_RECT;
-			me.on_invalidate(unsafe { &*scnm });
+			let scnm = unsafe { &*(pnm as *const SCN_INVALIDATE_RECT) };
+			// for `windowless::needs_redraw`
+			::windowless::invalidated(scnm.hwnd, &scnm.invalid_rect);
+			me.on_invalidate(scnm);
 			0
 		}
 
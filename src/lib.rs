@@ -78,6 +78,10 @@ folder for more complex usage and module-level sections for the guides about:
 #[macro_use] extern crate objc;
 #[macro_use] extern crate lazy_static;
 #[macro_use] extern crate bitflags;
+#[cfg(feature = "raw-window-handle")]
+extern crate raw_window_handle;
+#[cfg(feature = "serde")]
+extern crate serde;
 
 
 #[macro_use] pub mod macros;
@@ -95,8 +99,11 @@ mod eventhandler;
 pub mod dom;
 pub mod graphics;
 pub mod host;
+#[cfg(feature = "mock-api")]
+pub mod mock;
 pub mod om;
 pub mod request;
+pub mod sound;
 pub mod types;
 pub mod utf;
 pub mod value;
@@ -106,9 +113,10 @@ pub mod windowless;
 
 pub use dom::Element;
 pub use dom::event::EventHandler;
-pub use host::{Archive, Host, HostHandler};
+pub use host::{Archive, Host, HostBuilder, HostHandler};
 pub use value::{Value, FromValue};
 pub use window::Window;
+pub use platform::{App, TrayIcon};
 
 
 /// Builder pattern for window creation. See [`window::Builder`](window/struct.Builder.html) documentation.
@@ -150,7 +158,7 @@ mod ext {
 	{
 		fn LoadLibraryA(lpFileName: LPCSTR) -> LPCVOID;
     fn FreeLibrary(dll: LPCVOID) -> BOOL;
-		fn GetProcAddress(hModule: LPCVOID, lpProcName: LPCSTR) -> LPCVOID;
+		pub(crate) fn GetProcAddress(hModule: LPCVOID, lpProcName: LPCSTR) -> LPCVOID;
 	}
 
   pub fn try_load_library(permanent: bool) -> ::std::result::Result<ApiType, String> {
@@ -210,6 +218,7 @@ mod ext {
     Err(format!("error: '{}' was not found neither in PATH nor near the current executable.\n  {}", "sciter.dll", msg))
   }
 
+	#[cfg_attr(feature = "mock-api", allow(dead_code))]
 	pub unsafe fn SciterAPI() -> *const ISciterAPI {
     match try_load_library(true) {
       Ok(api) => api,
@@ -354,6 +363,7 @@ mod ext {
     Err(format!("error: '{}' was not found neither in PATH nor near the current executable.\n  {}", DLL_NAMES[0], msg))
   }
 
+  #[cfg_attr(feature = "mock-api", allow(dead_code))]
   pub fn SciterAPI() -> *const ISciterAPI {
     match try_load_library(true) {
       Ok(api) => api,
@@ -383,6 +393,10 @@ mod ext {
 #[doc(hidden)]
 #[allow(non_snake_case)]
 pub fn SciterAPI<'a>() -> &'a ISciterAPI {
+	#[cfg(feature = "mock-api")]
+	let ap = mock::api();
+
+	#[cfg(not(feature = "mock-api"))]
 	let ap = unsafe {
 		if cfg!(feature="extension") {
 			// TODO: it's not good to raise a panic inside `lazy_static!`,
@@ -423,6 +437,10 @@ pub fn SciterAPI<'a>() -> &'a ISciterAPI {
 #[doc(hidden)]
 #[allow(non_snake_case)]
 pub fn SciterAPI_unchecked<'a>() -> &'a ISciterAPI {
+	#[cfg(feature = "mock-api")]
+	let ap = mock::api();
+
+	#[cfg(not(feature = "mock-api"))]
 	let ap = unsafe {
 		if cfg!(feature="extension") {
 			EXT_API.expect("Sciter API is not available yet, call `sciter::set_api()` first.")
@@ -489,6 +507,46 @@ pub fn set_host_api(api: &'static ISciterAPI) {
 	}
 }
 
+/// Check that the Sciter library can be loaded without loading the API (and panicking on failure).
+pub(crate) fn check_api() -> ::std::result::Result<(), String> {
+	if cfg!(feature = "mock-api") {
+		return Ok(());
+	}
+	if cfg!(feature = "extension") {
+		return match unsafe { EXT_API } {
+			Some(_) => Ok(()),
+			None => Err("Sciter API is not available yet, call `sciter::set_api()` first.".to_owned()),
+		};
+	}
+
+	#[cfg(any(windows, all(feature = "dynamic", unix)))]
+	return ext::try_load_library(false).map(|_| ());
+
+	// linked statically
+	#[cfg(not(any(windows, all(feature = "dynamic", unix))))]
+	return Ok(());
+}
+
+/// Whether the Sciter library can be loaded.
+///
+/// Unlike the other functions here it does not panic when the library is missing,
+/// so an application can show a friendly message instead of crashing in [`Window::create`](window/struct.Window.html#method.create).
+pub fn is_api_available() -> bool {
+	check_api().is_ok()
+}
+
+/// Sciter engine `(major, minor)` version or `None` if the library can not be loaded.
+///
+/// Like [`is_api_available()`](fn.is_api_available.html) does not panic and skips the ABI checks,
+/// see [`api_version()`](fn.api_version.html) for the ABI version itself.
+pub fn runtime_version() -> Option<(u32, u32)> {
+	if check_api().is_err() {
+		return None;
+	}
+	let api = SciterAPI_unchecked();
+	return Some(((api.SciterVersion)(0), (api.SciterVersion)(1)));
+}
+
 /// Sciter engine version number (e.g. `0x03030200`).
 ///
 /// Note: does not return the `build` part because it doesn't fit in `0..255` byte range.
@@ -0,0 +1,879 @@
+/*! Mock Sciter API for unit testing.
+
+With the `mock-api` feature enabled, the crate uses a stub [`ISciterAPI`](../struct.ISciterAPI.html)
+implemented in pure Rust instead of the Sciter library, so the crate logic
+(flags translation, values marshalling, UTF conversions) can be tested without Sciter being installed.
+
+Every API call is recorded (per thread) and returns a canned value:
+
+* API functions returning `BOOL` succeed with `TRUE`,
+* `SCDOM_RESULT` and `VALUE_RESULT` ones return `OK`,
+* the rest return zero (or `null`).
+
+Values of primitive types (undefined, null, bool, int, float, string and so on), arrays and maps
+are functional; parsing and the other value types (objects, functions) are not supported.
+Window states set by `SciterWindowExec` are remembered per window handle.
+
+```rust,ignore
+use sciter::mock;
+
+#[test]
+fn minimal_width() {
+  mock::reset();
+  mock::set_result("SciterGetMinWidth", 320);
+
+  assert_eq!(get_min_width(), 320);
+  assert!(mock::was_called("SciterGetMinWidth"));
+}
+```
+
+Note: the Graphics and Request APIs are not mocked and panic on access.
+
+*/
+
+#![allow(non_snake_case, non_camel_case_types)]
+#![allow(unused_variables)]
+
+use std::cell::RefCell;
+use std::collections::HashMap;
+
+use capi::sctypes::*;
+use capi::scdef::*;
+use capi::scdom::*;
+use capi::scvalue::*;
+use capi::scbehavior::*;
+use capi::scgraphics::SciterGraphicsAPI;
+use capi::screquest::{SciterRequestAPI, HREQUEST, REQUEST_PARAM};
+use capi::scmsg::{SCITER_X_MSG};
+use capi::scom::{som_asset_t, som_atom_t};
+use capi::scapi::ISciterAPI;
+
+
+thread_local! {
+	static CALLS: RefCell<Vec<&'static str>> = const { RefCell::new(Vec::new()) };
+	static RESULTS: RefCell<HashMap<&'static str, i64>> = RefCell::new(HashMap::new());
+	static WINDOW_STATES: RefCell<HashMap<usize, UINT>> = RefCell::new(HashMap::new());
+}
+
+/// Clear the recorded calls and canned results of the current thread.
+pub fn reset() {
+	CALLS.with(|calls| calls.borrow_mut().clear());
+	RESULTS.with(|results| results.borrow_mut().clear());
+	WINDOW_STATES.with(|states| states.borrow_mut().clear());
+}
+
+/// Names of the API functions called by the current thread, in order.
+pub fn calls() -> Vec<&'static str> {
+	CALLS.with(|calls| calls.borrow().clone())
+}
+
+/// Whether the API function has been called by the current thread.
+pub fn was_called(name: &str) -> bool {
+	CALLS.with(|calls| calls.borrow().contains(&name))
+}
+
+/// Set a result to return from the API function, e.g. `set_result("SciterGetMinWidth", 320)`.
+///
+/// The value is converted to the function return type
+/// (and to a pointer for the functions returning handles).
+pub fn set_result(name: &'static str, value: i64) {
+	RESULTS.with(|results| results.borrow_mut().insert(name, value));
+}
+
+fn record<R: Canned>(name: &'static str) -> R {
+	CALLS.with(|calls| calls.borrow_mut().push(name));
+	let canned = RESULTS.with(|results| results.borrow().get(name).cloned());
+	R::from_canned(canned)
+}
+
+/// Conversion to the results of API functions.
+trait Canned {
+	fn from_canned(value: Option<i64>) -> Self;
+}
+
+impl Canned for () {
+	fn from_canned(_: Option<i64>) -> Self {}
+}
+
+impl Canned for VOID {
+	fn from_canned(_: Option<i64>) -> Self {
+		// `c_void` is a `u8` sized enum.
+		unsafe { ::std::mem::zeroed() }
+	}
+}
+
+macro_rules! canned_int {
+	($($t:ty = $default:expr),*) => {
+		$(
+			impl Canned for $t {
+				fn from_canned(value: Option<i64>) -> Self {
+					value.map_or($default, |v| v as $t)
+				}
+			}
+		)*
+	};
+}
+
+// `BOOL` (the only signed 32-bit and 8-bit result type) is `TRUE` by default.
+canned_int!(i8 = 1, i32 = 1, u32 = 0, u64 = 0, isize = 0, usize = 0);
+
+impl<T> Canned for *mut T {
+	fn from_canned(value: Option<i64>) -> Self {
+		value.unwrap_or(0) as usize as *mut T
+	}
+}
+
+impl<T> Canned for *const T {
+	fn from_canned(value: Option<i64>) -> Self {
+		value.unwrap_or(0) as usize as *const T
+	}
+}
+
+impl Canned for SCDOM_RESULT {
+	fn from_canned(value: Option<i64>) -> Self {
+		match value.unwrap_or(0) {
+			0 => SCDOM_RESULT::OK,
+			1 => SCDOM_RESULT::INVALID_HWND,
+			2 => SCDOM_RESULT::INVALID_HANDLE,
+			3 => SCDOM_RESULT::PASSIVE_HANDLE,
+			4 => SCDOM_RESULT::INVALID_PARAMETER,
+			-1 => SCDOM_RESULT::OK_NOT_HANDLED,
+			_ => SCDOM_RESULT::OPERATION_FAILED,
+		}
+	}
+}
+
+impl Canned for VALUE_RESULT {
+	fn from_canned(value: Option<i64>) -> Self {
+		match value.unwrap_or(0) {
+			0 => VALUE_RESULT::OK,
+			-1 => VALUE_RESULT::OK_TRUE,
+			2 => VALUE_RESULT::INCOMPATIBLE_TYPE,
+			_ => VALUE_RESULT::BAD_PARAMETER,
+		}
+	}
+}
+
+
+/* Implemented functions */
+
+extern "system" fn SciterClassName() -> LPCWSTR {
+	record::<()>("SciterClassName");
+	static NAME: [u16; 6] = [b'S' as u16, b'c' as u16, b'i' as u16, b'M' as u16, b'o' as u16, 0];
+	NAME.as_ptr()
+}
+
+extern "system" fn SciterVersion(num: u32) -> UINT {
+	let canned: Option<UINT> = RESULTS.with(|results| results.borrow().get("SciterVersion").map(|&v| v as UINT));
+	CALLS.with(|calls| calls.borrow_mut().push("SciterVersion"));
+	// 4.4.8.0 by default, or the packed `0xAABBCCDD` canned version.
+	let version = canned.unwrap_or(0x0404_0800);
+	match num {
+		0 => version >> 24,
+		1 => (version >> 16) & 0xFF,
+		2 => (version >> 8) & 0xFF,
+		_ => version & 0xFF,
+	}
+}
+
+extern "system" fn GetSciterGraphicsAPI() -> *const SciterGraphicsAPI {
+	panic!("Graphics API is not available in \"mock-api\" builds.");
+}
+
+extern "system" fn GetSciterRequestAPI() -> *const SciterRequestAPI {
+	panic!("Request API is not available in \"mock-api\" builds.");
+}
+
+fn value_type(vtype: UINT) -> VALUE_TYPE {
+	assert!(vtype <= VALUE_TYPE::T_UNKNOWN as UINT, "unknown value type");
+	unsafe { ::std::mem::transmute(vtype) }
+}
+
+fn string_of(pval: &VALUE) -> &'static [u16] {
+	if pval.t != VALUE_TYPE::T_STRING || pval.d == 0 {
+		return &[];
+	}
+	// Note: strings are leaked on purpose, since `VALUE` can be bitwise copied.
+	let chars = pval.d as usize as *const Vec<u16>;
+	unsafe { (*chars).as_slice() }
+}
+
+extern "system" fn ValueInit(pval: *mut VALUE) -> VALUE_RESULT {
+	let rv = record("ValueInit");
+	unsafe { *pval = VALUE::new() };
+	rv
+}
+
+extern "system" fn ValueClear(pval: *mut VALUE) -> VALUE_RESULT {
+	let rv = record("ValueClear");
+	unsafe { *pval = VALUE::new() };
+	rv
+}
+
+extern "system" fn ValueCompare(pval1: *const VALUE, pval2: *const VALUE) -> VALUE_RESULT {
+	record::<VALUE_RESULT>("ValueCompare");
+	let eq = unsafe { equals(&*pval1, &*pval2) };
+	if eq { VALUE_RESULT::OK_TRUE } else { VALUE_RESULT::OK }
+}
+
+extern "system" fn ValueCopy(pdst: *mut VALUE, psrc: *const VALUE) -> VALUE_RESULT {
+	let rv = record("ValueCopy");
+	unsafe { *pdst = (*psrc).clone() };
+	rv
+}
+
+extern "system" fn ValueType(pval: *const VALUE, pType: *mut UINT, pUnits: *mut UINT) -> VALUE_RESULT {
+	let rv = record("ValueType");
+	unsafe {
+		*pType = (*pval).t as UINT;
+		*pUnits = (*pval).u;
+	}
+	rv
+}
+
+extern "system" fn ValueStringData(pval: *const VALUE, pChars: *mut LPCWSTR, pNumChars: *mut UINT) -> VALUE_RESULT {
+	record::<VALUE_RESULT>("ValueStringData");
+	let pval = unsafe { &*pval };
+	if pval.t != VALUE_TYPE::T_STRING {
+		return VALUE_RESULT::INCOMPATIBLE_TYPE;
+	}
+	let chars = string_of(pval);
+	unsafe {
+		*pChars = chars.as_ptr();
+		*pNumChars = chars.len().saturating_sub(1) as UINT;
+	}
+	VALUE_RESULT::OK
+}
+
+extern "system" fn ValueStringDataSet(pval: *mut VALUE, chars: LPCWSTR, numChars: UINT, units: UINT) -> VALUE_RESULT {
+	let rv = record("ValueStringDataSet");
+	let mut copy = Vec::with_capacity(numChars as usize + 1);
+	if !chars.is_null() {
+		copy.extend_from_slice(unsafe { ::std::slice::from_raw_parts(chars, numChars as usize) });
+	}
+	copy.push(0);
+	let copy = Box::into_raw(Box::new(copy));
+	unsafe {
+		*pval = VALUE { t: VALUE_TYPE::T_STRING, u: units, d: copy as usize as UINT64 };
+	}
+	rv
+}
+
+extern "system" fn ValueIntData(pval: *const VALUE, pData: *mut INT) -> VALUE_RESULT {
+	let rv = record("ValueIntData");
+	unsafe { *pData = (*pval).d as INT };
+	rv
+}
+
+extern "system" fn ValueIntDataSet(pval: *mut VALUE, data: INT, vtype: UINT, units: UINT) -> VALUE_RESULT {
+	let rv = record("ValueIntDataSet");
+	let pval = unsafe { &mut *pval };
+	match value_type(vtype) {
+		VALUE_TYPE::T_ARRAY => make_items(pval, VALUE_TYPE::T_ARRAY, vec![(VALUE::new(), VALUE::new()); data as usize]),
+		VALUE_TYPE::T_MAP => make_items(pval, VALUE_TYPE::T_MAP, Items::new()),
+		vtype => *pval = VALUE { t: vtype, u: units, d: data as UINT as UINT64 },
+	}
+	rv
+}
+
+extern "system" fn ValueInt64Data(pval: *const VALUE, pData: *mut INT64) -> VALUE_RESULT {
+	let rv = record("ValueInt64Data");
+	unsafe { *pData = (*pval).d as INT64 };
+	rv
+}
+
+extern "system" fn ValueInt64DataSet(pval: *mut VALUE, data: INT64, vtype: UINT, units: UINT) -> VALUE_RESULT {
+	let rv = record("ValueInt64DataSet");
+	unsafe { *pval = VALUE { t: value_type(vtype), u: units, d: data as UINT64 } };
+	rv
+}
+
+extern "system" fn ValueFloatData(pval: *const VALUE, pData: *mut FLOAT_VALUE) -> VALUE_RESULT {
+	let rv = record("ValueFloatData");
+	unsafe { *pData = FLOAT_VALUE::from_bits((*pval).d) };
+	rv
+}
+
+extern "system" fn ValueFloatDataSet(pval: *mut VALUE, data: FLOAT_VALUE, vtype: UINT, units: UINT) -> VALUE_RESULT {
+	let rv = record("ValueFloatDataSet");
+	unsafe { *pval = VALUE { t: value_type(vtype), u: units, d: data.to_bits() } };
+	rv
+}
+
+
+type Items = Vec<(VALUE, VALUE)>;
+
+fn items_of(pval: &VALUE) -> Option<&'static mut Items> {
+	match pval.t {
+		VALUE_TYPE::T_ARRAY | VALUE_TYPE::T_MAP if pval.d != 0 => Some(unsafe { &mut *(pval.d as usize as *mut Items) }),
+		_ => None,
+	}
+}
+
+fn make_items(pval: &mut VALUE, vtype: VALUE_TYPE, items: Items) {
+	let items = Box::into_raw(Box::new(items));
+	*pval = VALUE { t: vtype, u: 0, d: items as usize as UINT64 };
+}
+
+fn equals(a: &VALUE, b: &VALUE) -> bool {
+	if a.t == VALUE_TYPE::T_STRING && b.t == VALUE_TYPE::T_STRING {
+		string_of(a) == string_of(b)
+	} else {
+		a.t == b.t && a.u == b.u && a.d == b.d
+	}
+}
+
+fn to_json(pval: &VALUE) -> String {
+	let join = |items: &Items, map: bool| {
+		items.iter().map(|(k, v)| if map { format!("{}:{}", to_json(k), to_json(v)) } else { to_json(v) }).collect::<Vec<_>>().join(",")
+	};
+	match pval.t {
+		VALUE_TYPE::T_UNDEFINED => "undefined".to_owned(),
+		VALUE_TYPE::T_NULL => "null".to_owned(),
+		VALUE_TYPE::T_BOOL => (if pval.d != 0 { "true" } else { "false" }).to_owned(),
+		VALUE_TYPE::T_INT => (pval.d as INT).to_string(),
+		VALUE_TYPE::T_FLOAT => FLOAT_VALUE::from_bits(pval.d).to_string(),
+		VALUE_TYPE::T_STRING => format!("{:?}", String::from_utf16_lossy(&string_of(pval)[..string_of(pval).len().saturating_sub(1)])),
+		VALUE_TYPE::T_ARRAY => format!("[{}]", items_of(pval).map(|items| join(items, false)).unwrap_or_default()),
+		VALUE_TYPE::T_MAP => format!("{{{}}}", items_of(pval).map(|items| join(items, true)).unwrap_or_default()),
+		_ => format!("{:?}", pval.t),
+	}
+}
+
+extern "system" fn ValueElementsCount(pval: *const VALUE, pn: *mut INT) -> VALUE_RESULT {
+	let rv = record("ValueElementsCount");
+	let n = items_of(unsafe { &*pval }).map_or(0, |items| items.len());
+	unsafe { *pn = n as INT };
+	rv
+}
+
+extern "system" fn ValueNthElementValue(pval: *const VALUE, n: INT, pretval: *mut VALUE) -> VALUE_RESULT {
+	let rv = record("ValueNthElementValue");
+	let item = items_of(unsafe { &*pval }).and_then(|items| items.get(n as usize)).map(|item| item.1.clone());
+	unsafe { *pretval = item.unwrap_or_default() };
+	rv
+}
+
+extern "system" fn ValueNthElementValueSet(pval: *mut VALUE, n: INT, pval_to_set: *const VALUE) -> VALUE_RESULT {
+	let rv = record("ValueNthElementValueSet");
+	let pval = unsafe { &mut *pval };
+	if items_of(pval).is_none() {
+		make_items(pval, VALUE_TYPE::T_ARRAY, Items::new());
+	}
+	let items = items_of(pval).unwrap();
+	let n = n as usize;
+	if n >= items.len() {
+		items.resize(n + 1, (VALUE::new(), VALUE::new()));
+	}
+	items[n].1 = unsafe { (*pval_to_set).clone() };
+	rv
+}
+
+extern "system" fn ValueNthElementKey(pval: *const VALUE, n: INT, pretval: *mut VALUE) -> VALUE_RESULT {
+	let rv = record("ValueNthElementKey");
+	let key = items_of(unsafe { &*pval }).and_then(|items| items.get(n as usize)).map(|item| item.0.clone());
+	unsafe { *pretval = key.unwrap_or_default() };
+	rv
+}
+
+extern "system" fn ValueEnumElements(pval: *const VALUE, penum: KeyValueCallback, param: LPVOID) -> VALUE_RESULT {
+	let rv = record("ValueEnumElements");
+	if let Some(items) = items_of(unsafe { &*pval }) {
+		for (key, val) in items.iter() {
+			if penum(param, key, val) == 0 {
+				break;
+			}
+		}
+	}
+	rv
+}
+
+extern "system" fn ValueSetValueToKey(pval: *mut VALUE, pkey: *const VALUE, pval_to_set: *const VALUE) -> VALUE_RESULT {
+	let rv = record("ValueSetValueToKey");
+	let (pval, key, val) = unsafe { (&mut *pval, (*pkey).clone(), (*pval_to_set).clone()) };
+	if pval.t != VALUE_TYPE::T_MAP || pval.d == 0 {
+		make_items(pval, VALUE_TYPE::T_MAP, Items::new());
+	}
+	let items = items_of(pval).unwrap();
+	match items.iter_mut().find(|item| equals(&item.0, &key)) {
+		Some(item) => item.1 = val,
+		None => items.push((key, val)),
+	}
+	rv
+}
+
+extern "system" fn ValueGetValueOfKey(pval: *const VALUE, pkey: *const VALUE, pretval: *mut VALUE) -> VALUE_RESULT {
+	let rv = record("ValueGetValueOfKey");
+	let key = unsafe { &*pkey };
+	let item = items_of(unsafe { &*pval }).and_then(|items| items.iter().find(|item| equals(&item.0, key))).map(|item| item.1.clone());
+	unsafe { *pretval = item.unwrap_or_default() };
+	rv
+}
+
+extern "system" fn ValueToString(pval: *mut VALUE, how: VALUE_STRING_CVT_TYPE) -> VALUE_RESULT {
+	let rv = record("ValueToString");
+	let pval = unsafe { &mut *pval };
+	if pval.t == VALUE_TYPE::T_STRING && how == VALUE_STRING_CVT_TYPE::SIMPLE {
+		return rv;
+	}
+	let json: Vec<u16> = to_json(pval).encode_utf16().collect();
+	ValueStringDataSet(pval, json.as_ptr(), json.len() as UINT, 0);
+	rv
+}
+
+extern "system" fn ValueBinaryData(pval: *const VALUE, pBytes: *mut LPCBYTE, pnBytes: *mut UINT) -> VALUE_RESULT {
+	record::<VALUE_RESULT>("ValueBinaryData");
+	let pval = unsafe { &*pval };
+	if pval.t != VALUE_TYPE::T_BYTES || pval.d == 0 {
+		return VALUE_RESULT::INCOMPATIBLE_TYPE;
+	}
+	let bytes = unsafe { &*(pval.d as usize as *const Vec<u8>) };
+	unsafe {
+		*pBytes = bytes.as_ptr();
+		*pnBytes = bytes.len() as UINT;
+	}
+	VALUE_RESULT::OK
+}
+
+extern "system" fn ValueBinaryDataSet(pval: *mut VALUE, pBytes: LPCBYTE, nBytes: UINT, vtype: UINT, units: UINT) -> VALUE_RESULT {
+	let rv = record("ValueBinaryDataSet");
+	let mut copy = Vec::with_capacity(nBytes as usize);
+	if !pBytes.is_null() {
+		copy.extend_from_slice(unsafe { ::std::slice::from_raw_parts(pBytes, nBytes as usize) });
+	}
+	let copy = Box::into_raw(Box::new(copy));
+	unsafe { *pval = VALUE { t: value_type(vtype), u: units, d: copy as usize as UINT64 } };
+	rv
+}
+
+extern "system" fn SciterWindowExec(hwnd: HWINDOW, windowCmd: UINT, p1: UINT_PTR, p2: UINT_PTR) -> INT_PTR {
+	let rv = record("SciterWindowExec");
+	// Windows remember their state, shown by default.
+	let cmd = SCITER_WINDOW_CMD::from_bits_truncate(windowCmd);
+	if cmd == SCITER_WINDOW_CMD::SCITER_WINDOW_SET_STATE {
+		WINDOW_STATES.with(|states| states.borrow_mut().insert(hwnd as usize, p1 as UINT));
+	} else if cmd == SCITER_WINDOW_CMD::SCITER_WINDOW_GET_STATE {
+		let shown = SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_SHOWN.bits();
+		return WINDOW_STATES.with(|states| states.borrow().get(&(hwnd as usize)).cloned().unwrap_or(shown)) as INT_PTR;
+	}
+	rv
+}
+
+
+/* Stubs */
+
+macro_rules! stubs {
+	($($(#[$attr:meta])* $name:ident ($($arg:ident: $t:ty),*) -> $rt:ty;)*) => {
+		$(
+			$(#[$attr])*
+			extern "system" fn $name($($arg: $t),*) -> $rt {
+				record(stringify!($name))
+			}
+		)*
+	};
+}
+
+stubs! {
+	SciterDataReady(hwnd: HWINDOW, uri: LPCWSTR, data: LPCBYTE, dataLength: UINT) -> BOOL;
+	SciterDataReadyAsync(hwnd: HWINDOW, uri: LPCWSTR, data: LPCBYTE, dataLength: UINT, requestId: HREQUEST) -> BOOL;
+	#[cfg(all(windows, not(feature = "windowless")))]
+	SciterProc(hwnd: HWINDOW, msg: UINT, wParam: WPARAM, lParam: LPARAM) -> LRESULT;
+	#[cfg(all(windows, not(feature = "windowless")))]
+	SciterProcND(hwnd: HWINDOW, msg: UINT, wParam: WPARAM, lParam: LPARAM, pbHandled: *mut BOOL) -> LRESULT;
+	SciterLoadFile(hWndSciter: HWINDOW, filename: LPCWSTR) -> BOOL;
+	SciterLoadHtml(hWndSciter: HWINDOW, html: LPCBYTE, htmlSize: UINT, baseUrl: LPCWSTR) -> BOOL;
+	SciterSetCallback(hWndSciter: HWINDOW, cb: SciterHostCallback, cbParam: LPVOID) -> VOID;
+	SciterSetMasterCSS(utf8: LPCBYTE, numBytes: UINT) -> BOOL;
+	SciterAppendMasterCSS(utf8: LPCBYTE, numBytes: UINT) -> BOOL;
+	SciterSetCSS(hWndSciter: HWINDOW, utf8: LPCBYTE, numBytes: UINT, baseUrl: LPCWSTR, mediaType: LPCWSTR) -> BOOL;
+	SciterSetMediaType(hWndSciter: HWINDOW, mediaType: LPCWSTR) -> BOOL;
+	SciterSetMediaVars(hWndSciter: HWINDOW, mediaVars: *const VALUE) -> BOOL;
+	SciterGetMinWidth(hWndSciter: HWINDOW) -> UINT;
+	SciterGetMinHeight(hWndSciter: HWINDOW, width: UINT) -> UINT;
+	SciterCall(hWnd: HWINDOW, functionName: LPCSTR, argc: UINT, argv: *const VALUE, retval: *mut VALUE) -> BOOL;
+	SciterEval(hwnd: HWINDOW, script: LPCWSTR, scriptLength: UINT, pretval: *mut VALUE) -> BOOL;
+	SciterUpdateWindow(hwnd: HWINDOW) -> VOID;
+	#[cfg(all(windows, not(feature = "windowless")))]
+	SciterTranslateMessage(lpMsg: LPMSG) -> BOOL;
+	SciterSetOption(hWnd: HWINDOW, option: SCITER_RT_OPTIONS, value: UINT_PTR) -> BOOL;
+	SciterGetPPI(hWndSciter: HWINDOW, px: *mut UINT, py: *mut UINT) -> VOID;
+	SciterGetViewExpando(hwnd: HWINDOW, pval: *mut VALUE) -> BOOL;
+	#[cfg(all(windows, not(feature = "windowless")))]
+	SciterRenderD2D(hWndSciter: HWINDOW, prt: *mut ID2D1RenderTarget) -> BOOL;
+	#[cfg(all(windows, not(feature = "windowless")))]
+	SciterD2DFactory(ppf: *mut*mut ID2D1Factory) -> BOOL;
+	#[cfg(all(windows, not(feature = "windowless")))]
+	SciterDWFactory(ppf: *mut*mut IDWriteFactory) -> BOOL;
+	SciterGraphicsCaps(pcaps: LPUINT) -> BOOL;
+	SciterSetHomeURL(hWndSciter: HWINDOW, baseUrl: LPCWSTR) -> BOOL;
+	#[cfg(all(target_os="macos", not(feature = "windowless")))]
+	SciterCreateNSView(frame: LPRECT) -> HWINDOW;
+	#[cfg(all(target_os="linux", not(feature = "windowless")))]
+	SciterCreateWidget(frame: LPRECT) -> HWINDOW;
+	#[cfg(not(feature = "windowless"))]
+	SciterCreateWindow(creationFlags: UINT, frame: LPCRECT, delegate: *const SciterWindowDelegate, delegateParam: LPVOID, parent: HWINDOW) -> HWINDOW;
+	SciterSetupDebugOutput(hwndOrNull: HWINDOW, param: LPVOID, pfOutput: DEBUG_OUTPUT_PROC) -> ();
+	Sciter_UseElement(he: HELEMENT) -> SCDOM_RESULT;
+	Sciter_UnuseElement(he: HELEMENT) -> SCDOM_RESULT;
+	SciterGetRootElement(hwnd: HWINDOW, phe: *mut HELEMENT) -> SCDOM_RESULT;
+	SciterGetFocusElement(hwnd: HWINDOW, phe: *mut HELEMENT) -> SCDOM_RESULT;
+	SciterFindElement(hwnd: HWINDOW, pt: POINT, phe: *mut HELEMENT) -> SCDOM_RESULT;
+	SciterGetChildrenCount(he: HELEMENT, count: *mut UINT) -> SCDOM_RESULT;
+	SciterGetNthChild(he: HELEMENT, n: UINT, phe: *mut HELEMENT) -> SCDOM_RESULT;
+	SciterGetParentElement(he: HELEMENT, p_parent_he: *mut HELEMENT) -> SCDOM_RESULT;
+	SciterGetElementHtmlCB(he: HELEMENT, outer: BOOL, rcv: LPCBYTE_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT;
+	SciterGetElementTextCB(he: HELEMENT, rcv: LPCWSTR_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT;
+	SciterSetElementText(he: HELEMENT, utf16: LPCWSTR, length: UINT) -> SCDOM_RESULT;
+	SciterGetAttributeCount(he: HELEMENT, p_count: LPUINT) -> SCDOM_RESULT;
+	SciterGetNthAttributeNameCB(he: HELEMENT, n: UINT, rcv: LPCSTR_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT;
+	SciterGetNthAttributeValueCB(he: HELEMENT, n: UINT, rcv: LPCWSTR_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT;
+	SciterGetAttributeByNameCB(he: HELEMENT, name: LPCSTR, rcv: LPCWSTR_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT;
+	SciterSetAttributeByName(he: HELEMENT, name: LPCSTR, value: LPCWSTR) -> SCDOM_RESULT;
+	SciterClearAttributes(he: HELEMENT) -> SCDOM_RESULT;
+	SciterGetElementIndex(he: HELEMENT, p_index: LPUINT) -> SCDOM_RESULT;
+	SciterGetElementType(he: HELEMENT, p_type: *mut LPCSTR) -> SCDOM_RESULT;
+	SciterGetElementTypeCB(he: HELEMENT, rcv: LPCSTR_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT;
+	SciterGetStyleAttributeCB(he: HELEMENT, name: LPCSTR, rcv: LPCWSTR_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT;
+	SciterSetStyleAttribute(he: HELEMENT, name: LPCSTR, value: LPCWSTR) -> SCDOM_RESULT;
+	SciterGetElementLocation(he: HELEMENT, p_location: LPRECT, areas: UINT) -> SCDOM_RESULT;
+	SciterScrollToView(he: HELEMENT, SciterScrollFlags: UINT) -> SCDOM_RESULT;
+	SciterUpdateElement(he: HELEMENT, andForceRender: BOOL) -> SCDOM_RESULT;
+	SciterRefreshElementArea(he: HELEMENT, rc: RECT) -> SCDOM_RESULT;
+	SciterSetCapture(he: HELEMENT) -> SCDOM_RESULT;
+	SciterReleaseCapture(he: HELEMENT) -> SCDOM_RESULT;
+	SciterGetElementHwnd(he: HELEMENT, p_hwnd: *mut HWINDOW, rootWindow: BOOL) -> SCDOM_RESULT;
+	SciterCombineURL(he: HELEMENT, szUrlBuffer: LPWSTR, UrlBufferSize: UINT) -> SCDOM_RESULT;
+	SciterSelectElements(he: HELEMENT, CSS_selectors: LPCSTR, callback: SciterElementCallback, param: LPVOID) -> SCDOM_RESULT;
+	SciterSelectElementsW(he: HELEMENT, CSS_selectors: LPCWSTR, callback: SciterElementCallback, param: LPVOID) -> SCDOM_RESULT;
+	SciterSelectParent(he: HELEMENT, selector: LPCSTR, depth: UINT, heFound: *mut HELEMENT) -> SCDOM_RESULT;
+	SciterSelectParentW(he: HELEMENT, selector: LPCWSTR, depth: UINT, heFound: *mut HELEMENT) -> SCDOM_RESULT;
+	SciterSetElementHtml(he: HELEMENT, html: *const BYTE, htmlLength: UINT, how: UINT) -> SCDOM_RESULT;
+	SciterGetElementUID(he: HELEMENT, puid: *mut UINT) -> SCDOM_RESULT;
+	SciterGetElementByUID(hwnd: HWINDOW, uid: UINT, phe: *mut HELEMENT) -> SCDOM_RESULT;
+	SciterShowPopup(hePopup: HELEMENT, heAnchor: HELEMENT, placement: UINT) -> SCDOM_RESULT;
+	SciterShowPopupAt(hePopup: HELEMENT, pos: POINT, placement: UINT) -> SCDOM_RESULT;
+	SciterHidePopup(he: HELEMENT) -> SCDOM_RESULT;
+	SciterGetElementState(he: HELEMENT, pstateBits: *mut UINT) -> SCDOM_RESULT;
+	SciterSetElementState(he: HELEMENT, stateBitsToSet: UINT, stateBitsToClear: UINT, updateView: BOOL) -> SCDOM_RESULT;
+	SciterCreateElement(tagname: LPCSTR, textOrNull: LPCWSTR,  phe: *mut HELEMENT) -> SCDOM_RESULT;
+	SciterCloneElement(he: HELEMENT,  phe: *mut HELEMENT) -> SCDOM_RESULT;
+	SciterInsertElement(he: HELEMENT, hparent: HELEMENT, index: UINT) -> SCDOM_RESULT;
+	SciterDetachElement(he: HELEMENT) -> SCDOM_RESULT;
+	SciterDeleteElement(he: HELEMENT) -> SCDOM_RESULT;
+	SciterSetTimer(he: HELEMENT, milliseconds: UINT, timer_id: UINT_PTR) -> SCDOM_RESULT;
+	SciterDetachEventHandler(he: HELEMENT, pep: ElementEventProc, tag: LPVOID) -> SCDOM_RESULT;
+	SciterAttachEventHandler(he: HELEMENT, pep: ElementEventProc, tag: LPVOID) -> SCDOM_RESULT;
+	SciterWindowAttachEventHandler(hwndLayout: HWINDOW, pep: ElementEventProc, tag: LPVOID, subscription: UINT) -> SCDOM_RESULT;
+	SciterWindowDetachEventHandler(hwndLayout: HWINDOW, pep: ElementEventProc, tag: LPVOID) -> SCDOM_RESULT;
+	SciterSendEvent(he: HELEMENT, appEventCode: UINT, heSource: HELEMENT, reason: UINT_PTR,  handled: *mut BOOL) -> SCDOM_RESULT;
+	SciterPostEvent(he: HELEMENT, appEventCode: UINT, heSource: HELEMENT, reason: UINT_PTR) -> SCDOM_RESULT;
+	SciterCallBehaviorMethod(he: HELEMENT, params: *const METHOD_PARAMS) -> SCDOM_RESULT;
+	SciterRequestElementData(he: HELEMENT, url: LPCWSTR, dataType: UINT, initiator: HELEMENT) -> SCDOM_RESULT;
+	SciterHttpRequest(he: HELEMENT, url: LPCWSTR, dataType: UINT, requestType: UINT, requestParams: *const REQUEST_PARAM, nParams: UINT) -> SCDOM_RESULT;
+	SciterGetScrollInfo(he: HELEMENT, scrollPos: LPPOINT, viewRect: LPRECT, contentSize: LPSIZE) -> SCDOM_RESULT;
+	SciterSetScrollPos(he: HELEMENT, scrollPos: POINT, smooth: BOOL) -> SCDOM_RESULT;
+	SciterGetElementIntrinsicWidths(he: HELEMENT, pMinWidth: *mut INT, pMaxWidth: *mut INT) -> SCDOM_RESULT;
+	SciterGetElementIntrinsicHeight(he: HELEMENT, forWidth: INT, pHeight: *mut INT) -> SCDOM_RESULT;
+	SciterIsElementVisible(he: HELEMENT, pVisible: *mut BOOL) -> SCDOM_RESULT;
+	SciterIsElementEnabled(he: HELEMENT, pEnabled: *mut BOOL) -> SCDOM_RESULT;
+	SciterSortElements(he: HELEMENT, firstIndex: UINT, lastIndex: UINT, cmpFunc: *mut ELEMENT_COMPARATOR, cmpFuncParam: LPVOID) -> SCDOM_RESULT;
+	SciterSwapElements(he1: HELEMENT, he2: HELEMENT) -> SCDOM_RESULT;
+	SciterTraverseUIEvent(evt: UINT, eventCtlStruct: LPVOID, bOutProcessed: *mut BOOL) -> SCDOM_RESULT;
+	SciterCallScriptingMethod(he: HELEMENT, name: LPCSTR, argv: *const VALUE, argc: UINT, retval: *mut VALUE) -> SCDOM_RESULT;
+	SciterCallScriptingFunction(he: HELEMENT, name: LPCSTR, argv: *const VALUE, argc: UINT, retval: *mut VALUE) -> SCDOM_RESULT;
+	SciterEvalElementScript(he: HELEMENT, script: LPCWSTR, scriptLength: UINT, retval: *mut VALUE) -> SCDOM_RESULT;
+	SciterAttachHwndToElement(he: HELEMENT, hwnd: HWINDOW) -> SCDOM_RESULT;
+	SciterControlGetType(he: HELEMENT,  pType: *mut UINT) -> SCDOM_RESULT;
+	SciterGetValue(he: HELEMENT, pval: *mut VALUE) -> SCDOM_RESULT;
+	SciterSetValue(he: HELEMENT, pval: *const VALUE) -> SCDOM_RESULT;
+	SciterGetExpando(he: HELEMENT, pval: *mut VALUE, forceCreation: BOOL) -> SCDOM_RESULT;
+	SciterGetHighlightedElement(hwnd: HWINDOW, phe: *mut HELEMENT) -> SCDOM_RESULT;
+	SciterSetHighlightedElement(hwnd: HWINDOW, he: HELEMENT) -> SCDOM_RESULT;
+	SciterNodeAddRef(hn: HNODE) -> SCDOM_RESULT;
+	SciterNodeRelease(hn: HNODE) -> SCDOM_RESULT;
+	SciterNodeCastFromElement(he: HELEMENT, phn: *mut HNODE) -> SCDOM_RESULT;
+	SciterNodeCastToElement(hn: HNODE, he: *mut HELEMENT) -> SCDOM_RESULT;
+	SciterNodeFirstChild(hn: HNODE, phn: *mut HNODE) -> SCDOM_RESULT;
+	SciterNodeLastChild(hn: HNODE, phn: *mut HNODE) -> SCDOM_RESULT;
+	SciterNodeNextSibling(hn: HNODE, phn: *mut HNODE) -> SCDOM_RESULT;
+	SciterNodePrevSibling(hn: HNODE, phn: *mut HNODE) -> SCDOM_RESULT;
+	SciterNodeParent(hnode: HNODE, pheParent: *mut HELEMENT) -> SCDOM_RESULT;
+	SciterNodeNthChild(hnode: HNODE, n: UINT, phn: *mut HNODE) -> SCDOM_RESULT;
+	SciterNodeChildrenCount(hnode: HNODE, pn: *mut UINT) -> SCDOM_RESULT;
+	SciterNodeType(hnode: HNODE, pNodeType: *mut UINT) -> SCDOM_RESULT;
+	SciterNodeGetText(hnode: HNODE, rcv: LPCWSTR_RECEIVER, rcv_param: LPVOID) -> SCDOM_RESULT;
+	SciterNodeSetText(hnode: HNODE, text: LPCWSTR, textLength: UINT) -> SCDOM_RESULT;
+	SciterNodeInsert(hnode: HNODE, how: UINT , what: HNODE) -> SCDOM_RESULT;
+	SciterNodeRemove(hnode: HNODE, finalize: BOOL) -> SCDOM_RESULT;
+	SciterCreateTextNode(text: LPCWSTR, textLength: UINT, phnode: *mut HNODE) -> SCDOM_RESULT;
+	SciterCreateCommentNode(text: LPCWSTR, textLength: UINT, phnode: *mut HNODE) -> SCDOM_RESULT;
+	ValueIsolate(pdst: *mut VALUE) -> VALUE_RESULT;
+	ValueFromString(pval: *mut VALUE, str: LPCWSTR, strLength: UINT, how: VALUE_STRING_CVT_TYPE) -> UINT;
+	ValueInvoke(pval: *const VALUE, pthis: *mut VALUE, argc: UINT, argv: *const VALUE, pretval: *mut VALUE, url: LPCWSTR) -> VALUE_RESULT;
+	ValueNativeFunctorSet(pval: *mut VALUE, pinvoke: NATIVE_FUNCTOR_INVOKE, prelease: NATIVE_FUNCTOR_RELEASE, tag: LPVOID) -> VALUE_RESULT;
+	ValueIsNativeFunctor(pval: *const VALUE) -> BOOL;
+	SciterOpenArchive(archiveData: LPCBYTE, archiveDataLength: UINT) -> HSARCHIVE;
+	SciterGetArchiveItem(harc: HSARCHIVE, path: LPCWSTR, pdata: *mut LPCBYTE, pdataLength: *mut UINT) -> BOOL;
+	SciterCloseArchive(harc: HSARCHIVE) -> BOOL;
+	SciterFireEvent(evt: *const BEHAVIOR_EVENT_PARAMS, post: BOOL, handled: *mut BOOL) -> SCDOM_RESULT;
+	SciterGetCallbackParam(hwnd: HWINDOW) -> LPVOID;
+	SciterPostCallback(hwnd: HWINDOW, wparam: UINT_PTR, lparam: UINT_PTR, timeoutms: UINT) -> UINT_PTR;
+	#[cfg(all(windows, not(feature = "windowless")))]
+	SciterCreateOnDirectXWindow(hwnd: HWINDOW, pSwapChain: *mut IDXGISwapChain) -> BOOL;
+	#[cfg(all(windows, not(feature = "windowless")))]
+	SciterRenderOnDirectXWindow(hwnd: HWINDOW, elementToRenderOrNull: HELEMENT, frontLayer: BOOL) -> BOOL;
+	#[cfg(all(windows, not(feature = "windowless")))]
+	SciterRenderOnDirectXTexture(hwnd: HWINDOW, elementToRenderOrNull: HELEMENT, surface: *mut IDXGISurface) -> BOOL;
+	SciterProcX(hwnd: HWINDOW, msg: *const SCITER_X_MSG) -> BOOL;
+	SciterAtomValue(name: LPCSTR) -> som_atom_t;
+	SciterAtomNameCB(atomv: som_atom_t, rcv: LPCSTR_RECEIVER, rcv_param: LPVOID) -> BOOL;
+	SciterSetGlobalAsset(pass: *mut som_asset_t) -> BOOL;
+	SciterGetElementAsset(el: HELEMENT, nameAtom: som_atom_t, ppass: *mut *mut som_asset_t) -> SCDOM_RESULT;
+	SciterSetVariable(hwndOrNull: HWINDOW, path: LPCSTR, pvalToSet: *const VALUE) -> SCDOM_RESULT;
+	SciterGetVariable(hwndOrNull: HWINDOW, path: LPCSTR, pvalToGet: *mut VALUE) -> SCDOM_RESULT;
+	SciterElementUnwrap(pval: *const VALUE, ppElement: *mut HELEMENT) -> SCDOM_RESULT;
+	SciterElementWrap(pval: *mut VALUE, pElement: HELEMENT) -> SCDOM_RESULT;
+	SciterNodeUnwrap(pval: *const VALUE, ppNode: *mut HNODE) -> SCDOM_RESULT;
+	SciterNodeWrap(pval: *mut VALUE, pNode: HNODE) -> SCDOM_RESULT;
+	SciterReleaseGlobalAsset(pass: *mut som_asset_t) -> BOOL;
+	SciterExec(appCmd: UINT, p1: UINT_PTR, p2: UINT_PTR) -> INT_PTR;
+	SciterEGLGetProcAddress(procName: *const CHAR) -> *const VOID;
+	SciterEGLSendEvent(he: HELEMENT, eventCode: UINT, reason: UINT_PTR) -> SCDOM_RESULT;
+	SciterRequestAnimationFrameEvent(he: HELEMENT, eventCode: UINT, reason: UINT_PTR) -> SCDOM_RESULT;
+}
+
+
+/// Get the mock API table.
+pub(crate) fn api() -> &'static ISciterAPI {
+	lazy_static! {
+		static ref API: ISciterAPI = ISciterAPI {
+			version: if cfg!(feature = "windowless") { 0x0001_0001 } else { 0x0000_0005 },
+
+			SciterClassName,
+			SciterVersion,
+			SciterDataReady,
+			SciterDataReadyAsync,
+			#[cfg(all(windows, not(feature = "windowless")))]
+			SciterProc,
+			#[cfg(not(all(windows, not(feature = "windowless"))))]
+			SciterProc: None,
+			#[cfg(all(windows, not(feature = "windowless")))]
+			SciterProcND,
+			#[cfg(not(all(windows, not(feature = "windowless"))))]
+			SciterProcND: None,
+			SciterLoadFile,
+			SciterLoadHtml,
+			SciterSetCallback,
+			SciterSetMasterCSS,
+			SciterAppendMasterCSS,
+			SciterSetCSS,
+			SciterSetMediaType,
+			SciterSetMediaVars,
+			SciterGetMinWidth,
+			SciterGetMinHeight,
+			SciterCall,
+			SciterEval,
+			SciterUpdateWindow,
+			#[cfg(all(windows, not(feature = "windowless")))]
+			SciterTranslateMessage,
+			#[cfg(not(all(windows, not(feature = "windowless"))))]
+			SciterTranslateMessage: None,
+			SciterSetOption,
+			SciterGetPPI,
+			SciterGetViewExpando,
+			#[cfg(all(windows, not(feature = "windowless")))]
+			SciterRenderD2D,
+			#[cfg(not(all(windows, not(feature = "windowless"))))]
+			SciterRenderD2D: None,
+			#[cfg(all(windows, not(feature = "windowless")))]
+			SciterD2DFactory,
+			#[cfg(not(all(windows, not(feature = "windowless"))))]
+			SciterD2DFactory: None,
+			#[cfg(all(windows, not(feature = "windowless")))]
+			SciterDWFactory,
+			#[cfg(not(all(windows, not(feature = "windowless"))))]
+			SciterDWFactory: None,
+			SciterGraphicsCaps,
+			SciterSetHomeURL,
+			#[cfg(all(target_os="macos", not(feature = "windowless")))]
+			SciterCreateNSView,
+			#[cfg(not(all(target_os="macos", not(feature = "windowless"))))]
+			SciterCreateNSView: None,
+			#[cfg(all(target_os="linux", not(feature = "windowless")))]
+			SciterCreateWidget,
+			#[cfg(not(all(target_os="linux", not(feature = "windowless"))))]
+			SciterCreateWidget: None,
+			#[cfg(not(feature = "windowless"))]
+			SciterCreateWindow,
+			#[cfg(feature = "windowless")]
+			SciterCreateWindow: None,
+			SciterSetupDebugOutput,
+			Sciter_UseElement,
+			Sciter_UnuseElement,
+			SciterGetRootElement,
+			SciterGetFocusElement,
+			SciterFindElement,
+			SciterGetChildrenCount,
+			SciterGetNthChild,
+			SciterGetParentElement,
+			SciterGetElementHtmlCB,
+			SciterGetElementTextCB,
+			SciterSetElementText,
+			SciterGetAttributeCount,
+			SciterGetNthAttributeNameCB,
+			SciterGetNthAttributeValueCB,
+			SciterGetAttributeByNameCB,
+			SciterSetAttributeByName,
+			SciterClearAttributes,
+			SciterGetElementIndex,
+			SciterGetElementType,
+			SciterGetElementTypeCB,
+			SciterGetStyleAttributeCB,
+			SciterSetStyleAttribute,
+			SciterGetElementLocation,
+			SciterScrollToView,
+			SciterUpdateElement,
+			SciterRefreshElementArea,
+			SciterSetCapture,
+			SciterReleaseCapture,
+			SciterGetElementHwnd,
+			SciterCombineURL,
+			SciterSelectElements,
+			SciterSelectElementsW,
+			SciterSelectParent,
+			SciterSelectParentW,
+			SciterSetElementHtml,
+			SciterGetElementUID,
+			SciterGetElementByUID,
+			SciterShowPopup,
+			SciterShowPopupAt,
+			SciterHidePopup,
+			SciterGetElementState,
+			SciterSetElementState,
+			SciterCreateElement,
+			SciterCloneElement,
+			SciterInsertElement,
+			SciterDetachElement,
+			SciterDeleteElement,
+			SciterSetTimer,
+			SciterDetachEventHandler,
+			SciterAttachEventHandler,
+			SciterWindowAttachEventHandler,
+			SciterWindowDetachEventHandler,
+			SciterSendEvent,
+			SciterPostEvent,
+			SciterCallBehaviorMethod,
+			SciterRequestElementData,
+			SciterHttpRequest,
+			SciterGetScrollInfo,
+			SciterSetScrollPos,
+			SciterGetElementIntrinsicWidths,
+			SciterGetElementIntrinsicHeight,
+			SciterIsElementVisible,
+			SciterIsElementEnabled,
+			SciterSortElements,
+			SciterSwapElements,
+			SciterTraverseUIEvent,
+			SciterCallScriptingMethod,
+			SciterCallScriptingFunction,
+			SciterEvalElementScript,
+			SciterAttachHwndToElement,
+			SciterControlGetType,
+			SciterGetValue,
+			SciterSetValue,
+			SciterGetExpando,
+			SciterGetObject: None,
+			SciterGetElementNamespace: None,
+			SciterGetHighlightedElement,
+			SciterSetHighlightedElement,
+			SciterNodeAddRef,
+			SciterNodeRelease,
+			SciterNodeCastFromElement,
+			SciterNodeCastToElement,
+			SciterNodeFirstChild,
+			SciterNodeLastChild,
+			SciterNodeNextSibling,
+			SciterNodePrevSibling,
+			SciterNodeParent,
+			SciterNodeNthChild,
+			SciterNodeChildrenCount,
+			SciterNodeType,
+			SciterNodeGetText,
+			SciterNodeSetText,
+			SciterNodeInsert,
+			SciterNodeRemove,
+			SciterCreateTextNode,
+			SciterCreateCommentNode,
+			ValueInit,
+			ValueClear,
+			ValueCompare,
+			ValueCopy,
+			ValueIsolate,
+			ValueType,
+			ValueStringData,
+			ValueStringDataSet,
+			ValueIntData,
+			ValueIntDataSet,
+			ValueInt64Data,
+			ValueInt64DataSet,
+			ValueFloatData,
+			ValueFloatDataSet,
+			ValueBinaryData,
+			ValueBinaryDataSet,
+			ValueElementsCount,
+			ValueNthElementValue,
+			ValueNthElementValueSet,
+			ValueNthElementKey,
+			ValueEnumElements,
+			ValueSetValueToKey,
+			ValueGetValueOfKey,
+			ValueToString,
+			ValueFromString,
+			ValueInvoke,
+			ValueNativeFunctorSet,
+			ValueIsNativeFunctor,
+			reserved1: None,
+			reserved2: None,
+			reserved3: None,
+			reserved4: None,
+			SciterOpenArchive,
+			SciterGetArchiveItem,
+			SciterCloseArchive,
+			SciterFireEvent,
+			SciterGetCallbackParam,
+			SciterPostCallback,
+			GetSciterGraphicsAPI,
+			GetSciterRequestAPI,
+			#[cfg(all(windows, not(feature = "windowless")))]
+			SciterCreateOnDirectXWindow,
+			#[cfg(not(all(windows, not(feature = "windowless"))))]
+			SciterCreateOnDirectXWindow: None,
+			#[cfg(all(windows, not(feature = "windowless")))]
+			SciterRenderOnDirectXWindow,
+			#[cfg(not(all(windows, not(feature = "windowless"))))]
+			SciterRenderOnDirectXWindow: None,
+			#[cfg(all(windows, not(feature = "windowless")))]
+			SciterRenderOnDirectXTexture,
+			#[cfg(not(all(windows, not(feature = "windowless"))))]
+			SciterRenderOnDirectXTexture: None,
+			SciterProcX,
+			SciterAtomValue,
+			SciterAtomNameCB,
+			SciterSetGlobalAsset,
+			SciterGetElementAsset,
+			SciterSetVariable,
+			SciterGetVariable,
+			SciterElementUnwrap,
+			SciterElementWrap,
+			SciterNodeUnwrap,
+			SciterNodeWrap,
+			SciterReleaseGlobalAsset,
+			SciterExec,
+			SciterWindowExec,
+			SciterEGLGetProcAddress,
+			SciterEGLSendEvent,
+			SciterRequestAnimationFrameEvent,
+		};
+	}
+	return &API;
+}
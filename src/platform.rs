@@ -1,337 +1,1428 @@
 //! Platform-dependent windows support.
 
+use capi::scdom::ELEMENT_STATE_BITS;
 use capi::sctypes::*;
+use dom::Element;
+use std::cell::{Cell, RefCell};
+use std::collections::{HashMap, HashSet};
+use std::path::PathBuf;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+use window::{Accelerator, BackdropKind, CursorKind, GeometryUpdate, HitTestResult, MonitorInfo, MouseButtons, ProgressState, Theme, Window, WindowError, WindowPlacement, WindowState};
+
+/// A closure to run on the UI thread, see `BaseWindow::post_task`.
+pub type UiTask = Box<dyn FnOnce() + Send>;
+
+/// How often `BaseWindow::run_app_with` calls its closure while there are no events, in milliseconds.
+const IDLE_INTERVAL: u32 = 16;
+
 
 pub trait BaseWindow {
 	fn create(&mut self, rc: RECT, flags: UINT, parent: HWINDOW) -> HWINDOW;
+	fn try_create(&mut self, rc: RECT, flags: UINT, parent: HWINDOW) -> Result<HWINDOW, WindowError>;
+	fn create_with_state(&mut self, rc: RECT, flags: UINT, parent: HWINDOW, state: WindowState) -> HWINDOW;
+	fn create_on_monitor(&mut self, monitor_index: usize, size: (i32, i32), flags: UINT, parent: HWINDOW) -> HWINDOW;
+	fn try_create_offscreen(&mut self, size: (i32, i32)) -> Result<HWINDOW, WindowError>;
+	fn create_hidden(&mut self, flags: UINT, parent: HWINDOW) -> HWINDOW;
 
 	fn get_hwnd(&self) -> HWINDOW;
+	fn release(&mut self);
+	fn set_parent(&mut self, parent: HWINDOW);
 
 	fn collapse(&self, hide: bool);
 	fn expand(&self, maximize: bool);
+	fn show(&self);
+	fn hide(&self);
+	fn minimize(&self);
+	fn maximize(&self);
+	fn restore(&self);
+	fn activate(&self);
 	fn dismiss(&self);
+	fn request_close(&self);
+	fn on_close<F: Fn() -> bool + 'static>(&mut self, callback: F);
+	fn on_file_drop<F: Fn(Vec<PathBuf>) + 'static>(&mut self, callback: F);
+	fn set_restore_focus(&mut self, enable: bool);
+	fn on_resize<F: Fn(i32, i32) + 'static>(&mut self, callback: F);
+	fn on_move<F: Fn(i32, i32) + 'static>(&mut self, callback: F);
+	fn on_dpi_changed<F: Fn(f32) + 'static>(&mut self, callback: F);
+	fn on_monitor_changed<F: Fn(MonitorInfo, Option<f32>) + 'static>(&mut self, callback: F);
+	fn on_theme_changed<F: Fn(Theme) + 'static>(&mut self, callback: F);
 
 	fn set_title(&mut self, title: &str);
 	fn get_title(&self) -> String;
+	fn set_automation_id(&mut self, id: &str);
+	fn set_accessible_name(&mut self, name: &str);
+
+	fn set_icon(&mut self, image: &[u8]);
+
+	fn move_to(&self, x: i32, y: i32);
+	fn resize(&self, width: i32, height: i32);
+	fn set_rect(&self, rc: RECT);
+	fn update_geometry(&self, changes: GeometryUpdate);
+	fn get_rect(&self) -> RECT;
+
+	fn set_min_size(&self, width: i32, height: i32);
+	fn set_max_size(&self, width: i32, height: i32);
+	fn sync_min_size_from_content(&self, track: bool);
+	fn center(&self);
+	fn current_monitor(&self) -> Option<MonitorInfo>;
+	fn get_refresh_rate(&self) -> Option<f32>;
+	fn save_placement(&self) -> WindowPlacement;
+	fn apply_placement(&self, placement: &WindowPlacement);
+
+	fn set_fullscreen(&self, enable: bool);
+	fn set_topmost(&self, enable: bool);
+	fn set_frameless(&self, enable: bool);
+	fn set_resizable(&self, enable: bool);
+	fn set_shadow(&self, enable: bool);
+	fn set_backdrop(&self, kind: BackdropKind);
+	fn set_background_color(&mut self, color: u32);
+	fn enable_touch(&mut self, enable: bool);
+	fn is_resizable(&self) -> bool;
+	fn begin_drag(&self);
+	fn set_hit_test_callback<F: Fn(i32, i32) -> HitTestResult + 'static>(&mut self, callback: F);
+	fn request_attention(&self, urgent: bool);
+	fn set_progress(&self, state: ProgressState, value: f32);
+	fn set_thumbnail_bitmap(&self, png: &[u8]);
+	fn set_live_preview_rect(&self, rc: RECT);
+	fn set_skip_taskbar(&self, skip: bool);
+	fn set_app_id(&self, app_id: &str);
+	fn set_opacity(&self, alpha: f32);
+	fn set_cursor(&self, cursor: CursorKind);
+	fn set_cursor_clip(&self, enable: bool);
+	fn cursor_position(&self) -> POINT;
+	fn mouse_buttons(&self) -> MouseButtons;
+	fn register_accelerator<F: Fn() -> bool + 'static>(&mut self, accelerator: Accelerator, callback: F);
+	fn snapshot(&self) -> Result<Vec<u8>, ()>;
+	fn get_state(&self) -> WindowState;
+	fn is_visible(&self) -> bool;
+	fn is_focused(&self) -> bool;
+	fn get_dpi_scale(&self) -> f32;
+	fn set_zoom(&self, factor: f32);
+	fn get_zoom(&self) -> f32;
+	fn last_input_time(&self) -> Duration;
+
+	fn clipboard_set_text(&self, text: &str);
+	fn clipboard_get_text(&self) -> Option<String>;
+
+	fn client_to_screen(&self, pt: POINT) -> POINT;
+
+	fn set_transparent_background(&self);
 
 	fn run_app(&self);
+	fn run_app_with<F: FnMut() + 'static>(&self, idle: F);
+	fn run_modal(&self, parent: &OsWindow);
+	fn pump_events(&self) -> bool;
+	fn run_until_idle(&self, settle: Duration, timeout: Duration) -> bool;
 	fn quit_app(&self);
+	fn post_quit(&self);
+	fn post_task(&self, task: UiTask);
 }
 
 #[cfg(windows)]
 mod windows {
 
+	use capi::scbehavior::KEYBOARD_STATES;
 	use capi::scdef::*;
+	use capi::scmsg::key_codes;
 	use capi::sctypes::*;
 	use _API;
+	use ext::GetProcAddress;
+
+	use super::{BaseWindow, UiTask};
+	use std::cell::{Cell, RefCell};
+	use std::collections::HashMap;
+	use std::path::PathBuf;
+	use std::rc::Rc;
+	use std::sync::Mutex;
+	use std::time::Duration;
+	use window::{Accelerator, BackdropKind, CreateHook, CursorKind, GeometryUpdate, HitTestResult, MonitorInfo, MouseButtons, ProgressState, Theme, WindowCreateParams, WindowError, WindowPlacement, WindowState, WindowStyle, ZOrder};
 
 	#[link(name = "user32")]
 	extern "system" {
 		fn ShowWindow(hwnd: HWINDOW, show: INT) -> BOOL;
 		fn PostMessageW(hwnd: HWINDOW, msg: UINT, w: WPARAM, l: LPARAM) -> BOOL;
+		fn DestroyWindow(hwnd: HWINDOW) -> BOOL;
+		fn PeekMessageW(msg: LPMSG, hwnd: HWINDOW, min: UINT, max: UINT, remove: UINT) -> BOOL;
 		fn SetWindowTextW(hwnd: HWINDOW, s: LPCWSTR) -> BOOL;
 		fn GetWindowTextLengthW(hwnd: HWINDOW) -> INT;
 		fn GetWindowTextW(hwnd: HWINDOW, s: LPWSTR, l: INT) -> INT;
+		fn ClientToScreen(hwnd: HWINDOW, pt: LPPOINT) -> BOOL;
+		fn SetWindowPos(hwnd: HWINDOW, after: HWINDOW, x: INT, y: INT, cx: INT, cy: INT, flags: UINT) -> BOOL;
+		fn GetWindowRect(hwnd: HWINDOW, rc: LPRECT) -> BOOL;
+		fn IsIconic(hwnd: HWINDOW) -> BOOL;
+		fn GetWindowPlacement(hwnd: HWINDOW, wp: *mut WINDOWPLACEMENT) -> BOOL;
+		fn SetWindowPlacement(hwnd: HWINDOW, wp: *const WINDOWPLACEMENT) -> BOOL;
+		fn MonitorFromWindow(hwnd: HWINDOW, flags: UINT) -> LPVOID;
+		fn MonitorFromRect(rc: *const RECT, flags: UINT) -> LPVOID;
+		fn IsWindowVisible(hwnd: HWINDOW) -> BOOL;
+		fn SendMessageW(hwnd: HWINDOW, msg: UINT, w: WPARAM, l: LPARAM) -> LRESULT;
+		fn GetClassInfoExW(instance: LPVOID, class: LPCWSTR, info: *mut WNDCLASSEXW) -> BOOL;
+		fn SetMenu(hwnd: HWINDOW, menu: LPVOID) -> BOOL;
+		fn CreateIconFromResourceEx(data: LPCBYTE, size: UINT, icon: BOOL, version: UINT, cx: INT, cy: INT, flags: UINT) -> LPVOID;
+		fn DestroyIcon(icon: LPVOID) -> BOOL;
+		fn SetLayeredWindowAttributes(hwnd: HWINDOW, key: UINT, alpha: BYTE, flags: UINT) -> BOOL;
+		fn GetMonitorInfoW(monitor: LPVOID, info: *mut MONITORINFO) -> BOOL;
+		fn EnumDisplaySettingsW(device: LPCWSTR, mode: UINT, devmode: *mut DEVMODEW) -> BOOL;
 		fn GetMessageW(msg: LPMSG, hwnd: HWINDOW, min: UINT, max: UINT) -> BOOL;
 		fn DispatchMessageW(msg: LPMSG) -> LRESULT;
 		fn TranslateMessage(msg: LPMSG) -> BOOL;
 		fn PostQuitMessage(code: INT);
+		fn EnableWindow(hwnd: HWINDOW, enable: BOOL) -> BOOL;
+		fn IsWindow(hwnd: HWINDOW) -> BOOL;
+		fn SetForegroundWindow(hwnd: HWINDOW) -> BOOL;
+		fn SetParent(hwnd: HWINDOW, parent: HWINDOW) -> HWINDOW;
+		fn GetParent(hwnd: HWINDOW) -> HWINDOW;
+		fn GetKeyState(key: INT) -> i16;
+		fn MsgWaitForMultipleObjects(count: UINT, handles: *const LPVOID, wait_all: BOOL, millis: UINT, wake_mask: UINT) -> UINT;
 	}
 
-	#[link(name = "ole32")]
+	const SWP_NOSIZE: UINT = 0x0001;
+	const SWP_NOMOVE: UINT = 0x0002;
+	const SWP_NOZORDER: UINT = 0x0004;
+	const SWP_NOACTIVATE: UINT = 0x0010;
+
+	#[cfg(target_pointer_width = "64")]
+	#[link(name = "user32")]
 	extern "system" {
-		fn OleInitialize(pv: LPCVOID) -> i32; // HRESULT
+		fn GetWindowLongPtrW(hwnd: HWINDOW, index: INT) -> isize;
+		pub(crate) fn SetWindowLongPtrW(hwnd: HWINDOW, index: INT, value: isize) -> isize;
+		fn GetClassLongPtrW(hwnd: HWINDOW, index: INT) -> isize;
+		fn SetClassLongPtrW(hwnd: HWINDOW, index: INT, value: isize) -> isize;
 	}
 
-	pub struct OsWindow {
-		hwnd: HWINDOW,
+	#[cfg(target_pointer_width = "32")]
+	#[link(name = "user32")]
+	extern "system" {
+		#[link_name = "GetWindowLongW"]
+		fn GetWindowLongPtrW(hwnd: HWINDOW, index: INT) -> isize;
+		#[link_name = "SetWindowLongW"]
+		pub(crate) fn SetWindowLongPtrW(hwnd: HWINDOW, index: INT, value: isize) -> isize;
+		#[link_name = "GetClassLongW"]
+		fn GetClassLongPtrW(hwnd: HWINDOW, index: INT) -> isize;
+		#[link_name = "SetClassLongW"]
+		fn SetClassLongPtrW(hwnd: HWINDOW, index: INT, value: isize) -> isize;
+	}
+
+	const GWL_STYLE: INT = -16;
+	const GWL_EXSTYLE: INT = -20;
+	const WS_EX_LAYERED: isize = 0x0008_0000;
+	const WS_OVERLAPPEDWINDOW: isize = 0x00CF_0000;
+	const MONITOR_DEFAULTTONEAREST: UINT = 2;
+	const SWP_FRAMECHANGED: UINT = 0x0020;
+	const SWP_NOOWNERZORDER: UINT = 0x0200;
+
+	#[repr(C)]
+	#[derive(Clone, Copy, Default)]
+	#[allow(non_snake_case)]
+	struct WINDOWPLACEMENT {
+		length: UINT,
 		flags: UINT,
+		showCmd: UINT,
+		ptMinPosition: POINT,
+		ptMaxPosition: POINT,
+		rcNormalPosition: RECT,
 	}
 
-	impl OsWindow {
-		pub fn new() -> OsWindow {
-			OsWindow {
-				hwnd: 0 as HWINDOW,
-				flags: 0,
-			}
-		}
+	#[repr(C)]
+	#[derive(Default)]
+	#[allow(non_snake_case)]
+	struct MONITORINFO {
+		cbSize: UINT,
+		rcMonitor: RECT,
+		rcWork: RECT,
+		dwFlags: UINT,
+	}
 
-		pub fn from(hwnd: HWINDOW) -> OsWindow {
-			OsWindow { hwnd: hwnd, flags: 0 }
-		}
+	/// `MONITORINFO` with the device name for `EnumDisplaySettingsW`.
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct MONITORINFOEXW {
+		info: MONITORINFO,
+		szDevice: [WCHAR; 32],
+	}
 
-		fn init_app() {
-			unsafe { OleInitialize(::std::ptr::null()) };
-		}
+	/// The display variant of `DEVMODEW`.
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct DEVMODEW {
+		dmDeviceName: [WCHAR; 32],
+		dmSpecVersion: u16,
+		dmDriverVersion: u16,
+		dmSize: u16,
+		dmDriverExtra: u16,
+		dmFields: UINT,
+		dmPosition: POINT,
+		dmDisplayOrientation: UINT,
+		dmDisplayFixedOutput: UINT,
+		dmColor: i16,
+		dmDuplex: i16,
+		dmYResolution: i16,
+		dmTTOption: i16,
+		dmCollate: i16,
+		dmFormName: [WCHAR; 32],
+		dmLogPixels: u16,
+		dmBitsPerPel: UINT,
+		dmPelsWidth: UINT,
+		dmPelsHeight: UINT,
+		dmDisplayFlags: UINT,
+		dmDisplayFrequency: UINT,
+		dmICMMethod: UINT,
+		dmICMIntent: UINT,
+		dmMediaType: UINT,
+		dmDitherType: UINT,
+		dmReserved1: UINT,
+		dmReserved2: UINT,
+		dmPanningWidth: UINT,
+		dmPanningHeight: UINT,
 	}
 
-	impl super::BaseWindow for OsWindow {
-		/// Get native window handle.
-		fn get_hwnd(&self) -> HWINDOW {
-			return self.hwnd;
-		}
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct MINMAXINFO {
+		ptReserved: POINT,
+		ptMaxSize: POINT,
+		ptMaxPosition: POINT,
+		ptMinTrackSize: POINT,
+		ptMaxTrackSize: POINT,
+	}
 
-		/// Create a new native window.
-		fn create(&mut self, rc: RECT, flags: UINT, parent: HWINDOW) -> HWINDOW {
-			if (flags & SCITER_CREATE_WINDOW_FLAGS::SW_MAIN.bits()) != 0 {
-				OsWindow::init_app();
-			}
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct FLASHWINFO {
+		cbSize: UINT,
+		hwnd: HWINDOW,
+		dwFlags: UINT,
+		uCount: UINT,
+		dwTimeout: UINT,
+	}
 
-			self.flags = flags;
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct WNDCLASSEXW {
+		cbSize: UINT,
+		style: UINT,
+		lpfnWndProc: LPVOID,
+		cbClsExtra: INT,
+		cbWndExtra: INT,
+		hInstance: LPVOID,
+		hIcon: LPVOID,
+		hCursor: LPVOID,
+		hbrBackground: LPVOID,
+		lpszMenuName: LPCWSTR,
+		lpszClassName: LPCWSTR,
+		hIconSm: LPVOID,
+	}
 
-			#[cfg(not(feature = "windowless"))]
-			{
-				let cb = ::std::ptr::null();
-				self.hwnd = (_API.SciterCreateWindow)(flags, &rc, cb, 0 as LPVOID, parent);
-				if self.hwnd.is_null() {
-					panic!("Failed to create window!");
-				}
-			}
-			#[cfg(feature = "windowless")]
-			{
-				let _ = rc;
-				let _ = parent;
-				let _ = &(_API.SciterVersion);
-			}
+	thread_local! {
+		/// Minimum and maximum window sizes for the `WM_GETMINMAXINFO` handling, zeros mean no limit.
+		static SIZE_LIMITS: RefCell<HashMap<HWINDOW, (SIZE, SIZE)>> = Default::default();
 
-			return self.hwnd;
-		}
+		/// Whether `init_app` has initialized OLE on this thread and `uninit_app` has to balance it.
+		static OLE_INITIALIZED: Cell<bool> = const { Cell::new(false) };
 
-		/// Minimize or hide window.
-		fn collapse(&self, hide: bool) {
-			let n: INT = if hide { 0 } else { 6 };
-			unsafe { ShowWindow(self.hwnd, n) };
+		/// Parents disabled by the `run_modal` windows.
+		static MODAL_OWNERS: RefCell<HashMap<HWINDOW, HWINDOW>> = Default::default();
+
+		/// `WM_ERASEBKGND` colors of the windows, see `set_background_color`.
+		static BACKGROUNDS: RefCell<HashMap<HWINDOW, u32>> = Default::default();
+
+		/// Windows with the touch input enabled along with the contact which drives the mouse, see `touch_to_mouse`.
+		static TOUCH_CONTACTS: RefCell<HashMap<HWINDOW, Option<UINT>>> = Default::default();
+
+		/// The DWM iconic bitmaps of the windows, see `set_thumbnail_bitmap` and `set_live_preview_rect`.
+		static ICONIC_BITMAPS: RefCell<HashMap<HWINDOW, IconicBitmaps>> = Default::default();
+	}
+
+	/// What the window gives to DWM instead of its own thumbnail and live preview.
+	#[derive(Default)]
+	struct IconicBitmaps {
+		/// Premultiplied `BGRA` thumbnail with its size, the window snapshot if there is none.
+		thumbnail: Option<(INT, INT, Vec<u8>)>,
+		/// The client area part for the live preview, the whole client area if there is none.
+		preview: Option<RECT>,
+	}
+
+	/// Create a top-down 32-bit DIB with the `BGRA` pixels.
+	fn create_bitmap(width: INT, height: INT, pixels: &[u8]) -> LPVOID {
+		let info = BITMAPINFO {
+			bmiHeader: BITMAPINFOHEADER {
+				biSize: ::std::mem::size_of::<BITMAPINFOHEADER>() as UINT,
+				biWidth: width,
+				biHeight: -height, // top-down
+				biPlanes: 1,
+				biBitCount: 32,
+				biCompression: 0, // BI_RGB
+				biSizeImage: 0,
+				biXPelsPerMeter: 0,
+				biYPelsPerMeter: 0,
+				biClrUsed: 0,
+				biClrImportant: 0,
+			},
+			bmiColors: [0],
+		};
+		let mut bits = ::std::ptr::null_mut();
+		let bitmap = unsafe { CreateDIBSection(::std::ptr::null_mut(), &info, 0, &mut bits, ::std::ptr::null_mut(), 0) };
+		if !bitmap.is_null() {
+			let size = (width * height * 4) as usize;
+			unsafe { ::std::ptr::copy_nonoverlapping(pixels.as_ptr(), bits as *mut u8, size.min(pixels.len())) };
 		}
+		return bitmap;
+	}
 
-		/// Show or maximize window.
-		fn expand(&self, maximize: bool) {
-			let n: INT = if maximize { 3 } else { 1 };
-			unsafe { ShowWindow(self.hwnd, n) };
+	/// Answer `WM_DWMSENDICONICTHUMBNAIL` with the thumbnail scaled down to fit in `max_width` x `max_height`.
+	#[cfg_attr(feature = "windowless", allow(dead_code))]
+	fn send_iconic_thumbnail(hwnd: HWINDOW, max_width: INT, max_height: INT) -> bool {
+		let thumbnail = ICONIC_BITMAPS.with(|bitmaps| bitmaps.borrow().get(&hwnd).map(|bitmaps| bitmaps.thumbnail.clone()));
+		let (width, height, pixels) = match thumbnail {
+			Some(Some(thumbnail)) => thumbnail,
+			Some(None) => match OsWindow::from(hwnd).capture_client() {
+				Some(snapshot) => snapshot,
+				None => return false,
+			},
+			None => return false,
+		};
+		if width <= 0 || height <= 0 || max_width <= 0 || max_height <= 0 {
+			return false;
+		}
+		// keep the aspect ratio, nearest neighbor is fine for a thumbnail
+		let scale = (max_width as f64 / width as f64).min(max_height as f64 / height as f64).min(1.0);
+		let (dst_width, dst_height) = (((width as f64 * scale) as INT).max(1), ((height as f64 * scale) as INT).max(1));
+		let mut scaled = Vec::with_capacity((dst_width * dst_height * 4) as usize);
+		for y in 0..dst_height {
+			let src_y = (y as f64 / scale) as INT;
+			for x in 0..dst_width {
+				let src = ((src_y.min(height - 1) * width + ((x as f64 / scale) as INT).min(width - 1)) * 4) as usize;
+				scaled.extend_from_slice(&pixels[src..src + 4]);
+			}
+		}
+		let bitmap = create_bitmap(dst_width, dst_height, &scaled);
+		if bitmap.is_null() {
+			return false;
 		}
+		let ok = unsafe { DwmSetIconicThumbnail(hwnd, bitmap, 0) } >= 0;
+		unsafe { DeleteObject(bitmap) };
+		return ok;
+	}
 
-		/// Close window.
-		fn dismiss(&self) {
-			unsafe { PostMessageW(self.hwnd, 0x0010, 0, 0) };
+	/// Answer `WM_DWMSENDICONICLIVEPREVIEWBITMAP` with the preview part of the window snapshot.
+	#[cfg_attr(feature = "windowless", allow(dead_code))]
+	fn send_live_preview(hwnd: HWINDOW) -> bool {
+		let preview = match ICONIC_BITMAPS.with(|bitmaps| bitmaps.borrow().get(&hwnd).map(|bitmaps| bitmaps.preview)) {
+			Some(preview) => preview,
+			None => return false,
+		};
+		let (width, height, pixels) = match OsWindow::from(hwnd).capture_client() {
+			Some(snapshot) => snapshot,
+			None => return false,
+		};
+		let rc = preview.unwrap_or(RECT { left: 0, top: 0, right: width, bottom: height });
+		let (left, top) = (rc.left.max(0), rc.top.max(0));
+		let (right, bottom) = (rc.right.min(width), rc.bottom.min(height));
+		if right <= left || bottom <= top {
+			return false;
+		}
+		let mut part = Vec::with_capacity(((right - left) * (bottom - top) * 4) as usize);
+		for y in top..bottom {
+			let row = ((y * width + left) * 4) as usize;
+			part.extend_from_slice(&pixels[row..row + ((right - left) * 4) as usize]);
 		}
+		let bitmap = create_bitmap(right - left, bottom - top, &part);
+		if bitmap.is_null() {
+			return false;
+		}
+		let offset = POINT { x: left, y: top };
+		let ok = unsafe { DwmSetIconicLivePreviewBitmap(hwnd, bitmap, &offset, 0) } >= 0;
+		unsafe { DeleteObject(bitmap) };
+		return ok;
+	}
 
-		/// Set native window title.
-		fn set_title(&mut self, title: &str) {
-			let s = s2w!(title);
-			unsafe { SetWindowTextW(self.hwnd, s.as_ptr()) };
+	/// Fill the client area with the `0xAARRGGBB` color, the alpha is ignored.
+	fn erase_background(hwnd: HWINDOW, hdc: LPVOID, color: u32) {
+		// `COLORREF` is `0x00BBGGRR`
+		let rgb = ((color & 0xFF) << 16) | (color & 0xFF00) | ((color >> 16) & 0xFF);
+		let mut rc = RECT::default();
+		unsafe {
+			GetClientRect(hwnd, &mut rc);
+			let brush = CreateSolidBrush(rgb);
+			FillRect(hdc, &rc, brush);
+			DeleteObject(brush);
 		}
+	}
 
-		/// Get native window title.
-		fn get_title(&self) -> String {
-			let n = unsafe { GetWindowTextLengthW(self.hwnd) + 1 };
-			let mut title: Vec<u16> = Vec::new();
-			title.resize(n as usize, 0);
-			unsafe { GetWindowTextW(self.hwnd, title.as_mut_ptr(), n) };
-			return ::utf::w2s(title.as_ptr());
+	/// The system cursor of the kind, null for the hidden one and `None` for the Sciter ones.
+	fn cursor_handle(cursor: CursorKind) -> Option<LPVOID> {
+		let id: usize = match cursor {
+			CursorKind::Default => return None,
+			CursorKind::Hidden => return Some(::std::ptr::null_mut()),
+			CursorKind::Arrow => 32512,
+			CursorKind::Text => 32513,
+			CursorKind::Wait => 32514,
+			CursorKind::Crosshair => 32515,
+			CursorKind::Move => 32646,
+			CursorKind::NotAllowed => 32648,
+			CursorKind::Hand => 32649,
+		};
+		// `MAKEINTRESOURCE` of `IDC_*`
+		return Some(unsafe { LoadCursorW(::std::ptr::null_mut(), id as LPCWSTR) });
+	}
+
+	/// Confine the cursor to the window client area or release it.
+	fn clip_cursor(hwnd: HWINDOW, enable: bool) {
+		if !enable {
+			unsafe { ClipCursor(::std::ptr::null()) };
+			return;
 		}
+		let mut rc = RECT::default();
+		unsafe { GetClientRect(hwnd, &mut rc) };
+		let (mut lt, mut rb) = (POINT { x: rc.left, y: rc.top }, POINT { x: rc.right, y: rc.bottom });
+		unsafe {
+			ClientToScreen(hwnd, &mut lt);
+			ClientToScreen(hwnd, &mut rb);
+			ClipCursor(&RECT { left: lt.x, top: lt.y, right: rb.x, bottom: rb.y });
+		}
+	}
 
-		/// Run the main app message loop until window been closed.
-		fn run_app(&self) {
-			let mut msg = MSG {
-				hwnd: 0 as HWINDOW,
-				message: 0,
-				wParam: 0,
-				lParam: 0,
-				time: 0,
-				pt: POINT { x: 0, y: 0 },
-			};
-			let pmsg: LPMSG = &mut msg;
-			let null: HWINDOW = ::std::ptr::null_mut();
-			unsafe {
-				while GetMessageW(pmsg, null, 0, 0) != 0 {
-					TranslateMessage(pmsg);
-					DispatchMessageW(pmsg);
-				}
-			};
+	/// The offset of the workspace coordinates of `WINDOWPLACEMENT` on the monitor of `rc`,
+	/// they exclude the taskbar on the left or at the top.
+	fn workspace_offset(rc: &RECT) -> (INT, INT) {
+		let mut monitor = MONITORINFO { cbSize: ::std::mem::size_of::<MONITORINFO>() as UINT, ..Default::default() };
+		if unsafe { GetMonitorInfoW(MonitorFromRect(rc, MONITOR_DEFAULTTONEAREST), &mut monitor) } == 0 {
+			return (0, 0);
 		}
+		return (monitor.rcWork.left - monitor.rcMonitor.left, monitor.rcWork.top - monitor.rcMonitor.top);
+	}
 
-		/// Post app quit message.
-		fn quit_app(&self) {
-			unsafe { PostQuitMessage(0) };
+	/// Enable the parent of the modal window again, before it is destroyed, otherwise another app gets activated.
+	fn enable_modal_owner(hwnd: HWINDOW) {
+		if let Some(owner) = MODAL_OWNERS.with(|owners| owners.borrow_mut().remove(&hwnd)) {
+			unsafe { EnableWindow(owner, true as BOOL) };
 		}
 	}
-}
 
-#[cfg(target_os = "linux")]
-mod linux {
-	use super::BaseWindow;
-	use capi::scdef::*;
-	use capi::sctypes::*;
-	use _API;
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct TOUCHINPUT {
+		x: INT,
+		y: INT,
+		hSource: LPVOID,
+		dwID: UINT,
+		dwFlags: UINT,
+		dwMask: UINT,
+		dwTime: UINT,
+		dwExtraInfo: usize,
+		cxContact: UINT,
+		cyContact: UINT,
+	}
 
-	use std::ptr;
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct POINTER_INFO {
+		pointerType: UINT,
+		pointerId: UINT,
+		frameId: UINT,
+		pointerFlags: UINT,
+		sourceDevice: LPVOID,
+		hwndTarget: HWINDOW,
+		ptPixelLocation: POINT,
+		ptHimetricLocation: POINT,
+		ptPixelLocationRaw: POINT,
+		ptHimetricLocationRaw: POINT,
+		dwTime: UINT,
+		historyCount: UINT,
+		InputData: INT,
+		dwKeyStates: UINT,
+		PerformanceCount: u64,
+		ButtonChangeType: INT,
+	}
 
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct POINTER_PEN_INFO {
+		pointerInfo: POINTER_INFO,
+		penFlags: UINT,
+		penMask: UINT,
+		pressure: UINT,
+		rotation: UINT,
+		tiltX: INT,
+		tiltY: INT,
+	}
 
-	pub struct OsWindow {
-		hwnd: HWINDOW,
-		flags: UINT,
+	#[link(name = "user32")]
+	extern "system" {
+		fn RegisterTouchWindow(hwnd: HWINDOW, flags: UINT) -> BOOL;
+		fn UnregisterTouchWindow(hwnd: HWINDOW) -> BOOL;
+		fn GetTouchInputInfo(input: LPVOID, count: UINT, inputs: *mut TOUCHINPUT, size: INT) -> BOOL;
+		fn CloseTouchInputHandle(input: LPVOID) -> BOOL;
+		fn ScreenToClient(hwnd: HWINDOW, pt: LPPOINT) -> BOOL;
 	}
 
-	impl OsWindow {
-		pub fn new() -> OsWindow {
-			OsWindow {
-				hwnd: 0 as HWINDOW,
-				flags: 0,
+	const TOUCHEVENTF_UP: UINT = 0x0004;
+
+	/// The mouse message of a touch contact or a pen at the `pt` screen pixel, `None` if it does not drive the mouse.
+	///
+	/// Sciter has a single mouse pointer, so the first contact which goes down drives it until it is lifted
+	/// and the other ones are ignored meanwhile. A contact that is not `in_contact` with the screen
+	/// (a hovering pen or a pen without pressure) moves the mouse without the button.
+	fn touch_to_mouse(hwnd: HWINDOW, id: UINT, pt: POINT, in_contact: bool) -> Option<(UINT, WPARAM, LPARAM)> {
+		const WM_MOUSEMOVE: UINT = 0x0200;
+		const WM_LBUTTONDOWN: UINT = 0x0201;
+		const WM_LBUTTONUP: UINT = 0x0202;
+		const MK_LBUTTON: WPARAM = 0x0001;
+		let message = TOUCH_CONTACTS.with(|contacts| {
+			let mut contacts = contacts.borrow_mut();
+			let active = contacts.get_mut(&hwnd)?;
+			match (*active, in_contact) {
+				(None, true) => {
+					*active = Some(id);
+					Some((WM_LBUTTONDOWN, MK_LBUTTON))
+				},
+				(Some(primary), true) if primary == id => Some((WM_MOUSEMOVE, MK_LBUTTON)),
+				(Some(primary), false) if primary == id => {
+					*active = None;
+					Some((WM_LBUTTONUP, 0))
+				},
+				(None, false) => Some((WM_MOUSEMOVE, 0)),
+				_ => None,
 			}
-		}
+		});
+		let (msg, buttons) = message?;
+		let mut pt = pt;
+		unsafe { ScreenToClient(hwnd, &mut pt) };
+		let pos = ((pt.y as u16 as u32) << 16) | (pt.x as u16 as u32);
+		return Some((msg, buttons, pos as LPARAM));
+	}
 
-		pub fn from(hwnd: HWINDOW) -> OsWindow {
-			OsWindow { hwnd: hwnd, flags: 0 }
+	/// Send the mouse messages of the `WM_TOUCH` contacts, which come in hundredths of a pixel.
+	fn touch_received(hwnd: HWINDOW, inputs: &[TOUCHINPUT]) {
+		for input in inputs {
+			let pt = POINT { x: input.x / 100, y: input.y / 100 };
+			let in_contact = (input.dwFlags & TOUCHEVENTF_UP) == 0;
+			if let Some((msg, wp, lp)) = touch_to_mouse(hwnd, input.dwID, pt, in_contact) {
+				unsafe { SendMessageW(hwnd, msg, wp, lp) };
+			}
 		}
+	}
 
-		fn init_app() {
-			(_API.SciterExec)(SCITER_APP_CMD::SCITER_APP_INIT.bits(), 0, 0);
+	/// Send the mouse message of the `WM_POINTER*` pen input, `false` for the other pointers.
+	///
+	/// `GetPointerType` and `GetPointerPenInfo` are Windows 8+.
+	fn pen_received(hwnd: HWINDOW, msg: UINT, wp: WPARAM) -> bool {
+		type GetPointerType = extern "system" fn(id: UINT, kind: *mut UINT) -> BOOL;
+		type GetPointerPenInfo = extern "system" fn(id: UINT, info: *mut POINTER_PEN_INFO) -> BOOL;
+		const WM_POINTERUP: UINT = 0x0247;
+		const PT_PEN: UINT = 3;
+		const POINTER_FLAG_INCONTACT: UINT = 0x0004;
+		let user32 = unsafe { GetModuleHandleW(s2w!("user32.dll").as_ptr()) };
+		let (get_type, get_pen) = unsafe {
+			(GetProcAddress(user32, b"GetPointerType\0".as_ptr() as LPCSTR), GetProcAddress(user32, b"GetPointerPenInfo\0".as_ptr() as LPCSTR))
+		};
+		if get_type.is_null() || get_pen.is_null() {
+			return false;
 		}
-
-		fn window(&self) -> HWINDOW {
-			self.get_hwnd()
+		let (get_type, get_pen) = unsafe { (::std::mem::transmute::<LPCVOID, GetPointerType>(get_type), ::std::mem::transmute::<LPCVOID, GetPointerPenInfo>(get_pen)) };
+		let id = (wp & 0xFFFF) as UINT;
+		let mut kind: UINT = 0;
+		if get_type(id, &mut kind) == 0 || kind != PT_PEN {
+			return false;
+		}
+		let mut pen: POINTER_PEN_INFO = unsafe { ::std::mem::zeroed() };
+		if get_pen(id, &mut pen) == 0 {
+			return false;
 		}
+		// the pen can touch the screen before it has some pressure
+		let touching = (pen.pointerInfo.pointerFlags & POINTER_FLAG_INCONTACT) != 0 && pen.pressure > 0;
+		let in_contact = msg != WM_POINTERUP && touching;
+		if let Some((msg, wp, lp)) = touch_to_mouse(hwnd, id, pen.pointerInfo.ptPixelLocation, in_contact) {
+			unsafe { SendMessageW(hwnd, msg, wp, lp) };
+		}
+		return true;
 	}
 
-	impl super::BaseWindow for OsWindow {
-		/// Get native window handle.
-		fn get_hwnd(&self) -> HWINDOW {
-			return self.hwnd;
-		}
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct COMPOSITIONFORM {
+		dwStyle: UINT,
+		ptCurrentPos: POINT,
+		rcArea: RECT,
+	}
 
-		/// Create a new native window.
-		fn create(&mut self, rc: RECT, flags: UINT, parent: HWINDOW) -> HWINDOW {
-			if (flags & SCITER_CREATE_WINDOW_FLAGS::SW_MAIN.bits()) != 0 {
-				OsWindow::init_app();
-			}
-			self.flags = flags;
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct CANDIDATEFORM {
+		dwIndex: UINT,
+		dwStyle: UINT,
+		ptCurrentPos: POINT,
+		rcArea: RECT,
+	}
 
-			#[cfg(not(feature = "windowless"))]
-			{
-				self.hwnd = (_API.SciterCreateWindow)(flags, &rc, ptr::null(), ptr::null_mut(), parent);
-				if self.hwnd.is_null() {
-					panic!("Failed to create window!");
-				}
-			}
-			#[cfg(feature = "windowless")]
-			{
-				let _ = rc;
-				let _ = parent;
-				let _ = &(_API.SciterVersion);
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct LOGFONTW {
+		lfHeight: INT,
+		lfWidth: INT,
+		lfEscapement: INT,
+		lfOrientation: INT,
+		lfWeight: INT,
+		lfItalic: BYTE,
+		lfUnderline: BYTE,
+		lfStrikeOut: BYTE,
+		lfCharSet: BYTE,
+		lfOutPrecision: BYTE,
+		lfClipPrecision: BYTE,
+		lfQuality: BYTE,
+		lfPitchAndFamily: BYTE,
+		lfFaceName: [u16; 32],
+	}
+
+	#[link(name = "imm32")]
+	extern "system" {
+		fn ImmGetContext(hwnd: HWINDOW) -> LPVOID;
+		fn ImmReleaseContext(hwnd: HWINDOW, himc: LPVOID) -> BOOL;
+		fn ImmSetCompositionWindow(himc: LPVOID, form: *const COMPOSITIONFORM) -> BOOL;
+		fn ImmSetCandidateWindow(himc: LPVOID, form: *const CANDIDATEFORM) -> BOOL;
+		fn ImmSetCompositionFontW(himc: LPVOID, font: *const LOGFONTW) -> BOOL;
+	}
+
+	/// The caret of the focused element in the client coordinates along with its font size in pixels and family.
+	///
+	/// Sciter does not expose the caret rect itself, so it is the start of the element content box.
+	fn ime_caret(hwnd: HWINDOW) -> Option<(RECT, INT, String)> {
+		use capi::scdom::ELEMENT_AREAS;
+		use dom::Element;
+		let focus = Element::from_focus(hwnd).ok()?;
+		let content = focus.get_location(ELEMENT_AREAS::VIEW_RELATIVE as u32 | ELEMENT_AREAS::CONTENT_BOX as u32).ok()?;
+		let caret = RECT { left: content.left, top: content.top, right: content.left + 1, bottom: content.bottom };
+		// e.g. `13px` and `"Segoe UI", sans-serif`
+		let size = focus.get_style_attribute("font-size");
+		let size: f32 = size.trim_end_matches(char::is_alphabetic).trim().parse().unwrap_or(0.0);
+		let family = focus.get_style_attribute("font-family");
+		let family = family.split(',').next().unwrap_or("").trim().trim_matches(|c| c == '"' || c == '\'').to_string();
+		return Some((caret, size.round() as INT, family));
+	}
+
+	/// Put the IME composition window at the caret and the candidate window below it, with the font of the input.
+	fn position_ime(hwnd: HWINDOW, caret: RECT, font_size: INT, font_family: &str) {
+		const CFS_POINT: UINT = 0x0002;
+		const CFS_EXCLUDE: UINT = 0x0080;
+		let himc = unsafe { ImmGetContext(hwnd) };
+		if himc.is_null() {
+			return;
+		}
+		let composition = COMPOSITIONFORM {
+			dwStyle: CFS_POINT,
+			ptCurrentPos: POINT { x: caret.left, y: caret.top },
+			rcArea: RECT::default(),
+		};
+		// the candidates do not cover the caret line
+		let candidate = CANDIDATEFORM {
+			dwIndex: 0,
+			dwStyle: CFS_EXCLUDE,
+			ptCurrentPos: POINT { x: caret.left, y: caret.bottom },
+			rcArea: caret,
+		};
+		unsafe {
+			ImmSetCompositionWindow(himc, &composition);
+			ImmSetCandidateWindow(himc, &candidate);
+		}
+		if font_size > 0 {
+			let mut font: LOGFONTW = unsafe { ::std::mem::zeroed() };
+			font.lfHeight = -font_size;
+			for (dst, src) in font.lfFaceName.iter_mut().take(31).zip(font_family.encode_utf16()) {
+				*dst = src;
 			}
-			return self.hwnd;
+			unsafe { ImmSetCompositionFontW(himc, &font) };
 		}
+		unsafe { ImmReleaseContext(hwnd, himc) };
+	}
 
-		/// Minimize or hide window.
-		fn collapse(&self, hide: bool) {
-			unsafe {
-				if hide {
-					(_API.SciterWindowExec)(
-						self.window(),
-						SCITER_WINDOW_CMD::SCITER_WINDOW_SET_STATE.bits(),
-						SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_HIDDEN.bits(),
-						0,
-					);
-				} else {
-					(_API.SciterWindowExec)(
-						self.window(),
-						SCITER_WINDOW_CMD::SCITER_WINDOW_SET_STATE.bits(),
-						SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_MINIMIZED.bits(),
-						0,
-					);
-				}
-			};
-		}
+	/// A closure of `OsWindow::subclass`, `None` passes the message on.
+	type SubclassProc = Box<dyn Fn(HWINDOW, UINT, WPARAM, LPARAM) -> Option<LRESULT>>;
 
-		/// Show or maximize window.
-		fn expand(&self, maximize: bool) {
-			let wnd = self.window();
-			unsafe {
-				if maximize {
-					(_API.SciterWindowExec)(
-						wnd,
-						SCITER_WINDOW_CMD::SCITER_WINDOW_SET_STATE.bits(),
-						SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_MAXIMIZED.bits(),
-						0,
-					);
-				} else {
-					(_API.SciterWindowExec)(
-						wnd,
-						SCITER_WINDOW_CMD::SCITER_WINDOW_SET_STATE.bits(),
-						SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_SHOWN.bits(),
-						0,
-					);
-				}
-			};
-		}
+	type SubclassCallback = extern "system" fn(hwnd: HWINDOW, msg: UINT, wp: WPARAM, lp: LPARAM, id: usize, data: usize) -> LRESULT;
 
-		/// Close window.
-		fn dismiss(&self) {
-			println!("linux::OsWindow::dismiss()");
+	#[link(name = "comctl32")]
+	extern "system" {
+		fn SetWindowSubclass(hwnd: HWINDOW, callback: SubclassCallback, id: usize, data: usize) -> BOOL;
+		fn GetWindowSubclass(hwnd: HWINDOW, callback: SubclassCallback, id: usize, data: *mut usize) -> BOOL;
+		fn RemoveWindowSubclass(hwnd: HWINDOW, callback: SubclassCallback, id: usize) -> BOOL;
+		fn DefSubclassProc(hwnd: HWINDOW, msg: UINT, wp: WPARAM, lp: LPARAM) -> LRESULT;
+	}
+
+	/// Call the `OsWindow::subclass` closure, which is dropped along with the window on `WM_NCDESTROY`.
+	extern "system" fn subclass_proc(hwnd: HWINDOW, msg: UINT, wp: WPARAM, lp: LPARAM, id: usize, data: usize) -> LRESULT {
+		const WM_NCDESTROY: UINT = 0x0082;
+		if msg == WM_NCDESTROY {
 			unsafe {
-				(_API.SciterWindowExec)(
-					self.window(),
-					SCITER_WINDOW_CMD::SCITER_WINDOW_SET_STATE.bits(),
-					SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_CLOSED.bits(),
-					0, // Set to FALSE for request_close behaviour
-				);
-			};
+				RemoveWindowSubclass(hwnd, subclass_proc, id);
+				drop(Box::from_raw(data as *mut SubclassProc));
+				return DefSubclassProc(hwnd, msg, wp, lp);
+			}
 		}
-
-		/// Set native window title.
-		fn set_title(&mut self, title: &str) {
-			unimplemented!();
+		let callback = unsafe { &*(data as *const SubclassProc) };
+		match callback(hwnd, msg, wp, lp) {
+			Some(result) => result,
+			None => unsafe { DefSubclassProc(hwnd, msg, wp, lp) },
 		}
+	}
 
-		/// Get native window title.
-		fn get_title(&self) -> String {
-			unimplemented!();
-		}
+	/// Private message to quit the app from other threads, see `post_quit`.
+	const WM_POST_QUIT: UINT = 0x8000 + 0x5C1; // WM_APP + n
 
-		/// Run the main app message loop until window been closed.
-		fn run_app(&self) {
-			(_API.SciterExec)(SCITER_APP_CMD::SCITER_APP_LOOP.bits(), 0, 0);
-		}
+	/// Private message to restore the focus after Sciter has handled the activation.
+	const WM_RESTORE_FOCUS: UINT = 0x8000 + 0x5C2;
 
-		/// Post app quit message.
-		fn quit_app(&self) {
-			(_API.SciterExec)(SCITER_APP_CMD::SCITER_APP_STOP.bits(), 0, 0);
+	/// `CF_UNICODETEXT` clipboard format.
+	const CF_UNICODETEXT: UINT = 13;
+
+	/// Private message to run the closures posted from other threads, see `post_task`.
+	const WM_RUN_TASKS: UINT = 0x8000 + 0x5C3;
+
+	/// Private message of the tray icons, see `OsTrayIcon`.
+	const WM_TRAY_ICON: UINT = 0x8000 + 0x5C4;
+
+	lazy_static! {
+		/// Closures posted from other threads along with their windows.
+		static ref POSTED_TASKS: Mutex<Vec<(usize, UiTask)>> = Default::default();
+	}
+
+	/// Run the closures posted to the window.
+	#[cfg_attr(feature = "windowless", allow(dead_code))]
+	fn run_posted_tasks(hwnd: HWINDOW) {
+		let tasks: Vec<(usize, UiTask)> = {
+			let mut posted = POSTED_TASKS.lock().unwrap();
+			let (tasks, others): (Vec<_>, Vec<_>) = posted.drain(..).partition(|&(owner, _)| owner == hwnd as usize);
+			*posted = others;
+			tasks
+		};
+		// without the lock, they can post other tasks
+		for (_, task) in tasks {
+			task();
 		}
 	}
-}
 
-#[cfg(target_os = "macos")]
-mod macos {
+	/// The key code of the virtual key, if it can be an accelerator.
+	fn key_code(vk: WPARAM) -> Option<u32> {
+		let code = match vk {
+			0x30..=0x39 | 0x41..=0x5A => vk as u32, // digits and letters
+			0x70..=0x87 => key_codes::KB_F1 + (vk - 0x70) as u32,
+			0x20 => key_codes::KB_SPACE,
+			0x1B => key_codes::KB_ESCAPE,
+			0x0D => key_codes::KB_ENTER,
+			0x09 => key_codes::KB_TAB,
+			0x08 => key_codes::KB_BACKSPACE,
+			0x2D => key_codes::KB_INSERT,
+			0x2E => key_codes::KB_DELETE,
+			0x27 => key_codes::KB_RIGHT,
+			0x25 => key_codes::KB_LEFT,
+			0x28 => key_codes::KB_DOWN,
+			0x26 => key_codes::KB_UP,
+			0x21 => key_codes::KB_PAGE_UP,
+			0x22 => key_codes::KB_PAGE_DOWN,
+			0x24 => key_codes::KB_HOME,
+			0x23 => key_codes::KB_END,
+			_ => return None,
+		};
+		return Some(code);
+	}
 
-	extern crate objc_foundation;
+	/// Pass the key press to the accelerator callbacks of its window and the parent ones before it is translated,
+	/// `true` means that it has been handled.
+	fn translate_accelerator(msg: &MSG) -> bool {
+		const WM_KEYDOWN: UINT = 0x0100;
+		const WM_SYSKEYDOWN: UINT = 0x0104;
+		const VK_SHIFT: INT = 0x10;
+		const VK_CONTROL: INT = 0x11;
+		const VK_MENU: INT = 0x12;
+		if msg.message != WM_KEYDOWN && msg.message != WM_SYSKEYDOWN {
+			return false;
+		}
+		let key = match key_code(msg.wParam) {
+			Some(key) => key,
+			None => return false,
+		};
+		let pressed = |vk: INT| unsafe { GetKeyState(vk) } < 0;
+		let mut modifiers = 0;
+		if pressed(VK_CONTROL) {
+			modifiers |= KEYBOARD_STATES::CONTROL_KEY_PRESSED;
+		}
+		if pressed(VK_SHIFT) {
+			modifiers |= KEYBOARD_STATES::SHIFT_KEY_PRESSED;
+		}
+		if pressed(VK_MENU) {
+			modifiers |= KEYBOARD_STATES::ALT_KEY_PRESSED;
+		}
+		let accelerator = Accelerator::new(modifiers, key);
+		let mut hwnd = msg.hwnd;
+		while !hwnd.is_null() {
+			if super::accelerator_pressed(hwnd, accelerator) {
+				return true;
+			}
+			hwnd = unsafe { GetParent(hwnd) };
+		}
+		return false;
+	}
 
-	use self::objc_foundation::{INSString, NSString};
-	use objc::runtime::{Class, Object};
+	/// The `WM_NCHITTEST` code of the window part.
+	#[cfg_attr(feature = "windowless", allow(dead_code))]
+	fn hit_test_code(result: HitTestResult) -> LRESULT {
+		return match result {
+			HitTestResult::Client => 1,
+			HitTestResult::Caption => 2,
+			HitTestResult::Left => 10,
+			HitTestResult::Right => 11,
+			HitTestResult::Top => 12,
+			HitTestResult::TopLeft => 13,
+			HitTestResult::TopRight => 14,
+			HitTestResult::Bottom => 15,
+			HitTestResult::BottomLeft => 16,
+			HitTestResult::BottomRight => 17,
+		};
+	}
+
+	/// Window messages handler called by Sciter before its own processing.
+	#[cfg_attr(feature = "windowless", allow(dead_code))]
+	extern "system" fn window_delegate(hwnd: HWINDOW, msg: UINT, wp: WPARAM, lp: LPARAM, _param: LPVOID, handled: *mut BOOL) -> LRESULT {
+		const WM_DROPFILES: UINT = 0x0233;
+		const WM_ACTIVATE: UINT = 0x0006;
+		const WM_GETMINMAXINFO: UINT = 0x0024;
+		const WM_CLOSE: UINT = 0x0010;
+		const WM_DESTROY: UINT = 0x0002;
+		const WM_NCDESTROY: UINT = 0x0082;
+		const WM_MOVE: UINT = 0x0003;
+		const WM_SIZE: UINT = 0x0005;
+		const SIZE_MINIMIZED: WPARAM = 1;
+		const WM_DPICHANGED: UINT = 0x02E0;
+		const WM_DISPLAYCHANGE: UINT = 0x007E;
+		const WM_SETTINGCHANGE: UINT = 0x001A;
+		const WM_SETCURSOR: UINT = 0x0020;
+		const HTCLIENT: LPARAM = 1;
+		const WM_ERASEBKGND: UINT = 0x0014;
+		const WM_NCHITTEST: UINT = 0x0084;
+		const WM_DWMSENDICONICTHUMBNAIL: UINT = 0x0323;
+		const WM_DWMSENDICONICLIVEPREVIEWBITMAP: UINT = 0x0326;
+		const WM_TOUCH: UINT = 0x0240;
+		const WM_IME_STARTCOMPOSITION: UINT = 0x010D;
+		const WM_IME_SETCONTEXT: UINT = 0x0281;
+		const WM_POINTERUPDATE: UINT = 0x0245;
+		const WM_POINTERUP: UINT = 0x0247;
+		const WM_KEYFIRST: UINT = 0x0100;
+		const WM_KEYLAST: UINT = 0x0109;
+		const WM_MOUSEFIRST: UINT = 0x0200;
+		const WM_MOUSELAST: UINT = 0x020E;
+		match msg {
+			WM_POST_QUIT => unsafe { PostQuitMessage(0) },
+			WM_KEYFIRST..=WM_KEYLAST | WM_MOUSEFIRST..=WM_MOUSELAST => super::input_received(hwnd),
+			WM_ACTIVATE => if (wp & 0xFFFF) == 0 {
+				// WA_INACTIVE
+				super::save_focus(hwnd);
+				if super::cursor_clip_enabled(hwnd) {
+					clip_cursor(hwnd, false);
+				}
+			} else {
+				unsafe { PostMessageW(hwnd, WM_RESTORE_FOCUS, 0, 0) };
+				if super::cursor_clip_enabled(hwnd) {
+					clip_cursor(hwnd, true);
+				}
+			},
+			WM_RESTORE_FOCUS => super::restore_focus(hwnd),
+			WM_RUN_TASKS => run_posted_tasks(hwnd),
+			// the maximum width is in the high word
+			WM_DWMSENDICONICTHUMBNAIL if send_iconic_thumbnail(hwnd, ((lp >> 16) & 0xFFFF) as INT, (lp & 0xFFFF) as INT) => {
+				unsafe { *handled = true as BOOL };
+			},
+			WM_DWMSENDICONICLIVEPREVIEWBITMAP if send_live_preview(hwnd) => {
+				unsafe { *handled = true as BOOL };
+			},
+			WM_TRAY_ICON => {
+				const WM_LBUTTONUP: LPARAM = 0x0202;
+				// the mouse message is in the low word
+				if (lp & 0xFFFF) == WM_LBUTTONUP {
+					super::TrayIcon::clicked(wp as u32);
+				}
+			},
+			WM_SIZE if wp != SIZE_MINIMIZED => {
+				// the clip rect follows the client area
+				if super::cursor_clip_enabled(hwnd) && OsWindow::from(hwnd).is_focused() {
+					clip_cursor(hwnd, true);
+				}
+				super::window_configured(hwnd, OsWindow::from(hwnd).get_rect());
+				super::monitor_changed(hwnd);
+			},
+			// minimized windows are moved off-screen
+			WM_MOVE if unsafe { IsIconic(hwnd) } == 0 => {
+				if super::cursor_clip_enabled(hwnd) && OsWindow::from(hwnd).is_focused() {
+					clip_cursor(hwnd, true);
+				}
+				super::window_configured(hwnd, OsWindow::from(hwnd).get_rect());
+				super::monitor_changed(hwnd);
+			},
+			// a resolution or refresh rate switch
+			WM_DISPLAYCHANGE => super::monitor_changed(hwnd),
+			WM_DPICHANGED => {
+				// the suggested rect keeps the window at the same physical size on the new monitor
+				let rc = unsafe { *(lp as *const RECT) };
+				unsafe { SetWindowPos(hwnd, ::std::ptr::null_mut(), rc.left, rc.top, rc.width(), rc.height(), SWP_NOZORDER | SWP_NOACTIVATE) };
+				super::dpi_changed(hwnd, (wp & 0xFFFF) as f32 / 96.0);
+			},
+			// the light/dark switch broadcasts the `ImmersiveColorSet` area
+			WM_SETTINGCHANGE if lp != 0 && ::utf::w2s(lp as LPCWSTR) == "ImmersiveColorSet" => {
+				super::theme_changed(hwnd, OsWindow::current_theme());
+			},
+			WM_NCHITTEST => {
+				// the screen point
+				let (x, y) = ((lp & 0xFFFF) as i16 as i32, ((lp >> 16) & 0xFFFF) as i16 as i32);
+				let rc = OsWindow::from(hwnd).get_rect();
+				if let Some(result) = super::hit_test(hwnd, x - rc.left, y - rc.top) {
+					unsafe { *handled = true as BOOL };
+					return hit_test_code(result);
+				}
+			},
+			WM_SETCURSOR if (lp & 0xFFFF) == HTCLIENT => {
+				if let Some(cursor) = cursor_handle(super::cursor_override(hwnd)) {
+					unsafe {
+						SetCursor(cursor);
+						*handled = true as BOOL;
+					}
+					return true as LRESULT;
+				}
+			},
+			// before Sciter shows the composition
+			WM_IME_SETCONTEXT | WM_IME_STARTCOMPOSITION => if let Some((caret, size, family)) = ime_caret(hwnd) {
+				position_ime(hwnd, caret, size, &family);
+			},
+			WM_TOUCH if TOUCH_CONTACTS.with(|contacts| contacts.borrow().contains_key(&hwnd)) => {
+				let (count, input) = (wp & 0xFFFF, lp as LPVOID);
+				let mut inputs: Vec<TOUCHINPUT> = (0..count).map(|_| unsafe { ::std::mem::zeroed() }).collect();
+				let size = ::std::mem::size_of::<TOUCHINPUT>() as INT;
+				if unsafe { GetTouchInputInfo(input, count as UINT, inputs.as_mut_ptr(), size) } != 0 {
+					touch_received(hwnd, &inputs);
+					unsafe {
+						CloseTouchInputHandle(input);
+						*handled = true as BOOL;
+					}
+				}
+			},
+			// down, up and the updates between them
+			WM_POINTERUPDATE..=WM_POINTERUP if TOUCH_CONTACTS.with(|contacts| contacts.borrow().contains_key(&hwnd)) && pen_received(hwnd, msg, wp) => {
+				// handled, not promoted to the mouse messages once again
+				unsafe { *handled = true as BOOL };
+			},
+			WM_ERASEBKGND => if let Some(color) = BACKGROUNDS.with(|colors| colors.borrow().get(&hwnd).cloned()) {
+				erase_background(hwnd, wp as LPVOID, color);
+				unsafe { *handled = true as BOOL };
+				return true as LRESULT;
+			},
+			WM_CLOSE => if !super::close_allowed(hwnd) {
+				unsafe { *handled = true as BOOL };
+			} else {
+				enable_modal_owner(hwnd);
+			},
+			WM_GETMINMAXINFO => SIZE_LIMITS.with(|limits| {
+				if let Some(&(min, max)) = limits.borrow().get(&hwnd) {
+					let info = unsafe { &mut *(lp as *mut MINMAXINFO) };
+					if min.cx > 0 { info.ptMinTrackSize.x = min.cx; }
+					if min.cy > 0 { info.ptMinTrackSize.y = min.cy; }
+					if max.cx > 0 { info.ptMaxTrackSize.x = max.cx; }
+					if max.cy > 0 { info.ptMaxTrackSize.y = max.cy; }
+					unsafe { *handled = true as BOOL };
+				}
+			}),
+			WM_DROPFILES => {
+				let drop = wp as LPVOID;
+				let files = dropped_files(drop);
+				unsafe { DragFinish(drop) };
+				if super::files_dropped(hwnd, files) {
+					unsafe { *handled = true as BOOL };
+				}
+			},
+			WM_DESTROY => {
+				enable_modal_owner(hwnd);
+				if super::cursor_clip_enabled(hwnd) && OsWindow::from(hwnd).is_focused() {
+					clip_cursor(hwnd, false);
+				}
+				super::App::window_destroyed(hwnd);
+			},
+			WM_NCDESTROY => {
+				// the ones posted before the destruction still run once
+				run_posted_tasks(hwnd);
+				SIZE_LIMITS.with(|limits| limits.borrow_mut().remove(&hwnd));
+				BACKGROUNDS.with(|colors| colors.borrow_mut().remove(&hwnd));
+				TOUCH_CONTACTS.with(|contacts| contacts.borrow_mut().remove(&hwnd));
+				ICONIC_BITMAPS.with(|bitmaps| bitmaps.borrow_mut().remove(&hwnd));
+				forget_automation_properties(hwnd);
+				super::forget_window(hwnd);
+				super::forget_close_handler(hwnd);
+				super::forget_drop_handler(hwnd);
+				super::forget_focus(hwnd);
+				super::forget_input(hwnd);
+				super::forget_geometry_handlers(hwnd);
+				super::forget_dpi_handler(hwnd);
+				super::forget_monitor_handler(hwnd);
+				super::forget_theme_handler(hwnd);
+				super::forget_cursor(hwnd);
+				super::forget_accelerators(hwnd);
+				super::forget_zoom(hwnd);
+				super::forget_hit_test_handler(hwnd);
+			},
+			_ => {},
+		}
+		return 0;
+	}
+
+	#[link(name = "user32")]
+	extern "system" {
+		fn GetDC(hwnd: HWINDOW) -> LPVOID;
+		fn GetForegroundWindow() -> HWINDOW;
+		fn GetFocus() -> HWINDOW;
+		fn GetWindowThreadProcessId(hwnd: HWINDOW, process: *mut UINT) -> UINT;
+		fn AttachThreadInput(attach: UINT, attach_to: UINT, enable: BOOL) -> BOOL;
+		fn BringWindowToTop(hwnd: HWINDOW) -> BOOL;
+		fn GetAncestor(hwnd: HWINDOW, flags: UINT) -> HWINDOW;
+		fn FlashWindowEx(info: *const FLASHWINFO) -> BOOL;
+		fn GetCursorPos(pt: LPPOINT) -> BOOL;
+		fn GetAsyncKeyState(key: INT) -> i16;
+		fn GetSystemMetrics(index: INT) -> INT;
+		fn WindowFromPoint(pt: POINT) -> HWINDOW;
+		fn LoadCursorW(instance: LPVOID, name: LPCWSTR) -> LPVOID;
+		fn SetCursor(cursor: LPVOID) -> LPVOID;
+		fn ReleaseCapture() -> BOOL;
+		fn ReleaseDC(hwnd: HWINDOW, hdc: LPVOID) -> INT;
+		fn GetClientRect(hwnd: HWINDOW, rc: LPRECT) -> BOOL;
+		fn ClipCursor(rc: *const RECT) -> BOOL;
+		fn FillRect(hdc: LPVOID, rc: *const RECT, brush: LPVOID) -> INT;
+		fn InvalidateRect(hwnd: HWINDOW, rc: *const RECT, erase: BOOL) -> BOOL;
+		fn PrintWindow(hwnd: HWINDOW, hdc: LPVOID, flags: UINT) -> BOOL;
+	}
 
-	/// Activation policies that control whether and how an app may be activated.
 	#[repr(C)]
-	#[allow(dead_code)]
-	enum NSApplicationActivationPolicy {
-		Regular = 0,
-		Accessory,
-		Prohibited,
+	#[allow(non_snake_case)]
+	struct BITMAPINFOHEADER {
+		biSize: UINT,
+		biWidth: INT,
+		biHeight: INT,
+		biPlanes: u16,
+		biBitCount: u16,
+		biCompression: UINT,
+		biSizeImage: UINT,
+		biXPelsPerMeter: INT,
+		biYPelsPerMeter: INT,
+		biClrUsed: UINT,
+		biClrImportant: UINT,
 	}
 
-	// Note: Starting some OSX version (perhaps, 10.13),
-	// the AppKit framework isn't loaded implicitly.
-	#[link(name = "CoreFoundation", kind = "framework")]
-	extern "C" {}
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct BITMAPINFO {
+		bmiHeader: BITMAPINFOHEADER,
+		bmiColors: [UINT; 1],
+	}
 
-	#[link(name = "AppKit", kind = "framework")]
-	extern "C" {}
+	#[link(name = "gdi32")]
+	extern "system" {
+		fn GetDeviceCaps(hdc: LPVOID, index: INT) -> INT;
+		fn CreateCompatibleDC(hdc: LPVOID) -> LPVOID;
+		fn CreateDIBSection(hdc: LPVOID, info: *const BITMAPINFO, usage: UINT, bits: *mut LPVOID, section: LPVOID, offset: UINT) -> LPVOID;
+		fn SelectObject(hdc: LPVOID, object: LPVOID) -> LPVOID;
+		fn DeleteObject(object: LPVOID) -> BOOL;
+		fn DeleteDC(hdc: LPVOID) -> BOOL;
+		fn CreateSolidBrush(color: UINT) -> LPVOID;
+	}
 
-	use super::BaseWindow;
-	use capi::scdef::*;
-	use capi::sctypes::*;
-	use _API;
+	#[link(name = "kernel32")]
+	extern "system" {
+		fn GetModuleHandleW(name: LPCWSTR) -> LPVOID;
+		fn GetCurrentThreadId() -> UINT;
+		fn LoadLibraryW(name: LPCWSTR) -> LPVOID;
+		fn GlobalAlloc(flags: UINT, bytes: usize) -> LPVOID;
+		fn GlobalLock(mem: LPVOID) -> LPVOID;
+		fn GlobalUnlock(mem: LPVOID) -> BOOL;
+		fn GlobalFree(mem: LPVOID) -> LPVOID;
+		fn GlobalAddAtomW(s: LPCWSTR) -> u16;
+		fn GlobalDeleteAtom(atom: u16) -> u16;
+	}
+
+	#[link(name = "user32")]
+	extern "system" {
+		fn OpenClipboard(owner: HWINDOW) -> BOOL;
+		fn CloseClipboard() -> BOOL;
+		fn EmptyClipboard() -> BOOL;
+		fn SetClipboardData(format: UINT, mem: LPVOID) -> LPVOID;
+		fn GetClipboardData(format: UINT) -> LPVOID;
+	}
+
+	type MonitorEnumProc = extern "system" fn(monitor: LPVOID, hdc: LPVOID, rc: LPRECT, param: LPARAM) -> BOOL;
+
+	#[link(name = "user32")]
+	extern "system" {
+		fn EnumDisplayMonitors(hdc: LPVOID, clip: *const RECT, callback: MonitorEnumProc, param: LPARAM) -> BOOL;
+		fn SetPropW(hwnd: HWINDOW, name: LPCWSTR, data: LPVOID) -> BOOL;
+		fn GetPropW(hwnd: HWINDOW, name: LPCWSTR) -> LPVOID;
+		fn RemovePropW(hwnd: HWINDOW, name: LPCWSTR) -> LPVOID;
+	}
+
+	#[link(name = "ole32")]
+	extern "system" {
+		fn OleInitialize(pv: LPCVOID) -> i32; // HRESULT
+		fn OleUninitialize();
+		fn RevokeDragDrop(hwnd: HWINDOW) -> i32; // HRESULT
+		fn CoCreateInstance(clsid: *const GUID, outer: LPVOID, context: UINT, iid: *const GUID, object: *mut LPVOID) -> i32; // HRESULT
+	}
+
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct GUID {
+		Data1: UINT,
+		Data2: u16,
+		Data3: u16,
+		Data4: [BYTE; 8],
+	}
+
+	/// `CLSID_TaskbarList`
+	const CLSID_TASKBAR_LIST: GUID = GUID { Data1: 0x56FD_F344, Data2: 0xFD6D, Data3: 0x11D0, Data4: [0x95, 0x8A, 0x00, 0x60, 0x97, 0xC9, 0xA0, 0x90] };
+	/// `IID_ITaskbarList3`
+	const IID_TASKBAR_LIST3: GUID = GUID { Data1: 0xEA1A_FB91, Data2: 0x9E28, Data3: 0x4B86, Data4: [0x90, 0xE9, 0x9E, 0x9F, 0x8A, 0x5E, 0xEF, 0xAF] };
+
+	/// The `ITaskbarList3` vtable up to the progress methods.
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct ITaskbarList3Vtbl {
+		QueryInterface: LPVOID,
+		AddRef: LPVOID,
+		Release: extern "system" fn(this: LPVOID) -> UINT,
+		HrInit: extern "system" fn(this: LPVOID) -> i32,
+		AddTab: LPVOID,
+		DeleteTab: LPVOID,
+		ActivateTab: LPVOID,
+		SetActiveAlt: LPVOID,
+		MarkFullscreenWindow: LPVOID,
+		SetProgressValue: extern "system" fn(this: LPVOID, hwnd: HWINDOW, completed: u64, total: u64) -> i32,
+		SetProgressState: extern "system" fn(this: LPVOID, hwnd: HWINDOW, flags: UINT) -> i32,
+	}
+
+	/// `CLSID_AccPropServices`
+	const CLSID_ACC_PROP_SERVICES: GUID = GUID { Data1: 0xB5F8_350B, Data2: 0x0548, Data3: 0x48B1, Data4: [0xA6, 0xEE, 0x88, 0xBD, 0x00, 0xB4, 0xA5, 0xE7] };
+	/// `IID_IAccPropServices`
+	const IID_ACC_PROP_SERVICES: GUID = GUID { Data1: 0x6E26_E776, Data2: 0x04F0, Data3: 0x495D, Data4: [0x80, 0xE4, 0x33, 0x30, 0x35, 0x2E, 0x31, 0x69] };
+	/// `AutomationId_Property_GUID`
+	const AUTOMATION_ID_PROPERTY: GUID = GUID { Data1: 0xC82C_0500, Data2: 0xB60E, Data3: 0x4310, Data4: [0xA2, 0x67, 0x30, 0x3C, 0x53, 0x1F, 0x8E, 0xE5] };
+	/// `PROPID_ACC_NAME`, UI Automation maps it to the `Name` property.
+	const ACCESSIBLE_NAME_PROPERTY: GUID = GUID { Data1: 0x608D_3DF8, Data2: 0x8128, Data3: 0x4AA7, Data4: [0xA4, 0x28, 0xF5, 0x5E, 0x49, 0x26, 0x72, 0x91] };
+
+	/// The window properties with the global atoms of the annotated values.
+	const AUTOMATION_ID_KEY: &str = "SciterAutomationId";
+	const ACCESSIBLE_NAME_KEY: &str = "SciterAccessibleName";
+
+	/// The `IAccPropServices` vtable up to the `HWND` annotation methods.
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct IAccPropServicesVtbl {
+		QueryInterface: LPVOID,
+		AddRef: LPVOID,
+		Release: extern "system" fn(this: LPVOID) -> UINT,
+		SetPropValue: LPVOID,
+		SetPropServer: LPVOID,
+		ClearProps: LPVOID,
+		SetHwndProp: LPVOID,
+		SetHwndPropStr: extern "system" fn(this: LPVOID, hwnd: HWINDOW, object: UINT, child: UINT, prop: GUID, s: LPCWSTR) -> i32,
+		SetHwndPropServer: LPVOID,
+		ClearHwndProps: extern "system" fn(this: LPVOID, hwnd: HWINDOW, object: UINT, child: UINT, props: *const GUID, count: INT) -> i32,
+	}
+
+	/// Annotate the window client via `IAccPropServices` (it needs COM to be initialized)
+	/// and keep the value as a global atom in the `key` window property, an empty value clears both.
+	fn set_automation_property(hwnd: HWINDOW, key: &str, prop: GUID, value: &str) {
+		const OBJID_CLIENT: UINT = 0xFFFF_FFFC;
+		const CHILDID_SELF: UINT = 0;
+		const CLSCTX_INPROC_SERVER: UINT = 0x1;
+		let key = s2w!(key);
+		let atom = unsafe { RemovePropW(hwnd, key.as_ptr()) } as usize as u16;
+		if atom != 0 {
+			unsafe { GlobalDeleteAtom(atom) };
+		}
+		let cleared = value.is_empty();
+		let value = s2w!(value);
+		if !cleared {
+			// atoms are limited to 255 characters, the annotation is not
+			let atom = unsafe { GlobalAddAtomW(value.as_ptr()) };
+			if atom != 0 {
+				unsafe { SetPropW(hwnd, key.as_ptr(), atom as usize as LPVOID) };
+			}
+		}
+
+		let mut services: LPVOID = ::std::ptr::null_mut();
+		unsafe {
+			if CoCreateInstance(&CLSID_ACC_PROP_SERVICES, ::std::ptr::null_mut(), CLSCTX_INPROC_SERVER, &IID_ACC_PROP_SERVICES, &mut services) < 0 || services.is_null() {
+				return;
+			}
+			let vtbl = &**(services as *const *const IAccPropServicesVtbl);
+			if cleared {
+				(vtbl.ClearHwndProps)(services, hwnd, OBJID_CLIENT, CHILDID_SELF, &prop, 1);
+			} else {
+				(vtbl.SetHwndPropStr)(services, hwnd, OBJID_CLIENT, CHILDID_SELF, prop, value.as_ptr());
+			}
+			(vtbl.Release)(services);
+		}
+	}
+
+	/// Clear the annotations of a destroyed window, `IAccPropServices` keeps them by the `HWND` value.
+	#[cfg_attr(feature = "windowless", allow(dead_code))]
+	fn forget_automation_properties(hwnd: HWINDOW) {
+		let is_set = |key: &str| {
+			let key = s2w!(key);
+			!unsafe { GetPropW(hwnd, key.as_ptr()) }.is_null()
+		};
+		if is_set(AUTOMATION_ID_KEY) {
+			set_automation_property(hwnd, AUTOMATION_ID_KEY, AUTOMATION_ID_PROPERTY, "");
+		}
+		if is_set(ACCESSIBLE_NAME_KEY) {
+			set_automation_property(hwnd, ACCESSIBLE_NAME_KEY, ACCESSIBLE_NAME_PROPERTY, "");
+		}
+	}
+
+	#[link(name = "shell32")]
+	extern "system" {
+		fn DragAcceptFiles(hwnd: HWINDOW, accept: BOOL);
+		fn DragQueryFileW(drop: LPVOID, index: UINT, file: LPWSTR, size: UINT) -> UINT;
+		fn DragFinish(drop: LPVOID);
+		fn SetCurrentProcessExplicitAppUserModelID(app_id: LPCWSTR) -> i32; // HRESULT
+		fn Shell_NotifyIconW(message: UINT, data: *const NOTIFYICONDATAW) -> BOOL;
+	}
+
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct NOTIFYICONDATAW {
+		cbSize: UINT,
+		hWnd: HWINDOW,
+		uID: UINT,
+		uFlags: UINT,
+		uCallbackMessage: UINT,
+		hIcon: LPVOID,
+		szTip: [WCHAR; 128],
+		dwState: UINT,
+		dwStateMask: UINT,
+		szInfo: [WCHAR; 256],
+		uVersion: UINT,
+		szInfoTitle: [WCHAR; 64],
+		dwInfoFlags: UINT,
+		guidItem: GUID,
+		hBalloonIcon: LPVOID,
+	}
+
+	/// `Shell_NotifyIconW` icon of `TrayIcon`, it reports the clicks to the window as `WM_TRAY_ICON`.
+	pub struct OsTrayIcon {
+		hwnd: HWINDOW,
+		id: UINT,
+		icon: LPVOID,
+	}
+
+	impl OsTrayIcon {
+		pub fn add(hwnd: HWINDOW, id: UINT, image: &[u8], tooltip: &str) -> OsTrayIcon {
+			const NIM_ADD: UINT = 0;
+			const NIF_MESSAGE: UINT = 0x01;
+			const NIF_ICON: UINT = 0x02;
+			const NIF_TIP: UINT = 0x04;
+
+			let icon = if image.is_empty() {
+				::std::ptr::null_mut()
+			} else {
+				unsafe { CreateIconFromResourceEx(image.as_ptr(), image.len() as UINT, true as BOOL, 0x0003_0000, 0, 0, 0) }
+			};
+			let tray = OsTrayIcon { hwnd: hwnd, id: id, icon: icon };
+			let mut data = tray.data();
+			data.uFlags = NIF_MESSAGE | NIF_ICON | NIF_TIP;
+			data.uCallbackMessage = WM_TRAY_ICON;
+			data.hIcon = icon;
+			// the string is truncated and keeps its null terminator
+			for (dst, src) in data.szTip.iter_mut().take(127).zip(tooltip.encode_utf16()) {
+				*dst = src;
+			}
+			unsafe { Shell_NotifyIconW(NIM_ADD, &data) };
+			return tray;
+		}
+
+		pub fn remove(&mut self) {
+			const NIM_DELETE: UINT = 2;
+			unsafe {
+				Shell_NotifyIconW(NIM_DELETE, &self.data());
+				if !self.icon.is_null() {
+					DestroyIcon(self.icon);
+				}
+			}
+			self.icon = ::std::ptr::null_mut();
+		}
+
+		/// The icon is identified by the window and the id.
+		fn data(&self) -> NOTIFYICONDATAW {
+			let mut data: NOTIFYICONDATAW = unsafe { ::std::mem::zeroed() };
+			data.cbSize = ::std::mem::size_of::<NOTIFYICONDATAW>() as UINT;
+			data.hWnd = self.hwnd;
+			data.uID = self.id;
+			return data;
+		}
+	}
+
+	/// Get the file paths of the `WM_DROPFILES` message.
+	#[cfg_attr(feature = "windowless", allow(dead_code))]
+	fn dropped_files(drop: LPVOID) -> Vec<PathBuf> {
+		use std::ffi::OsString;
+		use std::os::windows::ffi::OsStringExt;
+		let count = unsafe { DragQueryFileW(drop, !0, ::std::ptr::null_mut(), 0) };
+		let mut files = Vec::with_capacity(count as usize);
+		for index in 0..count {
+			let len = unsafe { DragQueryFileW(drop, index, ::std::ptr::null_mut(), 0) };
+			let mut file: Vec<u16> = vec![0; len as usize + 1];
+			let len = unsafe { DragQueryFileW(drop, index, file.as_mut_ptr(), len + 1) };
+			file.truncate(len as usize);
+			files.push(OsString::from_wide(&file).into());
+		}
+		return files;
+	}
+
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct MARGINS {
+		cxLeftWidth: INT,
+		cxRightWidth: INT,
+		cyTopHeight: INT,
+		cyBottomHeight: INT,
+	}
+
+	#[link(name = "dwmapi")]
+	extern "system" {
+		fn DwmExtendFrameIntoClientArea(hwnd: HWINDOW, margins: *const MARGINS) -> i32; // HRESULT
+		fn DwmSetWindowAttribute(hwnd: HWINDOW, attribute: UINT, value: LPCVOID, size: UINT) -> i32; // HRESULT
+		fn DwmEnableBlurBehindWindow(hwnd: HWINDOW, blur: *const DWM_BLURBEHIND) -> i32; // HRESULT
+		fn DwmSetIconicThumbnail(hwnd: HWINDOW, bitmap: LPVOID, flags: UINT) -> i32; // HRESULT
+		fn DwmSetIconicLivePreviewBitmap(hwnd: HWINDOW, bitmap: LPVOID, client: *const POINT, flags: UINT) -> i32; // HRESULT
+		fn DwmInvalidateIconicBitmaps(hwnd: HWINDOW) -> i32; // HRESULT
+	}
+
+	#[repr(C)]
+	#[allow(non_snake_case, non_camel_case_types)]
+	struct DWM_BLURBEHIND {
+		dwFlags: UINT,
+		fEnable: BOOL,
+		hRgnBlur: LPVOID,
+		fTransitionOnMaximized: BOOL,
+	}
+
+	#[link(name = "advapi32")]
+	extern "system" {
+		fn RegGetValueW(key: LPVOID, subkey: LPCWSTR, value: LPCWSTR, flags: UINT, kind: *mut UINT, data: LPVOID, size: *mut UINT) -> i32; // LSTATUS
+	}
 
 	pub struct OsWindow {
 		hwnd: HWINDOW,
 		flags: UINT,
+		/// Window style and placement before entering fullscreen.
+		windowed: Cell<Option<(isize, WINDOWPLACEMENT)>>,
+		/// Background color to apply on creation.
+		background: Option<u32>,
+		/// Whether this instance has created the window and destroys it on drop.
+		owned: bool,
+		/// Runs before `SciterCreateWindow`, see `set_create_hook`.
+		create_hook: Option<CreateHook>,
 	}
 
 	impl OsWindow {
@@ -339,36 +1430,331 @@ mod macos {
 			OsWindow {
 				hwnd: 0 as HWINDOW,
 				flags: 0,
+				windowed: Cell::new(None),
+				background: None,
+				owned: false,
+				create_hook: None,
 			}
 		}
 
 		pub fn from(hwnd: HWINDOW) -> OsWindow {
-			OsWindow { hwnd: hwnd, flags: 0 }
+			OsWindow { hwnd: hwnd, flags: 0, windowed: Cell::new(None), background: None, owned: false, create_hook: None }
 		}
 
-		fn get_app() -> *mut Object {
-			let cls = Class::get("NSApplication").expect("`NSApplication` is not registered.");
-			let obj = unsafe { msg_send!(cls, sharedApplication) };
-			return obj;
+		/// Let the `hook` fill the Win32 parameters of the window, applied by `try_create` right after `SciterCreateWindow`.
+		pub fn set_create_hook<F: FnOnce(&mut WindowCreateParams) + 'static>(&mut self, hook: F) {
+			self.create_hook = Some(Box::new(hook));
 		}
 
-		fn init_app() {
-			// By default, unbundled apps start with `NSApplicationActivationPolicyProhibited` (no dock, no menu).
-			let app = OsWindow::get_app();
-			let _: () = unsafe { msg_send!(app, setActivationPolicy:NSApplicationActivationPolicy::Regular) };
+		/// Let DWM ask the window for its thumbnail and live preview instead of rendering them, and drop the cached ones.
+		fn enable_iconic_bitmaps(&self) {
+			const DWMWA_FORCE_ICONIC_REPRESENTATION: UINT = 7;
+			const DWMWA_HAS_ICONIC_BITMAP: UINT = 10;
+			let enable = true as BOOL;
+			let size = ::std::mem::size_of::<BOOL>() as UINT;
+			unsafe {
+				DwmSetWindowAttribute(self.hwnd, DWMWA_FORCE_ICONIC_REPRESENTATION, &enable as *const BOOL as LPCVOID, size);
+				DwmSetWindowAttribute(self.hwnd, DWMWA_HAS_ICONIC_BITMAP, &enable as *const BOOL as LPCVOID, size);
+				DwmInvalidateIconicBitmaps(self.hwnd);
+			}
 		}
 
-		fn view(&self) -> *mut Object {
-			let hwnd = self.get_hwnd();
-			let hwnd = hwnd as *mut Object;
-			return hwnd;
+		/// `BGRA` pixels of the client area with its size, `PrintWindow` renders the window even if it is covered.
+		fn capture_client(&self) -> Option<(INT, INT, Vec<u8>)> {
+			const PW_CLIENTONLY: UINT = 1;
+			const PW_RENDERFULLCONTENT: UINT = 2;
+			let mut rc = RECT::default();
+			unsafe { GetClientRect(self.hwnd, &mut rc) };
+			let (width, height) = (rc.right - rc.left, rc.bottom - rc.top);
+			if width <= 0 || height <= 0 {
+				return None;
+			}
+			let info = BITMAPINFO {
+				bmiHeader: BITMAPINFOHEADER {
+					biSize: ::std::mem::size_of::<BITMAPINFOHEADER>() as UINT,
+					biWidth: width,
+					biHeight: -height, // top-down
+					biPlanes: 1,
+					biBitCount: 32,
+					biCompression: 0, // BI_RGB
+					biSizeImage: 0,
+					biXPelsPerMeter: 0,
+					biYPelsPerMeter: 0,
+					biClrUsed: 0,
+					biClrImportant: 0,
+				},
+				bmiColors: [0],
+			};
+			let pixels = unsafe {
+				let hdc = GetDC(self.hwnd);
+				let mem = CreateCompatibleDC(hdc);
+				let mut bits = ::std::ptr::null_mut();
+				let bitmap = CreateDIBSection(hdc, &info, 0, &mut bits, ::std::ptr::null_mut(), 0);
+				let pixels = if !bitmap.is_null() {
+					let old = SelectObject(mem, bitmap);
+					let ok = PrintWindow(self.hwnd, mem, PW_CLIENTONLY | PW_RENDERFULLCONTENT) != 0;
+					let pixels = if ok {
+						let mut pixels = ::std::slice::from_raw_parts(bits as LPCBYTE, (width * height * 4) as usize).to_vec();
+						// `PrintWindow` leaves the alpha undefined, DWM wants it opaque
+						for pixel in pixels.chunks_mut(4) {
+							pixel[3] = 0xFF;
+						}
+						Some(pixels)
+					} else {
+						None
+					};
+					SelectObject(mem, old);
+					DeleteObject(bitmap);
+					pixels
+				} else {
+					None
+				};
+				DeleteDC(mem);
+				ReleaseDC(self.hwnd, hdc);
+				pixels
+			};
+			return pixels.map(|pixels| (width, height, pixels));
 		}
 
-		fn window(&self) -> *mut Object {
-			let hwnd = self.view();
-			let obj: *mut Object = unsafe { msg_send!(hwnd, window) };
-			assert!(!obj.is_null());
-			return obj;
+		/// `SciterCreateWindow` registers the window class itself, so the parameters are applied to the created window.
+		#[cfg_attr(feature = "windowless", allow(dead_code))]
+		fn apply_create_params(&self, params: &WindowCreateParams) {
+			const GCL_STYLE: INT = -26;
+			const WM_SETICON: UINT = 0x0080;
+			const ICON_SMALL: WPARAM = 0;
+			const ICON_BIG: WPARAM = 1;
+
+			let mut class_style = params.class_style;
+			if let Some(ref name) = params.class_name {
+				let name = s2w!(name);
+				let mut info: WNDCLASSEXW = unsafe { ::std::mem::zeroed() };
+				info.cbSize = ::std::mem::size_of::<WNDCLASSEXW>() as UINT;
+				// the app classes first, then the system ones
+				let found = unsafe { GetClassInfoExW(GetModuleHandleW(::std::ptr::null()), name.as_ptr(), &mut info) } != 0
+					|| unsafe { GetClassInfoExW(::std::ptr::null_mut(), name.as_ptr(), &mut info) } != 0;
+				if found {
+					class_style |= info.style;
+					unsafe {
+						if !info.hIcon.is_null() {
+							SendMessageW(self.hwnd, WM_SETICON, ICON_BIG, info.hIcon as LPARAM);
+						}
+						if !info.hIconSm.is_null() {
+							SendMessageW(self.hwnd, WM_SETICON, ICON_SMALL, info.hIconSm as LPARAM);
+						}
+					}
+				}
+			}
+			unsafe {
+				if class_style != 0 {
+					let style = GetClassLongPtrW(self.hwnd, GCL_STYLE);
+					SetClassLongPtrW(self.hwnd, GCL_STYLE, style | class_style as isize);
+				}
+				if let Some(style) = params.style {
+					SetWindowLongPtrW(self.hwnd, GWL_STYLE, style as isize);
+				}
+				if let Some(ex_style) = params.ex_style {
+					SetWindowLongPtrW(self.hwnd, GWL_EXSTYLE, ex_style as isize);
+				}
+				if let Some(menu) = params.menu {
+					SetMenu(self.hwnd, menu);
+				}
+				if params.style.is_some() || params.ex_style.is_some() {
+					let flags = SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOOWNERZORDER | SWP_NOACTIVATE | SWP_FRAMECHANGED;
+					SetWindowPos(self.hwnd, ::std::ptr::null_mut(), 0, 0, 0, 0, flags);
+				}
+			}
+		}
+
+		/// The `HWND` of the window, Sciter windows are the native ones.
+		pub fn win32_hwnd(&self) -> HWINDOW {
+			self.hwnd
+		}
+
+		/// The `GWL_STYLE` and `GWL_EXSTYLE` of the window as one.
+		pub fn style(&self) -> WindowStyle {
+			let (style, ex_style) = unsafe { (GetWindowLongPtrW(self.hwnd, GWL_STYLE), GetWindowLongPtrW(self.hwnd, GWL_EXSTYLE)) };
+			let bits = u64::from(style as u32) | u64::from(ex_style as u32) << 32;
+			return WindowStyle::from_bits_truncate(bits);
+		}
+
+		/// Apply the style masks with `SetWindowLongPtrW` and `SWP_FRAMECHANGED`, the topmost one with `SetWindowPos`.
+		pub fn modify_style(&self, add: WindowStyle, remove: WindowStyle) {
+			let topmost = WindowStyle::EX_TOPMOST;
+			let update = |index: INT, shift: u32| -> bool {
+				let add = (((add - topmost).bits() >> shift) & 0xFFFF_FFFF) as u32;
+				let remove = (((remove - topmost).bits() >> shift) & 0xFFFF_FFFF) as u32;
+				let old = unsafe { GetWindowLongPtrW(self.hwnd, index) } as u32;
+				let new = (old | add) & !remove;
+				if new != old {
+					unsafe { SetWindowLongPtrW(self.hwnd, index, new as isize) };
+				}
+				return new != old;
+			};
+			let changed = update(GWL_STYLE, 0) | update(GWL_EXSTYLE, 32);
+			unsafe {
+				// `WS_EX_TOPMOST` can not be set directly
+				if add.contains(topmost) || remove.contains(topmost) {
+					let after = if add.contains(topmost) { -1isize } else { -2isize };	// HWND_TOPMOST, HWND_NOTOPMOST
+					SetWindowPos(self.hwnd, after as HWINDOW, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE);
+				}
+				if changed {
+					let flags = SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOOWNERZORDER | SWP_NOACTIVATE | SWP_FRAMECHANGED;
+					SetWindowPos(self.hwnd, ::std::ptr::null_mut(), 0, 0, 0, 0, flags);
+				}
+			}
+		}
+
+		/// Intercept the window messages before Sciter with `SetWindowSubclass`, replacing the previous closure.
+		pub fn subclass<F: Fn(HWINDOW, UINT, WPARAM, LPARAM) -> Option<LRESULT> + 'static>(&mut self, proc: F) {
+			const SUBCLASS_ID: usize = 0x5C;
+			let data = Box::into_raw(Box::new(Box::new(proc) as SubclassProc)) as usize;
+			let mut previous: usize = 0;
+			unsafe {
+				let replaced = GetWindowSubclass(self.hwnd, subclass_proc, SUBCLASS_ID, &mut previous) != 0;
+				if SetWindowSubclass(self.hwnd, subclass_proc, SUBCLASS_ID, data) == 0 {
+					drop(Box::from_raw(data as *mut SubclassProc));
+					return;
+				}
+				if replaced {
+					drop(Box::from_raw(previous as *mut SubclassProc));
+				}
+			}
+		}
+
+		/// Create a Sciter child window inside of the foreign `parent` window.
+		pub fn attach(parent: HWINDOW, rc: RECT) -> OsWindow {
+			assert!(!parent.is_null());
+			let flags = SCITER_CREATE_WINDOW_FLAGS::SW_CHILD | SCITER_CREATE_WINDOW_FLAGS::SW_CONTROLS;
+			let mut wnd = OsWindow::new();
+			BaseWindow::create(&mut wnd, rc, flags.bits() as UINT, parent);
+			return wnd;
+		}
+
+		pub(super) fn init_app() -> Result<(), WindowError> {
+			if OLE_INITIALIZED.with(|initialized| initialized.get()) {
+				return Ok(());
+			}
+			// `S_FALSE` means that the host has initialized OLE already, it has to be balanced as well;
+			// `RPC_E_CHANGED_MODE` means that COM is initialized in a multithreaded apartment.
+			let hr = unsafe { OleInitialize(::std::ptr::null()) };
+			if hr < 0 {
+				return Err(WindowError::OleInitFailed(hr));
+			}
+			OLE_INITIALIZED.with(|initialized| initialized.set(true));
+			return Ok(());
+		}
+
+		/// Balance the `OleInitialize` of `init_app` when the app loop is done.
+		pub(super) fn uninit_app() {
+			if OLE_INITIALIZED.with(|initialized| initialized.replace(false)) {
+				unsafe { OleUninitialize() };
+			}
+		}
+
+		/// Show the hidden window in the `state` with a single `ShowWindow`.
+		pub fn show_in_state(&self, state: WindowState) {
+			const SW_SHOWNORMAL: INT = 1;
+			const SW_SHOWMINIMIZED: INT = 2;
+			const SW_SHOWMAXIMIZED: INT = 3;
+			const SW_SHOW: INT = 5;
+			let show = match state {
+				WindowState::Hidden => return,
+				WindowState::Normal => SW_SHOWNORMAL,
+				WindowState::Minimized => SW_SHOWMINIMIZED,
+				WindowState::Maximized => SW_SHOWMAXIMIZED,
+				WindowState::Fullscreen => {
+					// the hidden window is moved over the monitor first
+					self.set_fullscreen(true);
+					SW_SHOW
+				},
+			};
+			unsafe { ShowWindow(self.hwnd, show) };
+		}
+
+		/// Read the `AppsUseLightTheme` personalization setting, Windows 10 1809+ (light before).
+		pub fn current_theme() -> Theme {
+			const HKEY_CURRENT_USER: usize = 0x8000_0001;
+			const RRF_RT_REG_DWORD: UINT = 0x0010;
+			let subkey = s2w!("Software\\Microsoft\\Windows\\CurrentVersion\\Themes\\Personalize");
+			let name = s2w!("AppsUseLightTheme");
+			let (mut light, mut size): (UINT, UINT) = (1, ::std::mem::size_of::<UINT>() as UINT);
+			let status = unsafe {
+				RegGetValueW(HKEY_CURRENT_USER as LPVOID, subkey.as_ptr(), name.as_ptr(), RRF_RT_REG_DWORD, ::std::ptr::null_mut(), &mut light as *mut UINT as LPVOID, &mut size)
+			};
+			return if status == 0 && light == 0 { Theme::Dark } else { Theme::Light };
+		}
+
+		/// List the monitors via `EnumDisplayMonitors`.
+		pub fn enumerate_monitors() -> Vec<MonitorInfo> {
+			extern "system" fn on_monitor(monitor: LPVOID, _hdc: LPVOID, _rc: LPRECT, param: LPARAM) -> BOOL {
+				let list = unsafe { &mut *(param as *mut Vec<MonitorInfo>) };
+				list.extend(OsWindow::monitor_info(monitor));
+				return true as BOOL;
+			}
+			let mut list = Vec::new();
+			unsafe { EnumDisplayMonitors(::std::ptr::null_mut(), ::std::ptr::null(), on_monitor, &mut list as *mut Vec<MonitorInfo> as LPARAM) };
+			return list;
+		}
+
+		/// Describe the `HMONITOR`.
+		fn monitor_info(monitor: LPVOID) -> Option<MonitorInfo> {
+			const MONITORINFOF_PRIMARY: UINT = 1;
+			let mut info = MONITORINFO { cbSize: ::std::mem::size_of::<MONITORINFO>() as UINT, ..Default::default() };
+			if unsafe { GetMonitorInfoW(monitor, &mut info) } == 0 {
+				return None;
+			}
+			return Some(MonitorInfo {
+				rect: info.rcMonitor,
+				work_area: info.rcWork,
+				scale: OsWindow::monitor_scale(monitor),
+				is_primary: (info.dwFlags & MONITORINFOF_PRIMARY) != 0,
+			});
+		}
+
+		/// Get the effective DPI scale of the monitor, falls back to the system DPI before Windows 8.1.
+		fn monitor_scale(monitor: LPVOID) -> f32 {
+			type GetDpiForMonitor = extern "system" fn(monitor: LPVOID, kind: UINT, x: *mut UINT, y: *mut UINT) -> i32;
+			const MDT_EFFECTIVE_DPI: UINT = 0;
+			const LOGPIXELSX: INT = 88;
+			let shcore = unsafe { LoadLibraryW(s2w!("shcore.dll").as_ptr()) };
+			let get_dpi = if shcore.is_null() { shcore as LPCVOID } else { unsafe { GetProcAddress(shcore, b"GetDpiForMonitor\0".as_ptr() as LPCSTR) } };
+			let (mut dpi, mut unused) = (0, 0);
+			if !get_dpi.is_null() {
+				let get_dpi: GetDpiForMonitor = unsafe { ::std::mem::transmute::<LPCVOID, GetDpiForMonitor>(get_dpi) };
+				get_dpi(monitor, MDT_EFFECTIVE_DPI, &mut dpi, &mut unused);
+			} else {
+				unsafe {
+					let hdc = GetDC(::std::ptr::null_mut());
+					dpi = GetDeviceCaps(hdc, LOGPIXELSX) as UINT;
+					ReleaseDC(::std::ptr::null_mut(), hdc);
+				}
+			}
+			return if dpi > 0 { dpi as f32 / 96.0 } else { 1.0 };
+		}
+
+		/// Update the window size limits and apply them to the current size.
+		fn set_size_limits(&self, min: Option<SIZE>, max: Option<SIZE>) {
+			SIZE_LIMITS.with(|limits| {
+				let mut limits = limits.borrow_mut();
+				let entry = limits.entry(self.hwnd).or_insert_with(Default::default);
+				if let Some(min) = min {
+					entry.0 = min;
+				}
+				if let Some(max) = max {
+					entry.1 = max;
+				}
+			});
+			let rc = self.get_rect();
+			self.resize(rc.width(), rc.height());
+		}
+	}
+
+	/// Destroy the window created by this instance, unless it is destroyed already.
+	impl Drop for OsWindow {
+		fn drop(&mut self) {
+			if self.owned && super::window_alive(self.hwnd) {
+				unsafe { DestroyWindow(self.hwnd) };
+			}
 		}
 	}
 
@@ -378,100 +1764,7168 @@ mod macos {
 			return self.hwnd;
 		}
 
+		/// Keep the window alive when this instance is dropped.
+		fn release(&mut self) {
+			self.owned = false;
+		}
+
+		/// Move the window into the `parent` as a child one, or make it a top-level window again if it is null.
+		fn set_parent(&mut self, parent: HWINDOW) {
+			const WS_CHILD: isize = 0x4000_0000;
+			const WS_POPUP: isize = 0x8000_0000u32 as INT as isize;
+			let child = SCITER_CREATE_WINDOW_FLAGS::SW_CHILD.bits() as UINT;
+			unsafe {
+				let style = GetWindowLongPtrW(self.hwnd, GWL_STYLE);
+				if parent.is_null() {
+					SetParent(self.hwnd, ::std::ptr::null_mut());
+					SetWindowLongPtrW(self.hwnd, GWL_STYLE, (style & !WS_CHILD) | WS_OVERLAPPEDWINDOW);
+				} else {
+					// the style goes first: a child can not have the frame or be a popup
+					SetWindowLongPtrW(self.hwnd, GWL_STYLE, (style & !(WS_POPUP | WS_OVERLAPPEDWINDOW)) | WS_CHILD);
+					SetParent(self.hwnd, parent);
+				}
+				let flags = SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOOWNERZORDER | SWP_NOACTIVATE | SWP_FRAMECHANGED;
+				SetWindowPos(self.hwnd, ::std::ptr::null_mut(), 0, 0, 0, 0, flags);
+			}
+			self.flags = if parent.is_null() { self.flags & !child } else { self.flags | child };
+		}
+
 		/// Create a new native window.
 		fn create(&mut self, rc: RECT, flags: UINT, parent: HWINDOW) -> HWINDOW {
+			match self.try_create(rc, flags, parent) {
+				Ok(hwnd) => hwnd,
+				Err(error) => panic!("Failed to create window: {}", error),
+			}
+		}
+
+		/// Create a new native window or tell why it can not be created.
+		fn try_create(&mut self, rc: RECT, flags: UINT, parent: HWINDOW) -> Result<HWINDOW, WindowError> {
+			::check_api().map_err(WindowError::LibraryNotFound)?;
+
 			if (flags & SCITER_CREATE_WINDOW_FLAGS::SW_MAIN.bits()) != 0 {
-				OsWindow::init_app();
+				OsWindow::init_app()?;
 			}
 
 			self.flags = flags;
 
 			#[cfg(not(feature = "windowless"))]
 			{
-				let w = rc.right - rc.left;
-				let h = rc.bottom - rc.top;
-				let prc: *const RECT = if w > 0 && h > 0 { &rc } else { std::ptr::null() };
-
-				let cb = std::ptr::null();
-				self.hwnd = (_API.SciterCreateWindow)(flags, prc, 0 as LPVOID, 0 as LPVOID, 0 as LPVOID);
+				let params = self.create_hook.take().map(|hook| {
+					let mut params = WindowCreateParams::default();
+					hook(&mut params);
+					params
+				});
+				let cb = window_delegate as SciterWindowDelegate as *const SciterWindowDelegate;
+				self.hwnd = (_API.SciterCreateWindow)(flags, &rc, cb, 0 as LPVOID, parent);
 				if self.hwnd.is_null() {
-					panic!("Failed to create window!");
+					return Err(WindowError::CreateFailed);
+				}
+				self.owned = true;
+				super::window_created(self.hwnd);
+				if let Some(params) = params {
+					// the window is still hidden
+					self.apply_create_params(&params);
+				}
+				// the idle time is counted since the creation
+				super::input_received(self.hwnd);
+				if let Some(color) = self.background {
+					self.set_background_color(color);
 				}
 			}
 			#[cfg(feature = "windowless")]
 			{
 				let _ = rc;
 				let _ = parent;
+				let _ = self.create_hook.take();
 				let _ = &(_API.SciterVersion);
 			}
-			return self.hwnd;
+
+			return Ok(self.hwnd);
 		}
 
-		/// Minimize or hide window.
-		fn collapse(&self, hide: bool) {
-			let wnd = self.window();
-			if hide {
-				let _: () = unsafe { msg_send!(wnd, orderOut:0) };
-			} else {
-				let hwnd = self.view();
-				let _: () = unsafe { msg_send!(wnd, performMiniaturize:hwnd) };
-			}
+		/// Create a new native window and show it in the `state` right away.
+		fn create_with_state(&mut self, rc: RECT, flags: UINT, parent: HWINDOW, state: WindowState) -> HWINDOW {
+			let hwnd = self.create(rc, flags, parent);
+			self.show_in_state(state);
+			return hwnd;
 		}
 
-		/// Show or maximize window.
-		fn expand(&self, maximize: bool) {
-			let wnd = self.window();
-			if (self.flags & SCITER_CREATE_WINDOW_FLAGS::SW_TITLEBAR.bits()) != 0 {
+		/// Create a new native window centered on the monitor, the size is scaled by the monitor DPI.
+		fn create_on_monitor(&mut self, monitor_index: usize, size: (i32, i32), flags: UINT, parent: HWINDOW) -> HWINDOW {
+			let rc = super::monitor_centered_rect(&OsWindow::enumerate_monitors(), monitor_index, size, true);
+			return self.create(rc, flags, parent);
+		}
+
+		/// Create a new native window which stays hidden and off the taskbar until it is shown.
+		fn create_hidden(&mut self, flags: UINT, parent: HWINDOW) -> HWINDOW {
+			let hwnd = self.create(RECT::default(), flags, parent);
+			self.set_skip_taskbar(true);
+			return hwnd;
+		}
+
+		/// Create a message-only window: it has the layout and scripts, but can not be shown.
+		fn try_create_offscreen(&mut self, size: (i32, i32)) -> Result<HWINDOW, WindowError> {
+			const HWND_MESSAGE: isize = -3;
+			let rc = RECT { left: 0, top: 0, right: size.0, bottom: size.1 };
+			return self.try_create(rc, SCITER_CREATE_WINDOW_FLAGS::SW_CHILD.bits() as UINT, HWND_MESSAGE as HWINDOW);
+		}
+
+		/// Minimize or hide window.
+		fn collapse(&self, hide: bool) {
+			let n: INT = if hide { 0 } else { 6 };
+			unsafe { ShowWindow(self.hwnd, n) };
+		}
+
+		/// Show or maximize window.
+		fn expand(&self, maximize: bool) {
+			let n: INT = if maximize { 3 } else { 1 };
+			unsafe { ShowWindow(self.hwnd, n) };
+		}
+
+		/// Show the window in its current state.
+		fn show(&self) {
+			const SW_SHOW: INT = 5;
+			unsafe { ShowWindow(self.hwnd, SW_SHOW) };
+		}
+
+		/// Hide the window.
+		fn hide(&self) {
+			self.collapse(true);
+		}
+
+		/// Minimize the window.
+		fn minimize(&self) {
+			self.collapse(false);
+		}
+
+		/// Show and maximize the window.
+		fn maximize(&self) {
+			self.expand(true);
+		}
+
+		/// Leave fullscreen, minimized and maximized states.
+		fn restore(&self) {
+			const SW_RESTORE: INT = 9;
+			self.set_fullscreen(false);
+			unsafe { ShowWindow(self.hwnd, SW_RESTORE) };
+			// a minimized window is restored to maximized if it was
+			if self.get_state() == WindowState::Maximized {
+				unsafe { ShowWindow(self.hwnd, SW_RESTORE) };
+			}
+		}
+
+		/// Restore the window if it is minimized and bring it to the foreground.
+		///
+		/// `SetForegroundWindow` is refused to the processes in the background,
+		/// unless their thread shares the input state with the foreground one.
+		fn activate(&self) {
+			const SW_RESTORE: INT = 9;
+			const SW_SHOW: INT = 5;
+			unsafe {
+				ShowWindow(self.hwnd, if IsIconic(self.hwnd) != 0 { SW_RESTORE } else { SW_SHOW });
+				let current = GetCurrentThreadId();
+				let foreground = GetForegroundWindow();
+				let other = if foreground.is_null() { current } else { GetWindowThreadProcessId(foreground, ::std::ptr::null_mut()) };
+				let attached = other != current && AttachThreadInput(current, other, true as BOOL) != 0;
+				BringWindowToTop(self.hwnd);
+				SetForegroundWindow(self.hwnd);
+				if attached {
+					AttachThreadInput(current, other, false as BOOL);
+				}
+			}
+		}
+
+		/// Close window.
+		fn dismiss(&self) {
+			unsafe { DestroyWindow(self.hwnd) };
+		}
+
+		/// Ask the window to close, it can be cancelled by the window.
+		fn request_close(&self) {
+			const WM_CLOSE: UINT = 0x0010;
+			unsafe { PostMessageW(self.hwnd, WM_CLOSE, 0, 0) };
+		}
+
+		/// Ask the callback on `WM_CLOSE`, `false` cancels the closing.
+		fn on_close<F: Fn() -> bool + 'static>(&mut self, callback: F) {
+			super::set_close_handler(self.hwnd, Rc::new(callback));
+		}
+
+		/// Restore the focused element on `WM_ACTIVATE`, enabled by default.
+		fn set_restore_focus(&mut self, enable: bool) {
+			super::set_restore_focus(self.hwnd, enable);
+		}
+
+		/// Call the callback with the new window size on `WM_SIZE`.
+		fn on_resize<F: Fn(i32, i32) + 'static>(&mut self, callback: F) {
+			super::set_resize_handler(self.hwnd, self.get_rect(), Rc::new(callback));
+		}
+
+		/// Call the callback with the new window position on `WM_MOVE`.
+		fn on_move<F: Fn(i32, i32) + 'static>(&mut self, callback: F) {
+			super::set_move_handler(self.hwnd, self.get_rect(), Rc::new(callback));
+		}
+
+		/// Call the callback with the new scale on `WM_DPICHANGED`.
+		fn on_dpi_changed<F: Fn(f32) + 'static>(&mut self, callback: F) {
+			super::set_dpi_handler(self.hwnd, self.get_dpi_scale(), Rc::new(callback));
+		}
+
+		/// Check the window monitor on `WM_MOVE`, `WM_SIZE` and `WM_DISPLAYCHANGE`.
+		fn on_monitor_changed<F: Fn(MonitorInfo, Option<f32>) + 'static>(&mut self, callback: F) {
+			super::set_monitor_handler(self.hwnd, Rc::new(callback));
+		}
+
+		/// Call the callback with the new theme on `WM_SETTINGCHANGE` of the `ImmersiveColorSet`, top-level windows only.
+		fn on_theme_changed<F: Fn(Theme) + 'static>(&mut self, callback: F) {
+			super::set_theme_handler(self.hwnd, OsWindow::current_theme(), Rc::new(callback));
+		}
+
+		/// Accept the files dropped from the Explorer via `WM_DROPFILES`.
+		fn on_file_drop<F: Fn(Vec<PathBuf>) + 'static>(&mut self, callback: F) {
+			if super::set_drop_handler(self.hwnd, Rc::new(callback)) {
+				// OLE drop targets take precedence over `WM_DROPFILES`
+				unsafe {
+					RevokeDragDrop(self.hwnd);
+					DragAcceptFiles(self.hwnd, true as BOOL);
+				}
+			}
+		}
+
+		/// Set native window title.
+		fn set_title(&mut self, title: &str) {
+			let s = s2w!(title);
+			unsafe { SetWindowTextW(self.hwnd, s.as_ptr()) };
+		}
+
+		/// Set the UI Automation `AutomationId` of the window client,
+		/// it is also stored in the `SciterAutomationId` window property for the older tools.
+		fn set_automation_id(&mut self, id: &str) {
+			set_automation_property(self.hwnd, AUTOMATION_ID_KEY, AUTOMATION_ID_PROPERTY, id);
+		}
+
+		/// Set the accessible `Name` of the window client,
+		/// it is also stored in the `SciterAccessibleName` window property.
+		fn set_accessible_name(&mut self, name: &str) {
+			set_automation_property(self.hwnd, ACCESSIBLE_NAME_KEY, ACCESSIBLE_NAME_PROPERTY, name);
+		}
+
+		/// Get native window title.
+		fn get_title(&self) -> String {
+			// the title may change between the calls, a full buffer means that it might be truncated
+			const ATTEMPTS: usize = 4;
+			let mut capacity = unsafe { GetWindowTextLengthW(self.hwnd) } + 1;
+			let mut title: Vec<u16> = Vec::new();
+			for _ in 0..ATTEMPTS {
+				title.resize(capacity as usize, 0);
+				let copied = unsafe { GetWindowTextW(self.hwnd, title.as_mut_ptr(), capacity) }.max(0);
+				title.truncate(copied as usize);
+				if copied < capacity - 1 {
+					break;
+				}
+				let length = unsafe { GetWindowTextLengthW(self.hwnd) } + 1;
+				capacity = length.max(capacity * 2);
+			}
+			return ::utf::w2sn(title.as_ptr(), title.len());
+		}
+
+		/// Move the window to the specified screen position.
+		fn move_to(&self, x: i32, y: i32) {
+			unsafe { SetWindowPos(self.hwnd, ::std::ptr::null_mut(), x, y, 0, 0, SWP_NOSIZE | SWP_NOZORDER | SWP_NOACTIVATE) };
+		}
+
+		/// Resize the window.
+		fn resize(&self, width: i32, height: i32) {
+			unsafe { SetWindowPos(self.hwnd, ::std::ptr::null_mut(), 0, 0, width, height, SWP_NOMOVE | SWP_NOZORDER | SWP_NOACTIVATE) };
+		}
+
+		/// Move and resize the window.
+		fn set_rect(&self, rc: RECT) {
+			let (w, h) = (rc.right - rc.left, rc.bottom - rc.top);
+			unsafe { SetWindowPos(self.hwnd, ::std::ptr::null_mut(), rc.left, rc.top, w, h, SWP_NOZORDER | SWP_NOACTIVATE) };
+		}
+
+		/// Apply the changes with a single `SetWindowPos`,
+		/// or with a single `SetWindowPlacement` to minimize, maximize or restore the window (and the z-order separately).
+		fn update_geometry(&self, changes: GeometryUpdate) {
+			const SWP_SHOWWINDOW: UINT = 0x0040;
+			const SWP_HIDEWINDOW: UINT = 0x0080;
+			const SW_SHOWNORMAL: UINT = 1;
+			const SW_SHOWMINIMIZED: UINT = 2;
+			const SW_SHOWMAXIMIZED: UINT = 3;
+			// `SetWindowPos` notifies the window even without changes
+			if changes == GeometryUpdate::default() {
+				return;
+			}
+			let current = self.get_state();
+			// it restores its own geometry
+			if current == WindowState::Fullscreen && changes.state.is_some() && changes.state != Some(WindowState::Fullscreen) {
+				self.set_fullscreen(false);
+			}
+			let after = match changes.z_order {
+				None => None,
+				Some(ZOrder::Top) => Some(0isize),
+				Some(ZOrder::Bottom) => Some(1isize),
+				Some(ZOrder::Topmost) => Some(-1isize),
+				Some(ZOrder::NotTopmost) => Some(-2isize),
+			};
+			let show = match changes.state {
+				Some(WindowState::Minimized) => Some(SW_SHOWMINIMIZED),
+				Some(WindowState::Maximized) => Some(SW_SHOWMAXIMIZED),
+				Some(WindowState::Normal) if current == WindowState::Minimized || current == WindowState::Maximized => Some(SW_SHOWNORMAL),
+				_ => None,
+			};
+
+			if let Some(show) = show {
+				let mut wp = WINDOWPLACEMENT { length: ::std::mem::size_of::<WINDOWPLACEMENT>() as UINT, ..Default::default() };
+				unsafe { GetWindowPlacement(self.hwnd, &mut wp) };
+				if changes.position.is_some() || changes.size.is_some() {
+					let normal = wp.rcNormalPosition;
+					let (dx, dy) = workspace_offset(&normal);
+					let (x, y) = changes.position.unwrap_or((normal.left + dx, normal.top + dy));
+					let (width, height) = changes.size.unwrap_or((normal.width(), normal.height()));
+					let rc = RECT { left: x, top: y, right: x + width, bottom: y + height };
+					let (dx, dy) = workspace_offset(&rc);
+					wp.rcNormalPosition = RECT { left: rc.left - dx, top: rc.top - dy, right: rc.right - dx, bottom: rc.bottom - dy };
+				}
+				wp.showCmd = show;
+				unsafe { SetWindowPlacement(self.hwnd, &wp) };
+				if let Some(after) = after {
+					unsafe { SetWindowPos(self.hwnd, after as HWINDOW, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOACTIVATE) };
+				}
+				return;
+			}
+
+			let mut flags = SWP_NOACTIVATE;
+			let (x, y) = changes.position.unwrap_or_else(|| { flags |= SWP_NOMOVE; (0, 0) });
+			let (width, height) = changes.size.unwrap_or_else(|| { flags |= SWP_NOSIZE; (0, 0) });
+			let after = after.unwrap_or_else(|| { flags |= SWP_NOZORDER; 0 });
+			match changes.state {
+				Some(WindowState::Hidden) => flags |= SWP_HIDEWINDOW,
+				Some(WindowState::Normal) => flags |= SWP_SHOWWINDOW,
+				_ => {},
+			}
+			unsafe { SetWindowPos(self.hwnd, after as HWINDOW, x, y, width, height, flags) };
+			if changes.state == Some(WindowState::Fullscreen) {
+				self.show_in_state(WindowState::Fullscreen);
+			}
+		}
+
+		/// Get the window rectangle in screen coordinates.
+		fn get_rect(&self) -> RECT {
+			let mut rc = RECT::default();
+			unsafe { GetWindowRect(self.hwnd, &mut rc) };
+			return rc;
+		}
+
+		/// Set the minimum window size, zero means no limit.
+		fn set_min_size(&self, width: i32, height: i32) {
+			self.set_size_limits(Some(SIZE { cx: width, cy: height }), None);
+		}
+
+		/// Set the maximum window size, zero means no limit.
+		fn set_max_size(&self, width: i32, height: i32) {
+			self.set_size_limits(None, Some(SIZE { cx: width, cy: height }));
+		}
+
+		/// The document minimal size from `SciterGetMinWidth` and `SciterGetMinHeight` plus the window frame.
+		fn sync_min_size_from_content(&self, track: bool) {
+			let content = super::content_min_size(self.hwnd);
+			let (window, mut client) = (self.get_rect(), RECT::default());
+			unsafe { GetClientRect(self.hwnd, &mut client) };
+			let frame = SIZE { cx: window.width() - client.width(), cy: window.height() - client.height() };
+			self.set_min_size(content.cx + frame.cx, content.cy + frame.cy);
+			super::track_content_min_size(self.hwnd, track);
+		}
+
+		/// Center the window in the work area of the monitor it overlaps most.
+		fn center(&self) {
+			let mut monitor = MONITORINFO { cbSize: ::std::mem::size_of::<MONITORINFO>() as UINT, ..Default::default() };
+			if unsafe { GetMonitorInfoW(MonitorFromWindow(self.hwnd, MONITOR_DEFAULTTONEAREST), &mut monitor) } == 0 {
+				return;
+			}
+			let (rc, work) = (self.get_rect(), monitor.rcWork);
+			self.move_to(work.left + (work.width() - rc.width()) / 2, work.top + (work.height() - rc.height()) / 2);
+		}
+
+		/// The monitor the window overlaps most, or the nearest one if it is off-screen.
+		fn current_monitor(&self) -> Option<MonitorInfo> {
+			if self.hwnd.is_null() {
+				return None;
+			}
+			return OsWindow::monitor_info(unsafe { MonitorFromWindow(self.hwnd, MONITOR_DEFAULTTONEAREST) });
+		}
+
+		/// `dmDisplayFrequency` of the current settings of the window monitor.
+		fn get_refresh_rate(&self) -> Option<f32> {
+			const ENUM_CURRENT_SETTINGS: UINT = 0xFFFF_FFFF;
+			if self.hwnd.is_null() {
+				return None;
+			}
+			let mut info: MONITORINFOEXW = unsafe { ::std::mem::zeroed() };
+			info.info.cbSize = ::std::mem::size_of::<MONITORINFOEXW>() as UINT;
+			let monitor = unsafe { MonitorFromWindow(self.hwnd, MONITOR_DEFAULTTONEAREST) };
+			if unsafe { GetMonitorInfoW(monitor, &mut info as *mut MONITORINFOEXW as *mut MONITORINFO) } == 0 {
+				return None;
+			}
+			let mut mode: DEVMODEW = unsafe { ::std::mem::zeroed() };
+			mode.dmSize = ::std::mem::size_of::<DEVMODEW>() as u16;
+			if unsafe { EnumDisplaySettingsW(info.szDevice.as_ptr(), ENUM_CURRENT_SETTINGS, &mut mode) } == 0 {
+				return None;
+			}
+			// 0 and 1 mean the hardware default one
+			return if mode.dmDisplayFrequency > 1 { Some(mode.dmDisplayFrequency as f32) } else { None };
+		}
+
+		/// Save the normal (not maximized) rect of `GetWindowPlacement`.
+		fn save_placement(&self) -> WindowPlacement {
+			const SW_SHOWMAXIMIZED: UINT = 3;
+			let mut placement = WINDOWPLACEMENT { length: ::std::mem::size_of::<WINDOWPLACEMENT>() as UINT, ..Default::default() };
+			let mut monitor = MONITORINFO { cbSize: ::std::mem::size_of::<MONITORINFO>() as UINT, ..Default::default() };
+			unsafe {
+				GetWindowPlacement(self.hwnd, &mut placement);
+				GetMonitorInfoW(MonitorFromWindow(self.hwnd, MONITOR_DEFAULTTONEAREST), &mut monitor);
+			}
+			// the normal rect is in the work area coordinates
+			let (dx, dy) = (monitor.rcWork.left - monitor.rcMonitor.left, monitor.rcWork.top - monitor.rcMonitor.top);
+			let (rc, m) = (placement.rcNormalPosition, monitor.rcMonitor);
+			return WindowPlacement {
+				position: (rc.left + dx, rc.top + dy),
+				size: (rc.width(), rc.height()),
+				maximized: placement.showCmd == SW_SHOWMAXIMIZED,
+				monitor: (m.left, m.top, m.right, m.bottom),
+			};
+		}
+
+		/// Set the normal rect and the state via `SetWindowPlacement`, hidden windows stay hidden unless maximized.
+		fn apply_placement(&self, placement: &WindowPlacement) {
+			const SW_HIDE: UINT = 0;
+			const SW_SHOWNORMAL: UINT = 1;
+			const SW_SHOWMAXIMIZED: UINT = 3;
+			let rc = super::placement_rect(placement, &OsWindow::enumerate_monitors());
+			let mut wp = WINDOWPLACEMENT { length: ::std::mem::size_of::<WINDOWPLACEMENT>() as UINT, ..Default::default() };
+			unsafe { GetWindowPlacement(self.hwnd, &mut wp) };
+			let (dx, dy) = workspace_offset(&rc);
+			wp.rcNormalPosition = RECT { left: rc.left - dx, top: rc.top - dy, right: rc.right - dx, bottom: rc.bottom - dy };
+			wp.showCmd = if placement.maximized {
+				SW_SHOWMAXIMIZED
+			} else if self.is_visible() {
+				SW_SHOWNORMAL
+			} else {
+				SW_HIDE
+			};
+			unsafe { SetWindowPlacement(self.hwnd, &wp) };
+		}
+
+		/// Enter or leave borderless fullscreen on the current monitor.
+		fn set_fullscreen(&self, enable: bool) {
+			// https://devblogs.microsoft.com/oldnewthing/20100412-00/?p=14353
+			let null: HWINDOW = ::std::ptr::null_mut();
+			unsafe {
+				if enable {
+					if self.windowed.get().is_some() {
+						return;
+					}
+					let style = GetWindowLongPtrW(self.hwnd, GWL_STYLE);
+					let mut placement = WINDOWPLACEMENT { length: ::std::mem::size_of::<WINDOWPLACEMENT>() as UINT, ..Default::default() };
+					let mut monitor = MONITORINFO { cbSize: ::std::mem::size_of::<MONITORINFO>() as UINT, ..Default::default() };
+					if GetWindowPlacement(self.hwnd, &mut placement) == 0
+						|| GetMonitorInfoW(MonitorFromWindow(self.hwnd, MONITOR_DEFAULTTONEAREST), &mut monitor) == 0 {
+						return;
+					}
+					self.windowed.set(Some((style, placement)));
+
+					let rc = monitor.rcMonitor;
+					SetWindowLongPtrW(self.hwnd, GWL_STYLE, style & !WS_OVERLAPPEDWINDOW);
+					SetWindowPos(self.hwnd, null, rc.left, rc.top, rc.right - rc.left, rc.bottom - rc.top, SWP_NOOWNERZORDER | SWP_FRAMECHANGED);
+				} else if let Some((style, placement)) = self.windowed.take() {
+					SetWindowLongPtrW(self.hwnd, GWL_STYLE, style);
+					SetWindowPlacement(self.hwnd, &placement);
+					SetWindowPos(self.hwnd, null, 0, 0, 0, 0, SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOOWNERZORDER | SWP_FRAMECHANGED);
+				}
+			}
+		}
+
+		/// Remove or restore the native caption and sizing border.
+		fn set_frameless(&self, enable: bool) {
+			let frame = WindowStyle::CAPTION | WindowStyle::THICKFRAME;
+			if enable {
+				self.modify_style(WindowStyle::empty(), frame);
+			} else {
+				self.modify_style(frame, WindowStyle::empty());
+			}
+		}
+
+		/// Add or remove the sizing border and the maximize button, the window keeps its size.
+		fn set_resizable(&self, enable: bool) {
+			let sizing = WindowStyle::THICKFRAME | WindowStyle::MAXIMIZEBOX;
+			if enable {
+				self.modify_style(sizing, WindowStyle::empty());
+			} else {
+				self.modify_style(WindowStyle::empty(), sizing);
+			}
+		}
+
+		/// Whether the window has the sizing border.
+		fn is_resizable(&self) -> bool {
+			return self.style().contains(WindowStyle::THICKFRAME);
+		}
+
+		/// Toggle the DWM non-client rendering, which draws the shadow, and the `CS_DROPSHADOW` class style.
+		fn set_shadow(&self, enable: bool) {
+			const DWMWA_NCRENDERING_POLICY: UINT = 2;
+			const DWMNCRP_DISABLED: INT = 1;
+			const DWMNCRP_ENABLED: INT = 2;
+			const GCL_STYLE: INT = -26;
+			const CS_DROPSHADOW: isize = 0x0002_0000;
+			let policy = if enable { DWMNCRP_ENABLED } else { DWMNCRP_DISABLED };
+			unsafe {
+				// fails without the desktop composition, there is no shadow then
+				DwmSetWindowAttribute(self.hwnd, DWMWA_NCRENDERING_POLICY, &policy as *const INT as LPCVOID, ::std::mem::size_of::<INT>() as UINT);
+				let style = GetClassLongPtrW(self.hwnd, GCL_STYLE);
+				let style = if enable { style | CS_DROPSHADOW } else { style & !CS_DROPSHADOW };
+				SetClassLongPtrW(self.hwnd, GCL_STYLE, style);
+				let flags = SWP_NOMOVE | SWP_NOSIZE | SWP_NOZORDER | SWP_NOOWNERZORDER | SWP_NOACTIVATE | SWP_FRAMECHANGED;
+				SetWindowPos(self.hwnd, ::std::ptr::null_mut(), 0, 0, 0, 0, flags);
+			}
+		}
+
+		/// Set `DWMWA_SYSTEMBACKDROP_TYPE` of Windows 11 22H2+, `DwmEnableBlurBehindWindow` is the blur and the fallback.
+		fn set_backdrop(&self, kind: BackdropKind) {
+			const DWMWA_SYSTEMBACKDROP_TYPE: UINT = 38;
+			const DWMSBT_NONE: INT = 1;
+			const DWMSBT_MAINWINDOW: INT = 2;
+			const DWMSBT_TRANSIENTWINDOW: INT = 3;
+			const DWMSBT_TABBEDWINDOW: INT = 4;
+			const DWM_BB_ENABLE: UINT = 0x0001;
+			let backdrop = match kind {
+				BackdropKind::Mica => DWMSBT_MAINWINDOW,
+				BackdropKind::Acrylic => DWMSBT_TRANSIENTWINDOW,
+				BackdropKind::Tabbed => DWMSBT_TABBEDWINDOW,
+				BackdropKind::None | BackdropKind::Blur => DWMSBT_NONE,
+			};
+			unsafe {
+				// unknown attribute before 22H2
+				let system = DwmSetWindowAttribute(self.hwnd, DWMWA_SYSTEMBACKDROP_TYPE, &backdrop as *const INT as LPCVOID, ::std::mem::size_of::<INT>() as UINT) == 0;
+				let blur = match kind {
+					BackdropKind::None => false,
+					BackdropKind::Blur => true,
+					_ => !system,
+				};
+				let blur_behind = DWM_BLURBEHIND { dwFlags: DWM_BB_ENABLE, fEnable: blur as BOOL, hRgnBlur: ::std::ptr::null_mut(), fTransitionOnMaximized: 0 };
+				DwmEnableBlurBehindWindow(self.hwnd, &blur_behind);
+			}
+			if kind != BackdropKind::None {
+				// the backdrop is drawn in the frame
+				self.set_transparent_background();
+			}
+		}
+
+		/// Fill the client area with the color on `WM_ERASEBKGND`, which comes before Sciter paints the document.
+		fn set_background_color(&mut self, color: u32) {
+			self.background = Some(color);
+			if self.hwnd.is_null() {
+				// applied by `try_create`
+				return;
+			}
+			BACKGROUNDS.with(|colors| colors.borrow_mut().insert(self.hwnd, color));
+			unsafe { InvalidateRect(self.hwnd, ::std::ptr::null(), true as BOOL) };
+		}
+
+		/// Get the `WM_TOUCH` messages and handle the `WM_POINTER` pen ones, see `touch_to_mouse`.
+		fn enable_touch(&mut self, enable: bool) {
+			const TWF_WANTPALM: UINT = 0x0002;
+			if enable {
+				// an immediate response, without the palm rejection delay
+				if unsafe { RegisterTouchWindow(self.hwnd, TWF_WANTPALM) } != 0 {
+					TOUCH_CONTACTS.with(|contacts| { contacts.borrow_mut().entry(self.hwnd).or_insert(None); });
+				}
+			} else if TOUCH_CONTACTS.with(|contacts| contacts.borrow_mut().remove(&self.hwnd)).is_some() {
+				unsafe { UnregisterTouchWindow(self.hwnd) };
+			}
+		}
+
+		/// Start moving the window with the mouse as if its caption was clicked.
+		fn begin_drag(&self) {
+			const WM_NCLBUTTONDOWN: UINT = 0x00A1;
+			const HTCAPTION: WPARAM = 2;
+			let mut pt = POINT::default();
+			unsafe {
+				GetCursorPos(&mut pt);
+				ReleaseCapture();
+				let pos = ((pt.y as u16 as u32) << 16) | (pt.x as u16 as u32);
+				PostMessageW(self.hwnd, WM_NCLBUTTONDOWN, HTCAPTION, pos as LPARAM);
+			}
+		}
+
+		/// Answer `WM_NCHITTEST` with the callback, the system does the rest.
+		fn set_hit_test_callback<F: Fn(i32, i32) -> HitTestResult + 'static>(&mut self, callback: F) {
+			super::set_hit_test_handler(self.hwnd, Rc::new(callback));
+		}
+
+		/// Flash the taskbar button until the window is activated (or just a few times).
+		fn request_attention(&self, urgent: bool) {
+			const FLASHW_TRAY: UINT = 0x0002;
+			const FLASHW_TIMERNOFG: UINT = 0x000C;
+			if unsafe { GetForegroundWindow() } == self.hwnd {
+				return;
+			}
+			let info = FLASHWINFO {
+				cbSize: ::std::mem::size_of::<FLASHWINFO>() as UINT,
+				hwnd: self.hwnd,
+				dwFlags: if urgent { FLASHW_TRAY | FLASHW_TIMERNOFG } else { FLASHW_TRAY },
+				uCount: if urgent { 0 } else { 3 },
+				dwTimeout: 0,
+			};
+			unsafe { FlashWindowEx(&info) };
+		}
+
+		/// Decode the image with Sciter, DWM asks for the thumbnail with `WM_DWMSENDICONICTHUMBNAIL`.
+		fn set_thumbnail_bitmap(&self, png: &[u8]) {
+			use graphics::{Image, SaveImageEncoding};
+			let image = Image::load(png).ok();
+			let thumbnail = image.and_then(|image| match (image.dimensions(), image.save(SaveImageEncoding::Raw)) {
+				// a `BGRA` pixel per point, `send_iconic_thumbnail` relies on that
+				(Ok((width, height)), Ok(pixels)) if pixels.len() == width as usize * height as usize * 4 => Some((width as INT, height as INT, pixels)),
+				_ => None,
+			});
+			if thumbnail.is_none() {
+				return;
+			}
+			ICONIC_BITMAPS.with(|bitmaps| bitmaps.borrow_mut().entry(self.hwnd).or_default().thumbnail = thumbnail);
+			self.enable_iconic_bitmaps();
+		}
+
+		/// DWM asks for the live preview with `WM_DWMSENDICONICLIVEPREVIEWBITMAP`, it gets this part of the window snapshot.
+		fn set_live_preview_rect(&self, rc: RECT) {
+			ICONIC_BITMAPS.with(|bitmaps| bitmaps.borrow_mut().entry(self.hwnd).or_default().preview = Some(rc));
+			self.enable_iconic_bitmaps();
+		}
+
+		/// Show the progress on the taskbar button via `ITaskbarList3`, it needs COM (OLE) to be initialized.
+		fn set_progress(&self, state: ProgressState, value: f32) {
+			const CLSCTX_INPROC_SERVER: UINT = 0x1;
+			const TOTAL: u64 = 10_000;
+			let flags: UINT = match state {
+				ProgressState::None => 0,
+				ProgressState::Indeterminate => 1,
+				ProgressState::Normal => 2,
+				ProgressState::Error => 4,
+				ProgressState::Paused => 8,
+			};
+			let mut taskbar: LPVOID = ::std::ptr::null_mut();
+			unsafe {
+				if CoCreateInstance(&CLSID_TASKBAR_LIST, ::std::ptr::null_mut(), CLSCTX_INPROC_SERVER, &IID_TASKBAR_LIST3, &mut taskbar) < 0 || taskbar.is_null() {
+					return;
+				}
+				let vtbl = &**(taskbar as *const *const ITaskbarList3Vtbl);
+				if (vtbl.HrInit)(taskbar) >= 0 {
+					(vtbl.SetProgressState)(taskbar, self.hwnd, flags);
+					if state != ProgressState::None && state != ProgressState::Indeterminate {
+						let completed = (value.clamp(0.0, 1.0) as f64 * TOTAL as f64).round() as u64;
+						(vtbl.SetProgressValue)(taskbar, self.hwnd, completed, TOTAL);
+					}
+				}
+				(vtbl.Release)(taskbar);
+			}
+		}
+
+		/// Hide the window from the taskbar and the Alt+Tab list as a tool window.
+		fn set_skip_taskbar(&self, skip: bool) {
+			const SW_HIDE: INT = 0;
+			const SW_SHOWNA: INT = 8;
+			// the taskbar notices the change on showing only
+			let visible = unsafe { IsWindowVisible(self.hwnd) } != 0;
+			if visible {
+				unsafe { ShowWindow(self.hwnd, SW_HIDE) };
+			}
+			if skip {
+				self.modify_style(WindowStyle::EX_TOOLWINDOW, WindowStyle::EX_APPWINDOW);
+			} else {
+				self.modify_style(WindowStyle::EX_APPWINDOW, WindowStyle::EX_TOOLWINDOW);
+			}
+			if visible {
+				unsafe { ShowWindow(self.hwnd, SW_SHOWNA) };
+			}
+		}
+
+		/// Set the explicit `AppUserModelID` of the process, the taskbar groups the windows by it.
+		fn set_app_id(&self, app_id: &str) {
+			let id = s2w!(app_id);
+			unsafe { SetCurrentProcessExplicitAppUserModelID(id.as_ptr()) };
+		}
+
+		/// Capture the client area via `PrintWindow`, which works for the GPU rendered windows too.
+		fn snapshot(&self) -> Result<Vec<u8>, ()> {
+			if !self.is_visible() {
+				return Err(());
+			}
+			match self.capture_client() {
+				Some((width, height, pixels)) => super::encode_png(width as u32, height as u32, &pixels),
+				None => Err(()),
+			}
+		}
+
+		/// Keep the window above all non-topmost windows.
+		fn set_topmost(&self, enable: bool) {
+			if enable {
+				self.modify_style(WindowStyle::EX_TOPMOST, WindowStyle::empty());
+			} else {
+				self.modify_style(WindowStyle::empty(), WindowStyle::EX_TOPMOST);
+			}
+		}
+
+		/// Override the Sciter cursors on `WM_SETCURSOR` in the client area.
+		fn set_cursor(&self, cursor: CursorKind) {
+			super::set_cursor_override(self.hwnd, cursor);
+			// apply it right away if the mouse is over the window, not with the next move
+			let mut pt = POINT::default();
+			if unsafe { GetCursorPos(&mut pt) } != 0 && unsafe { WindowFromPoint(pt) } == self.hwnd {
+				let handle = cursor_handle(cursor).unwrap_or_else(|| cursor_handle(CursorKind::Arrow).unwrap_or(::std::ptr::null_mut()));
+				unsafe { SetCursor(handle) };
+			}
+		}
+
+		/// `ClipCursor` to the client area while the window is active, released and re-applied on `WM_ACTIVATE`.
+		fn set_cursor_clip(&self, enable: bool) {
+			if super::set_cursor_clip_enabled(self.hwnd, enable) && self.is_focused() {
+				clip_cursor(self.hwnd, enable);
+			}
+		}
+
+		fn cursor_position(&self) -> POINT {
+			let mut pt = POINT::default();
+			unsafe {
+				GetCursorPos(&mut pt);
+				ScreenToClient(self.hwnd, &mut pt);
+			}
+			return pt;
+		}
+
+		/// `GetAsyncKeyState` reports the physical buttons, so the swapped ones are swapped back.
+		fn mouse_buttons(&self) -> MouseButtons {
+			const VK_LBUTTON: INT = 0x01;
+			const VK_RBUTTON: INT = 0x02;
+			const VK_MBUTTON: INT = 0x04;
+			const VK_XBUTTON1: INT = 0x05;
+			const VK_XBUTTON2: INT = 0x06;
+			const SM_SWAPBUTTON: INT = 23;
+
+			let pressed = |vk: INT| unsafe { GetAsyncKeyState(vk) } < 0;
+			let (primary, secondary) = if unsafe { GetSystemMetrics(SM_SWAPBUTTON) } != 0 { (VK_RBUTTON, VK_LBUTTON) } else { (VK_LBUTTON, VK_RBUTTON) };
+			let mut buttons = MouseButtons::empty();
+			buttons.set(MouseButtons::LEFT, pressed(primary));
+			buttons.set(MouseButtons::RIGHT, pressed(secondary));
+			buttons.set(MouseButtons::MIDDLE, pressed(VK_MBUTTON));
+			buttons.set(MouseButtons::X1, pressed(VK_XBUTTON1));
+			buttons.set(MouseButtons::X2, pressed(VK_XBUTTON2));
+			return buttons;
+		}
+
+		/// The callback is checked by the message loops before `TranslateMessage`, see `translate_accelerator`.
+		fn register_accelerator<F: Fn() -> bool + 'static>(&mut self, accelerator: Accelerator, callback: F) {
+			super::set_accelerator(self.hwnd, accelerator, Rc::new(callback));
+		}
+
+		/// Set the whole window opacity.
+		fn set_opacity(&self, alpha: f32) {
+			const LWA_ALPHA: UINT = 0x0002;
+			let alpha = (alpha.clamp(0.0, 1.0) * 255.0).round() as BYTE;
+			unsafe {
+				let ex_style = GetWindowLongPtrW(self.hwnd, GWL_EXSTYLE);
+				if (ex_style & WS_EX_LAYERED) == 0 {
+					SetWindowLongPtrW(self.hwnd, GWL_EXSTYLE, ex_style | WS_EX_LAYERED);
+				}
+				SetLayeredWindowAttributes(self.hwnd, 0, alpha, LWA_ALPHA);
+			}
+		}
+
+		/// Get the DPI scale of the monitor with the window, `1.0` means 96 DPI.
+		fn get_dpi_scale(&self) -> f32 {
+			type GetDpiForWindow = extern "system" fn(hwnd: HWINDOW) -> UINT;
+			const LOGPIXELSX: INT = 88;
+			// Windows 10 1607+
+			let user32 = unsafe { GetModuleHandleW(s2w!("user32.dll").as_ptr()) };
+			let get_dpi = unsafe { GetProcAddress(user32, b"GetDpiForWindow\0".as_ptr() as LPCSTR) };
+			let dpi = if !get_dpi.is_null() {
+				let get_dpi: GetDpiForWindow = unsafe { ::std::mem::transmute::<LPCVOID, GetDpiForWindow>(get_dpi) };
+				get_dpi(self.hwnd)
+			} else {
+				unsafe {
+					let hdc = GetDC(self.hwnd);
+					let dpi = GetDeviceCaps(hdc, LOGPIXELSX);
+					ReleaseDC(self.hwnd, hdc);
+					dpi as UINT
+				}
+			};
+			return if dpi > 0 { dpi as f32 / 96.0 } else { 1.0 };
+		}
+
+		/// The zoom is the CSS `zoom` of the document root, see `apply_zoom`.
+		fn set_zoom(&self, factor: f32) {
+			super::set_zoom_factor(self.hwnd, factor);
+			super::apply_zoom(self.hwnd);
+		}
+
+		fn get_zoom(&self) -> f32 {
+			super::zoom_factor(self.hwnd)
+		}
+
+		/// Whether the window is shown (it can be minimized though).
+		fn is_visible(&self) -> bool {
+			unsafe { IsWindowVisible(self.hwnd) != 0 }
+		}
+
+		/// Whether the window is the foreground one, or a child one with the focus inside of it.
+		fn is_focused(&self) -> bool {
+			const GA_ROOT: UINT = 2;
+			unsafe {
+				let foreground = GetForegroundWindow();
+				return foreground == self.hwnd || (GetFocus() == self.hwnd && GetAncestor(self.hwnd, GA_ROOT) == foreground);
+			}
+		}
+
+		/// Time since the last keyboard or mouse message of this window.
+		fn last_input_time(&self) -> Duration {
+			super::since_last_input(self.hwnd)
+		}
+
+		/// Put the text on the clipboard as `CF_UNICODETEXT`.
+		fn clipboard_set_text(&self, text: &str) {
+			const GMEM_MOVEABLE: UINT = 0x0002;
+			let wide: Vec<u16> = text.encode_utf16().chain(Some(0)).collect();
+			unsafe {
+				if OpenClipboard(self.hwnd) == 0 {
+					return;
+				}
+				EmptyClipboard();
+				let mem = GlobalAlloc(GMEM_MOVEABLE, wide.len() * 2);
+				let data = if mem.is_null() { mem } else { GlobalLock(mem) };
+				if !data.is_null() {
+					::std::ptr::copy_nonoverlapping(wide.as_ptr(), data as *mut u16, wide.len());
+					GlobalUnlock(mem);
+					// the clipboard owns the memory on success
+					if SetClipboardData(CF_UNICODETEXT, mem).is_null() {
+						GlobalFree(mem);
+					}
+				} else if !mem.is_null() {
+					GlobalFree(mem);
+				}
+				CloseClipboard();
+			}
+		}
+
+		/// Get the `CF_UNICODETEXT` clipboard text.
+		fn clipboard_get_text(&self) -> Option<String> {
+			unsafe {
+				if OpenClipboard(self.hwnd) == 0 {
+					return None;
+				}
+				let mem = GetClipboardData(CF_UNICODETEXT);
+				let data = if mem.is_null() { mem } else { GlobalLock(mem) };
+				let text = if data.is_null() {
+					None
+				} else {
+					let text = ::utf::w2s(data as LPCWSTR);
+					GlobalUnlock(mem);
+					Some(text)
+				};
+				CloseClipboard();
+				return text;
+			}
+		}
+
+		/// Get the current window state.
+		fn get_state(&self) -> WindowState {
+			const SW_SHOWMINIMIZED: UINT = 2;
+			const SW_SHOWMAXIMIZED: UINT = 3;
+			if unsafe { IsWindowVisible(self.hwnd) } == 0 {
+				return WindowState::Hidden;
+			}
+			if self.windowed.get().is_some() {
+				return WindowState::Fullscreen;
+			}
+			let mut placement = WINDOWPLACEMENT { length: ::std::mem::size_of::<WINDOWPLACEMENT>() as UINT, ..Default::default() };
+			unsafe { GetWindowPlacement(self.hwnd, &mut placement) };
+			return match placement.showCmd {
+				SW_SHOWMINIMIZED => WindowState::Minimized,
+				SW_SHOWMAXIMIZED => WindowState::Maximized,
+				_ => WindowState::Normal,
+			};
+		}
+
+		/// Set the window icon from PNG data.
+		fn set_icon(&mut self, image: &[u8]) {
+			const WM_SETICON: UINT = 0x0080;
+			const ICON_SMALL: WPARAM = 0;
+			const ICON_BIG: WPARAM = 1;
+			unsafe {
+				for &kind in &[ICON_SMALL, ICON_BIG] {
+					// PNG compressed icons are supported since Vista
+					let icon = if image.is_empty() {
+						::std::ptr::null_mut()
+					} else {
+						CreateIconFromResourceEx(image.as_ptr(), image.len() as UINT, true as BOOL, 0x0003_0000, 0, 0, 0)
+					};
+					let prev = SendMessageW(self.hwnd, WM_SETICON, kind, icon as LPARAM);
+					if prev != 0 {
+						DestroyIcon(prev as LPVOID);
+					}
+				}
+			}
+		}
+
+		/// Convert a point from the window client area to screen coordinates.
+		fn client_to_screen(&self, pt: POINT) -> POINT {
+			let mut pt = pt;
+			unsafe { ClientToScreen(self.hwnd, &mut pt) };
+			return pt;
+		}
+
+		/// Make the window background transparent before the document is painted.
+		fn set_transparent_background(&self) {
+			// Alpha windows are layered ones (nothing is shown until the first `UpdateLayeredWindow`),
+			// glassy ones need the DWM frame to be extended into the whole client area.
+			let whole = MARGINS { cxLeftWidth: -1, cxRightWidth: -1, cyTopHeight: -1, cyBottomHeight: -1 };
+			unsafe { DwmExtendFrameIntoClientArea(self.hwnd, &whole) };
+		}
+
+		/// Run the main app message loop until window been closed.
+		fn run_app(&self) {
+			let mut msg = MSG {
+				hwnd: 0 as HWINDOW,
+				message: 0,
+				wParam: 0,
+				lParam: 0,
+				time: 0,
+				pt: POINT { x: 0, y: 0 },
+			};
+			let pmsg: LPMSG = &mut msg;
+			let null: HWINDOW = ::std::ptr::null_mut();
+			unsafe {
+				while GetMessageW(pmsg, null, 0, 0) != 0 {
+					if !translate_accelerator(&*pmsg) {
+						TranslateMessage(pmsg);
+						DispatchMessageW(pmsg);
+					}
+				}
+			};
+			OsWindow::uninit_app();
+		}
+
+		/// Run the main app message loop, calling `idle` after the pending messages and every `IDLE_INTERVAL` without them.
+		fn run_app_with<F: FnMut() + 'static>(&self, mut idle: F) {
+			const PM_REMOVE: UINT = 0x0001;
+			const WM_QUIT: UINT = 0x0012;
+			const QS_ALLINPUT: UINT = 0x04FF;
+			let mut msg = MSG {
+				hwnd: 0 as HWINDOW,
+				message: 0,
+				wParam: 0,
+				lParam: 0,
+				time: 0,
+				pt: POINT { x: 0, y: 0 },
+			};
+			let pmsg: LPMSG = &mut msg;
+			let null: HWINDOW = ::std::ptr::null_mut();
+			'run: loop {
+				unsafe {
+					while PeekMessageW(pmsg, null, 0, 0, PM_REMOVE) != 0 {
+						if (*pmsg).message == WM_QUIT {
+							break 'run;
+						}
+						if !translate_accelerator(&*pmsg) {
+							TranslateMessage(pmsg);
+							DispatchMessageW(pmsg);
+						}
+					}
+				}
+				idle();
+				// wakes up on a new message or on timeout
+				unsafe { MsgWaitForMultipleObjects(0, ::std::ptr::null(), false as BOOL, super::IDLE_INTERVAL, QS_ALLINPUT) };
+			}
+			OsWindow::uninit_app();
+		}
+
+		/// Disable the `parent` and run a nested message loop until the window is destroyed.
+		fn run_modal(&self, parent: &OsWindow) {
+			const GWLP_HWNDPARENT: INT = -8;
+			unsafe {
+				// keep it above the parent
+				SetWindowLongPtrW(self.hwnd, GWLP_HWNDPARENT, parent.hwnd as isize);
+				EnableWindow(parent.hwnd, false as BOOL);
+			}
+			MODAL_OWNERS.with(|owners| owners.borrow_mut().insert(self.hwnd, parent.hwnd));
+			self.expand(false);
+
+			let mut msg = MSG {
+				hwnd: 0 as HWINDOW,
+				message: 0,
+				wParam: 0,
+				lParam: 0,
+				time: 0,
+				pt: POINT { x: 0, y: 0 },
+			};
+			let pmsg: LPMSG = &mut msg;
+			let null: HWINDOW = ::std::ptr::null_mut();
+			unsafe {
+				while IsWindow(self.hwnd) != 0 {
+					if GetMessageW(pmsg, null, 0, 0) <= 0 {
+						// let the outer loop quit as well
+						PostQuitMessage((*pmsg).wParam as INT);
+						break;
+					}
+					if !translate_accelerator(&*pmsg) {
+						TranslateMessage(pmsg);
+						DispatchMessageW(pmsg);
+					}
+				}
+			};
+			// the window is still open after `WM_QUIT`
+			MODAL_OWNERS.with(|owners| owners.borrow_mut().remove(&self.hwnd));
+			unsafe {
+				EnableWindow(parent.hwnd, true as BOOL);
+				SetForegroundWindow(parent.hwnd);
+			}
+		}
+
+		/// Process the pending messages without waiting, `false` means the app quit message was received.
+		fn pump_events(&self) -> bool {
+			const PM_REMOVE: UINT = 0x0001;
+			const WM_QUIT: UINT = 0x0012;
+			let mut msg = MSG {
+				hwnd: 0 as HWINDOW,
+				message: 0,
+				wParam: 0,
+				lParam: 0,
+				time: 0,
+				pt: POINT { x: 0, y: 0 },
+			};
+			let pmsg: LPMSG = &mut msg;
+			let null: HWINDOW = ::std::ptr::null_mut();
+			unsafe {
+				while PeekMessageW(pmsg, null, 0, 0, PM_REMOVE) != 0 {
+					if (*pmsg).message == WM_QUIT {
+						return false;
+					}
+					if !translate_accelerator(&*pmsg) {
+						TranslateMessage(pmsg);
+						DispatchMessageW(pmsg);
+					}
+				}
+			};
+			return true;
+		}
+
+		/// Process the messages until there are none for a while.
+		fn run_until_idle(&self, settle: Duration, timeout: Duration) -> bool {
+			const PM_NOREMOVE: UINT = 0x0000;
+			let pending = || {
+				let mut msg = MSG {
+					hwnd: 0 as HWINDOW,
+					message: 0,
+					wParam: 0,
+					lParam: 0,
+					time: 0,
+					pt: POINT { x: 0, y: 0 },
+				};
+				let null: HWINDOW = ::std::ptr::null_mut();
+				unsafe { PeekMessageW(&mut msg, null, 0, 0, PM_NOREMOVE) != 0 }
+			};
+			return super::pump_until_idle(settle, timeout, || self.pump_events(), pending);
+		}
+
+		/// Post app quit message.
+		fn quit_app(&self) {
+			unsafe { PostQuitMessage(0) };
+		}
+
+		/// Quit the app from any thread: the window thread posts the quit message by itself.
+		fn post_quit(&self) {
+			unsafe { PostMessageW(self.hwnd, WM_POST_QUIT, 0, 0) };
+		}
+
+		/// Run the closure on the window thread, it is dropped if the window is destroyed already.
+		fn post_task(&self, task: UiTask) {
+			let mut posted = POSTED_TASKS.lock().unwrap();
+			posted.push((self.hwnd as usize, task));
+			if unsafe { PostMessageW(self.hwnd, WM_RUN_TASKS, 0, 0) } == 0 {
+				let task = posted.pop();
+				// its captures may post again
+				drop(posted);
+				drop(task);
+			}
+		}
+	}
+
+	#[cfg(feature = "raw-window-handle")]
+	unsafe impl ::raw_window_handle::HasRawWindowHandle for OsWindow {
+		fn raw_window_handle(&self) -> ::raw_window_handle::RawWindowHandle {
+			const GWLP_HINSTANCE: INT = -6;
+			let mut handle = ::raw_window_handle::Win32Handle::empty();
+			if !self.hwnd.is_null() {
+				handle.hwnd = self.hwnd as LPVOID;
+				handle.hinstance = unsafe { GetWindowLongPtrW(self.hwnd, GWLP_HINSTANCE) } as LPVOID;
+			}
+			::raw_window_handle::RawWindowHandle::Win32(handle)
+		}
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::OsWindow;
+
+		#[test]
+		fn ole_init_is_balanced() {
+			assert_eq!(OsWindow::init_app(), Ok(()));
+			// the second window must not initialize OLE again
+			assert_eq!(OsWindow::init_app(), Ok(()));
+			OsWindow::uninit_app();
+			assert!(!super::OLE_INITIALIZED.with(|initialized| initialized.get()));
+
+			// and again after the app loop has finished
+			assert_eq!(OsWindow::init_app(), Ok(()));
+			OsWindow::uninit_app();
+		}
+
+		#[test]
+		fn reparented_child_moves_to_new_host() {
+			use capi::sctypes::*;
+			use super::super::BaseWindow;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+				fn GetParent(hwnd: HWINDOW) -> HWINDOW;
+			}
+
+			const WS_OVERLAPPEDWINDOW: UINT = 0x00CF_0000;
+			const WS_CHILD: UINT = 0x4000_0000;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let create = |style: UINT, parent: HWINDOW| unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), style, 10, 10, 300, 200, parent, null, null, null) };
+			let (first, second) = (create(WS_OVERLAPPEDWINDOW, null), create(WS_OVERLAPPEDWINDOW, null));
+			let hwnd = create(WS_CHILD, first);
+			assert!(!hwnd.is_null());
+			assert_eq!(unsafe { GetParent(hwnd) }, first);
+
+			let mut wnd = OsWindow::from(hwnd);
+			wnd.set_parent(second);
+			assert_eq!(unsafe { GetParent(hwnd) }, second);
+
+			// detached, it is a top-level window
+			wnd.set_parent(null);
+			assert!(unsafe { GetParent(hwnd) }.is_null());
+			assert!(wnd.is_resizable());
+
+			for &hwnd in &[hwnd, first, second] {
+				OsWindow::from(hwnd).dismiss();
+			}
+		}
+
+		#[test]
+		fn taskbar_progress_is_set() {
+			use capi::sctypes::*;
+			use super::super::BaseWindow;
+			use window::ProgressState;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+			}
+
+			const WS_OVERLAPPEDWINDOW: UINT = 0x00CF_0000;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_OVERLAPPEDWINDOW, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			// `ITaskbarList3` is a COM object
+			assert_eq!(OsWindow::init_app(), Ok(()));
+			let wnd = OsWindow::from(hwnd);
+			wnd.set_progress(ProgressState::Normal, 0.5);
+			wnd.set_progress(ProgressState::Indeterminate, 0.0);
+			wnd.set_progress(ProgressState::None, 0.0);
+			wnd.dismiss();
+			OsWindow::uninit_app();
+		}
+
+		#[test]
+		fn mica_backdrop_is_set() {
+			use capi::sctypes::*;
+			use super::super::BaseWindow;
+			use window::BackdropKind;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+			}
+
+			const WS_OVERLAPPEDWINDOW: UINT = 0x00CF_0000;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_OVERLAPPEDWINDOW, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			// falls back to the blur before Windows 11
+			let wnd = OsWindow::from(hwnd);
+			wnd.set_backdrop(BackdropKind::Mica);
+			wnd.set_backdrop(BackdropKind::Blur);
+			wnd.set_backdrop(BackdropKind::None);
+			wnd.dismiss();
+		}
+
+		#[test]
+		fn background_color_is_erased() {
+			use capi::sctypes::*;
+			use super::super::BaseWindow;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+			}
+
+			const WS_OVERLAPPEDWINDOW: UINT = 0x00CF_0000;
+			const WM_ERASEBKGND: UINT = 0x0014;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_OVERLAPPEDWINDOW, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			let mut wnd = OsWindow::from(hwnd);
+			wnd.set_background_color(0xFF20_3040);
+
+			// erase the hidden window into a bitmap, as before the document is loaded
+			let info = super::BITMAPINFO {
+				bmiHeader: super::BITMAPINFOHEADER {
+					biSize: ::std::mem::size_of::<super::BITMAPINFOHEADER>() as UINT,
+					biWidth: 300,
+					biHeight: -200,
+					biPlanes: 1,
+					biBitCount: 32,
+					biCompression: 0,
+					biSizeImage: 0,
+					biXPelsPerMeter: 0,
+					biYPelsPerMeter: 0,
+					biClrUsed: 0,
+					biClrImportant: 0,
+				},
+				bmiColors: [0],
+			};
+			let mut handled = false as BOOL;
+			let pixel = unsafe {
+				let hdc = super::GetDC(hwnd);
+				let mem = super::CreateCompatibleDC(hdc);
+				let mut bits = null;
+				let bitmap = super::CreateDIBSection(hdc, &info, 0, &mut bits, null, 0);
+				assert!(!bitmap.is_null());
+				let old = super::SelectObject(mem, bitmap);
+				let erased = super::window_delegate(hwnd, WM_ERASEBKGND, mem as WPARAM, 0, null, &mut handled);
+				assert_eq!(erased, true as LRESULT);
+				let pixel = ::std::slice::from_raw_parts(bits as LPCBYTE, 4).to_vec();
+				super::SelectObject(mem, old);
+				super::DeleteObject(bitmap);
+				super::DeleteDC(mem);
+				super::ReleaseDC(hwnd, hdc);
+				pixel
+			};
+			assert!(handled != 0);
+			// BGRA
+			assert_eq!(&pixel[..3], &[0x40, 0x30, 0x20]);
+
+			wnd.dismiss();
+			super::BACKGROUNDS.with(|colors| colors.borrow_mut().remove(&hwnd));
+		}
+
+		#[test]
+		fn touch_drives_mouse() {
+			use capi::sctypes::*;
+			use super::super::BaseWindow;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+			}
+
+			const WS_OVERLAPPEDWINDOW: UINT = 0x00CF_0000;
+			const WM_MOUSEMOVE: UINT = 0x0200;
+			const WM_LBUTTONDOWN: UINT = 0x0201;
+			const WM_LBUTTONUP: UINT = 0x0202;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_OVERLAPPEDWINDOW, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			let mut wnd = OsWindow::from(hwnd);
+			let at = |x, y| OsWindow::from(hwnd).client_to_screen(POINT { x: x, y: y });
+			let pos = |x: LPARAM, y: LPARAM| y << 16 | x;
+
+			// nothing until enabled
+			assert_eq!(super::touch_to_mouse(hwnd, 1, at(10, 20), true), None);
+			wnd.enable_touch(true);
+
+			// the `WM_TOUCH` contacts as `GetTouchInputInfo` returns them
+			let contact = |id: UINT, pt: POINT, flags: UINT| super::TOUCHINPUT {
+				x: pt.x * 100, y: pt.y * 100, hSource: null, dwID: id, dwFlags: flags, dwMask: 0, dwTime: 0, dwExtraInfo: 0, cxContact: 0, cyContact: 0,
+			};
+			let first = contact(7, at(10, 20), 0x0002);
+			super::touch_received(hwnd, &[first]);
+			assert_eq!(super::TOUCH_CONTACTS.with(|contacts| contacts.borrow()[&hwnd]), Some(7));
+
+			// the second finger does not move the mouse
+			assert_eq!(super::touch_to_mouse(hwnd, 8, at(50, 50), true), None);
+			assert_eq!(super::touch_to_mouse(hwnd, 7, at(15, 25), true), Some((WM_MOUSEMOVE, 1, pos(15, 25))));
+			assert_eq!(super::touch_to_mouse(hwnd, 7, at(15, 25), false), Some((WM_LBUTTONUP, 0, pos(15, 25))));
+
+			// a hovering pen moves it without the button
+			assert_eq!(super::touch_to_mouse(hwnd, 9, at(30, 40), false), Some((WM_MOUSEMOVE, 0, pos(30, 40))));
+			assert_eq!(super::touch_to_mouse(hwnd, 9, at(30, 40), true), Some((WM_LBUTTONDOWN, 1, pos(30, 40))));
+
+			wnd.enable_touch(false);
+			assert_eq!(super::touch_to_mouse(hwnd, 9, at(30, 40), false), None);
+			wnd.dismiss();
+		}
+
+		#[test]
+		fn subclass_handles_custom_message() {
+			use capi::sctypes::*;
+			use std::rc::Rc;
+			use super::super::BaseWindow;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+			}
+
+			const WS_OVERLAPPEDWINDOW: UINT = 0x00CF_0000;
+			const WM_GETTEXTLENGTH: UINT = 0x000E;
+			const WM_CUSTOM: UINT = 0x8000 + 0x100;
+			const SENTINEL: LRESULT = 0x5EC;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_OVERLAPPEDWINDOW, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			let alive = Rc::new(());
+			let held = alive.clone();
+			let mut wnd = OsWindow::from(hwnd);
+			wnd.subclass(move |_, msg, _, _| {
+				let _ = &held;
+				if msg == WM_CUSTOM { Some(SENTINEL) } else { None }
+			});
+			assert_eq!(unsafe { super::SendMessageW(hwnd, WM_CUSTOM, 0, 0) }, SENTINEL);
+			// the rest goes to the original window procedure
+			assert_eq!(unsafe { super::SendMessageW(hwnd, WM_GETTEXTLENGTH, 0, 0) }, 6);
+
+			// the closure goes away with the window
+			assert_eq!(Rc::strong_count(&alive), 2);
+			wnd.dismiss();
+			assert_eq!(Rc::strong_count(&alive), 1);
+		}
+
+		#[test]
+		fn geometry_update_is_one_window_pos_change() {
+			use capi::sctypes::*;
+			use std::cell::Cell;
+			use std::rc::Rc;
+			use window::{GeometryUpdate, ZOrder};
+			use super::super::BaseWindow;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+			}
+
+			const WS_OVERLAPPEDWINDOW: UINT = 0x00CF_0000;
+			const WM_WINDOWPOSCHANGED: UINT = 0x0047;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_OVERLAPPEDWINDOW, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			let changes = Rc::new(Cell::new(0));
+			let counter = changes.clone();
+			let mut wnd = OsWindow::from(hwnd);
+			wnd.subclass(move |_, msg, _, _| {
+				if msg == WM_WINDOWPOSCHANGED {
+					counter.set(counter.get() + 1);
+				}
+				None
+			});
+
+			wnd.update_geometry(GeometryUpdate { position: Some((40, 50)), size: Some((320, 240)), z_order: Some(ZOrder::Top), ..Default::default() });
+			assert_eq!(changes.get(), 1);
+			assert_eq!(wnd.get_rect(), RECT { left: 40, top: 50, right: 360, bottom: 290 });
+
+			// nothing to change
+			wnd.update_geometry(GeometryUpdate::default());
+			assert_eq!(changes.get(), 1);
+
+			wnd.dismiss();
+		}
+
+		#[test]
+		fn hit_test_declares_caption() {
+			use capi::sctypes::*;
+			use window::HitTestResult;
+			use super::super::BaseWindow;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+			}
+
+			const WS_POPUP: UINT = 0x8000_0000;
+			const WM_NCHITTEST: UINT = 0x0084;
+			const HTCLIENT: LRESULT = 1;
+			const HTCAPTION: LRESULT = 2;
+			const HTBOTTOMRIGHT: LRESULT = 17;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_POPUP, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			// a 30 px titlebar and a resize grip at the bottom-right corner
+			let mut wnd = OsWindow::from(hwnd);
+			wnd.set_hit_test_callback(|x, y| match (x, y) {
+				(_, 0..=29) => HitTestResult::Caption,
+				(290..=299, 190..=199) => HitTestResult::BottomRight,
+				_ => HitTestResult::Client,
+			});
+			let hit_test = |x: i32, y: i32| {
+				// the screen point
+				let lp = ((10 + y) as LPARAM) << 16 | (10 + x) as LPARAM;
+				let mut handled = false as BOOL;
+				let result = super::window_delegate(hwnd, WM_NCHITTEST, 0, lp, null, &mut handled);
+				assert_ne!(handled, false as BOOL);
+				result
+			};
+			assert_eq!(hit_test(150, 10), HTCAPTION);
+			assert_eq!(hit_test(295, 195), HTBOTTOMRIGHT);
+			assert_eq!(hit_test(150, 100), HTCLIENT);
+
+			wnd.dismiss();
+		}
+
+		#[test]
+		fn automation_id_is_stored_in_window_property() {
+			use capi::sctypes::*;
+			use super::super::BaseWindow;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+				fn GetPropW(hwnd: HWINDOW, name: LPCWSTR) -> LPVOID;
+			}
+			#[link(name = "kernel32")]
+			extern "system" {
+				fn GlobalGetAtomNameW(atom: u16, buffer: LPWSTR, size: INT) -> UINT;
+			}
+
+			const WS_POPUP: UINT = 0x8000_0000;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_POPUP, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			let read = |key: &str| {
+				let key = s2w!(key);
+				let atom = unsafe { GetPropW(hwnd, key.as_ptr()) } as usize as u16;
+				let mut buffer = [0 as WCHAR; 256];
+				let len = unsafe { GlobalGetAtomNameW(atom, buffer.as_mut_ptr(), buffer.len() as INT) };
+				::utf::w2sn(buffer.as_ptr(), len as usize)
+			};
+
+			let mut wnd = OsWindow::from(hwnd);
+			wnd.set_automation_id("main-window");
+			wnd.set_accessible_name("Main window");
+			assert_eq!(read("SciterAutomationId"), "main-window");
+			assert_eq!(read("SciterAccessibleName"), "Main window");
+
+			// a new value replaces the atom, an empty one removes the property
+			wnd.set_automation_id("settings");
+			assert_eq!(read("SciterAutomationId"), "settings");
+			wnd.set_automation_id("");
+			assert!(unsafe { GetPropW(hwnd, s2w!("SciterAutomationId").as_ptr()) }.is_null());
+
+			let mut handled = false as BOOL;
+			super::window_delegate(hwnd, 0x0082, 0, 0, null, &mut handled); // WM_NCDESTROY
+			assert!(unsafe { GetPropW(hwnd, s2w!("SciterAccessibleName").as_ptr()) }.is_null());
+			wnd.dismiss();
+		}
+
+		#[test]
+		fn iconic_thumbnail_is_sent() {
+			use capi::sctypes::*;
+			use super::super::BaseWindow;
+			use super::ICONIC_BITMAPS;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+			}
+
+			const WS_POPUP: UINT = 0x8000_0000;
+			const WM_DWMSENDICONICTHUMBNAIL: UINT = 0x0323;
+			const WM_DWMSENDICONICLIVEPREVIEWBITMAP: UINT = 0x0326;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_POPUP, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			let wnd = OsWindow::from(hwnd);
+			wnd.set_live_preview_rect(RECT { left: 0, top: 0, right: 150, bottom: 100 });
+			// `set_thumbnail_bitmap` decodes the image with Sciter
+			ICONIC_BITMAPS.with(|bitmaps| bitmaps.borrow_mut().get_mut(&hwnd).unwrap().thumbnail = Some((400, 200, vec![0xFF; 400 * 200 * 4])));
+
+			// at most 200 x 120
+			let mut handled = false as BOOL;
+			super::window_delegate(hwnd, WM_DWMSENDICONICTHUMBNAIL, 0, (200 << 16) | 120, null, &mut handled);
+			assert_ne!(handled, false as BOOL);
+
+			// the hidden window may have nothing to print, it must not panic anyway
+			let mut handled = false as BOOL;
+			super::window_delegate(hwnd, WM_DWMSENDICONICLIVEPREVIEWBITMAP, 0, 0, null, &mut handled);
+
+			wnd.dismiss();
+			super::window_delegate(hwnd, 0x0082, 0, 0, null, &mut handled); // WM_NCDESTROY
+			assert!(ICONIC_BITMAPS.with(|bitmaps| !bitmaps.borrow().contains_key(&hwnd)));
+		}
+
+		#[test]
+		fn tray_icon_reports_clicks() {
+			use capi::sctypes::*;
+			use std::cell::Cell;
+			use std::rc::Rc;
+			use super::super::{BaseWindow, TrayIcon};
+			use super::{OsTrayIcon, WM_TRAY_ICON};
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+			}
+
+			const WS_POPUP: UINT = 0x8000_0000;
+			const WM_LBUTTONUP: LPARAM = 0x0202;
+			const WM_RBUTTONUP: LPARAM = 0x0205;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_POPUP, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			// `TrayIcon::add` wants a Sciter window
+			let id = 0x5C;
+			let mut tray = TrayIcon { id: id, icon: OsTrayIcon::add(hwnd, id, &[], "sciter-rs test") };
+			let clicks = Rc::new(Cell::new(0));
+			let sink = clicks.clone();
+			tray.on_click(move || sink.set(sink.get() + 1));
+
+			let mut handled = false as BOOL;
+			super::window_delegate(hwnd, WM_TRAY_ICON, id as WPARAM, WM_RBUTTONUP, null, &mut handled);
+			assert_eq!(clicks.get(), 0);
+			super::window_delegate(hwnd, WM_TRAY_ICON, id as WPARAM, WM_LBUTTONUP, null, &mut handled);
+			assert_eq!(clicks.get(), 1);
+
+			// no callbacks after the removal
+			tray.remove();
+			super::window_delegate(hwnd, WM_TRAY_ICON, id as WPARAM, WM_LBUTTONUP, null, &mut handled);
+			assert_eq!(clicks.get(), 1);
+
+			OsWindow::from(hwnd).dismiss();
+		}
+
+		#[test]
+		fn ime_follows_caret() {
+			use capi::sctypes::*;
+			use super::super::BaseWindow;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+			}
+
+			#[link(name = "imm32")]
+			extern "system" {
+				fn ImmGetCompositionWindow(himc: LPVOID, form: *mut super::COMPOSITIONFORM) -> BOOL;
+				fn ImmGetCandidateWindow(himc: LPVOID, index: UINT, form: *mut super::CANDIDATEFORM) -> BOOL;
+			}
+
+			const WS_OVERLAPPEDWINDOW: UINT = 0x00CF_0000;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_OVERLAPPEDWINDOW, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			// the caret of a focused input, as `ime_caret` finds it at `WM_IME_STARTCOMPOSITION`
+			let caret = RECT { left: 40, top: 12, right: 41, bottom: 30 };
+			super::position_ime(hwnd, caret, 16, "Segoe UI");
+
+			let himc = unsafe { super::ImmGetContext(hwnd) };
+			assert!(!himc.is_null());
+			let mut composition: super::COMPOSITIONFORM = unsafe { ::std::mem::zeroed() };
+			let mut candidate: super::CANDIDATEFORM = unsafe { ::std::mem::zeroed() };
+			unsafe {
+				assert!(ImmGetCompositionWindow(himc, &mut composition) != 0);
+				assert!(ImmGetCandidateWindow(himc, 0, &mut candidate) != 0);
+				super::ImmReleaseContext(hwnd, himc);
+			}
+			assert_eq!((composition.ptCurrentPos.x, composition.ptCurrentPos.y), (40, 12));
+			assert_eq!(candidate.rcArea, caret);
+
+			OsWindow::from(hwnd).dismiss();
+		}
+
+		#[test]
+		fn cursor_clip_is_client_rect() {
+			use capi::sctypes::*;
+			use super::super::BaseWindow;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+				fn SetForegroundWindow(hwnd: HWINDOW) -> BOOL;
+				fn GetClipCursor(rc: LPRECT) -> BOOL;
+			}
+
+			const WS_OVERLAPPEDWINDOW: UINT = 0x00CF_0000;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_OVERLAPPEDWINDOW, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			// the clip is applied to the active window only
+			let wnd = OsWindow::from(hwnd);
+			wnd.show();
+			unsafe { SetForegroundWindow(hwnd) };
+			assert!(wnd.is_focused());
+
+			let mut client = RECT::default();
+			unsafe { super::GetClientRect(hwnd, &mut client) };
+			let origin = wnd.client_to_screen(POINT { x: 0, y: 0 });
+			let expected = RECT { left: origin.x, top: origin.y, right: origin.x + client.width(), bottom: origin.y + client.height() };
+
+			let mut clip = RECT::default();
+			wnd.set_cursor_clip(true);
+			unsafe { GetClipCursor(&mut clip) };
+			assert_eq!(clip, expected);
+
+			wnd.set_cursor_clip(false);
+			unsafe { GetClipCursor(&mut clip) };
+			assert_ne!(clip, expected);
+			wnd.dismiss();
+		}
+
+		#[test]
+		fn resizable_style_toggles() {
+			use capi::sctypes::*;
+			use super::super::BaseWindow;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+			}
+
+			// a plain native window is enough for the styles
+			const WS_OVERLAPPEDWINDOW: UINT = 0x00CF_0000;
+			const WS_THICKFRAME: isize = 0x0004_0000;
+			const WS_MAXIMIZEBOX: isize = 0x0001_0000;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_OVERLAPPEDWINDOW, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			let wnd = OsWindow::from(hwnd);
+			assert!(wnd.is_resizable());
+			let rect = wnd.get_rect();
+
+			wnd.set_resizable(false);
+			assert!(!wnd.is_resizable());
+			let style = unsafe { super::GetWindowLongPtrW(hwnd, super::GWL_STYLE) };
+			assert_eq!(style & (WS_THICKFRAME | WS_MAXIMIZEBOX), 0);
+			assert_eq!(wnd.get_rect(), rect);
+
+			wnd.set_resizable(true);
+			assert!(wnd.is_resizable());
+			assert_eq!(wnd.get_rect(), rect);
+			wnd.dismiss();
+		}
+
+		#[test]
+		fn style_bits_toggle() {
+			use capi::sctypes::*;
+			use super::super::BaseWindow;
+			use window::WindowStyle;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+			}
+
+			const WS_OVERLAPPEDWINDOW: UINT = 0x00CF_0000;
+			const WS_EX_TOOLWINDOW: isize = 0x0000_0080;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_OVERLAPPEDWINDOW, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			let wnd = OsWindow::from(hwnd);
+			assert!(wnd.style().contains(WindowStyle::CAPTION | WindowStyle::THICKFRAME));
+			assert!(!wnd.style().contains(WindowStyle::EX_TOOLWINDOW));
+
+			// both words in one go
+			wnd.modify_style(WindowStyle::EX_TOOLWINDOW, WindowStyle::MAXIMIZEBOX);
+			let ex_style = unsafe { super::GetWindowLongPtrW(hwnd, super::GWL_EXSTYLE) };
+			assert_eq!(ex_style & WS_EX_TOOLWINDOW, WS_EX_TOOLWINDOW);
+			assert!(wnd.style().contains(WindowStyle::EX_TOOLWINDOW));
+			assert!(!wnd.style().contains(WindowStyle::MAXIMIZEBOX));
+
+			wnd.modify_style(WindowStyle::MAXIMIZEBOX, WindowStyle::EX_TOOLWINDOW);
+			assert!(wnd.style().contains(WindowStyle::MAXIMIZEBOX));
+			assert!(!wnd.style().contains(WindowStyle::EX_TOOLWINDOW));
+
+			wnd.set_topmost(true);
+			assert!(wnd.style().contains(WindowStyle::EX_TOPMOST));
+			wnd.set_topmost(false);
+			assert!(!wnd.style().contains(WindowStyle::EX_TOPMOST));
+			wnd.dismiss();
+		}
+
+		#[test]
+		fn long_title_roundtrip() {
+			use capi::sctypes::*;
+			use super::super::BaseWindow;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+			}
+
+			const WS_OVERLAPPEDWINDOW: UINT = 0x00CF_0000;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_OVERLAPPEDWINDOW, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			// surrogate pairs included
+			let title = "Заголовок 🪟 ".repeat(2000);
+			let mut wnd = OsWindow::from(hwnd);
+			wnd.set_title(&title);
+			assert_eq!(wnd.get_title(), title);
+
+			wnd.set_title("");
+			assert_eq!(wnd.get_title(), "");
+			wnd.dismiss();
+		}
+
+		#[test]
+		fn dpi_change_calls_back() {
+			use capi::sctypes::*;
+			use std::cell::Cell;
+			use std::rc::Rc;
+			use super::super::BaseWindow;
+
+			#[link(name = "user32")]
+			extern "system" {
+				fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT, x: INT, y: INT, width: INT, height: INT, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+			}
+
+			const WS_OVERLAPPEDWINDOW: UINT = 0x00CF_0000;
+			const WM_DPICHANGED: UINT = 0x02E0;
+			let class = s2w!("STATIC");
+			let null = ::std::ptr::null_mut();
+			let hwnd = unsafe { CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_OVERLAPPEDWINDOW, 10, 10, 300, 200, null, null, null, null) };
+			assert!(!hwnd.is_null());
+
+			let mut wnd = OsWindow::from(hwnd);
+			let scale = Rc::new(Cell::new(0.0));
+			let received = scale.clone();
+			wnd.on_dpi_changed(move |new_scale| received.set(new_scale));
+
+			// 240 DPI is 250%, which no test machine runs at
+			let suggested = RECT { left: 20, top: 20, right: 770, bottom: 520 };
+			let mut handled = false as BOOL;
+			super::window_delegate(hwnd, WM_DPICHANGED, 240 << 16 | 240, &suggested as *const RECT as LPARAM, null, &mut handled);
+			assert_eq!(scale.get(), 2.5);
+			assert_eq!(wnd.get_rect(), suggested);
+
+			// the same scale again is not a change
+			scale.set(0.0);
+			super::window_delegate(hwnd, WM_DPICHANGED, 240 << 16 | 240, &suggested as *const RECT as LPARAM, null, &mut handled);
+			assert_eq!(scale.get(), 0.0);
+
+			wnd.dismiss();
+			super::super::forget_dpi_handler(hwnd);
+		}
+
+		#[test]
+		fn app_id_is_set() {
+			use capi::sctypes::*;
+			use super::super::BaseWindow;
+
+			#[link(name = "shell32")]
+			extern "system" {
+				fn GetCurrentProcessExplicitAppUserModelID(app_id: *mut LPWSTR) -> i32;
+			}
+			#[link(name = "ole32")]
+			extern "system" {
+				fn CoTaskMemFree(mem: LPVOID);
+			}
+
+			OsWindow::new().set_app_id("SciterRs.Tests.AppId");
+			let mut id: LPWSTR = ::std::ptr::null_mut();
+			assert_eq!(unsafe { GetCurrentProcessExplicitAppUserModelID(&mut id) }, 0);
+			let app_id = ::utf::w2s(id);
+			unsafe { CoTaskMemFree(id as LPVOID) };
+			assert_eq!(app_id, "SciterRs.Tests.AppId");
+		}
+	}
+}
+
+#[cfg(target_os = "linux")]
+mod linux {
+	use super::{BaseWindow, UiTask};
+	use capi::scbehavior::KEYBOARD_STATES;
+	use capi::scdef::*;
+	use capi::scmsg::key_codes;
+	use capi::sctypes::*;
+	use _API;
+
+	use std::cell::{Cell, RefCell};
+	use std::collections::HashMap;
+	use std::ffi::{CStr, CString, OsStr};
+	use std::os::unix::ffi::OsStrExt;
+	use std::path::PathBuf;
+	use std::ptr;
+	use std::rc::Rc;
+	use std::sync::atomic::{AtomicBool, Ordering};
+	use std::time::Duration;
+	use window::{Accelerator, BackdropKind, CursorKind, GeometryUpdate, HitTestResult, MonitorInfo, MouseButtons, ProgressState, Theme, WindowError, WindowPlacement, WindowState, ZOrder};
+
+	/// GTK functions which are not exposed by Sciter.
+	///
+	/// Sciter is linked with GTK already, so they are loaded at runtime
+	/// instead of being a build dependency (GDK-PixBuf and GObject ones are found via GTK dependencies).
+	mod gtk {
+		extern crate libc;
+
+		use capi::sctypes::*;
+
+		pub type GtkWidget = LPVOID;
+		pub type GtkWindow = LPVOID;
+		pub type Gboolean = INT;
+		pub type GType = usize;
+
+		#[repr(C)]
+		#[derive(Default)]
+		pub struct GtkAllocation {
+			pub x: INT,
+			pub y: INT,
+			pub width: INT,
+			pub height: INT,
+		}
+
+		type WidgetGetToplevel = extern "C" fn(widget: GtkWidget) -> GtkWidget;
+		type WidgetIsToplevel = extern "C" fn(widget: GtkWidget) -> Gboolean;
+		type WindowSetTitle = extern "C" fn(window: GtkWindow, title: LPCSTR);
+		type WindowGetTitle = extern "C" fn(window: GtkWindow) -> LPCSTR;
+		type WindowMove = extern "C" fn(window: GtkWindow, x: INT, y: INT);
+		type WindowResize = extern "C" fn(window: GtkWindow, width: INT, height: INT);
+		type WindowGetPosition = extern "C" fn(window: GtkWindow, x: *mut INT, y: *mut INT);
+		type WidgetGetAllocation = extern "C" fn(widget: GtkWidget, allocation: *mut GtkAllocation);
+		type WindowFullscreen = extern "C" fn(window: GtkWindow);
+		type WindowUnmaximize = extern "C" fn(window: GtkWindow);
+		type WindowDeiconify = extern "C" fn(window: GtkWindow);
+		type WindowSetKeepAbove = extern "C" fn(window: GtkWindow, setting: Gboolean);
+		type WidgetSetOpacity = extern "C" fn(widget: GtkWidget, opacity: f64);
+		type WindowSetResizable = extern "C" fn(window: GtkWindow, resizable: Gboolean);
+		type WindowGetResizable = extern "C" fn(window: GtkWindow) -> Gboolean;
+		type WidgetSetSizeRequest = extern "C" fn(widget: GtkWidget, width: INT, height: INT);
+		type OffscreenWindowNew = extern "C" fn() -> GtkWidget;
+		type WidgetShow = extern "C" fn(widget: GtkWidget);
+		type WidgetDestroy = extern "C" fn(widget: GtkWidget);
+		type WidgetHide = extern "C" fn(widget: GtkWidget);
+		type WidgetGetParent = extern "C" fn(widget: GtkWidget) -> GtkWidget;
+		type ContainerAdd = extern "C" fn(container: GtkWidget, widget: GtkWidget);
+		type ContainerRemove = extern "C" fn(container: GtkWidget, widget: GtkWidget);
+		type WindowNew = extern "C" fn(kind: INT) -> GtkWidget;
+		type ObjectRef = extern "C" fn(object: LPVOID) -> LPVOID;
+		pub const GDK_SHIFT_MASK: UINT = 1 << 0;
+		pub const GDK_CONTROL_MASK: UINT = 1 << 2;
+		pub const GDK_MOD1_MASK: UINT = 1 << 3;
+		pub const GDK_SUPER_MASK: UINT = 1 << 26;
+
+		type AccelGroupNew = extern "C" fn() -> LPVOID;
+		type AccelGroupConnect = extern "C" fn(group: LPVOID, key: UINT, modifiers: UINT, flags: UINT, closure: LPVOID);
+		type WindowAddAccelGroup = extern "C" fn(window: GtkWindow, group: LPVOID);
+		type CClosureNew = extern "C" fn(callback: GCallback, data: LPVOID, destroy: GClosureNotify) -> LPVOID;
+		type WindowSetTransientFor = extern "C" fn(window: GtkWindow, parent: GtkWindow);
+		type WindowSetModal = extern "C" fn(window: GtkWindow, modal: Gboolean);
+		type WindowPresent = extern "C" fn(window: GtkWindow);
+		type WindowPresentWithTime = extern "C" fn(window: GtkWindow, time: u32);
+		type CursorNewFromName = extern "C" fn(display: LPVOID, name: LPCSTR) -> LPVOID;
+		type WindowSetCursor = extern "C" fn(window: LPVOID, cursor: LPVOID);
+		type PointerGrab = extern "C" fn(window: LPVOID, owner_events: Gboolean, event_mask: UINT, confine_to: LPVOID, cursor: LPVOID, time: u32) -> INT;
+		type PointerUngrab = extern "C" fn(time: u32);
+		type AtomIntern = extern "C" fn(name: LPCSTR, only_if_exists: Gboolean) -> LPVOID;
+		type PropertyChange = extern "C" fn(window: LPVOID, property: LPVOID, kind: LPVOID, format: INT, mode: INT, data: LPCBYTE, elements: INT);
+		type PropertyDelete = extern "C" fn(window: LPVOID, property: LPVOID);
+		type CssProviderNew = extern "C" fn() -> LPVOID;
+		type CssProviderLoadFromData = extern "C" fn(provider: LPVOID, data: LPCSTR, length: isize, error: *mut LPVOID) -> Gboolean;
+		type WidgetGetStyleContext = extern "C" fn(widget: GtkWidget) -> LPVOID;
+		type StyleContextAddProvider = extern "C" fn(context: LPVOID, provider: LPVOID, priority: UINT);
+		pub const GDK_POINTER_MOTION_MASK: UINT = 1 << 2;
+		pub const GDK_BUTTON_PRESS_MASK: UINT = 1 << 8;
+		pub const GDK_BUTTON_RELEASE_MASK: UINT = 1 << 9;
+		type PixbufLoaderNew = extern "C" fn() -> LPVOID;
+		type PixbufLoaderWrite = extern "C" fn(loader: LPVOID, buf: LPCBYTE, count: usize, error: *mut LPVOID) -> Gboolean;
+		type PixbufLoaderClose = extern "C" fn(loader: LPVOID, error: *mut LPVOID) -> Gboolean;
+		type PixbufLoaderGetPixbuf = extern "C" fn(loader: LPVOID) -> LPVOID;
+		type StatusIconNew = extern "C" fn() -> LPVOID;
+		type StatusIconNewFromPixbuf = extern "C" fn(pixbuf: LPVOID) -> LPVOID;
+		type StatusIconSetTooltipText = extern "C" fn(icon: LPVOID, text: LPCSTR);
+		type StatusIconSetVisible = extern "C" fn(icon: LPVOID, visible: Gboolean);
+		type WindowSetIcon = extern "C" fn(window: GtkWindow, pixbuf: LPVOID);
+		type ObjectUnref = extern "C" fn(object: LPVOID);
+		type ErrorFree = extern "C" fn(error: LPVOID);
+		#[repr(C)]
+		pub struct GdkEventButton {
+			pub event_type: INT,
+			pub window: LPVOID,
+			pub send_event: i8,
+			pub time: u32,
+			pub x: f64,
+			pub y: f64,
+			pub axes: *const f64,
+			pub state: UINT,
+			pub button: UINT,
+			pub device: LPVOID,
+			pub x_root: f64,
+			pub y_root: f64,
+		}
+		/// `GdkWindowEdge`
+		pub const GDK_WINDOW_EDGE_NORTH_WEST: INT = 0;
+		pub const GDK_WINDOW_EDGE_NORTH: INT = 1;
+		pub const GDK_WINDOW_EDGE_NORTH_EAST: INT = 2;
+		pub const GDK_WINDOW_EDGE_WEST: INT = 3;
+		pub const GDK_WINDOW_EDGE_EAST: INT = 4;
+		pub const GDK_WINDOW_EDGE_SOUTH_WEST: INT = 5;
+		pub const GDK_WINDOW_EDGE_SOUTH: INT = 6;
+		pub const GDK_WINDOW_EDGE_SOUTH_EAST: INT = 7;
+		/// `GdkModifierType` bits of the mouse buttons.
+		pub const GDK_BUTTON1_MASK: UINT = 1 << 8;
+		pub const GDK_BUTTON2_MASK: UINT = 1 << 9;
+		pub const GDK_BUTTON3_MASK: UINT = 1 << 10;
+		#[repr(C)]
+		pub struct GdkEventConfigure {
+			pub event_type: INT,
+			pub window: LPVOID,
+			pub send_event: i8,
+			pub x: INT,
+			pub y: INT,
+			pub width: INT,
+			pub height: INT,
+		}
+		#[repr(C)]
+		#[derive(Default)]
+		pub struct GdkGeometry {
+			pub min_width: INT,
+			pub min_height: INT,
+			pub max_width: INT,
+			pub max_height: INT,
+			pub base_width: INT,
+			pub base_height: INT,
+			pub width_inc: INT,
+			pub height_inc: INT,
+			pub min_aspect: f64,
+			pub max_aspect: f64,
+			pub win_gravity: INT,
+		}
+
+		pub const GDK_HINT_MIN_SIZE: UINT = 1 << 1;
+		pub const GDK_HINT_MAX_SIZE: UINT = 1 << 2;
+
+		type WindowSetGeometryHints = extern "C" fn(window: GtkWindow, geometry_widget: GtkWidget, geometry: *const GdkGeometry, mask: UINT);
+		pub type GdkRectangle = GtkAllocation;
+		pub const GTK_WIN_POS_CENTER: INT = 1;
+
+		type WindowSetPosition = extern "C" fn(window: GtkWindow, position: INT);
+		type WindowGetSize = extern "C" fn(window: GtkWindow, width: *mut INT, height: *mut INT);
+		type DisplayGetMonitorAtWindow = extern "C" fn(display: LPVOID, window: LPVOID) -> LPVOID;
+		type DisplayGetMonitorAtPoint = extern "C" fn(display: LPVOID, x: INT, y: INT) -> LPVOID;
+		type MonitorGetWorkarea = extern "C" fn(monitor: LPVOID, workarea: *mut GdkRectangle);
+		type WidgetGetScaleFactor = extern "C" fn(widget: GtkWidget) -> INT;
+		pub type GCallback = LPVOID;
+		pub type GClosureNotify = extern "C" fn(data: LPVOID, closure: LPVOID);
+
+		type SignalConnectData = extern "C" fn(instance: LPVOID, signal: LPCSTR, handler: GCallback, data: LPVOID, destroy: GClosureNotify, flags: UINT) -> ::std::os::raw::c_ulong;
+		type WindowSetDecorated = extern "C" fn(window: GtkWindow, setting: Gboolean);
+		type GetCurrentEvent = extern "C" fn() -> LPVOID;
+		type EventFree = extern "C" fn(event: LPVOID);
+		type GetCurrentEventTime = extern "C" fn() -> u32;
+		type EventGetRootCoords = extern "C" fn(event: LPVOID, x: *mut f64, y: *mut f64) -> Gboolean;
+		type WindowBeginMoveDrag = extern "C" fn(window: GtkWindow, button: INT, root_x: INT, root_y: INT, timestamp: u32);
+		type WindowBeginResizeDrag = extern "C" fn(window: GtkWindow, edge: INT, button: INT, root_x: INT, root_y: INT, timestamp: u32);
+		type DisplayGetDefaultSeat = extern "C" fn(display: LPVOID) -> LPVOID;
+		type SeatGetPointer = extern "C" fn(seat: LPVOID) -> LPVOID;
+		type WindowGetDevicePosition = extern "C" fn(window: LPVOID, device: LPVOID, x: *mut INT, y: *mut INT, mask: *mut UINT) -> LPVOID;
+		type IdleAdd = extern "C" fn(function: extern "C" fn(data: LPVOID) -> Gboolean, data: LPVOID) -> UINT;
+		type TimeoutAdd = extern "C" fn(interval: UINT, function: extern "C" fn(data: LPVOID) -> Gboolean, data: LPVOID) -> UINT;
+		type SourceRemove = extern "C" fn(id: UINT) -> Gboolean;
+		type WidgetGetVisible = extern "C" fn(widget: GtkWidget) -> Gboolean;
+		type WindowIsActive = extern "C" fn(window: GtkWindow) -> Gboolean;
+		type WindowSetUrgencyHint = extern "C" fn(window: GtkWindow, setting: Gboolean);
+		type WindowSetSkipHint = extern "C" fn(window: GtkWindow, setting: Gboolean);
+		type EventsPending = extern "C" fn() -> Gboolean;
+		type MainIterationDo = extern "C" fn(blocking: Gboolean) -> Gboolean;
+		type WidgetGetWindow = extern "C" fn(widget: GtkWidget) -> LPVOID;
+		type WindowGetDisplay = extern "C" fn(window: LPVOID) -> LPVOID;
+		type TypeCheckInstanceIsA = extern "C" fn(instance: LPVOID, iface_type: GType) -> Gboolean;
+		type GetType = extern "C" fn() -> GType;
+		type X11WindowGetXid = extern "C" fn(window: LPVOID) -> ::std::os::raw::c_ulong;
+		type BackendGetHandle = extern "C" fn(object: LPVOID) -> LPVOID;
+		type WidgetTranslateCoordinates = extern "C" fn(src: GtkWidget, dest: GtkWidget, x: INT, y: INT, dest_x: *mut INT, dest_y: *mut INT) -> Gboolean;
+		type PixbufGetFromWindow = extern "C" fn(window: LPVOID, x: INT, y: INT, width: INT, height: INT) -> LPVOID;
+		type PixbufGetInt = extern "C" fn(pixbuf: LPVOID) -> INT;
+		type PixbufGetPixels = extern "C" fn(pixbuf: LPVOID) -> LPCBYTE;
+		type InitCheck = extern "C" fn(argc: *mut INT, argv: LPVOID) -> Gboolean;
+		type DisplayGetDefault = extern "C" fn() -> LPVOID;
+		type DisplayGetNMonitors = extern "C" fn(display: LPVOID) -> INT;
+		type DisplayGetMonitor = extern "C" fn(display: LPVOID, index: INT) -> LPVOID;
+		type MonitorGetInt = extern "C" fn(monitor: LPVOID) -> INT;
+
+		#[repr(C)]
+		pub struct GtkTargetEntry {
+			pub target: LPCSTR,
+			pub flags: UINT,
+			pub info: UINT,
+		}
+
+		pub const GTK_DEST_DEFAULT_ALL: UINT = 0x07;
+		pub const GDK_ACTION_COPY: UINT = 1 << 1;
+
+		type DragDestSet = extern "C" fn(widget: GtkWidget, flags: UINT, targets: *const GtkTargetEntry, n_targets: INT, actions: UINT);
+		type SelectionDataGetUris = extern "C" fn(data: LPVOID) -> *mut LPSTR;
+		type FilenameFromUri = extern "C" fn(uri: LPCSTR, hostname: *mut LPSTR, error: *mut LPVOID) -> LPSTR;
+		type Strfreev = extern "C" fn(list: *mut LPSTR);
+		type Free = extern "C" fn(mem: LPVOID);
+		type SettingsGetDefault = extern "C" fn() -> LPVOID;
+		type ObjectGet = extern "C" fn(object: LPVOID, name: LPCSTR, ...);
+		type SignalConnectObject = extern "C" fn(instance: LPVOID, signal: LPCSTR, handler: GCallback, object: LPVOID, flags: UINT) -> ::std::os::raw::c_ulong;
+
+		/// `GDK_SELECTION_CLIPBOARD` atom.
+		pub const GDK_SELECTION_CLIPBOARD: LPVOID = 69 as LPVOID;
+
+		type ClipboardGet = extern "C" fn(selection: LPVOID) -> LPVOID;
+		type ClipboardSetText = extern "C" fn(clipboard: LPVOID, text: LPCSTR, len: INT);
+		type ClipboardWaitForText = extern "C" fn(clipboard: LPVOID) -> LPSTR;
+		type WidgetGetAccessible = extern "C" fn(widget: GtkWidget) -> LPVOID;
+		type AtkObjectSetString = extern "C" fn(accessible: LPVOID, s: LPCSTR);
+		type WindowRestack = extern "C" fn(window: LPVOID);
+
+		/// GDK X11 backend functions.
+		#[cfg_attr(not(feature = "raw-window-handle"), allow(dead_code))]
+		pub struct X11 {
+			pub gdk_x11_display_get_type: GetType,
+			pub gdk_x11_display_get_xdisplay: BackendGetHandle,
+			pub gdk_x11_window_get_xid: X11WindowGetXid,
+		}
+
+		/// The deprecated GDK pointer grabs of `set_cursor_clip`.
+		pub struct PointerGrabs {
+			pub gdk_pointer_grab: PointerGrab,
+			pub gdk_pointer_ungrab: PointerUngrab,
+		}
+
+		/// The deprecated `GtkStatusIcon` functions of `TrayIcon`.
+		pub struct StatusIcon {
+			pub gtk_status_icon_new: StatusIconNew,
+			pub gtk_status_icon_new_from_pixbuf: StatusIconNewFromPixbuf,
+			pub gtk_status_icon_set_tooltip_text: StatusIconSetTooltipText,
+			pub gtk_status_icon_set_visible: StatusIconSetVisible,
+		}
+
+		/// GDK Wayland backend functions.
+		#[cfg_attr(not(feature = "raw-window-handle"), allow(dead_code))]
+		pub struct Wayland {
+			pub gdk_wayland_display_get_type: GetType,
+			pub gdk_wayland_display_get_wl_display: BackendGetHandle,
+			pub gdk_wayland_window_get_wl_surface: BackendGetHandle,
+		}
+
+		#[cfg_attr(not(feature = "raw-window-handle"), allow(dead_code))]
+		pub struct Gtk {
+			pub gtk_widget_get_toplevel: WidgetGetToplevel,
+			pub gtk_widget_is_toplevel: WidgetIsToplevel,
+			pub gtk_window_set_title: WindowSetTitle,
+			pub gtk_window_get_title: WindowGetTitle,
+			pub gtk_window_move: WindowMove,
+			pub gtk_window_resize: WindowResize,
+			pub gtk_window_set_resizable: WindowSetResizable,
+			pub gtk_window_get_resizable: WindowGetResizable,
+			pub gtk_widget_set_size_request: WidgetSetSizeRequest,
+			pub gtk_offscreen_window_new: OffscreenWindowNew,
+			pub gtk_widget_show: WidgetShow,
+			pub gtk_widget_destroy: WidgetDestroy,
+			pub gtk_widget_hide: WidgetHide,
+			pub gtk_widget_get_parent: WidgetGetParent,
+			pub gtk_container_add: ContainerAdd,
+			pub gtk_container_remove: ContainerRemove,
+			pub gtk_window_new: WindowNew,
+			pub g_object_ref: ObjectRef,
+			pub gtk_accel_group_new: AccelGroupNew,
+			pub gtk_accel_group_connect: AccelGroupConnect,
+			pub gtk_window_add_accel_group: WindowAddAccelGroup,
+			pub g_cclosure_new: CClosureNew,
+			pub gtk_window_set_transient_for: WindowSetTransientFor,
+			pub gtk_window_set_modal: WindowSetModal,
+			pub gtk_window_present: WindowPresent,
+			pub gtk_window_present_with_time: WindowPresentWithTime,
+			pub gdk_cursor_new_from_name: CursorNewFromName,
+			pub gdk_window_set_cursor: WindowSetCursor,
+			pub gdk_atom_intern: AtomIntern,
+			pub gdk_property_change: PropertyChange,
+			pub gdk_property_delete: PropertyDelete,
+			pub gtk_css_provider_new: CssProviderNew,
+			pub gtk_css_provider_load_from_data: CssProviderLoadFromData,
+			pub gtk_widget_get_style_context: WidgetGetStyleContext,
+			pub gtk_style_context_add_provider: StyleContextAddProvider,
+			pub gtk_window_get_position: WindowGetPosition,
+			pub gtk_widget_get_allocation: WidgetGetAllocation,
+			pub gtk_window_fullscreen: WindowFullscreen,
+			pub gtk_window_unfullscreen: WindowFullscreen,
+			pub gtk_window_unmaximize: WindowUnmaximize,
+			pub gtk_window_deiconify: WindowDeiconify,
+			pub gtk_window_set_keep_above: WindowSetKeepAbove,
+			pub gtk_widget_set_opacity: WidgetSetOpacity,
+			pub gdk_pixbuf_loader_new: PixbufLoaderNew,
+			pub gdk_pixbuf_loader_write: PixbufLoaderWrite,
+			pub gdk_pixbuf_loader_close: PixbufLoaderClose,
+			pub gdk_pixbuf_loader_get_pixbuf: PixbufLoaderGetPixbuf,
+			pub gtk_window_set_icon: WindowSetIcon,
+			pub g_object_unref: ObjectUnref,
+			pub g_error_free: ErrorFree,
+			pub gtk_window_set_geometry_hints: WindowSetGeometryHints,
+			pub gtk_window_set_position: WindowSetPosition,
+			pub gtk_window_get_size: WindowGetSize,
+			/// GTK 3.22+
+			pub gdk_display_get_monitor_at_window: Option<DisplayGetMonitorAtWindow>,
+			/// GTK 3.22+
+			pub gdk_display_get_monitor_at_point: Option<DisplayGetMonitorAtPoint>,
+			/// GTK 3.22+
+			pub gdk_monitor_get_workarea: Option<MonitorGetWorkarea>,
+			pub gtk_widget_get_scale_factor: WidgetGetScaleFactor,
+			pub g_signal_connect_data: SignalConnectData,
+			pub gtk_window_set_decorated: WindowSetDecorated,
+			pub gtk_get_current_event: GetCurrentEvent,
+			pub gtk_get_current_event_time: GetCurrentEventTime,
+			pub gdk_event_get_root_coords: EventGetRootCoords,
+			pub gdk_event_free: EventFree,
+			pub gtk_window_begin_move_drag: WindowBeginMoveDrag,
+			pub gtk_window_begin_resize_drag: WindowBeginResizeDrag,
+			/// GTK 3.20+
+			pub gdk_display_get_default_seat: Option<DisplayGetDefaultSeat>,
+			/// GTK 3.20+
+			pub gdk_seat_get_pointer: Option<SeatGetPointer>,
+			pub gdk_window_get_device_position: WindowGetDevicePosition,
+			pub g_idle_add: IdleAdd,
+			pub g_timeout_add: TimeoutAdd,
+			pub g_source_remove: SourceRemove,
+			pub gtk_widget_get_visible: WidgetGetVisible,
+			pub gtk_window_is_active: WindowIsActive,
+			pub gtk_window_set_urgency_hint: WindowSetUrgencyHint,
+			pub gtk_window_set_skip_taskbar_hint: WindowSetSkipHint,
+			pub gtk_window_set_skip_pager_hint: WindowSetSkipHint,
+			pub gtk_events_pending: EventsPending,
+			pub gtk_main_iteration_do: MainIterationDo,
+			pub gtk_widget_get_window: WidgetGetWindow,
+			pub gdk_window_get_display: WindowGetDisplay,
+			pub g_type_check_instance_is_a: TypeCheckInstanceIsA,
+			pub gtk_widget_translate_coordinates: WidgetTranslateCoordinates,
+			pub gdk_pixbuf_get_from_window: PixbufGetFromWindow,
+			pub gdk_pixbuf_get_width: PixbufGetInt,
+			pub gdk_pixbuf_get_height: PixbufGetInt,
+			pub gdk_pixbuf_get_rowstride: PixbufGetInt,
+			pub gdk_pixbuf_get_n_channels: PixbufGetInt,
+			pub gdk_pixbuf_get_pixels: PixbufGetPixels,
+			pub gtk_init_check: InitCheck,
+			pub gdk_display_get_default: DisplayGetDefault,
+			/// GTK 3.22+
+			pub gdk_display_get_n_monitors: Option<DisplayGetNMonitors>,
+			/// GTK 3.22+
+			pub gdk_display_get_monitor: Option<DisplayGetMonitor>,
+			/// GTK 3.22+
+			pub gdk_monitor_get_geometry: Option<MonitorGetWorkarea>,
+			/// GTK 3.22+
+			pub gdk_monitor_get_scale_factor: Option<MonitorGetInt>,
+			/// GTK 3.22+
+			pub gdk_monitor_get_refresh_rate: Option<MonitorGetInt>,
+			/// GTK 3.22+
+			pub gdk_monitor_is_primary: Option<MonitorGetInt>,
+			pub gtk_drag_dest_set: DragDestSet,
+			pub gtk_selection_data_get_uris: SelectionDataGetUris,
+			pub g_filename_from_uri: FilenameFromUri,
+			pub g_strfreev: Strfreev,
+			pub g_free: Free,
+			pub gtk_settings_get_default: SettingsGetDefault,
+			pub g_object_get: ObjectGet,
+			pub g_signal_connect_object: SignalConnectObject,
+			pub gtk_clipboard_get: ClipboardGet,
+			pub gtk_clipboard_set_text: ClipboardSetText,
+			pub gtk_clipboard_wait_for_text: ClipboardWaitForText,
+			pub gtk_widget_get_accessible: WidgetGetAccessible,
+			pub gdk_window_raise: WindowRestack,
+			pub gdk_window_lower: WindowRestack,
+			pub atk_object_set_name: AtkObjectSetString,
+			/// ATK 2.34+
+			pub atk_object_set_accessible_id: Option<AtkObjectSetString>,
+			/// GDK can be built without some of its backends.
+			pub x11: Option<X11>,
+			pub wayland: Option<Wayland>,
+			/// The deprecated APIs, which some distributions drop.
+			pub pointer_grabs: Option<PointerGrabs>,
+			pub status_icon: Option<StatusIcon>,
+		}
+
+		lazy_static! {
+			pub static ref GTK: Option<Gtk> = unsafe { load() };
+		}
+
+		unsafe fn load() -> Option<Gtk> {
+			let dll = libc::dlopen(b"libgtk-3.so.0\0".as_ptr() as LPCSTR, libc::RTLD_LOCAL | libc::RTLD_LAZY);
+			if dll.is_null() {
+				return None;
+			}
+
+			macro_rules! sym {
+				($name:ident: $t:ty) => {{
+					let sym = libc::dlsym(dll, concat!(stringify!($name), "\0").as_ptr() as LPCSTR);
+					if sym.is_null() {
+						return None;
+					}
+					::std::mem::transmute::<LPVOID, $t>(sym)
+				}};
+			}
+
+			Some(Gtk {
+				gtk_widget_get_toplevel: sym!(gtk_widget_get_toplevel: WidgetGetToplevel),
+				gtk_widget_is_toplevel: sym!(gtk_widget_is_toplevel: WidgetIsToplevel),
+				gtk_window_set_title: sym!(gtk_window_set_title: WindowSetTitle),
+				gtk_window_get_title: sym!(gtk_window_get_title: WindowGetTitle),
+				gtk_window_move: sym!(gtk_window_move: WindowMove),
+				gtk_window_resize: sym!(gtk_window_resize: WindowResize),
+				gtk_window_set_resizable: sym!(gtk_window_set_resizable: WindowSetResizable),
+				gtk_window_get_resizable: sym!(gtk_window_get_resizable: WindowGetResizable),
+				gtk_widget_set_size_request: sym!(gtk_widget_set_size_request: WidgetSetSizeRequest),
+				gtk_offscreen_window_new: sym!(gtk_offscreen_window_new: OffscreenWindowNew),
+				gtk_widget_show: sym!(gtk_widget_show: WidgetShow),
+				gtk_widget_destroy: sym!(gtk_widget_destroy: WidgetDestroy),
+				gtk_widget_hide: sym!(gtk_widget_hide: WidgetHide),
+				gtk_widget_get_parent: sym!(gtk_widget_get_parent: WidgetGetParent),
+				gtk_container_add: sym!(gtk_container_add: ContainerAdd),
+				gtk_container_remove: sym!(gtk_container_remove: ContainerRemove),
+				gtk_window_new: sym!(gtk_window_new: WindowNew),
+				g_object_ref: sym!(g_object_ref: ObjectRef),
+				gtk_accel_group_new: sym!(gtk_accel_group_new: AccelGroupNew),
+				gtk_accel_group_connect: sym!(gtk_accel_group_connect: AccelGroupConnect),
+				gtk_window_add_accel_group: sym!(gtk_window_add_accel_group: WindowAddAccelGroup),
+				g_cclosure_new: sym!(g_cclosure_new: CClosureNew),
+				gtk_window_set_transient_for: sym!(gtk_window_set_transient_for: WindowSetTransientFor),
+				gtk_window_set_modal: sym!(gtk_window_set_modal: WindowSetModal),
+				gtk_window_present: sym!(gtk_window_present: WindowPresent),
+				gtk_window_present_with_time: sym!(gtk_window_present_with_time: WindowPresentWithTime),
+				gdk_cursor_new_from_name: sym!(gdk_cursor_new_from_name: CursorNewFromName),
+				gdk_window_set_cursor: sym!(gdk_window_set_cursor: WindowSetCursor),
+				gdk_atom_intern: sym!(gdk_atom_intern: AtomIntern),
+				gdk_property_change: sym!(gdk_property_change: PropertyChange),
+				gdk_property_delete: sym!(gdk_property_delete: PropertyDelete),
+				gtk_css_provider_new: sym!(gtk_css_provider_new: CssProviderNew),
+				gtk_css_provider_load_from_data: sym!(gtk_css_provider_load_from_data: CssProviderLoadFromData),
+				gtk_widget_get_style_context: sym!(gtk_widget_get_style_context: WidgetGetStyleContext),
+				gtk_style_context_add_provider: sym!(gtk_style_context_add_provider: StyleContextAddProvider),
+				gtk_window_get_position: sym!(gtk_window_get_position: WindowGetPosition),
+				gtk_widget_get_allocation: sym!(gtk_widget_get_allocation: WidgetGetAllocation),
+				gtk_window_fullscreen: sym!(gtk_window_fullscreen: WindowFullscreen),
+				gtk_window_unfullscreen: sym!(gtk_window_unfullscreen: WindowFullscreen),
+				gtk_window_unmaximize: sym!(gtk_window_unmaximize: WindowUnmaximize),
+				gtk_window_deiconify: sym!(gtk_window_deiconify: WindowDeiconify),
+				gtk_window_set_keep_above: sym!(gtk_window_set_keep_above: WindowSetKeepAbove),
+				gtk_widget_set_opacity: sym!(gtk_widget_set_opacity: WidgetSetOpacity),
+				gdk_pixbuf_loader_new: sym!(gdk_pixbuf_loader_new: PixbufLoaderNew),
+				gdk_pixbuf_loader_write: sym!(gdk_pixbuf_loader_write: PixbufLoaderWrite),
+				gdk_pixbuf_loader_close: sym!(gdk_pixbuf_loader_close: PixbufLoaderClose),
+				gdk_pixbuf_loader_get_pixbuf: sym!(gdk_pixbuf_loader_get_pixbuf: PixbufLoaderGetPixbuf),
+				gtk_window_set_icon: sym!(gtk_window_set_icon: WindowSetIcon),
+				g_object_unref: sym!(g_object_unref: ObjectUnref),
+				g_error_free: sym!(g_error_free: ErrorFree),
+				gtk_window_set_geometry_hints: sym!(gtk_window_set_geometry_hints: WindowSetGeometryHints),
+				gtk_window_set_position: sym!(gtk_window_set_position: WindowSetPosition),
+				gtk_window_get_size: sym!(gtk_window_get_size: WindowGetSize),
+				gdk_display_get_monitor_at_window: (|| Some(sym!(gdk_display_get_monitor_at_window: DisplayGetMonitorAtWindow)))(),
+				gdk_display_get_monitor_at_point: (|| Some(sym!(gdk_display_get_monitor_at_point: DisplayGetMonitorAtPoint)))(),
+				gdk_monitor_get_workarea: (|| Some(sym!(gdk_monitor_get_workarea: MonitorGetWorkarea)))(),
+				gtk_widget_get_scale_factor: sym!(gtk_widget_get_scale_factor: WidgetGetScaleFactor),
+				g_signal_connect_data: sym!(g_signal_connect_data: SignalConnectData),
+				gtk_window_set_decorated: sym!(gtk_window_set_decorated: WindowSetDecorated),
+				gtk_get_current_event: sym!(gtk_get_current_event: GetCurrentEvent),
+				gtk_get_current_event_time: sym!(gtk_get_current_event_time: GetCurrentEventTime),
+				gdk_event_get_root_coords: sym!(gdk_event_get_root_coords: EventGetRootCoords),
+				gdk_event_free: sym!(gdk_event_free: EventFree),
+				gtk_window_begin_move_drag: sym!(gtk_window_begin_move_drag: WindowBeginMoveDrag),
+				gtk_window_begin_resize_drag: sym!(gtk_window_begin_resize_drag: WindowBeginResizeDrag),
+				gdk_display_get_default_seat: (|| Some(sym!(gdk_display_get_default_seat: DisplayGetDefaultSeat)))(),
+				gdk_seat_get_pointer: (|| Some(sym!(gdk_seat_get_pointer: SeatGetPointer)))(),
+				gdk_window_get_device_position: sym!(gdk_window_get_device_position: WindowGetDevicePosition),
+				g_idle_add: sym!(g_idle_add: IdleAdd),
+				g_timeout_add: sym!(g_timeout_add: TimeoutAdd),
+				g_source_remove: sym!(g_source_remove: SourceRemove),
+				gtk_widget_get_visible: sym!(gtk_widget_get_visible: WidgetGetVisible),
+				gtk_window_is_active: sym!(gtk_window_is_active: WindowIsActive),
+				gtk_window_set_urgency_hint: sym!(gtk_window_set_urgency_hint: WindowSetUrgencyHint),
+				gtk_window_set_skip_taskbar_hint: sym!(gtk_window_set_skip_taskbar_hint: WindowSetSkipHint),
+				gtk_window_set_skip_pager_hint: sym!(gtk_window_set_skip_pager_hint: WindowSetSkipHint),
+				gtk_events_pending: sym!(gtk_events_pending: EventsPending),
+				gtk_main_iteration_do: sym!(gtk_main_iteration_do: MainIterationDo),
+				gtk_widget_get_window: sym!(gtk_widget_get_window: WidgetGetWindow),
+				gdk_window_get_display: sym!(gdk_window_get_display: WindowGetDisplay),
+				g_type_check_instance_is_a: sym!(g_type_check_instance_is_a: TypeCheckInstanceIsA),
+				gtk_widget_translate_coordinates: sym!(gtk_widget_translate_coordinates: WidgetTranslateCoordinates),
+				gdk_pixbuf_get_from_window: sym!(gdk_pixbuf_get_from_window: PixbufGetFromWindow),
+				gdk_pixbuf_get_width: sym!(gdk_pixbuf_get_width: PixbufGetInt),
+				gdk_pixbuf_get_height: sym!(gdk_pixbuf_get_height: PixbufGetInt),
+				gdk_pixbuf_get_rowstride: sym!(gdk_pixbuf_get_rowstride: PixbufGetInt),
+				gdk_pixbuf_get_n_channels: sym!(gdk_pixbuf_get_n_channels: PixbufGetInt),
+				gdk_pixbuf_get_pixels: sym!(gdk_pixbuf_get_pixels: PixbufGetPixels),
+				gtk_init_check: sym!(gtk_init_check: InitCheck),
+				gdk_display_get_default: sym!(gdk_display_get_default: DisplayGetDefault),
+				gdk_display_get_n_monitors: (|| Some(sym!(gdk_display_get_n_monitors: DisplayGetNMonitors)))(),
+				gdk_display_get_monitor: (|| Some(sym!(gdk_display_get_monitor: DisplayGetMonitor)))(),
+				gdk_monitor_get_geometry: (|| Some(sym!(gdk_monitor_get_geometry: MonitorGetWorkarea)))(),
+				gdk_monitor_get_scale_factor: (|| Some(sym!(gdk_monitor_get_scale_factor: MonitorGetInt)))(),
+				gdk_monitor_get_refresh_rate: (|| Some(sym!(gdk_monitor_get_refresh_rate: MonitorGetInt)))(),
+				gdk_monitor_is_primary: (|| Some(sym!(gdk_monitor_is_primary: MonitorGetInt)))(),
+				gtk_drag_dest_set: sym!(gtk_drag_dest_set: DragDestSet),
+				gtk_selection_data_get_uris: sym!(gtk_selection_data_get_uris: SelectionDataGetUris),
+				g_filename_from_uri: sym!(g_filename_from_uri: FilenameFromUri),
+				g_strfreev: sym!(g_strfreev: Strfreev),
+				g_free: sym!(g_free: Free),
+				gtk_settings_get_default: sym!(gtk_settings_get_default: SettingsGetDefault),
+				g_object_get: sym!(g_object_get: ObjectGet),
+				g_signal_connect_object: sym!(g_signal_connect_object: SignalConnectObject),
+				gtk_clipboard_get: sym!(gtk_clipboard_get: ClipboardGet),
+				gtk_clipboard_set_text: sym!(gtk_clipboard_set_text: ClipboardSetText),
+				gtk_clipboard_wait_for_text: sym!(gtk_clipboard_wait_for_text: ClipboardWaitForText),
+				gtk_widget_get_accessible: sym!(gtk_widget_get_accessible: WidgetGetAccessible),
+				gdk_window_raise: sym!(gdk_window_raise: WindowRestack),
+				gdk_window_lower: sym!(gdk_window_lower: WindowRestack),
+				// ATK is a dependency of GTK, so `dlsym` finds it via the GTK handle
+				atk_object_set_name: sym!(atk_object_set_name: AtkObjectSetString),
+				atk_object_set_accessible_id: (|| Some(sym!(atk_object_set_accessible_id: AtkObjectSetString)))(),
+				// `sym!` returns from the closure here, so a missing backend is not an error
+				x11: (|| Some(X11 {
+					gdk_x11_display_get_type: sym!(gdk_x11_display_get_type: GetType),
+					gdk_x11_display_get_xdisplay: sym!(gdk_x11_display_get_xdisplay: BackendGetHandle),
+					gdk_x11_window_get_xid: sym!(gdk_x11_window_get_xid: X11WindowGetXid),
+				}))(),
+				wayland: (|| Some(Wayland {
+					gdk_wayland_display_get_type: sym!(gdk_wayland_display_get_type: GetType),
+					gdk_wayland_display_get_wl_display: sym!(gdk_wayland_display_get_wl_display: BackendGetHandle),
+					gdk_wayland_window_get_wl_surface: sym!(gdk_wayland_window_get_wl_surface: BackendGetHandle),
+				}))(),
+				pointer_grabs: (|| Some(PointerGrabs {
+					gdk_pointer_grab: sym!(gdk_pointer_grab: PointerGrab),
+					gdk_pointer_ungrab: sym!(gdk_pointer_ungrab: PointerUngrab),
+				}))(),
+				status_icon: (|| Some(StatusIcon {
+					gtk_status_icon_new: sym!(gtk_status_icon_new: StatusIconNew),
+					gtk_status_icon_new_from_pixbuf: sym!(gtk_status_icon_new_from_pixbuf: StatusIconNewFromPixbuf),
+					gtk_status_icon_set_tooltip_text: sym!(gtk_status_icon_set_tooltip_text: StatusIconSetTooltipText),
+					gtk_status_icon_set_visible: sym!(gtk_status_icon_set_visible: StatusIconSetVisible),
+				}))(),
+			})
+		}
+	}
+
+
+	/// Decode the image into a new `GdkPixbuf`, null if it is not an image.
+	fn load_pixbuf(gtk: &gtk::Gtk, image: &[u8]) -> LPVOID {
+		let loader = (gtk.gdk_pixbuf_loader_new)();
+		if loader.is_null() {
+			return ptr::null_mut();
+		}
+		let mut pixbuf = ptr::null_mut();
+		let mut error = ptr::null_mut();
+		if (gtk.gdk_pixbuf_loader_write)(loader, image.as_ptr(), image.len(), &mut error) == 0 {
+			(gtk.g_error_free)(error);
+			error = ptr::null_mut();
+		}
+		// the loader must be closed anyway
+		if (gtk.gdk_pixbuf_loader_close)(loader, &mut error) != 0 {
+			pixbuf = (gtk.gdk_pixbuf_loader_get_pixbuf)(loader);
+			if !pixbuf.is_null() {
+				// it is owned by the loader
+				(gtk.g_object_ref)(pixbuf);
+			}
+		} else if !error.is_null() {
+			(gtk.g_error_free)(error);
+		}
+		(gtk.g_object_unref)(loader);
+		return pixbuf;
+	}
+
+	/// `GtkStatusIcon` of `TrayIcon`, it reports the `activate` signal.
+	pub struct OsTrayIcon {
+		icon: LPVOID,
+	}
+
+	impl OsTrayIcon {
+		pub fn add(_hwnd: HWINDOW, id: u32, image: &[u8], tooltip: &str) -> OsTrayIcon {
+			extern "C" fn on_activate(_icon: LPVOID, id: LPVOID) {
+				super::TrayIcon::clicked(id as usize as u32);
+			}
+
+			extern "C" fn on_disconnect(_id: LPVOID, _closure: LPVOID) {
+			}
+
+			// no icon without `GtkStatusIcon`
+			let (gtk, status_icon) = match gtk::GTK.as_ref().and_then(|gtk| gtk.status_icon.as_ref().map(|status_icon| (gtk, status_icon))) {
+				Some(functions) => functions,
+				None => return OsTrayIcon { icon: ptr::null_mut() },
+			};
+			let pixbuf = if image.is_empty() { ptr::null_mut() } else { load_pixbuf(gtk, image) };
+			let icon = if pixbuf.is_null() {
+				(status_icon.gtk_status_icon_new)()
+			} else {
+				let icon = (status_icon.gtk_status_icon_new_from_pixbuf)(pixbuf);
+				(gtk.g_object_unref)(pixbuf);
+				icon
+			};
+			if !icon.is_null() {
+				let tooltip = CString::new(tooltip.replace('\0', "")).unwrap_or_default();
+				(status_icon.gtk_status_icon_set_tooltip_text)(icon, tooltip.as_ptr());
+				let signal = b"activate\0".as_ptr() as LPCSTR;
+				(gtk.g_signal_connect_data)(icon, signal, on_activate as *const () as gtk::GCallback, id as usize as LPVOID, on_disconnect, 0);
+			}
+			return OsTrayIcon { icon: icon };
+		}
+
+		pub fn remove(&mut self) {
+			// there is an icon only with `GtkStatusIcon`
+			let functions = gtk::GTK.as_ref().and_then(|gtk| gtk.status_icon.as_ref().map(|status_icon| (gtk, status_icon)));
+			if let (Some((gtk, status_icon)), false) = (functions, self.icon.is_null()) {
+				// hide it right away, someone else may hold a reference
+				(status_icon.gtk_status_icon_set_visible)(self.icon, false as gtk::Gboolean);
+				(gtk.g_object_unref)(self.icon);
+			}
+			self.icon = ptr::null_mut();
+		}
+	}
+
+
+	/// Set by `quit_app` for the external event loops driven by `pump_events`.
+	static QUIT_POSTED: AtomicBool = AtomicBool::new(false);
+
+	thread_local! {
+		/// Minimum and maximum sizes of the windows, GTK sets both with the same call.
+		static SIZE_LIMITS: RefCell<HashMap<HWINDOW, (SIZE, SIZE)>> = Default::default();
+	}
+
+	pub struct OsWindow {
+		hwnd: HWINDOW,
+		flags: UINT,
+		/// Background color to apply on creation.
+		background: Option<u32>,
+		/// Whether this instance has created the window and destroys it on drop.
+		owned: bool,
+	}
+
+	impl OsWindow {
+		pub fn new() -> OsWindow {
+			OsWindow {
+				hwnd: 0 as HWINDOW,
+				flags: 0,
+				background: None,
+				owned: false,
+			}
+		}
+
+		pub fn from(hwnd: HWINDOW) -> OsWindow {
+			OsWindow { hwnd: hwnd, flags: 0, background: None, owned: false }
+		}
+
+		/// Create a Sciter child window inside of the foreign `parent` window.
+		pub fn attach(parent: HWINDOW, rc: RECT) -> OsWindow {
+			assert!(!parent.is_null());
+			let flags = SCITER_CREATE_WINDOW_FLAGS::SW_CHILD | SCITER_CREATE_WINDOW_FLAGS::SW_CONTROLS;
+			let mut wnd = OsWindow::new();
+			BaseWindow::create(&mut wnd, rc, flags.bits() as UINT, parent);
+			return wnd;
+		}
+
+		pub(super) fn init_app() -> Result<(), WindowError> {
+			(_API.SciterExec)(SCITER_APP_CMD::SCITER_APP_INIT.bits(), 0, 0);
+			return Ok(());
+		}
+
+		/// Save the focused element when the toplevel loses the focus and restore it on `focus-in-event`.
+		#[cfg_attr(feature = "windowless", allow(dead_code))]
+		fn track_focus(&self) {
+			extern "C" fn on_focus_out(_widget: gtk::GtkWidget, _event: LPVOID, hwnd: LPVOID) -> gtk::Gboolean {
+				super::save_focus(hwnd as HWINDOW);
+				if super::cursor_clip_enabled(hwnd as HWINDOW) {
+					OsWindow::clip_cursor(hwnd as gtk::GtkWidget, false);
+				}
+				return false as gtk::Gboolean;
+			}
+
+			extern "C" fn on_focus_in(_widget: gtk::GtkWidget, _event: LPVOID, hwnd: LPVOID) -> gtk::Gboolean {
+				extern "C" fn on_idle(hwnd: LPVOID) -> gtk::Gboolean {
+					super::restore_focus(hwnd as HWINDOW);
+					if super::cursor_clip_enabled(hwnd as HWINDOW) {
+						OsWindow::clip_cursor(hwnd as gtk::GtkWidget, true);
+					}
+					return false as gtk::Gboolean;
+				}
+				// after Sciter has handled the activation
+				if let Some(gtk) = gtk::GTK.as_ref() {
+					(gtk.g_idle_add)(on_idle, hwnd);
+				}
+				return false as gtk::Gboolean;
+			}
+
+			extern "C" fn on_disconnect(hwnd: LPVOID, _closure: LPVOID) {
+				super::forget_focus(hwnd as HWINDOW);
+			}
+
+			if let Some((gtk, top)) = self.toplevel() {
+				let signal = b"focus-out-event\0".as_ptr() as LPCSTR;
+				(gtk.g_signal_connect_data)(top, signal, on_focus_out as *const () as gtk::GCallback, self.hwnd as LPVOID, on_disconnect, 0);
+				let signal = b"focus-in-event\0".as_ptr() as LPCSTR;
+				(gtk.g_signal_connect_data)(top, signal, on_focus_in as *const () as gtk::GCallback, self.hwnd as LPVOID, on_disconnect, 0);
+			}
+		}
+
+		/// Remember the created widget until its `destroy`, see `Drop for OsWindow`.
+		#[cfg_attr(feature = "windowless", allow(dead_code))]
+		fn track_destruction(&self) {
+			extern "C" fn on_destroy(_widget: gtk::GtkWidget, _hwnd: LPVOID) {
+			}
+
+			extern "C" fn on_disconnect(hwnd: LPVOID, _closure: LPVOID) {
+				super::forget_window(hwnd as HWINDOW);
+			}
+
+			super::window_created(self.hwnd);
+			if let Some(gtk) = gtk::GTK.as_ref() {
+				let signal = b"destroy\0".as_ptr() as LPCSTR;
+				(gtk.g_signal_connect_data)(self.window() as gtk::GtkWidget, signal, on_destroy as *const () as gtk::GCallback, self.hwnd as LPVOID, on_disconnect, 0);
+			}
+		}
+
+		/// Report the toplevel geometry changes to the `on_resize` and `on_move` callbacks.
+		fn track_geometry(&self) {
+			extern "C" fn on_configure(widget: gtk::GtkWidget, event: *const gtk::GdkEventConfigure, hwnd: LPVOID) -> gtk::Gboolean {
+				if let (Some(gtk), Some(event)) = (gtk::GTK.as_ref(), unsafe { event.as_ref() }) {
+					// the frame position as `get_rect` has it, the event has the client one
+					let (mut x, mut y) = (0, 0);
+					(gtk.gtk_window_get_position)(widget as gtk::GtkWindow, &mut x, &mut y);
+					super::window_configured(hwnd as HWINDOW, RECT { left: x, top: y, right: x + event.width, bottom: y + event.height });
+				}
+				return false as gtk::Gboolean;
+			}
+
+			extern "C" fn on_disconnect(hwnd: LPVOID, _closure: LPVOID) {
+				super::forget_geometry_handlers(hwnd as HWINDOW);
+			}
+
+			if let Some((gtk, top)) = self.toplevel() {
+				let signal = b"configure-event\0".as_ptr() as LPCSTR;
+				(gtk.g_signal_connect_data)(top, signal, on_configure as *const () as gtk::GCallback, self.hwnd as LPVOID, on_disconnect, 0);
+			}
+		}
+
+		/// Remember the time of the keyboard and mouse events of the Sciter widget.
+		#[cfg_attr(feature = "windowless", allow(dead_code))]
+		fn track_input(&self) {
+			extern "C" fn on_input(_widget: gtk::GtkWidget, _event: LPVOID, hwnd: LPVOID) -> gtk::Gboolean {
+				super::input_received(hwnd as HWINDOW);
+				return false as gtk::Gboolean;
+			}
+
+			extern "C" fn on_disconnect(hwnd: LPVOID, _closure: LPVOID) {
+				super::forget_input(hwnd as HWINDOW);
+			}
+
+			// the idle time is counted since the creation
+			super::input_received(self.hwnd);
+
+			if let Some(gtk) = gtk::GTK.as_ref() {
+				let widget = self.window() as gtk::GtkWidget;
+				let signals: [&[u8]; 5] = [b"key-press-event\0", b"key-release-event\0", b"button-press-event\0", b"motion-notify-event\0", b"scroll-event\0"];
+				for signal in signals.iter() {
+					(gtk.g_signal_connect_data)(widget, signal.as_ptr() as LPCSTR, on_input as *const () as gtk::GCallback, self.hwnd as LPVOID, on_disconnect, 0);
+				}
+			}
+		}
+
+		/// Show the hidden window in the `state`, GTK applies it before mapping the window.
+		pub fn show_in_state(&self, state: WindowState) {
+			let state = match state {
+				WindowState::Hidden => return,
+				WindowState::Normal => SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_SHOWN,
+				WindowState::Minimized => SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_MINIMIZED,
+				WindowState::Maximized => SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_MAXIMIZED,
+				WindowState::Fullscreen => SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_FULL_SCREEN,
+			};
+			(_API.SciterWindowExec)(self.window(), SCITER_WINDOW_CMD::SCITER_WINDOW_SET_STATE.bits(), state.bits() as UINT_PTR, 0);
+		}
+
+		/// The dark variant of the GTK theme: `gtk-application-prefer-dark-theme` or a `-dark` theme name, e.g. `Adwaita-dark`.
+		pub fn current_theme() -> Theme {
+			let gtk = match gtk::GTK.as_ref() {
+				Some(gtk) => gtk,
+				None => return Theme::Light,
+			};
+			// there are no settings before `gtk_init`
+			let settings = (gtk.gtk_settings_get_default)();
+			if settings.is_null() {
+				return Theme::Light;
+			}
+			let mut prefer_dark: gtk::Gboolean = 0;
+			let mut name: LPSTR = ptr::null_mut();
+			let prefer_dark_property = b"gtk-application-prefer-dark-theme\0".as_ptr() as LPCSTR;
+			let name_property = b"gtk-theme-name\0".as_ptr() as LPCSTR;
+			(gtk.g_object_get)(settings, prefer_dark_property, &mut prefer_dark as *mut gtk::Gboolean, name_property, &mut name as *mut LPSTR, ptr::null::<CHAR>());
+			let dark_name = !name.is_null() && {
+				let name = unsafe { CStr::from_ptr(name) }.to_string_lossy().to_lowercase();
+				name.ends_with("-dark") || name.ends_with(":dark")
+			};
+			if !name.is_null() {
+				(gtk.g_free)(name as LPVOID);
+			}
+			return if prefer_dark != 0 || dark_name { Theme::Dark } else { Theme::Light };
+		}
+
+		/// List the monitors of the default GDK display.
+		pub fn enumerate_monitors() -> Vec<MonitorInfo> {
+			let mut list = Vec::new();
+			let gtk = match gtk::GTK.as_ref() {
+				Some(gtk) => gtk,
+				None => return list,
+			};
+			// no-op if GTK is initialized already
+			if (gtk.gtk_init_check)(ptr::null_mut(), ptr::null_mut()) == 0 {
+				return list;
+			}
+			let display = (gtk.gdk_display_get_default)();
+			if display.is_null() {
+				return list;
+			}
+			let (get_n_monitors, get_monitor) = match (gtk.gdk_display_get_n_monitors, gtk.gdk_display_get_monitor) {
+				(Some(get_n_monitors), Some(get_monitor)) => (get_n_monitors, get_monitor),
+				_ => return list,
+			};
+			for index in 0..get_n_monitors(display) {
+				let monitor = get_monitor(display, index);
+				if !monitor.is_null() {
+					list.extend(OsWindow::monitor_info(gtk, display, monitor));
+				}
+			}
+			return list;
+		}
+
+		/// The GDK key value of the key code, if it can be an accelerator.
+		fn keyval(key: u32) -> Option<UINT> {
+			let value = match key {
+				// the lowercase letters, as the accelerators expect
+				key_codes::KB_A..=key_codes::KB_Z => key + 0x20,
+				key_codes::KB_0..=key_codes::KB_9 | key_codes::KB_SPACE => key,
+				key_codes::KB_F1..=key_codes::KB_F25 => 0xFFBE + (key - key_codes::KB_F1),
+				key_codes::KB_ESCAPE => 0xFF1B,
+				key_codes::KB_ENTER => 0xFF0D,
+				key_codes::KB_TAB => 0xFF09,
+				key_codes::KB_BACKSPACE => 0xFF08,
+				key_codes::KB_INSERT => 0xFF63,
+				key_codes::KB_DELETE => 0xFFFF,
+				key_codes::KB_RIGHT => 0xFF53,
+				key_codes::KB_LEFT => 0xFF51,
+				key_codes::KB_DOWN => 0xFF54,
+				key_codes::KB_UP => 0xFF52,
+				key_codes::KB_PAGE_UP => 0xFF55,
+				key_codes::KB_PAGE_DOWN => 0xFF56,
+				key_codes::KB_HOME => 0xFF50,
+				key_codes::KB_END => 0xFF57,
+				_ => return None,
+			};
+			return Some(value);
+		}
+
+		/// Describe the `GdkMonitor` of the display, `None` before GTK 3.22.
+		fn monitor_info(gtk: &gtk::Gtk, display: LPVOID, monitor: LPVOID) -> Option<MonitorInfo> {
+			let (get_geometry, get_workarea, get_scale_factor) = (gtk.gdk_monitor_get_geometry?, gtk.gdk_monitor_get_workarea?, gtk.gdk_monitor_get_scale_factor?);
+			let (get_n_monitors, get_monitor, is_primary) = (gtk.gdk_display_get_n_monitors?, gtk.gdk_display_get_monitor?, gtk.gdk_monitor_is_primary?);
+			let to_rect = |rc: gtk::GdkRectangle| RECT { left: rc.x, top: rc.y, right: rc.x + rc.width, bottom: rc.y + rc.height };
+			let (mut geometry, mut work) = (gtk::GdkRectangle::default(), gtk::GdkRectangle::default());
+			get_geometry(monitor, &mut geometry);
+			get_workarea(monitor, &mut work);
+			let monitors = (0..get_n_monitors(display)).map(|index| get_monitor(display, index));
+			// Wayland has no notion of the primary monitor, so the first one is
+			let primary = is_primary(monitor) != 0
+				|| (!monitors.clone().any(|m| !m.is_null() && is_primary(m) != 0) && monitors.clone().next() == Some(monitor));
+			return Some(MonitorInfo {
+				rect: to_rect(geometry),
+				work_area: to_rect(work),
+				scale: get_scale_factor(monitor).max(1) as f32,
+				is_primary: primary,
+			});
+		}
+
+		fn window(&self) -> HWINDOW {
+			self.get_hwnd()
+		}
+
+		/// The minimum and maximum window sizes, zero means no limit.
+		fn size_limits(&self) -> (SIZE, SIZE) {
+			SIZE_LIMITS.with(|limits| limits.borrow().get(&self.hwnd).cloned().unwrap_or_default())
+		}
+
+		fn set_size_limits(&self, min: SIZE, max: SIZE) {
+			extern "C" fn on_destroy(_widget: gtk::GtkWidget, _hwnd: LPVOID) {
+			}
+
+			extern "C" fn on_disconnect(hwnd: LPVOID, _closure: LPVOID) {
+				SIZE_LIMITS.with(|limits| limits.borrow_mut().remove(&(hwnd as HWINDOW)));
+			}
+
+			let first = SIZE_LIMITS.with(|limits| limits.borrow_mut().insert(self.hwnd, (min, max)).is_none());
+			if first && !self.hwnd.is_null() {
+				if let Some(gtk) = gtk::GTK.as_ref() {
+					// only to forget the limits with the widget
+					let signal = b"destroy\0".as_ptr() as LPCSTR;
+					(gtk.g_signal_connect_data)(self.window() as gtk::GtkWidget, signal, on_destroy as *const () as gtk::GCallback, self.hwnd as LPVOID, on_disconnect, 0);
+				}
+			}
+			if let Some((gtk, top)) = self.toplevel() {
+				let unlimited = |v: i32| if v > 0 { v } else { i32::MAX };
+				let geometry = gtk::GdkGeometry {
+					min_width: min.cx.max(0),
+					min_height: min.cy.max(0),
+					max_width: unlimited(max.cx),
+					max_height: unlimited(max.cy),
+					..Default::default()
+				};
+				let mut mask = 0;
+				if min.cx > 0 || min.cy > 0 {
+					mask |= gtk::GDK_HINT_MIN_SIZE;
+				}
+				if max.cx > 0 || max.cy > 0 {
+					mask |= gtk::GDK_HINT_MAX_SIZE;
+				}
+				(gtk.gtk_window_set_geometry_hints)(top, ptr::null_mut(), &geometry, mask);
+			}
+		}
+
+		/// The toplevel `GtkWindow` of the Sciter widget along with the GTK functions.
+		fn toplevel(&self) -> Option<(&'static gtk::Gtk, gtk::GtkWindow)> {
+			let gtk = gtk::GTK.as_ref()?;
+			let hwnd = self.window();
+			if hwnd.is_null() {
+				return None;
+			}
+			let top = (gtk.gtk_widget_get_toplevel)(hwnd as gtk::GtkWidget);
+			if top.is_null() || (gtk.gtk_widget_is_toplevel)(top) == 0 {
+				return None;
+			}
+			Some((gtk, top))
+		}
+
+		/// The pointer position relative to the toplevel GDK window and the modifier mask with the pressed buttons.
+		fn pointer_state(gtk: &gtk::Gtk, top: gtk::GtkWindow) -> Option<(INT, INT, UINT)> {
+			let window = (gtk.gtk_widget_get_window)(top);
+			if window.is_null() {
+				return None;
+			}
+			let seat = (gtk.gdk_display_get_default_seat?)((gtk.gdk_window_get_display)(window));
+			let pointer = if seat.is_null() { seat } else { (gtk.gdk_seat_get_pointer?)(seat) };
+			if pointer.is_null() {
+				return None;
+			}
+			let (mut x, mut y, mut mask) = (0, 0, 0);
+			(gtk.gdk_window_get_device_position)(window, pointer, &mut x, &mut y, &mut mask);
+			Some((x, y, mask))
+		}
+
+		/// The `GdkDisplay` and `GdkMonitor` of the toplevel window, the one under its center if it is not realized yet.
+		fn gdk_monitor(&self) -> Option<(&'static gtk::Gtk, LPVOID, LPVOID)> {
+			let (gtk, top) = self.toplevel()?;
+			let window = (gtk.gtk_widget_get_window)(top);
+			let (display, monitor) = if window.is_null() {
+				let display = (gtk.gdk_display_get_default)();
+				if display.is_null() {
+					return None;
+				}
+				let (mut x, mut y, mut width, mut height) = (0, 0, 0, 0);
+				(gtk.gtk_window_get_position)(top, &mut x, &mut y);
+				(gtk.gtk_window_get_size)(top, &mut width, &mut height);
+				(display, (gtk.gdk_display_get_monitor_at_point?)(display, x + width / 2, y + height / 2))
+			} else {
+				let display = (gtk.gdk_window_get_display)(window);
+				(display, (gtk.gdk_display_get_monitor_at_window?)(display, window))
+			};
+			if monitor.is_null() {
+				return None;
+			}
+			Some((gtk, display, monitor))
+		}
+
+		/// Confine the pointer to the GDK window of the widget with a pointer grab, or release it.
+		///
+		/// The grab keeps delivering the events to the app windows as usual,
+		/// but the confinement works on X11 only, Wayland compositors ignore it.
+		fn clip_cursor(widget: gtk::GtkWidget, enable: bool) {
+			let (gtk, grabs) = match gtk::GTK.as_ref().and_then(|gtk| gtk.pointer_grabs.as_ref().map(|grabs| (gtk, grabs))) {
+				Some(functions) => functions,
+				None => return,
+			};
+			// `GDK_CURRENT_TIME`
+			if !enable {
+				(grabs.gdk_pointer_ungrab)(0);
+				return;
+			}
+			let window = (gtk.gtk_widget_get_window)(widget);
+			if !window.is_null() {
+				let mask = gtk::GDK_POINTER_MOTION_MASK | gtk::GDK_BUTTON_PRESS_MASK | gtk::GDK_BUTTON_RELEASE_MASK;
+				(grabs.gdk_pointer_grab)(window, true as gtk::Gboolean, mask, window, ptr::null_mut(), 0);
+			}
+		}
+
+		/// Set the named cursor of the kind on the GDK window of the widget, the Sciter ones are left as is.
+		fn apply_cursor(gtk: &gtk::Gtk, widget: gtk::GtkWidget, cursor: CursorKind) {
+			let name: &[u8] = match cursor {
+				CursorKind::Default => return,
+				CursorKind::Arrow => b"default\0",
+				CursorKind::Wait => b"wait\0",
+				CursorKind::Hand => b"pointer\0",
+				CursorKind::Text => b"text\0",
+				CursorKind::Crosshair => b"crosshair\0",
+				CursorKind::Move => b"move\0",
+				CursorKind::NotAllowed => b"not-allowed\0",
+				CursorKind::Hidden => b"none\0",
+			};
+			let window = (gtk.gtk_widget_get_window)(widget);
+			if window.is_null() {
+				return;
+			}
+			let cursor = (gtk.gdk_cursor_new_from_name)((gtk.gdk_window_get_display)(window), name.as_ptr() as LPCSTR);
+			if !cursor.is_null() {
+				(gtk.gdk_window_set_cursor)(window, cursor);
+				(gtk.g_object_unref)(cursor);
+			}
+		}
+
+		/// The toplevel `GtkWindow` of the Sciter widget, null if there is none.
+		pub fn gtk_window(&self) -> gtk::GtkWindow {
+			self.toplevel().map_or(ptr::null_mut(), |(_, top)| top)
+		}
+	}
+
+	/// Destroy the window created by this instance, unless it is destroyed already.
+	impl Drop for OsWindow {
+		fn drop(&mut self) {
+			if !self.owned || !super::window_alive(self.hwnd) {
+				return;
+			}
+			if let Some(gtk) = gtk::GTK.as_ref() {
+				// the Sciter children only, the toplevel of the offscreen ones goes along
+				let widget = if (self.flags & SCITER_CREATE_WINDOW_FLAGS::SW_CHILD.bits()) != 0 {
+					self.window() as gtk::GtkWidget
+				} else {
+					(gtk.gtk_widget_get_toplevel)(self.window() as gtk::GtkWidget)
+				};
+				(gtk.gtk_widget_destroy)(widget);
+			}
+		}
+	}
+
+	impl super::BaseWindow for OsWindow {
+		/// Get native window handle.
+		fn get_hwnd(&self) -> HWINDOW {
+			return self.hwnd;
+		}
+
+		/// Keep the window alive when this instance is dropped.
+		fn release(&mut self) {
+			self.owned = false;
+		}
+
+		/// Move the Sciter widget into the `parent` container, or into a new `GtkWindow` if it is null.
+		fn set_parent(&mut self, parent: HWINDOW) {
+			const GTK_WINDOW_TOPLEVEL: INT = 0;
+			let gtk = match gtk::GTK.as_ref() {
+				Some(gtk) => gtk,
+				None => return,
+			};
+			let child = SCITER_CREATE_WINDOW_FLAGS::SW_CHILD.bits() as UINT;
+			let widget = self.hwnd as gtk::GtkWidget;
+			let mut size = gtk::GtkAllocation::default();
+			(gtk.gtk_widget_get_allocation)(widget, &mut size);
+
+			// the old container holds the only reference
+			(gtk.g_object_ref)(widget);
+			let old = (gtk.gtk_widget_get_parent)(widget);
+			if !old.is_null() {
+				(gtk.gtk_container_remove)(old, widget);
+				// the toplevel window of Sciter is left empty otherwise
+				if (self.flags & child) == 0 && (gtk.gtk_widget_is_toplevel)(old) != 0 {
+					(gtk.gtk_widget_hide)(old);
+				}
+			}
+			if parent.is_null() {
+				let top = (gtk.gtk_window_new)(GTK_WINDOW_TOPLEVEL);
+				(gtk.gtk_window_resize)(top, size.width, size.height);
+				(gtk.gtk_container_add)(top, widget);
+				(gtk.gtk_widget_show)(top);
+			} else {
+				(gtk.gtk_container_add)(parent as gtk::GtkWidget, widget);
+			}
+			(gtk.g_object_unref)(widget);
+			self.flags = if parent.is_null() { self.flags & !child } else { self.flags | child };
+		}
+
+		/// Create a new native window.
+		fn create(&mut self, rc: RECT, flags: UINT, parent: HWINDOW) -> HWINDOW {
+			match self.try_create(rc, flags, parent) {
+				Ok(hwnd) => hwnd,
+				Err(error) => panic!("Failed to create window: {}", error),
+			}
+		}
+
+		/// Create a new native window or tell why it can not be created.
+		fn try_create(&mut self, rc: RECT, flags: UINT, parent: HWINDOW) -> Result<HWINDOW, WindowError> {
+			::check_api().map_err(WindowError::LibraryNotFound)?;
+
+			if (flags & SCITER_CREATE_WINDOW_FLAGS::SW_MAIN.bits()) != 0 {
+				OsWindow::init_app()?;
+			}
+			self.flags = flags;
+
+			#[cfg(not(feature = "windowless"))]
+			{
+				self.hwnd = (_API.SciterCreateWindow)(flags, &rc, ptr::null(), ptr::null_mut(), parent);
+				if self.hwnd.is_null() {
+					return Err(WindowError::CreateFailed);
+				}
+				self.owned = true;
+				self.track_destruction();
+				if (flags & SCITER_CREATE_WINDOW_FLAGS::SW_CHILD.bits()) == 0 {
+					self.track_focus();
+				}
+				self.track_input();
+				if let Some(color) = self.background {
+					self.set_background_color(color);
+				}
+			}
+			#[cfg(feature = "windowless")]
+			{
+				let _ = rc;
+				let _ = parent;
+				let _ = &(_API.SciterVersion);
+			}
+			return Ok(self.hwnd);
+		}
+
+		/// Create a new native window and show it in the `state` right away.
+		fn create_with_state(&mut self, rc: RECT, flags: UINT, parent: HWINDOW, state: WindowState) -> HWINDOW {
+			let hwnd = self.create(rc, flags, parent);
+			self.show_in_state(state);
+			return hwnd;
+		}
+
+		/// Create a new native window centered on the monitor, GTK scales the size itself.
+		fn create_on_monitor(&mut self, monitor_index: usize, size: (i32, i32), flags: UINT, parent: HWINDOW) -> HWINDOW {
+			let rc = super::monitor_centered_rect(&OsWindow::enumerate_monitors(), monitor_index, size, false);
+			return self.create(rc, flags, parent);
+		}
+
+		/// Create a new native window which stays hidden and off the taskbar until it is shown.
+		fn create_hidden(&mut self, flags: UINT, parent: HWINDOW) -> HWINDOW {
+			let hwnd = self.create(RECT::default(), flags, parent);
+			self.set_skip_taskbar(true);
+			return hwnd;
+		}
+
+		/// Create a Sciter child inside of a `GtkOffscreenWindow`, which is never mapped on the screen.
+		///
+		/// Fails without a display (or GTK itself) rather than panicking, e.g. on a headless CI runner.
+		fn try_create_offscreen(&mut self, size: (i32, i32)) -> Result<HWINDOW, WindowError> {
+			extern "C" fn on_destroy(_widget: gtk::GtkWidget, offscreen: LPVOID) {
+				if let Some(gtk) = gtk::GTK.as_ref() {
+					(gtk.gtk_widget_destroy)(offscreen as gtk::GtkWidget);
+				}
+			}
+
+			extern "C" fn on_disconnect(_offscreen: LPVOID, _closure: LPVOID) {}
+
+			let gtk = gtk::GTK.as_ref().ok_or(WindowError::CreateFailed)?;
+			// no-op if GTK is initialized already
+			if (gtk.gtk_init_check)(ptr::null_mut(), ptr::null_mut()) == 0 {
+				return Err(WindowError::CreateFailed);
+			}
+			let offscreen = (gtk.gtk_offscreen_window_new)();
+			(gtk.gtk_widget_set_size_request)(offscreen, size.0, size.1);
+			(gtk.gtk_widget_show)(offscreen);
+
+			let rc = RECT { left: 0, top: 0, right: size.0, bottom: size.1 };
+			let hwnd = match self.try_create(rc, SCITER_CREATE_WINDOW_FLAGS::SW_CHILD.bits() as UINT, offscreen as HWINDOW) {
+				Ok(hwnd) => hwnd,
+				Err(error) => {
+					(gtk.gtk_widget_destroy)(offscreen);
+					return Err(error);
+				},
+			};
+			// the offscreen container goes along with the Sciter widget
+			let signal = b"destroy\0".as_ptr() as LPCSTR;
+			(gtk.g_signal_connect_data)(hwnd as LPVOID, signal, on_destroy as *const () as gtk::GCallback, offscreen, on_disconnect, 0);
+			return Ok(hwnd);
+		}
+
+		/// Minimize or hide window.
+		fn collapse(&self, hide: bool) {
+			unsafe {
+				if hide {
+					(_API.SciterWindowExec)(
+						self.window(),
+						SCITER_WINDOW_CMD::SCITER_WINDOW_SET_STATE.bits(),
+						SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_HIDDEN.bits() as UINT_PTR,
+						0,
+					);
+				} else {
+					(_API.SciterWindowExec)(
+						self.window(),
+						SCITER_WINDOW_CMD::SCITER_WINDOW_SET_STATE.bits(),
+						SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_MINIMIZED.bits() as UINT_PTR,
+						0,
+					);
+				}
+			};
+		}
+
+		/// Show or maximize window.
+		fn expand(&self, maximize: bool) {
+			let wnd = self.window();
+			unsafe {
+				if maximize {
+					(_API.SciterWindowExec)(
+						wnd,
+						SCITER_WINDOW_CMD::SCITER_WINDOW_SET_STATE.bits(),
+						SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_MAXIMIZED.bits() as UINT_PTR,
+						0,
+					);
+				} else {
+					(_API.SciterWindowExec)(
+						wnd,
+						SCITER_WINDOW_CMD::SCITER_WINDOW_SET_STATE.bits(),
+						SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_SHOWN.bits() as UINT_PTR,
+						0,
+					);
+				}
+			};
+		}
+
+		/// Show and raise the window in its current state.
+		fn show(&self) {
+			if let Some((gtk, top)) = self.toplevel() {
+				(gtk.gtk_window_present)(top);
+			}
+		}
+
+		/// Hide the window.
+		fn hide(&self) {
+			self.collapse(true);
+		}
+
+		/// Minimize the window.
+		fn minimize(&self) {
+			self.collapse(false);
+		}
+
+		/// Show and maximize the window.
+		fn maximize(&self) {
+			self.expand(true);
+		}
+
+		/// Leave fullscreen, minimized and maximized states, the window manager applies them asynchronously.
+		fn restore(&self) {
+			if let Some((gtk, top)) = self.toplevel() {
+				(gtk.gtk_window_unfullscreen)(top);
+				(gtk.gtk_window_deiconify)(top);
+				(gtk.gtk_window_unmaximize)(top);
+				(gtk.gtk_window_present)(top);
+			}
+		}
+
+		/// Deiconify the toplevel and present it with the time of the current event,
+		/// the window managers refuse the focus to the requests without one.
+		fn activate(&self) {
+			if let Some((gtk, top)) = self.toplevel() {
+				(gtk.gtk_window_deiconify)(top);
+				(gtk.gtk_window_present_with_time)(top, (gtk.gtk_get_current_event_time)());
+			}
+		}
+
+		/// Close window.
+		fn dismiss(&self) {
+			(_API.SciterWindowExec)(
+				self.window(),
+				SCITER_WINDOW_CMD::SCITER_WINDOW_SET_STATE.bits(),
+				SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_CLOSED.bits() as UINT_PTR,
+				true as UINT_PTR, // force close
+			);
+		}
+
+		/// Ask the window to close, it can be cancelled by the window.
+		fn request_close(&self) {
+			(_API.SciterWindowExec)(
+				self.window(),
+				SCITER_WINDOW_CMD::SCITER_WINDOW_SET_STATE.bits(),
+				SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_CLOSED.bits() as UINT_PTR,
+				false as UINT_PTR, // request close
+			);
+		}
+
+		/// Ask the callback on the `delete-event` signal, `false` cancels the closing.
+		fn on_close<F: Fn() -> bool + 'static>(&mut self, callback: F) {
+			extern "C" fn on_delete_event(_widget: gtk::GtkWidget, _event: LPVOID, hwnd: LPVOID) -> gtk::Gboolean {
+				// `TRUE` stops the event
+				return !super::close_allowed(hwnd as HWINDOW) as gtk::Gboolean;
+			}
+
+			extern "C" fn on_disconnect(hwnd: LPVOID, _closure: LPVOID) {
+				super::forget_close_handler(hwnd as HWINDOW);
+			}
+
+			if let Some((gtk, top)) = self.toplevel() {
+				if super::set_close_handler(self.hwnd, Rc::new(callback)) {
+					let signal = b"delete-event\0".as_ptr() as LPCSTR;
+					(gtk.g_signal_connect_data)(top, signal, on_delete_event as *const () as gtk::GCallback, self.hwnd as LPVOID, on_disconnect, 0);
+				}
+			}
+		}
+
+		/// Restore the focused element on `focus-in-event`, enabled by default.
+		fn set_restore_focus(&mut self, enable: bool) {
+			super::set_restore_focus(self.hwnd, enable);
+		}
+
+		/// Call the callback with the new window size on `configure-event`.
+		fn on_resize<F: Fn(i32, i32) + 'static>(&mut self, callback: F) {
+			if super::set_resize_handler(self.hwnd, self.get_rect(), Rc::new(callback)) {
+				self.track_geometry();
+			}
+		}
+
+		/// Call the callback with the new window position on `configure-event`.
+		fn on_move<F: Fn(i32, i32) + 'static>(&mut self, callback: F) {
+			if super::set_move_handler(self.hwnd, self.get_rect(), Rc::new(callback)) {
+				self.track_geometry();
+			}
+		}
+
+		/// Check the window monitor on `configure-event`.
+		fn on_monitor_changed<F: Fn(MonitorInfo, Option<f32>) + 'static>(&mut self, callback: F) {
+			extern "C" fn on_configure(_widget: gtk::GtkWidget, _event: LPVOID, hwnd: LPVOID) -> gtk::Gboolean {
+				super::monitor_changed(hwnd as HWINDOW);
+				return false as gtk::Gboolean;
+			}
+
+			extern "C" fn on_disconnect(hwnd: LPVOID, _closure: LPVOID) {
+				super::forget_monitor_handler(hwnd as HWINDOW);
+			}
+
+			if !super::set_monitor_handler(self.hwnd, Rc::new(callback)) {
+				return;
+			}
+			if let Some((gtk, top)) = self.toplevel() {
+				let signal = b"configure-event\0".as_ptr() as LPCSTR;
+				(gtk.g_signal_connect_data)(top, signal, on_configure as *const () as gtk::GCallback, self.hwnd as LPVOID, on_disconnect, 0);
+			}
+		}
+
+		/// Call the callback with the new integer scale on `notify::scale-factor`.
+		fn on_dpi_changed<F: Fn(f32) + 'static>(&mut self, callback: F) {
+			extern "C" fn on_scale_factor(widget: gtk::GtkWidget, _pspec: LPVOID, hwnd: LPVOID) {
+				if let Some(gtk) = gtk::GTK.as_ref() {
+					super::dpi_changed(hwnd as HWINDOW, (gtk.gtk_widget_get_scale_factor)(widget).max(1) as f32);
+				}
+			}
+
+			extern "C" fn on_disconnect(hwnd: LPVOID, _closure: LPVOID) {
+				super::forget_dpi_handler(hwnd as HWINDOW);
+			}
+
+			if !super::set_dpi_handler(self.hwnd, self.get_dpi_scale(), Rc::new(callback)) {
+				return;
+			}
+			if let Some((gtk, top)) = self.toplevel() {
+				let signal = b"notify::scale-factor\0".as_ptr() as LPCSTR;
+				(gtk.g_signal_connect_data)(top, signal, on_scale_factor as *const () as gtk::GCallback, self.hwnd as LPVOID, on_disconnect, 0);
+			}
+		}
+
+		/// Call the callback with the new theme on `notify::gtk-application-prefer-dark-theme` and `notify::gtk-theme-name` of the `GtkSettings`.
+		fn on_theme_changed<F: Fn(Theme) + 'static>(&mut self, callback: F) {
+			extern "C" fn on_notify(_settings: LPVOID, _pspec: LPVOID, hwnd: LPVOID) {
+				super::theme_changed(hwnd as HWINDOW, OsWindow::current_theme());
+			}
+
+			extern "C" fn on_destroy(_widget: gtk::GtkWidget, _hwnd: LPVOID) {
+			}
+
+			extern "C" fn on_disconnect(hwnd: LPVOID, _closure: LPVOID) {
+				super::forget_theme_handler(hwnd as HWINDOW);
+			}
+
+			if !super::set_theme_handler(self.hwnd, OsWindow::current_theme(), Rc::new(callback)) {
+				return;
+			}
+			let gtk = match gtk::GTK.as_ref() {
+				Some(gtk) => gtk,
+				None => return,
+			};
+			let (widget, settings) = (self.window() as gtk::GtkWidget, (gtk.gtk_settings_get_default)());
+			if widget.is_null() || settings.is_null() {
+				return;
+			}
+			// the settings outlive the window, these handlers are disconnected with the widget
+			let signals: [&[u8]; 2] = [b"notify::gtk-application-prefer-dark-theme\0", b"notify::gtk-theme-name\0"];
+			for signal in signals.iter() {
+				(gtk.g_signal_connect_object)(settings, signal.as_ptr() as LPCSTR, on_notify as *const () as gtk::GCallback, widget, 0);
+			}
+			let signal = b"destroy\0".as_ptr() as LPCSTR;
+			(gtk.g_signal_connect_data)(widget, signal, on_destroy as *const () as gtk::GCallback, self.hwnd as LPVOID, on_disconnect, 0);
+		}
+
+		/// Accept the `text/uri-list` drops of local files, replacing the Sciter drag-and-drop handling.
+		fn on_file_drop<F: Fn(Vec<PathBuf>) + 'static>(&mut self, callback: F) {
+			extern "C" fn on_drag_data_received(_widget: gtk::GtkWidget, _context: LPVOID, _x: INT, _y: INT, data: LPVOID, _info: UINT, _time: UINT, hwnd: LPVOID) {
+				let gtk = match gtk::GTK.as_ref() {
+					Some(gtk) => gtk,
+					None => return,
+				};
+				let uris = (gtk.gtk_selection_data_get_uris)(data);
+				if uris.is_null() {
+					return;
+				}
+				let mut files = Vec::new();
+				let mut uri = uris;
+				unsafe {
+					while !(*uri).is_null() {
+						// `NULL` for the non-local URIs
+						let file = (gtk.g_filename_from_uri)(*uri, ptr::null_mut(), ptr::null_mut());
+						if !file.is_null() {
+							files.push(PathBuf::from(OsStr::from_bytes(CStr::from_ptr(file).to_bytes())));
+							(gtk.g_free)(file as LPVOID);
+						}
+						uri = uri.add(1);
+					}
+				}
+				(gtk.g_strfreev)(uris);
+				if !files.is_empty() {
+					super::files_dropped(hwnd as HWINDOW, files);
+				}
+			}
+
+			extern "C" fn on_disconnect(hwnd: LPVOID, _closure: LPVOID) {
+				super::forget_drop_handler(hwnd as HWINDOW);
+			}
+
+			if let Some(gtk) = gtk::GTK.as_ref() {
+				if super::set_drop_handler(self.hwnd, Rc::new(callback)) {
+					let widget = self.window() as gtk::GtkWidget;
+					let target = gtk::GtkTargetEntry { target: b"text/uri-list\0".as_ptr() as LPCSTR, flags: 0, info: 0 };
+					(gtk.gtk_drag_dest_set)(widget, gtk::GTK_DEST_DEFAULT_ALL, &target, 1, gtk::GDK_ACTION_COPY);
+					let signal = b"drag-data-received\0".as_ptr() as LPCSTR;
+					(gtk.g_signal_connect_data)(widget, signal, on_drag_data_received as *const () as gtk::GCallback, self.hwnd as LPVOID, on_disconnect, 0);
+				}
+			}
+		}
+
+		/// Set native window title.
+		fn set_title(&mut self, title: &str) {
+			if let Some((gtk, top)) = self.toplevel() {
+				// interior NULs would truncate the title anyway
+				let title = CString::new(title.replace('\0', "")).unwrap_or_default();
+				(gtk.gtk_window_set_title)(top, title.as_ptr());
+			}
+		}
+
+		/// Set the ATK accessible id of the toplevel window, it needs ATK 2.34+.
+		fn set_automation_id(&mut self, id: &str) {
+			let (gtk, top) = match self.toplevel() {
+				Some(toplevel) => toplevel,
+				None => return,
+			};
+			let accessible = (gtk.gtk_widget_get_accessible)(top);
+			if let (Some(set_accessible_id), false) = (gtk.atk_object_set_accessible_id, accessible.is_null()) {
+				let id = CString::new(id.replace('\0', "")).unwrap_or_default();
+				set_accessible_id(accessible, id.as_ptr());
+			}
+		}
+
+		/// Set the ATK accessible name of the toplevel window.
+		fn set_accessible_name(&mut self, name: &str) {
+			if let Some((gtk, top)) = self.toplevel() {
+				let accessible = (gtk.gtk_widget_get_accessible)(top);
+				if !accessible.is_null() {
+					let name = CString::new(name.replace('\0', "")).unwrap_or_default();
+					(gtk.atk_object_set_name)(accessible, name.as_ptr());
+				}
+			}
+		}
+
+		/// Get native window title.
+		fn get_title(&self) -> String {
+			if let Some((gtk, top)) = self.toplevel() {
+				let title = (gtk.gtk_window_get_title)(top);
+				if !title.is_null() {
+					return unsafe { CStr::from_ptr(title) }.to_string_lossy().into_owned();
+				}
+			}
+			String::new()
+		}
+
+		/// Set the window icon from PNG data.
+		fn set_icon(&mut self, image: &[u8]) {
+			let (gtk, top) = match self.toplevel() {
+				Some(found) => found,
+				None => return,
+			};
+			if image.is_empty() {
+				(gtk.gtk_window_set_icon)(top, ptr::null_mut());
+				return;
+			}
+
+			let pixbuf = load_pixbuf(gtk, image);
+			if !pixbuf.is_null() {
+				// the window takes its own reference
+				(gtk.gtk_window_set_icon)(top, pixbuf);
+				(gtk.g_object_unref)(pixbuf);
+			}
+		}
+
+		/// Move the window to the specified screen position.
+		fn move_to(&self, x: i32, y: i32) {
+			if let Some((gtk, top)) = self.toplevel() {
+				(gtk.gtk_window_move)(top, x, y);
+			}
+		}
+
+		/// Resize the window.
+		fn resize(&self, width: i32, height: i32) {
+			if let Some((gtk, top)) = self.toplevel() {
+				// Note: GTK window size does not include the window decorations.
+				(gtk.gtk_window_resize)(top, width, height);
+			}
+		}
+
+		/// Move and resize the window.
+		fn set_rect(&self, rc: RECT) {
+			self.move_to(rc.left, rc.top);
+			self.resize(rc.right - rc.left, rc.bottom - rc.top);
+		}
+
+		/// Apply the changes at once, GTK sends the move and the resize as one configure request.
+		fn update_geometry(&self, changes: GeometryUpdate) {
+			super::update_geometry_in_steps(self, changes.state, || {
+				let (gtk, top) = match self.toplevel() {
+					Some(toplevel) => toplevel,
+					None => return,
+				};
+				if let Some((x, y)) = changes.position {
+					(gtk.gtk_window_move)(top, x, y);
+				}
+				if let Some((width, height)) = changes.size {
+					(gtk.gtk_window_resize)(top, width, height);
+				}
+				let window = (gtk.gtk_widget_get_window)(top);
+				match changes.z_order {
+					Some(ZOrder::Top) if !window.is_null() => (gtk.gdk_window_raise)(window),
+					Some(ZOrder::Bottom) if !window.is_null() => (gtk.gdk_window_lower)(window),
+					Some(ZOrder::Topmost) => (gtk.gtk_window_set_keep_above)(top, 1),
+					Some(ZOrder::NotTopmost) => (gtk.gtk_window_set_keep_above)(top, 0),
+					_ => {},
+				}
+			});
+		}
+
+		/// Set the minimum window size, zero means no limit.
+		fn set_min_size(&self, width: i32, height: i32) {
+			let (_, max) = self.size_limits();
+			self.set_size_limits(SIZE { cx: width, cy: height }, max);
+		}
+
+		/// Set the maximum window size, zero means no limit.
+		fn set_max_size(&self, width: i32, height: i32) {
+			let (min, _) = self.size_limits();
+			self.set_size_limits(min, SIZE { cx: width, cy: height });
+		}
+
+		/// The document minimal size from `SciterGetMinWidth` and `SciterGetMinHeight`, GTK limits the client area already.
+		fn sync_min_size_from_content(&self, track: bool) {
+			let content = super::content_min_size(self.hwnd);
+			self.set_min_size(content.cx, content.cy);
+			super::track_content_min_size(self.hwnd, track);
+		}
+
+		/// Center the window in the work area of the monitor it overlaps most.
+		fn center(&self) {
+			if let Some((gtk, top)) = self.toplevel() {
+				let window = (gtk.gtk_widget_get_window)(top);
+				if window.is_null() {
+					// not shown yet, GTK will place it
+					(gtk.gtk_window_set_position)(top, gtk::GTK_WIN_POS_CENTER);
+					return;
+				}
+				let (get_monitor_at_window, get_workarea) = match (gtk.gdk_display_get_monitor_at_window, gtk.gdk_monitor_get_workarea) {
+					(Some(get_monitor_at_window), Some(get_workarea)) => (get_monitor_at_window, get_workarea),
+					_ => return,
+				};
+				let display = (gtk.gdk_window_get_display)(window);
+				let monitor = get_monitor_at_window(display, window);
+				if monitor.is_null() {
+					return;
+				}
+				let mut work = gtk::GdkRectangle::default();
+				get_workarea(monitor, &mut work);
+				let (mut width, mut height) = (0, 0);
+				(gtk.gtk_window_get_size)(top, &mut width, &mut height);
+				(gtk.gtk_window_move)(top, work.x + (work.width - width) / 2, work.y + (work.height - height) / 2);
+			}
+		}
+
+		/// The monitor the window overlaps most, or the one under its center if it is not realized yet.
+		fn current_monitor(&self) -> Option<MonitorInfo> {
+			let (gtk, display, monitor) = self.gdk_monitor()?;
+			return OsWindow::monitor_info(gtk, display, monitor);
+		}
+
+		/// `gdk_monitor_get_refresh_rate` is in millihertz, zero if unknown.
+		fn get_refresh_rate(&self) -> Option<f32> {
+			let (gtk, _display, monitor) = self.gdk_monitor()?;
+			let rate = (gtk.gdk_monitor_get_refresh_rate?)(monitor);
+			return if rate > 0 { Some(rate as f32 / 1000.0) } else { None };
+		}
+
+		/// Save the current rect, GTK does not keep the one before maximizing.
+		fn save_placement(&self) -> WindowPlacement {
+			let rc = self.get_rect();
+			let m = self.current_monitor().map_or(RECT::default(), |monitor| monitor.rect);
+			return WindowPlacement {
+				position: (rc.left, rc.top),
+				size: (rc.width(), rc.height()),
+				maximized: self.get_state() == WindowState::Maximized,
+				monitor: (m.left, m.top, m.right, m.bottom),
+			};
+		}
+
+		/// Move the window to the clamped rect and maximize it if needed.
+		fn apply_placement(&self, placement: &WindowPlacement) {
+			let rc = super::placement_rect(placement, &OsWindow::enumerate_monitors());
+			self.update_geometry(GeometryUpdate {
+				position: Some((rc.left, rc.top)),
+				size: Some((rc.width(), rc.height())),
+				state: if placement.maximized { Some(WindowState::Maximized) } else { None },
+				..Default::default()
+			});
+		}
+
+		/// Get the window rectangle in screen coordinates.
+		fn get_rect(&self) -> RECT {
+			let mut rc = RECT::default();
+			if let Some((gtk, top)) = self.toplevel() {
+				// Note: the position is of the window frame, but the size does not include the decorations.
+				let (mut x, mut y) = (0, 0);
+				let mut size = gtk::GtkAllocation::default();
+				(gtk.gtk_window_get_position)(top, &mut x, &mut y);
+				(gtk.gtk_widget_get_allocation)(top, &mut size);
+				rc = RECT { left: x, top: y, right: x + size.width, bottom: y + size.height };
+			}
+			return rc;
+		}
+
+		/// Enter or leave fullscreen.
+		fn set_fullscreen(&self, enable: bool) {
+			if let Some((gtk, top)) = self.toplevel() {
+				// GTK restores the previous geometry itself.
+				if enable {
+					(gtk.gtk_window_fullscreen)(top);
+				} else {
+					(gtk.gtk_window_unfullscreen)(top);
+				}
+			}
+		}
+
+		/// Remove or restore the window manager decorations.
+		fn set_frameless(&self, enable: bool) {
+			if let Some((gtk, top)) = self.toplevel() {
+				(gtk.gtk_window_set_decorated)(top, !enable as gtk::Gboolean);
+			}
+		}
+
+		/// Allow or forbid resizing the window, it keeps its current size.
+		fn set_resizable(&self, enable: bool) {
+			if let Some((gtk, top)) = self.toplevel() {
+				let mut size = gtk::GtkAllocation::default();
+				(gtk.gtk_widget_get_allocation)(top, &mut size);
+				if enable {
+					(gtk.gtk_widget_set_size_request)(top, -1, -1);
+					(gtk.gtk_window_set_resizable)(top, true as gtk::Gboolean);
+					(gtk.gtk_window_resize)(top, size.width, size.height);
+				} else {
+					// non-resizable GTK windows shrink to their requested size
+					(gtk.gtk_widget_set_size_request)(top, size.width, size.height);
+					(gtk.gtk_window_set_resizable)(top, false as gtk::Gboolean);
+				}
+			}
+		}
+
+		/// Whether the user can resize the window.
+		fn is_resizable(&self) -> bool {
+			match self.toplevel() {
+				Some((gtk, top)) => (gtk.gtk_window_get_resizable)(top) != 0,
+				None => false,
+			}
+		}
+
+		/// GTK has no shadow setting: the compositor draws it for the server-side decorations
+		/// and the theme for the client-side ones, so it is a no-op.
+		fn set_shadow(&self, _enable: bool) {
+		}
+
+		/// Set the `_KDE_NET_WM_BLUR_BEHIND_REGION` property of the toplevel, empty for the whole window.
+		fn set_backdrop(&self, kind: BackdropKind) {
+			const GDK_PROP_MODE_REPLACE: INT = 0;
+			if let Some((gtk, top)) = self.toplevel() {
+				let window = (gtk.gtk_widget_get_window)(top);
+				if window.is_null() {
+					return;
+				}
+				let property = (gtk.gdk_atom_intern)(b"_KDE_NET_WM_BLUR_BEHIND_REGION\0".as_ptr() as LPCSTR, false as gtk::Gboolean);
+				if kind == BackdropKind::None {
+					(gtk.gdk_property_delete)(window, property);
+				} else {
+					let cardinal = (gtk.gdk_atom_intern)(b"CARDINAL\0".as_ptr() as LPCSTR, false as gtk::Gboolean);
+					(gtk.gdk_property_change)(window, property, cardinal, 32, GDK_PROP_MODE_REPLACE, ptr::null(), 0);
+				}
+			}
+		}
+
+		/// Add a CSS provider with the color as the background of the toplevel `GtkWindow`.
+		fn set_background_color(&mut self, color: u32) {
+			const GTK_STYLE_PROVIDER_PRIORITY_APPLICATION: UINT = 600;
+			self.background = Some(color);
+			if let Some((gtk, top)) = self.toplevel() {
+				let (a, r, g, b) = (color >> 24, (color >> 16) & 0xFF, (color >> 8) & 0xFF, color & 0xFF);
+				let css = format!("* {{ background-color: rgba({}, {}, {}, {:.3}); }}", r, g, b, a as f64 / 255.0);
+				let provider = (gtk.gtk_css_provider_new)();
+				if (gtk.gtk_css_provider_load_from_data)(provider, css.as_ptr() as LPCSTR, css.len() as isize, ptr::null_mut()) != 0 {
+					let context = (gtk.gtk_widget_get_style_context)(top);
+					(gtk.gtk_style_context_add_provider)(context, provider, GTK_STYLE_PROVIDER_PRIORITY_APPLICATION);
+				}
+				// the style context holds its own reference
+				(gtk.g_object_unref)(provider);
+			}
+		}
+
+		/// GTK delivers the touch and pen input as the pointer events already, so it is a no-op.
+		fn enable_touch(&mut self, _enable: bool) {
+		}
+
+		/// Start moving the window with the mouse from the current button press.
+		fn begin_drag(&self) {
+			if let Some((gtk, top)) = self.toplevel() {
+				let event = (gtk.gtk_get_current_event)();
+				if event.is_null() {
+					return;
+				}
+				let (mut x, mut y) = (0.0, 0.0);
+				if (gtk.gdk_event_get_root_coords)(event, &mut x, &mut y) != 0 {
+					(gtk.gtk_window_begin_move_drag)(top, 1, x as INT, y as INT, (gtk.gtk_get_current_event_time)());
+				}
+				(gtk.gdk_event_free)(event);
+			}
+		}
+
+		/// Start the move or resize drags of the toplevel on the `button-press-event` off the client area.
+		fn set_hit_test_callback<F: Fn(i32, i32) -> HitTestResult + 'static>(&mut self, callback: F) {
+			extern "C" fn on_button_press(widget: gtk::GtkWidget, event: *const gtk::GdkEventButton, hwnd: LPVOID) -> gtk::Gboolean {
+				const GDK_BUTTON_PRESS: INT = 4;
+				const GDK_2BUTTON_PRESS: INT = 5;
+				let (gtk, event) = match (gtk::GTK.as_ref(), unsafe { event.as_ref() }) {
+					(Some(gtk), Some(event)) if event.button == 1 => (gtk, event),
+					_ => return false as gtk::Gboolean,
+				};
+				let top = (gtk.gtk_widget_get_toplevel)(widget);
+				let (x, y, time) = (event.x_root as INT, event.y_root as INT, event.time);
+				let edge = match super::hit_test(hwnd as HWINDOW, event.x as INT, event.y as INT) {
+					Some(HitTestResult::Client) | None => return false as gtk::Gboolean,
+					Some(HitTestResult::Caption) => {
+						let wnd = OsWindow::from(hwnd as HWINDOW);
+						if event.event_type == GDK_2BUTTON_PRESS {
+							if wnd.get_state() == WindowState::Maximized { wnd.restore() } else { wnd.maximize() }
+						} else if event.event_type == GDK_BUTTON_PRESS {
+							(gtk.gtk_window_begin_move_drag)(top, 1, x, y, time);
+						}
+						return true as gtk::Gboolean;
+					},
+					Some(HitTestResult::TopLeft) => gtk::GDK_WINDOW_EDGE_NORTH_WEST,
+					Some(HitTestResult::Top) => gtk::GDK_WINDOW_EDGE_NORTH,
+					Some(HitTestResult::TopRight) => gtk::GDK_WINDOW_EDGE_NORTH_EAST,
+					Some(HitTestResult::Left) => gtk::GDK_WINDOW_EDGE_WEST,
+					Some(HitTestResult::Right) => gtk::GDK_WINDOW_EDGE_EAST,
+					Some(HitTestResult::BottomLeft) => gtk::GDK_WINDOW_EDGE_SOUTH_WEST,
+					Some(HitTestResult::Bottom) => gtk::GDK_WINDOW_EDGE_SOUTH,
+					Some(HitTestResult::BottomRight) => gtk::GDK_WINDOW_EDGE_SOUTH_EAST,
+				};
+				if event.event_type == GDK_BUTTON_PRESS {
+					(gtk.gtk_window_begin_resize_drag)(top, edge, 1, x, y, time);
+				}
+				return true as gtk::Gboolean;
+			}
+
+			extern "C" fn on_disconnect(hwnd: LPVOID, _closure: LPVOID) {
+				super::forget_hit_test_handler(hwnd as HWINDOW);
+			}
+
+			if !super::set_hit_test_handler(self.hwnd, Rc::new(callback)) || self.hwnd.is_null() {
+				return;
+			}
+			if let Some(gtk) = gtk::GTK.as_ref() {
+				// before Sciter gets the press
+				let signal = b"button-press-event\0".as_ptr() as LPCSTR;
+				(gtk.g_signal_connect_data)(self.window() as gtk::GtkWidget, signal, on_button_press as *const () as gtk::GCallback, self.hwnd as LPVOID, on_disconnect, 0);
+			}
+		}
+
+		/// Set the urgency hint, the window manager clears it on activation.
+		fn request_attention(&self, _urgent: bool) {
+			// GTK has no informational variant
+			if let Some((gtk, top)) = self.toplevel() {
+				if (gtk.gtk_window_is_active)(top) == 0 {
+					(gtk.gtk_window_set_urgency_hint)(top, true as gtk::Gboolean);
+				}
+			}
+		}
+
+		/// The launcher progress needs the Unity `LauncherEntry` D-Bus API, there is no D-Bus client here, so it is a no-op.
+		fn set_progress(&self, _state: ProgressState, _value: f32) {
+		}
+
+		/// Does nothing, the taskbars of Linux make their own previews.
+		fn set_thumbnail_bitmap(&self, _png: &[u8]) {
+		}
+
+		/// Does nothing, the taskbars of Linux make their own previews.
+		fn set_live_preview_rect(&self, _rc: RECT) {
+		}
+
+		/// Hide the window from the taskbar and the workspace pager.
+		fn set_skip_taskbar(&self, skip: bool) {
+			if let Some((gtk, top)) = self.toplevel() {
+				(gtk.gtk_window_set_skip_taskbar_hint)(top, skip as gtk::Gboolean);
+				(gtk.gtk_window_set_skip_pager_hint)(top, skip as gtk::Gboolean);
+			}
+		}
+
+		/// Does nothing, there is no `AppUserModelID` on Linux.
+		fn set_app_id(&self, _app_id: &str) {
+		}
+
+		/// Capture the Sciter widget area of the toplevel GDK window.
+		fn snapshot(&self) -> Result<Vec<u8>, ()> {
+			let (gtk, top) = self.toplevel().ok_or(())?;
+			let window = (gtk.gtk_widget_get_window)(top);
+			if window.is_null() || !self.is_visible() {
+				return Err(());
+			}
+			let widget = self.window() as gtk::GtkWidget;
+			let (mut x, mut y) = (0, 0);
+			let mut size = gtk::GtkAllocation::default();
+			(gtk.gtk_widget_translate_coordinates)(widget, top, 0, 0, &mut x, &mut y);
+			(gtk.gtk_widget_get_allocation)(widget, &mut size);
+			if size.width <= 0 || size.height <= 0 {
+				return Err(());
+			}
+			let pixbuf = (gtk.gdk_pixbuf_get_from_window)(window, x, y, size.width, size.height);
+			if pixbuf.is_null() {
+				return Err(());
+			}
+			// the pixbuf is RGB(A) with padded rows, but Sciter wants BGRA
+			let (width, height) = ((gtk.gdk_pixbuf_get_width)(pixbuf) as usize, (gtk.gdk_pixbuf_get_height)(pixbuf) as usize);
+			let stride = (gtk.gdk_pixbuf_get_rowstride)(pixbuf) as usize;
+			let channels = (gtk.gdk_pixbuf_get_n_channels)(pixbuf) as usize;
+			let src = (gtk.gdk_pixbuf_get_pixels)(pixbuf);
+			let mut pixels = Vec::with_capacity(width * height * 4);
+			for row in 0..height {
+				let line = unsafe { ::std::slice::from_raw_parts(src.add(row * stride), width * channels) };
+				for px in line.chunks(channels) {
+					pixels.extend_from_slice(&[px[2], px[1], px[0], if channels > 3 { px[3] } else { 255 }]);
+				}
+			}
+			(gtk.g_object_unref)(pixbuf);
+			return super::encode_png(width as u32, height as u32, &pixels);
+		}
+
+		/// Keep the window above the other ones.
+		fn set_topmost(&self, enable: bool) {
+			if let Some((gtk, top)) = self.toplevel() {
+				(gtk.gtk_window_set_keep_above)(top, enable as gtk::Gboolean);
+			}
+		}
+
+		/// Override the Sciter cursors after each `motion-notify-event` of the widget.
+		fn set_cursor(&self, cursor: CursorKind) {
+			extern "C" fn on_motion(widget: gtk::GtkWidget, _event: LPVOID, hwnd: LPVOID) -> gtk::Gboolean {
+				if let Some(gtk) = gtk::GTK.as_ref() {
+					OsWindow::apply_cursor(gtk, widget, super::cursor_override(hwnd as HWINDOW));
+				}
+				return false as gtk::Gboolean;
+			}
+
+			extern "C" fn on_disconnect(hwnd: LPVOID, _closure: LPVOID) {
+				super::forget_cursor(hwnd as HWINDOW);
+			}
+
+			let gtk = match gtk::GTK.as_ref() {
+				Some(gtk) => gtk,
+				None => return,
+			};
+			let widget = self.window() as gtk::GtkWidget;
+			if widget.is_null() {
+				return;
+			}
+			if super::set_cursor_override(self.hwnd, cursor).is_none() {
+				// after the Sciter handler has set its cursor
+				const G_CONNECT_AFTER: UINT = 1;
+				let signal = b"motion-notify-event\0".as_ptr() as LPCSTR;
+				(gtk.g_signal_connect_data)(widget, signal, on_motion as *const () as gtk::GCallback, self.hwnd as LPVOID, on_disconnect, G_CONNECT_AFTER);
+			}
+			let window = (gtk.gtk_widget_get_window)(widget);
+			if cursor == CursorKind::Default && !window.is_null() {
+				// Sciter sets its own one with the next move
+				(gtk.gdk_window_set_cursor)(window, ptr::null_mut());
+			}
+			OsWindow::apply_cursor(gtk, widget, cursor);
+		}
+
+		/// Grab the pointer confined to the Sciter widget while the toplevel is active,
+		/// released on `focus-out-event` and grabbed again on `focus-in-event`, see `clip_cursor`.
+		fn set_cursor_clip(&self, enable: bool) {
+			if super::set_cursor_clip_enabled(self.hwnd, enable) && self.is_focused() {
+				OsWindow::clip_cursor(self.window() as gtk::GtkWidget, enable);
+			}
+		}
+
+		/// The pointer position relative to the toplevel GDK window, translated to the Sciter widget.
+		fn cursor_position(&self) -> POINT {
+			let mut pt = POINT::default();
+			if let Some((gtk, top)) = self.toplevel() {
+				if let Some((x, y, _)) = OsWindow::pointer_state(gtk, top) {
+					let widget = self.window() as gtk::GtkWidget;
+					(gtk.gtk_widget_translate_coordinates)(top, widget, x, y, &mut pt.x, &mut pt.y);
+				}
+			}
+			return pt;
+		}
+
+		/// GDK has no masks for the extra buttons.
+		fn mouse_buttons(&self) -> MouseButtons {
+			let mask = match self.toplevel() {
+				Some((gtk, top)) => OsWindow::pointer_state(gtk, top).map_or(0, |(_, _, mask)| mask),
+				None => 0,
+			};
+			let mut buttons = MouseButtons::empty();
+			buttons.set(MouseButtons::LEFT, mask & gtk::GDK_BUTTON1_MASK != 0);
+			buttons.set(MouseButtons::MIDDLE, mask & gtk::GDK_BUTTON2_MASK != 0);
+			buttons.set(MouseButtons::RIGHT, mask & gtk::GDK_BUTTON3_MASK != 0);
+			return buttons;
+		}
+
+		/// Connect the accelerator to a `GtkAccelGroup` of the toplevel window,
+		/// which activates it before the key press goes to the focused widget.
+		fn register_accelerator<F: Fn() -> bool + 'static>(&mut self, accelerator: Accelerator, callback: F) {
+			extern "C" fn on_accel(_group: LPVOID, _acceleratable: LPVOID, _key: UINT, _modifiers: UINT, data: LPVOID) -> gtk::Gboolean {
+				let &(hwnd, accelerator) = unsafe { &*(data as *const (HWINDOW, Accelerator)) };
+				return super::accelerator_pressed(hwnd, accelerator) as gtk::Gboolean;
+			}
+
+			extern "C" fn on_disconnect(data: LPVOID, _closure: LPVOID) {
+				let (hwnd, _) = *unsafe { Box::from_raw(data as *mut (HWINDOW, Accelerator)) };
+				super::forget_accelerators(hwnd);
+			}
+
+			let key = match OsWindow::keyval(accelerator.key) {
+				Some(key) => key,
+				None => return,
+			};
+			if !super::set_accelerator(self.hwnd, accelerator, Rc::new(callback)) {
+				return;
+			}
+			if let Some((gtk, top)) = self.toplevel() {
+				let flags = accelerator.modifiers;
+				let mut modifiers = 0;
+				if (flags & KEYBOARD_STATES::CONTROL_KEY_PRESSED) != 0 {
+					modifiers |= gtk::GDK_CONTROL_MASK;
+				}
+				if (flags & (KEYBOARD_STATES::SHIFT_KEY_PRESSED | KEYBOARD_STATES::RIGHT_SHIFT_KEY_PRESSED)) != 0 {
+					modifiers |= gtk::GDK_SHIFT_MASK;
+				}
+				if (flags & KEYBOARD_STATES::ALT_KEY_PRESSED) != 0 {
+					modifiers |= gtk::GDK_MOD1_MASK;
+				}
+				if (flags & KEYBOARD_STATES::CMD_KEY_PRESSED) != 0 {
+					modifiers |= gtk::GDK_SUPER_MASK;
+				}
+				let data = Box::into_raw(Box::new((self.hwnd, accelerator)));
+				let closure = (gtk.g_cclosure_new)(on_accel as *const () as gtk::GCallback, data as LPVOID, on_disconnect);
+				// the window keeps the group alive
+				let group = (gtk.gtk_accel_group_new)();
+				(gtk.gtk_accel_group_connect)(group, key, modifiers, 0, closure);
+				(gtk.gtk_window_add_accel_group)(top, group);
+				(gtk.g_object_unref)(group);
+			}
+		}
+
+		/// Set the whole window opacity.
+		fn set_opacity(&self, alpha: f32) {
+			if let Some((gtk, top)) = self.toplevel() {
+				// Note: it requires a compositing window manager.
+				(gtk.gtk_widget_set_opacity)(top, alpha.clamp(0.0, 1.0) as f64);
+			}
+		}
+
+		/// Get the scale factor of the monitor with the window.
+		fn get_dpi_scale(&self) -> f32 {
+			if let Some(gtk) = gtk::GTK.as_ref() {
+				let hwnd = self.window();
+				if !hwnd.is_null() {
+					return (gtk.gtk_widget_get_scale_factor)(hwnd as gtk::GtkWidget).max(1) as f32;
+				}
+			}
+			return 1.0;
+		}
+
+		/// The zoom is the CSS `zoom` of the document root, see `apply_zoom`.
+		fn set_zoom(&self, factor: f32) {
+			extern "C" fn on_destroy(_widget: gtk::GtkWidget, _hwnd: LPVOID) {
+			}
+
+			extern "C" fn on_disconnect(hwnd: LPVOID, _closure: LPVOID) {
+				super::forget_zoom(hwnd as HWINDOW);
+			}
+
+			let widget = self.window() as gtk::GtkWidget;
+			if super::set_zoom_factor(self.hwnd, factor) && !widget.is_null() {
+				if let Some(gtk) = gtk::GTK.as_ref() {
+					// only to forget the factor with the widget
+					let signal = b"destroy\0".as_ptr() as LPCSTR;
+					(gtk.g_signal_connect_data)(widget, signal, on_destroy as *const () as gtk::GCallback, self.hwnd as LPVOID, on_disconnect, 0);
+				}
+			}
+			super::apply_zoom(self.hwnd);
+		}
+
+		fn get_zoom(&self) -> f32 {
+			super::zoom_factor(self.hwnd)
+		}
+
+		/// Whether the window is shown (it can be minimized though).
+		fn is_visible(&self) -> bool {
+			match self.toplevel() {
+				Some((gtk, top)) => (gtk.gtk_widget_get_visible)(top) != 0,
+				None => false,
+			}
+		}
+
+		/// Whether the toplevel window is the active one, i.e. it gets the keyboard input.
+		fn is_focused(&self) -> bool {
+			match self.toplevel() {
+				Some((gtk, top)) => (gtk.gtk_window_is_active)(top) != 0,
+				None => false,
+			}
+		}
+
+		/// Time since the last keyboard or mouse event of this window.
+		fn last_input_time(&self) -> Duration {
+			super::since_last_input(self.hwnd)
+		}
+
+		/// Put the text on the `CLIPBOARD` selection.
+		fn clipboard_set_text(&self, text: &str) {
+			if let Some(gtk) = gtk::GTK.as_ref() {
+				let clipboard = (gtk.gtk_clipboard_get)(gtk::GDK_SELECTION_CLIPBOARD);
+				if !clipboard.is_null() {
+					(gtk.gtk_clipboard_set_text)(clipboard, text.as_ptr() as LPCSTR, text.len() as INT);
+				}
+			}
+		}
+
+		/// Get the `CLIPBOARD` selection text, it runs the main loop until the owner responds.
+		fn clipboard_get_text(&self) -> Option<String> {
+			let gtk = gtk::GTK.as_ref()?;
+			let clipboard = (gtk.gtk_clipboard_get)(gtk::GDK_SELECTION_CLIPBOARD);
+			if clipboard.is_null() {
+				return None;
+			}
+			let text = (gtk.gtk_clipboard_wait_for_text)(clipboard);
+			if text.is_null() {
+				return None;
+			}
+			let result = unsafe { CStr::from_ptr(text) }.to_string_lossy().into_owned();
+			(gtk.g_free)(text as LPVOID);
+			return Some(result);
+		}
+
+		/// Get the current window state.
+		fn get_state(&self) -> WindowState {
+			let state = (_API.SciterWindowExec)(self.window(), SCITER_WINDOW_CMD::SCITER_WINDOW_GET_STATE.bits(), 0, 0);
+			let state = SCITER_WINDOW_STATE::from_bits_truncate(state as UINT);
+			return match state {
+				SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_MINIMIZED => WindowState::Minimized,
+				SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_MAXIMIZED => WindowState::Maximized,
+				SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_FULL_SCREEN => WindowState::Fullscreen,
+				SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_SHOWN => WindowState::Normal,
+				_ => WindowState::Hidden,
+			};
+		}
+
+		/// Convert a point from the window client area to screen coordinates.
+		fn client_to_screen(&self, pt: POINT) -> POINT {
+			// Note: the window placement does not include the window decorations (if any).
+			let mut pos = POINT::default();
+			(_API.SciterWindowExec)(
+				self.window(),
+				SCITER_WINDOW_CMD::SCITER_WINDOW_GET_PLACEMENT.bits(),
+				&mut pos as *mut POINT as UINT_PTR,
+				0,
+			);
+			return POINT { x: pos.x + pt.x, y: pos.y + pt.y };
+		}
+
+		/// Make the window background transparent before the document is painted.
+		fn set_transparent_background(&self) {
+			// Sciter creates `SW_ALPHA` windows with an RGBA visual already,
+			// there is nothing else to prepare here.
+		}
+
+		/// Run the main app message loop until window been closed.
+		fn run_app(&self) {
+			(_API.SciterExec)(SCITER_APP_CMD::SCITER_APP_LOOP.bits(), 0, 0);
+		}
+
+		/// Run the main app message loop with a GLib timeout source calling `idle` every `IDLE_INTERVAL`.
+		fn run_app_with<F: FnMut() + 'static>(&self, mut idle: F) {
+			extern "C" fn on_timeout(data: LPVOID) -> gtk::Gboolean {
+				let idle = unsafe { &mut *(data as *mut &mut dyn FnMut()) };
+				idle();
+				return true as gtk::Gboolean; // keep the source
+			}
+
+			let gtk = match gtk::GTK.as_ref() {
+				Some(gtk) => gtk,
+				None => return self.run_app(),
+			};
+			let mut idle: &mut dyn FnMut() = &mut idle;
+			let source = (gtk.g_timeout_add)(super::IDLE_INTERVAL, on_timeout, &mut idle as *mut &mut dyn FnMut() as LPVOID);
+			self.run_app();
+			(gtk.g_source_remove)(source);
+		}
+
+		/// Make the window modal and transient for the `parent`, then iterate the main loop until it is destroyed.
+		fn run_modal(&self, parent: &OsWindow) {
+			extern "C" fn on_destroy(_widget: gtk::GtkWidget, closed: LPVOID) {
+				let closed = unsafe { &*(closed as *const Cell<bool>) };
+				closed.set(true);
+			}
+
+			extern "C" fn on_disconnect(closed: LPVOID, _closure: LPVOID) {
+				drop(unsafe { Rc::from_raw(closed as *const Cell<bool>) });
+			}
+
+			let (gtk, top) = match self.toplevel() {
+				Some(toplevel) => toplevel,
+				None => return,
+			};
+			let owner = parent.gtk_window();
+			if !owner.is_null() {
+				(gtk.gtk_window_set_transient_for)(top, owner);
+			}
+			(gtk.gtk_window_set_modal)(top, true as gtk::Gboolean);
+
+			let closed = Rc::new(Cell::new(false));
+			let signal = b"destroy\0".as_ptr() as LPCSTR;
+			(gtk.g_signal_connect_data)(top, signal, on_destroy as *const () as gtk::GCallback, Rc::into_raw(closed.clone()) as LPVOID, on_disconnect, 0);
+			self.expand(false);
+
+			// the quit request is left for the outer loop
+			while !closed.get() && !QUIT_POSTED.load(Ordering::SeqCst) {
+				(gtk.gtk_main_iteration_do)(true as gtk::Gboolean);
+			}
+			if !closed.get() {
+				(gtk.gtk_window_set_modal)(top, false as gtk::Gboolean);
+			}
+			if !owner.is_null() {
+				(gtk.gtk_window_present)(owner);
+			}
+		}
+
+		/// Process the pending events without waiting, `false` means the app quit was requested.
+		fn pump_events(&self) -> bool {
+			if let Some(gtk) = gtk::GTK.as_ref() {
+				while (gtk.gtk_events_pending)() != 0 {
+					(gtk.gtk_main_iteration_do)(false as gtk::Gboolean);
+				}
+			}
+			return !QUIT_POSTED.swap(false, Ordering::SeqCst);
+		}
+
+		/// Process the events until there are none for a while.
+		fn run_until_idle(&self, settle: Duration, timeout: Duration) -> bool {
+			let pending = || match gtk::GTK.as_ref() {
+				Some(gtk) => (gtk.gtk_events_pending)() != 0,
+				None => false,
+			};
+			return super::pump_until_idle(settle, timeout, || self.pump_events(), pending);
+		}
+
+		/// Post app quit message.
+		fn quit_app(&self) {
+			QUIT_POSTED.store(true, Ordering::SeqCst);
+			(_API.SciterExec)(SCITER_APP_CMD::SCITER_APP_STOP.bits(), 0, 0);
+		}
+
+		/// Quit the app from any thread: the main loop stops itself when idle.
+		fn post_quit(&self) {
+			extern "C" fn on_idle(_data: LPVOID) -> gtk::Gboolean {
+				OsWindow::new().quit_app();
+				return false as gtk::Gboolean; // remove the source
+			}
+
+			match gtk::GTK.as_ref() {
+				Some(gtk) => { (gtk.g_idle_add)(on_idle, ptr::null_mut()); },
+				None => self.quit_app(),
+			}
+		}
+
+		/// Run the closure on the main loop when it is idle, the window may be closed already.
+		fn post_task(&self, task: UiTask) {
+			extern "C" fn on_idle(data: LPVOID) -> gtk::Gboolean {
+				let task = unsafe { Box::from_raw(data as *mut UiTask) };
+				task();
+				return false as gtk::Gboolean; // remove the source
+			}
+
+			if let Some(gtk) = gtk::GTK.as_ref() {
+				let data = Box::into_raw(Box::new(task));
+				(gtk.g_idle_add)(on_idle, data as LPVOID);
+			}
+		}
+	}
+
+	#[cfg(feature = "raw-window-handle")]
+	unsafe impl ::raw_window_handle::HasRawWindowHandle for OsWindow {
+		/// The handle of the GDK window, which exists once the toplevel window is realized.
+		fn raw_window_handle(&self) -> ::raw_window_handle::RawWindowHandle {
+			use raw_window_handle::{RawWindowHandle, WaylandHandle, XlibHandle};
+			if let Some((gtk, top)) = self.toplevel() {
+				let window = (gtk.gtk_widget_get_window)(top);
+				let display = if window.is_null() { ptr::null_mut() } else { (gtk.gdk_window_get_display)(window) };
+				if !display.is_null() {
+					if let Some(ref x11) = gtk.x11 {
+						if (gtk.g_type_check_instance_is_a)(display, (x11.gdk_x11_display_get_type)()) != 0 {
+							let mut handle = XlibHandle::empty();
+							handle.window = (x11.gdk_x11_window_get_xid)(window);
+							handle.display = (x11.gdk_x11_display_get_xdisplay)(display);
+							return RawWindowHandle::Xlib(handle);
+						}
+					}
+					if let Some(ref wayland) = gtk.wayland {
+						if (gtk.g_type_check_instance_is_a)(display, (wayland.gdk_wayland_display_get_type)()) != 0 {
+							let mut handle = WaylandHandle::empty();
+							handle.surface = (wayland.gdk_wayland_window_get_wl_surface)(window);
+							handle.display = (wayland.gdk_wayland_display_get_wl_display)(display);
+							return RawWindowHandle::Wayland(handle);
+						}
+					}
+				}
+			}
+			RawWindowHandle::Xlib(XlibHandle::empty())
+		}
+	}
+}
+
+#[cfg(target_os = "macos")]
+mod macos {
+
+	extern crate objc_foundation;
+
+	use self::objc_foundation::{INSString, NSString};
+	use objc::runtime::{Class, Object, NO, YES};
+	use std::ffi::{CStr, OsStr};
+	use std::os::unix::ffi::OsStrExt;
+	use std::path::PathBuf;
+	use std::rc::Rc;
+	use std::sync::atomic::{AtomicBool, Ordering};
+	use std::sync::Once;
+
+	/// Activation policies that control whether and how an app may be activated.
+	#[repr(C)]
+	#[allow(dead_code)]
+	enum NSApplicationActivationPolicy {
+		Regular = 0,
+		Accessory,
+		Prohibited,
+	}
+
+	/// `NSPoint`, `NSSize` and `NSRect` are `CGFloat` based, which is `f64` on 64-bit platforms.
+	#[repr(C)]
+	#[derive(Clone, Copy, Default)]
+	struct NSPoint {
+		x: f64,
+		y: f64,
+	}
+
+	#[repr(C)]
+	#[derive(Clone, Copy, Default)]
+	struct NSSize {
+		width: f64,
+		height: f64,
+	}
+
+	#[repr(C)]
+	#[derive(Clone, Copy, Default)]
+	struct NSRect {
+		origin: NSPoint,
+		size: NSSize,
+	}
+
+	#[repr(C)]
+	#[allow(non_snake_case)]
+	struct CFRunLoopContext {
+		version: isize,
+		info: LPVOID,
+		retain: LPVOID,
+		release: LPVOID,
+		copyDescription: LPVOID,
+	}
+
+	// Note: Starting some OSX version (perhaps, 10.13),
+	// the AppKit framework isn't loaded implicitly.
+	#[link(name = "CoreFoundation", kind = "framework")]
+	extern "C" {
+		static kCFRunLoopCommonModes: LPVOID;
+		fn CFRunLoopGetMain() -> LPVOID;
+		fn CFAbsoluteTimeGetCurrent() -> f64;
+		fn CFRunLoopObserverCreate(allocator: LPVOID, activities: usize, repeats: u8, order: isize,
+			callout: extern "C" fn(observer: LPVOID, activity: usize, info: LPVOID), context: *mut CFRunLoopContext) -> LPVOID;
+		fn CFRunLoopAddObserver(run_loop: LPVOID, observer: LPVOID, mode: LPVOID);
+		fn CFRunLoopObserverInvalidate(observer: LPVOID);
+		fn CFRunLoopTimerCreate(allocator: LPVOID, fire_date: f64, interval: f64, flags: usize, order: isize,
+			callout: extern "C" fn(timer: LPVOID, info: LPVOID), context: *mut CFRunLoopContext) -> LPVOID;
+		fn CFRunLoopAddTimer(run_loop: LPVOID, timer: LPVOID, mode: LPVOID);
+		fn CFRunLoopTimerInvalidate(timer: LPVOID);
+		fn CFRelease(object: LPVOID);
+	}
+
+	// libSystem
+	extern "C" {
+		/// `dispatch_get_main_queue()`
+		static _dispatch_main_q: u8;
+		fn dispatch_async_f(queue: LPVOID, context: LPVOID, work: extern "C" fn(context: LPVOID));
+		/// The class of the blocks without captured variables.
+		static _NSConcreteGlobalBlock: u8;
+	}
+
+	#[repr(C)]
+	struct BlockDescriptor {
+		reserved: usize,
+		size: usize,
+	}
+
+	/// A global block literal of `NSEvent* (^)(NSEvent*)`, there is no `block` crate dependency for the only one.
+	#[repr(C)]
+	struct EventHandlerBlock {
+		isa: *const u8,
+		flags: INT,
+		reserved: INT,
+		invoke: extern "C" fn(block: *mut EventHandlerBlock, event: *mut Object) -> *mut Object,
+		descriptor: *const BlockDescriptor,
+	}
+
+	#[link(name = "AppKit", kind = "framework")]
+	extern "C" {
+		static NSDefaultRunLoopMode: *mut Object;
+		static NSFilenamesPboardType: *mut Object;
+		static NSPasteboardTypeString: *mut Object;
+	}
+
+	#[link(name = "CoreGraphics", kind = "framework")]
+	extern "C" {
+		fn CGWindowListCreateImage(bounds: NSRect, list_option: UINT, window_id: UINT, image_option: UINT) -> LPVOID;
+		fn CGImageRelease(image: LPVOID);
+		fn CGEventSourceSecondsSinceLastEventType(state: INT, event_type: UINT) -> f64;
+		fn CGWarpMouseCursorPosition(point: NSPoint) -> INT;
+		fn CGAssociateMouseAndMouseCursorPosition(connected: UINT) -> INT;
+		fn CGDisplayCopyDisplayMode(display: UINT) -> LPVOID;
+		fn CGDisplayModeGetRefreshRate(mode: LPVOID) -> f64;
+		fn CGDisplayModeRelease(mode: LPVOID);
+	}
+
+	use super::{BaseWindow, UiTask};
+	use capi::scbehavior::KEYBOARD_STATES;
+	use capi::scdef::*;
+	use capi::scmsg::key_codes;
+	use capi::sctypes::*;
+	use std::time::Duration;
+	use window::{Accelerator, BackdropKind, CursorKind, GeometryUpdate, HitTestResult, MonitorInfo, MouseButtons, ProgressState, Theme, WindowError, WindowPlacement, WindowState, ZOrder};
+	use _API;
+
+	// NSWindowStyleMaskFullScreen
+	const NS_FULLSCREEN_WINDOW_MASK: usize = 1 << 14;
+
+	/// Set by `quit_app` for the external event loops driven by `pump_events`.
+	static QUIT_POSTED: AtomicBool = AtomicBool::new(false);
+
+	/// `NSWindowDelegate` which asks the `on_close` callback first, accepts the files for `on_file_drop`,
+	/// tracks the focused element and the geometry, and forwards everything else to the original Sciter delegate.
+	fn window_delegate_class() -> &'static Class {
+		use objc::declare::ClassDecl;
+		use objc::runtime::{Sel, BOOL};
+
+		const NAME: &str = "SciterRsWindowDelegate";
+		static REGISTER: Once = Once::new();
+
+		fn target(this: &Object) -> *mut Object {
+			unsafe { *this.get_ivar::<*mut Object>("target") }
+		}
+
+		fn target_responds(this: &Object, sel: Sel) -> bool {
+			let target = target(this);
+			let responds: BOOL = if target.is_null() { NO } else { unsafe { msg_send!(target, respondsToSelector:sel) } };
+			return responds != NO;
+		}
+
+		extern "C" fn should_close(this: &Object, _: Sel, sender: *mut Object) -> BOOL {
+			let hwnd = unsafe { *this.get_ivar::<usize>("hwnd") } as HWINDOW;
+			if !super::close_allowed(hwnd) {
+				return NO;
+			}
+			if target_responds(this, sel!(windowShouldClose:)) {
+				return unsafe { msg_send!(target(this), windowShouldClose:sender) };
+			}
+			return YES;
+		}
+
+		extern "C" fn dragging_entered(this: &Object, _: Sel, sender: *mut Object) -> usize {
+			const NS_DRAG_OPERATION_COPY: usize = 1;
+			let hwnd = unsafe { *this.get_ivar::<usize>("hwnd") } as HWINDOW;
+			if super::accepts_drop(hwnd) {
+				return NS_DRAG_OPERATION_COPY;
+			}
+			if target_responds(this, sel!(draggingEntered:)) {
+				return unsafe { msg_send!(target(this), draggingEntered:sender) };
+			}
+			return 0;
+		}
+
+		extern "C" fn perform_drag_operation(this: &Object, _: Sel, sender: *mut Object) -> BOOL {
+			let hwnd = unsafe { *this.get_ivar::<usize>("hwnd") } as HWINDOW;
+			let mut files = Vec::new();
+			unsafe {
+				let pasteboard: *mut Object = msg_send!(sender, draggingPasteboard);
+				let list: *mut Object = msg_send!(pasteboard, propertyListForType:NSFilenamesPboardType);
+				let count: usize = if list.is_null() { 0 } else { msg_send!(list, count) };
+				for index in 0..count {
+					let path: *mut Object = msg_send!(list, objectAtIndex:index);
+					let path: *const ::std::os::raw::c_char = msg_send!(path, fileSystemRepresentation);
+					if !path.is_null() {
+						files.push(PathBuf::from(OsStr::from_bytes(CStr::from_ptr(path).to_bytes())));
+					}
+				}
+			}
+			if !files.is_empty() && super::files_dropped(hwnd, files) {
+				return YES;
+			}
+			if target_responds(this, sel!(performDragOperation:)) {
+				return unsafe { msg_send!(target(this), performDragOperation:sender) };
+			}
+			return NO;
+		}
+
+		extern "C" fn did_resign_key(this: &Object, _: Sel, notification: *mut Object) {
+			let hwnd = unsafe { *this.get_ivar::<usize>("hwnd") } as HWINDOW;
+			super::save_focus(hwnd);
+			if super::cursor_clip_enabled(hwnd) {
+				OsWindow::from(hwnd).clip_cursor(false);
+			}
+			if target_responds(this, sel!(windowDidResignKey:)) {
+				let _: () = unsafe { msg_send!(target(this), windowDidResignKey:notification) };
+			}
+		}
+
+		extern "C" fn did_become_key(this: &Object, _: Sel, notification: *mut Object) {
+			if target_responds(this, sel!(windowDidBecomeKey:)) {
+				let _: () = unsafe { msg_send!(target(this), windowDidBecomeKey:notification) };
+			}
+			// after Sciter has handled the activation
+			let hwnd = unsafe { *this.get_ivar::<usize>("hwnd") } as HWINDOW;
+			super::restore_focus(hwnd);
+			if super::cursor_clip_enabled(hwnd) {
+				OsWindow::from(hwnd).clip_cursor(true);
+			}
+		}
+
+		extern "C" fn did_resize(this: &Object, _: Sel, notification: *mut Object) {
+			if target_responds(this, sel!(windowDidResize:)) {
+				let _: () = unsafe { msg_send!(target(this), windowDidResize:notification) };
+			}
+			let hwnd = unsafe { *this.get_ivar::<usize>("hwnd") } as HWINDOW;
+			super::window_configured(hwnd, OsWindow::from(hwnd).frame_rect());
+			super::monitor_changed(hwnd);
+		}
+
+		extern "C" fn did_move(this: &Object, _: Sel, notification: *mut Object) {
+			if target_responds(this, sel!(windowDidMove:)) {
+				let _: () = unsafe { msg_send!(target(this), windowDidMove:notification) };
+			}
+			let hwnd = unsafe { *this.get_ivar::<usize>("hwnd") } as HWINDOW;
+			super::window_configured(hwnd, OsWindow::from(hwnd).frame_rect());
+			super::monitor_changed(hwnd);
+		}
+
+		extern "C" fn did_change_screen(this: &Object, _: Sel, notification: *mut Object) {
+			if target_responds(this, sel!(windowDidChangeScreen:)) {
+				let _: () = unsafe { msg_send!(target(this), windowDidChangeScreen:notification) };
+			}
+			let hwnd = unsafe { *this.get_ivar::<usize>("hwnd") } as HWINDOW;
+			super::monitor_changed(hwnd);
+		}
+
+		extern "C" fn did_change_backing_properties(this: &Object, _: Sel, notification: *mut Object) {
+			if target_responds(this, sel!(windowDidChangeBackingProperties:)) {
+				let _: () = unsafe { msg_send!(target(this), windowDidChangeBackingProperties:notification) };
+			}
+			let hwnd = unsafe { *this.get_ivar::<usize>("hwnd") } as HWINDOW;
+			super::dpi_changed(hwnd, OsWindow::from(hwnd).get_dpi_scale());
+		}
+
+		extern "C" fn theme_changed(this: &Object, _: Sel, _notification: *mut Object) {
+			let hwnd = unsafe { *this.get_ivar::<usize>("hwnd") } as HWINDOW;
+			super::theme_changed(hwnd, OsWindow::current_theme());
+		}
+
+		extern "C" fn will_close(this: &Object, _: Sel, notification: *mut Object) {
+			if target_responds(this, sel!(windowWillClose:)) {
+				let _: () = unsafe { msg_send!(target(this), windowWillClose:notification) };
+			}
+			// restore the Sciter delegate and drop this one
+			let hwnd = unsafe { *this.get_ivar::<usize>("hwnd") } as HWINDOW;
+			super::forget_window(hwnd);
+			super::forget_close_handler(hwnd);
+			super::forget_drop_handler(hwnd);
+			super::forget_focus(hwnd);
+			super::forget_geometry_handlers(hwnd);
+			super::forget_dpi_handler(hwnd);
+			super::forget_monitor_handler(hwnd);
+			super::forget_theme_handler(hwnd);
+			// pop the pushed cursor and release the locked one
+			OsWindow::from(hwnd).set_cursor(CursorKind::Default);
+			if super::cursor_clip_enabled(hwnd) && OsWindow::from(hwnd).is_focused() {
+				OsWindow::from(hwnd).clip_cursor(false);
+			}
+			super::forget_cursor(hwnd);
+			super::forget_accelerators(hwnd);
+			super::forget_zoom(hwnd);
+			super::forget_hit_test_handler(hwnd);
+			unsafe {
+				let wnd: *mut Object = msg_send!(notification, object);
+				let cls = Class::get("NSDistributedNotificationCenter").expect("`NSDistributedNotificationCenter` is not registered.");
+				let center: *mut Object = msg_send!(cls, defaultCenter);
+				let _: () = msg_send!(center, removeObserver:this as *const Object as *mut Object);
+				// the `run_modal` session ends with the window
+				let app = OsWindow::get_app();
+				let modal: *mut Object = msg_send!(app, modalWindow);
+				if modal == wnd {
+					let _: () = msg_send!(app, stopModal);
+				}
+				let _: () = msg_send!(wnd, setDelegate:target(this));
+				let this = this as *const Object as *mut Object;
+				let _: *mut Object = msg_send!(this, autorelease);
+			}
+		}
+
+		extern "C" fn forwarding_target(this: &Object, _: Sel, _sel: Sel) -> *mut Object {
+			target(this)
+		}
+
+		extern "C" fn responds_to_selector(this: &Object, _: Sel, sel: Sel) -> BOOL {
+			if this.class().instance_method(sel).is_some() || target_responds(this, sel) { YES } else { NO }
+		}
+
+		REGISTER.call_once(|| {
+			let superclass = Class::get("NSObject").expect("`NSObject` is not registered.");
+			let mut decl = ClassDecl::new(NAME, superclass).expect("`SciterRsWindowDelegate` is registered already.");
+			decl.add_ivar::<*mut Object>("target");
+			decl.add_ivar::<usize>("hwnd");
+			unsafe {
+				decl.add_method(sel!(windowShouldClose:), should_close as extern "C" fn(&Object, Sel, *mut Object) -> BOOL);
+				decl.add_method(sel!(windowWillClose:), will_close as extern "C" fn(&Object, Sel, *mut Object));
+				decl.add_method(sel!(windowDidResignKey:), did_resign_key as extern "C" fn(&Object, Sel, *mut Object));
+				decl.add_method(sel!(windowDidBecomeKey:), did_become_key as extern "C" fn(&Object, Sel, *mut Object));
+				decl.add_method(sel!(windowDidResize:), did_resize as extern "C" fn(&Object, Sel, *mut Object));
+				decl.add_method(sel!(windowDidMove:), did_move as extern "C" fn(&Object, Sel, *mut Object));
+				decl.add_method(sel!(windowDidChangeScreen:), did_change_screen as extern "C" fn(&Object, Sel, *mut Object));
+				decl.add_method(sel!(windowDidChangeBackingProperties:), did_change_backing_properties as extern "C" fn(&Object, Sel, *mut Object));
+				decl.add_method(sel!(themeChanged:), theme_changed as extern "C" fn(&Object, Sel, *mut Object));
+				decl.add_method(sel!(draggingEntered:), dragging_entered as extern "C" fn(&Object, Sel, *mut Object) -> usize);
+				decl.add_method(sel!(performDragOperation:), perform_drag_operation as extern "C" fn(&Object, Sel, *mut Object) -> BOOL);
+				decl.add_method(sel!(forwardingTargetForSelector:), forwarding_target as extern "C" fn(&Object, Sel, Sel) -> *mut Object);
+				decl.add_method(sel!(respondsToSelector:), responds_to_selector as extern "C" fn(&Object, Sel, Sel) -> BOOL);
+			}
+			decl.register();
+		});
+		return Class::get(NAME).expect("`SciterRsWindowDelegate` is not registered.");
+	}
+
+	/// The app delegate proxy, which forwards everything but `applicationShouldTerminateAfterLastWindowClosed:` to the previous delegate.
+	fn app_delegate_class() -> &'static Class {
+		use objc::declare::ClassDecl;
+		use objc::runtime::{Sel, BOOL};
+
+		const NAME: &str = "SciterRsAppDelegate";
+		static REGISTER: Once = Once::new();
+
+		fn target(this: &Object) -> *mut Object {
+			unsafe { *this.get_ivar::<*mut Object>("target") }
+		}
+
+		// `App` stops the loop by itself, terminating here would exit before `App::run` returns
+		extern "C" fn should_terminate_after_last_window_closed(_this: &Object, _: Sel, _sender: *mut Object) -> BOOL {
+			NO
+		}
+
+		extern "C" fn forwarding_target(this: &Object, _: Sel, _sel: Sel) -> *mut Object {
+			target(this)
+		}
+
+		extern "C" fn responds_to_selector(this: &Object, _: Sel, sel: Sel) -> BOOL {
+			if this.class().instance_method(sel).is_some() {
+				return YES;
+			}
+			let target = target(this);
+			return if target.is_null() { NO } else { unsafe { msg_send!(target, respondsToSelector:sel) } };
+		}
+
+		REGISTER.call_once(|| {
+			let superclass = Class::get("NSObject").expect("`NSObject` is not registered.");
+			let mut decl = ClassDecl::new(NAME, superclass).expect("`SciterRsAppDelegate` is registered already.");
+			decl.add_ivar::<*mut Object>("target");
+			unsafe {
+				decl.add_method(sel!(applicationShouldTerminateAfterLastWindowClosed:), should_terminate_after_last_window_closed as extern "C" fn(&Object, Sel, *mut Object) -> BOOL);
+				decl.add_method(sel!(forwardingTargetForSelector:), forwarding_target as extern "C" fn(&Object, Sel, Sel) -> *mut Object);
+				decl.add_method(sel!(respondsToSelector:), responds_to_selector as extern "C" fn(&Object, Sel, Sel) -> BOOL);
+			}
+			decl.register();
+		});
+		return Class::get(NAME).expect("`SciterRsAppDelegate` is not registered.");
+	}
+
+	/// The click target of the status items, it keeps the `TrayIcon` id.
+	fn tray_target_class() -> &'static Class {
+		use objc::declare::ClassDecl;
+		use objc::runtime::Sel;
+
+		const NAME: &str = "SciterRsTrayTarget";
+		static REGISTER: Once = Once::new();
+
+		extern "C" fn clicked(this: &Object, _: Sel, _sender: *mut Object) {
+			let id = unsafe { *this.get_ivar::<u32>("id") };
+			super::TrayIcon::clicked(id);
+		}
+
+		REGISTER.call_once(|| {
+			let superclass = Class::get("NSObject").expect("`NSObject` is not registered.");
+			let mut decl = ClassDecl::new(NAME, superclass).expect("`SciterRsTrayTarget` is registered already.");
+			decl.add_ivar::<u32>("id");
+			unsafe {
+				decl.add_method(sel!(clicked:), clicked as extern "C" fn(&Object, Sel, *mut Object));
+			}
+			decl.register();
+		});
+		return Class::get(NAME).expect("`SciterRsTrayTarget` is not registered.");
+	}
+
+	/// `NSStatusItem` of `TrayIcon` in the menu bar, its button calls the `SciterRsTrayTarget`.
+	pub struct OsTrayIcon {
+		item: *mut Object,
+		target: *mut Object,
+	}
+
+	impl OsTrayIcon {
+		pub fn add(_hwnd: HWINDOW, id: u32, image: &[u8], tooltip: &str) -> OsTrayIcon {
+			const NS_VARIABLE_STATUS_ITEM_LENGTH: f64 = -1.0;
+			// the menu bar icons are 18 points high
+			const ICON_SIZE: f64 = 18.0;
+
+			let bar_cls = Class::get("NSStatusBar").expect("`NSStatusBar` is not registered.");
+			unsafe {
+				let bar: *mut Object = msg_send!(bar_cls, systemStatusBar);
+				let item: *mut Object = msg_send!(bar, statusItemWithLength:NS_VARIABLE_STATUS_ITEM_LENGTH);
+				let _: *mut Object = msg_send!(item, retain);
+				let target: *mut Object = msg_send!(tray_target_class(), new);
+				(*target).set_ivar::<u32>("id", id);
+
+				let button: *mut Object = msg_send!(item, button);
+				if !image.is_empty() {
+					let data_cls = Class::get("NSData").expect("`NSData` is not registered.");
+					let image_cls = Class::get("NSImage").expect("`NSImage` is not registered.");
+					let data: *mut Object = msg_send!(data_cls, dataWithBytes:image.as_ptr() length:image.len());
+					let icon: *mut Object = msg_send!(image_cls, alloc);
+					let icon: *mut Object = msg_send!(icon, initWithData:data);
+					if !icon.is_null() {
+						let _: () = msg_send!(icon, setSize:NSSize { width: ICON_SIZE, height: ICON_SIZE });
+						let _: () = msg_send!(button, setImage:icon);
+						let _: () = msg_send!(icon, release);
+					}
+				}
+				let tooltip = NSString::from_str(tooltip);
+				let _: () = msg_send!(button, setToolTip:tooltip);
+				// the target is not retained by the button
+				let _: () = msg_send!(button, setTarget:target);
+				let _: () = msg_send!(button, setAction:sel!(clicked:));
+				return OsTrayIcon { item: item, target: target };
+			}
+		}
+
+		pub fn remove(&mut self) {
+			if self.item.is_null() {
+				return;
+			}
+			let bar_cls = Class::get("NSStatusBar").expect("`NSStatusBar` is not registered.");
+			unsafe {
+				let bar: *mut Object = msg_send!(bar_cls, systemStatusBar);
+				let _: () = msg_send!(bar, removeStatusItem:self.item);
+				let _: () = msg_send!(self.item, release);
+				let _: () = msg_send!(self.target, release);
+			}
+			self.item = ::std::ptr::null_mut();
+			self.target = ::std::ptr::null_mut();
+		}
+	}
+
+	pub struct OsWindow {
+		hwnd: HWINDOW,
+		flags: UINT,
+		/// Background color to apply on creation.
+		background: Option<u32>,
+		/// Whether this instance has created the window and destroys it on drop.
+		owned: bool,
+	}
+
+	impl OsWindow {
+		pub fn new() -> OsWindow {
+			OsWindow {
+				hwnd: 0 as HWINDOW,
+				flags: 0,
+				background: None,
+				owned: false,
+			}
+		}
+
+		pub fn from(hwnd: HWINDOW) -> OsWindow {
+			OsWindow { hwnd: hwnd, flags: 0, background: None, owned: false }
+		}
+
+		/// Create a Sciter child window inside of the foreign `parent` window.
+		pub fn attach(parent: HWINDOW, rc: RECT) -> OsWindow {
+			assert!(!parent.is_null());
+			let flags = SCITER_CREATE_WINDOW_FLAGS::SW_CHILD | SCITER_CREATE_WINDOW_FLAGS::SW_CONTROLS;
+			let mut wnd = OsWindow::new();
+			BaseWindow::create(&mut wnd, rc, flags.bits() as UINT, parent);
+			return wnd;
+		}
+
+		/// The accelerator of the key-down event, if its key can be one.
+		fn event_accelerator(event: *mut Object) -> Option<Accelerator> {
+			const NS_SHIFT_KEY_MASK: usize = 1 << 17;
+			const NS_CONTROL_KEY_MASK: usize = 1 << 18;
+			const NS_ALTERNATE_KEY_MASK: usize = 1 << 19;
+			const NS_COMMAND_KEY_MASK: usize = 1 << 20;
+			let (flags, character) = unsafe {
+				let flags: usize = msg_send!(event, modifierFlags);
+				let characters: *mut Object = msg_send!(event, charactersIgnoringModifiers);
+				let length: usize = if characters.is_null() { 0 } else { msg_send!(characters, length) };
+				if length == 0 {
+					return None;
+				}
+				let character: u16 = msg_send!(characters, characterAtIndex:0usize);
+				(flags, character as u32)
+			};
+			let key = match character {
+				0x61..=0x7A => character - 0x20, // lowercase letters
+				0x41..=0x5A | 0x30..=0x39 | 0x20 => character,
+				0xF704..=0xF71C => key_codes::KB_F1 + (character - 0xF704),
+				0x1B => key_codes::KB_ESCAPE,
+				0x0D | 0x03 => key_codes::KB_ENTER,
+				0x09 => key_codes::KB_TAB,
+				0x7F => key_codes::KB_BACKSPACE,
+				0xF727 => key_codes::KB_INSERT,
+				0xF728 => key_codes::KB_DELETE,
+				0xF703 => key_codes::KB_RIGHT,
+				0xF702 => key_codes::KB_LEFT,
+				0xF701 => key_codes::KB_DOWN,
+				0xF700 => key_codes::KB_UP,
+				0xF72C => key_codes::KB_PAGE_UP,
+				0xF72D => key_codes::KB_PAGE_DOWN,
+				0xF729 => key_codes::KB_HOME,
+				0xF72B => key_codes::KB_END,
+				_ => return None,
+			};
+			let mut modifiers = 0;
+			if (flags & NS_CONTROL_KEY_MASK) != 0 {
+				modifiers |= KEYBOARD_STATES::CONTROL_KEY_PRESSED;
+			}
+			if (flags & NS_SHIFT_KEY_MASK) != 0 {
+				modifiers |= KEYBOARD_STATES::SHIFT_KEY_PRESSED;
+			}
+			if (flags & NS_ALTERNATE_KEY_MASK) != 0 {
+				modifiers |= KEYBOARD_STATES::ALT_KEY_PRESSED;
+			}
+			if (flags & NS_COMMAND_KEY_MASK) != 0 {
+				modifiers |= KEYBOARD_STATES::CMD_KEY_PRESSED;
+			}
+			return Some(Accelerator::new(modifiers, key));
+		}
+
+		fn get_app() -> *mut Object {
+			let cls = Class::get("NSApplication").expect("`NSApplication` is not registered.");
+			let obj = unsafe { msg_send!(cls, sharedApplication) };
+			return obj;
+		}
+
+		pub(super) fn init_app() -> Result<(), WindowError> {
+			// By default, unbundled apps start with `NSApplicationActivationPolicyProhibited` (no dock, no menu).
+			let app = OsWindow::get_app();
+			let _: () = unsafe { msg_send!(app, setActivationPolicy:NSApplicationActivationPolicy::Regular) };
+			return Ok(());
+		}
+
+		/// Stop `run` so that it returns instead of terminating the process like `quit_app` does.
+		///
+		/// `stop:` takes effect only after the current event, so an empty event is posted to wake the loop up.
+		pub(super) fn stop_app() {
+			const NS_APPLICATION_DEFINED: usize = 15;
+			QUIT_POSTED.store(true, Ordering::SeqCst);
+			let app = OsWindow::get_app();
+			let cls = Class::get("NSEvent").expect("`NSEvent` is not registered.");
+			unsafe {
+				let nil: *mut Object = ::std::ptr::null_mut();
+				let _: () = msg_send!(app, stop:nil);
+				let event: *mut Object = msg_send!(cls, otherEventWithType:NS_APPLICATION_DEFINED location:NSPoint::default() modifierFlags:0usize
+					timestamp:0.0f64 windowNumber:0isize context:nil subtype:0i16 data1:0isize data2:0isize);
+				let _: () = msg_send!(app, postEvent:event atStart:YES);
+			}
+		}
+
+		/// Put the app delegate proxy in front of the current one, so Cocoa leaves quitting after the last window to `App`.
+		pub(super) fn install_app_delegate() {
+			let app = OsWindow::get_app();
+			let cls = app_delegate_class();
+			unsafe {
+				let target: *mut Object = msg_send!(app, delegate);
+				let installed: ::objc::runtime::BOOL = if target.is_null() { NO } else { msg_send!(target, isKindOfClass:cls) };
+				if installed != NO {
+					return;
+				}
+				// `NSApplication` does not retain its delegate, this one lives until the process exits
+				let delegate: *mut Object = msg_send!(cls, new);
+				(*delegate).set_ivar("target", target);
+				let _: () = msg_send!(app, setDelegate:delegate);
+			}
+		}
+
+		/// Show the hidden window in the `state`, the geometry is changed before it is ordered front.
+		pub fn show_in_state(&self, state: WindowState) {
+			let nil: *mut Object = ::std::ptr::null_mut();
+			match state {
+				WindowState::Hidden => {},
+				WindowState::Normal => self.expand(false),
+				WindowState::Minimized => {
+					// goes to the Dock without being shown
+					let _: () = unsafe { msg_send!(self.window(), miniaturize:nil) };
+				},
+				WindowState::Maximized => {
+					// zoom before showing, unlike `expand(true)`
+					let _: () = unsafe { msg_send!(self.window(), zoom:nil) };
+					self.expand(false);
+				},
+				WindowState::Fullscreen => {
+					self.expand(false);
+					self.set_fullscreen(true);
+				},
+			}
+		}
+
+		/// Read the `AppleInterfaceStyle` user default, which is `Dark` in the dark mode and missing otherwise.
+		pub fn current_theme() -> Theme {
+			let cls = Class::get("NSUserDefaults").expect("`NSUserDefaults` is not registered.");
+			let key = NSString::from_str("AppleInterfaceStyle");
+			let dark = NSString::from_str("Dark");
+			unsafe {
+				let defaults: *mut Object = msg_send!(cls, standardUserDefaults);
+				let style: *mut Object = msg_send!(defaults, stringForKey:key);
+				let is_dark: ::objc::runtime::BOOL = if style.is_null() { NO } else { msg_send!(style, isEqualToString:dark) };
+				return if is_dark != NO { Theme::Dark } else { Theme::Light };
+			}
+		}
+
+		/// List the screens, the first one has the menu bar and is the primary.
+		pub fn enumerate_monitors() -> Vec<MonitorInfo> {
+			let cls = Class::get("NSScreen").expect("`NSScreen` is not registered.");
+			let mut list = Vec::new();
+			unsafe {
+				let screens: *mut Object = msg_send!(cls, screens);
+				let count: usize = msg_send!(screens, count);
+				for index in 0..count {
+					let screen: *mut Object = msg_send!(screens, objectAtIndex:index);
+					list.push(OsWindow::monitor_info(screen, index == 0));
+				}
+			}
+			return list;
+		}
+
+		/// Describe the `NSScreen` in the flipped coordinates of `enumerate_monitors`.
+		fn monitor_info(screen: *mut Object, is_primary: bool) -> MonitorInfo {
+			let primary_height = OsWindow::primary_screen_height();
+			let to_rect = |rc: NSRect| {
+				let top = primary_height - (rc.origin.y + rc.size.height);
+				RECT {
+					left: rc.origin.x as LONG,
+					top: top as LONG,
+					right: (rc.origin.x + rc.size.width) as LONG,
+					bottom: (top + rc.size.height) as LONG,
+				}
+			};
+			unsafe {
+				let frame: NSRect = msg_send!(screen, frame);
+				let visible: NSRect = msg_send!(screen, visibleFrame);
+				let scale: f64 = msg_send!(screen, backingScaleFactor);
+				return MonitorInfo {
+					rect: to_rect(frame),
+					work_area: to_rect(visible),
+					scale: scale as f32,
+					is_primary: is_primary,
+				};
+			}
+		}
+
+		fn view(&self) -> *mut Object {
+			let hwnd = self.get_hwnd();
+			let hwnd = hwnd as *mut Object;
+			return hwnd;
+		}
+
+		/// The `NSWindow` of the view, for the windows created by Sciter which always have one.
+		fn window(&self) -> *mut Object {
+			return self.try_window().expect("the view is not in a window");
+		}
+
+		/// The `NSWindow` of the view, `None` if it is not added to a window yet.
+		fn try_window(&self) -> Option<*mut Object> {
+			let hwnd = self.view();
+			if hwnd.is_null() {
+				return None;
+			}
+			let obj: *mut Object = unsafe { msg_send!(hwnd, window) };
+			return if obj.is_null() { None } else { Some(obj) };
+		}
+
+		/// The `NSWindow` of the Sciter view, null if there is no view or it is not in a window.
+		pub fn ns_window(&self) -> *mut Object {
+			return self.try_window().unwrap_or(::std::ptr::null_mut());
+		}
+
+		/// Height of the primary screen, used to flip the Cocoa Y axis (which goes from the bottom).
+		fn primary_screen_height() -> f64 {
+			let cls = Class::get("NSScreen").expect("`NSScreen` is not registered.");
+			unsafe {
+				let screens: *mut Object = msg_send!(cls, screens);
+				let primary: *mut Object = msg_send!(screens, objectAtIndex:0usize);
+				let frame: NSRect = msg_send!(primary, frame);
+				return frame.size.height;
+			}
+		}
+
+		/// Lock the cursor over the Sciter view or release it.
+		///
+		/// There is no confinement on macOS: the cursor is moved into the view if it is outside
+		/// and then detached from the mouse, which still reports the movement deltas.
+		fn clip_cursor(&self, enable: bool) {
+			if enable {
+				let cls = Class::get("NSEvent").expect("`NSEvent` is not registered.");
+				let (view, wnd) = (self.view(), self.window());
+				unsafe {
+					let bounds: NSRect = msg_send!(view, bounds);
+					let rect: NSRect = msg_send!(view, convertRect:bounds toView:0 as *mut Object);
+					let rect: NSRect = msg_send!(wnd, convertRectToScreen:rect);
+					let mouse: NSPoint = msg_send!(cls, mouseLocation);
+					let inside = mouse.x >= rect.origin.x && mouse.x < rect.origin.x + rect.size.width
+						&& mouse.y >= rect.origin.y && mouse.y < rect.origin.y + rect.size.height;
+					if !inside {
+						// CoreGraphics wants the top-left origin
+						let center = NSPoint {
+							x: rect.origin.x + rect.size.width / 2.0,
+							y: OsWindow::primary_screen_height() - (rect.origin.y + rect.size.height / 2.0),
+						};
+						CGWarpMouseCursorPosition(center);
+					}
+				}
+			}
+			unsafe { CGAssociateMouseAndMouseCursorPosition(!enable as UINT) };
+		}
+
+		/// Window frame in points relative to the top-left corner of the primary screen.
+		fn frame_rect(&self) -> RECT {
+			let wnd = self.window();
+			let frame: NSRect = unsafe { msg_send!(wnd, frame) };
+			let top = OsWindow::primary_screen_height() - (frame.origin.y + frame.size.height);
+			return RECT {
+				left: frame.origin.x as LONG,
+				top: top as LONG,
+				right: (frame.origin.x + frame.size.width) as LONG,
+				bottom: (top + frame.size.height) as LONG,
+			};
+		}
+
+		/// Put the delegate proxy in front of the Sciter one unless it is there already.
+		fn install_delegate(&self) {
+			let wnd = self.window();
+			let cls = window_delegate_class();
+			unsafe {
+				let target: *mut Object = msg_send!(wnd, delegate);
+				let installed: ::objc::runtime::BOOL = if target.is_null() { NO } else { msg_send!(target, isKindOfClass:cls) };
+				if installed != NO {
+					return;
+				}
+				let delegate: *mut Object = msg_send!(cls, new);
+				(*delegate).set_ivar("target", target);
+				(*delegate).set_ivar("hwnd", self.hwnd as usize);
+				let _: () = msg_send!(wnd, setDelegate:delegate);
+			}
+		}
+
+		fn set_frame_rect(&self, rc: RECT) {
+			let wnd = self.window();
+			// unlike the user resizing, `setFrame:` ignores the size limits
+			let (min, max): (NSSize, NSSize) = unsafe { (msg_send!(wnd, minSize), msg_send!(wnd, maxSize)) };
+			let width = ((rc.right - rc.left) as f64).max(min.width).min(max.width);
+			let height = ((rc.bottom - rc.top) as f64).max(min.height).min(max.height);
+			let frame = NSRect {
+				origin: NSPoint { x: rc.left as f64, y: OsWindow::primary_screen_height() - rc.top as f64 - height },
+				size: NSSize { width: width, height: height },
+			};
+			let _: () = unsafe { msg_send!(wnd, setFrame:frame display:YES) };
+		}
+	}
+
+	/// Close the window created by this instance, unless it is closed already.
+	impl Drop for OsWindow {
+		fn drop(&mut self) {
+			if self.owned && super::window_alive(self.hwnd) {
+				self.dismiss();
+			}
+		}
+	}
+
+	impl super::BaseWindow for OsWindow {
+		/// Get native window handle.
+		fn get_hwnd(&self) -> HWINDOW {
+			return self.hwnd;
+		}
+
+		/// Keep the window alive when this instance is dropped.
+		fn release(&mut self) {
+			self.owned = false;
+		}
+
+		/// Move the Sciter view into the `parent` view, or into a new `NSWindow` if it is null.
+		fn set_parent(&mut self, parent: HWINDOW) {
+			const NS_TITLED_WINDOW_MASKS: usize = 1 | 2 | 4 | 8; // titled, closable, miniaturizable, resizable
+			const NS_BACKING_STORE_BUFFERED: usize = 2;
+			let child = SCITER_CREATE_WINDOW_FLAGS::SW_CHILD.bits() as UINT;
+			let view = self.view();
+			let own = (self.flags & child) == 0;
+			let old = self.window();
+			unsafe {
+				let nil: *mut Object = ::std::ptr::null_mut();
+				// the superview holds the only reference
+				let _: *mut Object = msg_send!(view, retain);
+				let _: () = msg_send!(view, removeFromSuperview);
+				if own {
+					// the window of Sciter is left empty otherwise
+					let _: () = msg_send!(old, orderOut:nil);
+				}
+				if parent.is_null() {
+					let frame: NSRect = msg_send!(view, frame);
+					let cls = Class::get("NSWindow").expect("`NSWindow` is not registered.");
+					let wnd: *mut Object = msg_send!(cls, alloc);
+					let wnd: *mut Object = msg_send!(wnd, initWithContentRect:frame styleMask:NS_TITLED_WINDOW_MASKS backing:NS_BACKING_STORE_BUFFERED defer:NO);
+					let _: () = msg_send!(wnd, setContentView:view);
+					let _: () = msg_send!(wnd, makeKeyAndOrderFront:nil);
+				} else {
+					let host = parent as *mut Object;
+					let _: () = msg_send!(host, addSubview:view);
+				}
+				let _: () = msg_send!(view, release);
+			}
+			self.flags = if parent.is_null() { self.flags & !child } else { self.flags | child };
+		}
+
+		/// Create a new native window.
+		fn create(&mut self, rc: RECT, flags: UINT, parent: HWINDOW) -> HWINDOW {
+			match self.try_create(rc, flags, parent) {
+				Ok(hwnd) => hwnd,
+				Err(error) => panic!("Failed to create window: {}", error),
+			}
+		}
+
+		/// Create a new native window or tell why it can not be created.
+		fn try_create(&mut self, rc: RECT, flags: UINT, parent: HWINDOW) -> Result<HWINDOW, WindowError> {
+			::check_api().map_err(WindowError::LibraryNotFound)?;
+
+			if (flags & SCITER_CREATE_WINDOW_FLAGS::SW_MAIN.bits()) != 0 {
+				OsWindow::init_app()?;
+			}
+
+			self.flags = flags;
+
+			#[cfg(not(feature = "windowless"))]
+			{
+				let w = rc.right - rc.left;
+				let h = rc.bottom - rc.top;
+				let prc: *const RECT = if w > 0 && h > 0 { &rc } else { std::ptr::null() };
+
+				let cb = std::ptr::null();
+				self.hwnd = (_API.SciterCreateWindow)(flags, prc, 0 as LPVOID, 0 as LPVOID, 0 as LPVOID);
+				if self.hwnd.is_null() {
+					return Err(WindowError::CreateFailed);
+				}
+				if (flags & SCITER_CREATE_WINDOW_FLAGS::SW_CHILD.bits()) == 0 {
+					// to restore the focus on `windowDidBecomeKey:` and to see the window closed
+					self.install_delegate();
+					// the child views go with their foreign windows
+					self.owned = true;
+					super::window_created(self.hwnd);
+				}
+				if let Some(color) = self.background {
+					self.set_background_color(color);
+				}
+			}
+			#[cfg(feature = "windowless")]
+			{
+				let _ = rc;
+				let _ = parent;
+				let _ = &(_API.SciterVersion);
+			}
+			return Ok(self.hwnd);
+		}
+
+		/// Create a new native window and show it in the `state` right away.
+		fn create_with_state(&mut self, rc: RECT, flags: UINT, parent: HWINDOW, state: WindowState) -> HWINDOW {
+			let hwnd = self.create(rc, flags, parent);
+			self.show_in_state(state);
+			return hwnd;
+		}
+
+		/// Create a new native window centered on the screen, the size is in points already.
+		fn create_on_monitor(&mut self, monitor_index: usize, size: (i32, i32), flags: UINT, parent: HWINDOW) -> HWINDOW {
+			let rc = super::monitor_centered_rect(&OsWindow::enumerate_monitors(), monitor_index, size, false);
+			return self.create(rc, flags, parent);
+		}
+
+		/// Create a new native window which stays hidden and off the taskbar until it is shown.
+		fn create_hidden(&mut self, flags: UINT, parent: HWINDOW) -> HWINDOW {
+			let hwnd = self.create(RECT::default(), flags, parent);
+			self.set_skip_taskbar(true);
+			return hwnd;
+		}
+
+		/// Create a borderless `NSWindow` far outside of the screens, it is never ordered in.
+		fn try_create_offscreen(&mut self, size: (i32, i32)) -> Result<HWINDOW, WindowError> {
+			const OFFSCREEN: i32 = -32000;
+			let rc = RECT { left: OFFSCREEN, top: OFFSCREEN, right: OFFSCREEN + size.0, bottom: OFFSCREEN + size.1 };
+			return self.try_create(rc, SCITER_CREATE_WINDOW_FLAGS::SW_POPUP.bits() as UINT, ::std::ptr::null_mut());
+		}
+
+		/// Minimize or hide window.
+		fn collapse(&self, hide: bool) {
+			let wnd = match self.try_window() {
+				Some(wnd) => wnd,
+				None => return,
+			};
+			if hide {
+				let _: () = unsafe { msg_send!(wnd, orderOut:0) };
+			} else {
+				let hwnd = self.view();
+				let _: () = unsafe { msg_send!(wnd, performMiniaturize:hwnd) };
+			}
+		}
+
+		/// Show or maximize window.
+		fn expand(&self, maximize: bool) {
+			let wnd = match self.try_window() {
+				Some(wnd) => wnd,
+				None => return,
+			};
+			if (self.flags & SCITER_CREATE_WINDOW_FLAGS::SW_TITLEBAR.bits()) != 0 {
 				let app = OsWindow::get_app();
 				let _: () = unsafe { msg_send!(app, activateIgnoringOtherApps:true) };
 			}
 			unsafe {
-				let _: () = msg_send!(wnd, makeKeyAndOrderFront:0);
-				// msg_send!(wnd, orderFrontRegardless);
+				let _: () = msg_send!(wnd, makeKeyAndOrderFront:0);
+				// msg_send!(wnd, orderFrontRegardless);
+			}
+			// `performZoom:` toggles the zoomed state
+			let zoomed: ::objc::runtime::BOOL = unsafe { msg_send!(wnd, isZoomed) };
+			if maximize && zoomed == NO {
+				let _: () = unsafe { msg_send!(wnd, performZoom:0) };
+			}
+		}
+
+		/// Show the window in its current state.
+		fn show(&self) {
+			self.expand(false);
+		}
+
+		/// Hide the window.
+		fn hide(&self) {
+			self.collapse(true);
+		}
+
+		/// Minimize the window to the Dock.
+		fn minimize(&self) {
+			self.collapse(false);
+		}
+
+		/// Show and zoom the window.
+		fn maximize(&self) {
+			self.expand(true);
+		}
+
+		/// Leave fullscreen, miniaturized and zoomed states.
+		fn restore(&self) {
+			let wnd = self.window();
+			let nil: *mut Object = ::std::ptr::null_mut();
+			self.set_fullscreen(false);
+			unsafe {
+				let minimized: ::objc::runtime::BOOL = msg_send!(wnd, isMiniaturized);
+				if minimized != NO {
+					let _: () = msg_send!(wnd, deminiaturize:nil);
+				}
+				let zoomed: ::objc::runtime::BOOL = msg_send!(wnd, isZoomed);
+				if zoomed != NO {
+					let _: () = msg_send!(wnd, zoom:nil);
+				}
+			}
+			self.expand(false);
+		}
+
+		/// Deminiaturize the window and make it key in the activated app.
+		fn activate(&self) {
+			let wnd = match self.try_window() {
+				Some(wnd) => wnd,
+				None => return,
+			};
+			let nil: *mut Object = ::std::ptr::null_mut();
+			unsafe {
+				let minimized: ::objc::runtime::BOOL = msg_send!(wnd, isMiniaturized);
+				if minimized != NO {
+					let _: () = msg_send!(wnd, deminiaturize:nil);
+				}
+				let app = OsWindow::get_app();
+				let _: () = msg_send!(app, activateIgnoringOtherApps:YES);
+				let _: () = msg_send!(wnd, makeKeyAndOrderFront:nil);
+			}
+		}
+
+		/// Close window.
+		fn dismiss(&self) {
+			if let Some(wnd) = self.try_window() {
+				let _: () = unsafe { msg_send!(wnd, close) };
+			}
+		}
+
+		/// Ask the window to close, it can be cancelled by the window.
+		fn request_close(&self) {
+			// Note: `performClose:` beeps instead for windows without the close button.
+			let wnd = self.window();
+			let nil: *mut Object = ::std::ptr::null_mut();
+			let _: () = unsafe { msg_send!(wnd, performClose:nil) };
+		}
+
+		/// Ask the callback on `windowShouldClose:`, `false` cancels the closing.
+		fn on_close<F: Fn() -> bool + 'static>(&mut self, callback: F) {
+			super::set_close_handler(self.hwnd, Rc::new(callback));
+			self.install_delegate();
+		}
+
+		/// Restore the focused element on `windowDidBecomeKey:`, enabled by default.
+		fn set_restore_focus(&mut self, enable: bool) {
+			super::set_restore_focus(self.hwnd, enable);
+		}
+
+		/// Call the callback with the new window size (in points) on `windowDidResize:`.
+		fn on_resize<F: Fn(i32, i32) + 'static>(&mut self, callback: F) {
+			self.install_delegate();
+			super::set_resize_handler(self.hwnd, self.frame_rect(), Rc::new(callback));
+		}
+
+		/// Call the callback with the new window position (in points) on `windowDidMove:`.
+		fn on_move<F: Fn(i32, i32) + 'static>(&mut self, callback: F) {
+			self.install_delegate();
+			super::set_move_handler(self.hwnd, self.frame_rect(), Rc::new(callback));
+		}
+
+		/// Call the callback with the new `backingScaleFactor` on `windowDidChangeBackingProperties:`.
+		fn on_dpi_changed<F: Fn(f32) + 'static>(&mut self, callback: F) {
+			self.install_delegate();
+			super::set_dpi_handler(self.hwnd, self.get_dpi_scale(), Rc::new(callback));
+		}
+
+		/// Check the window monitor on `windowDidChangeScreen:`, `windowDidMove:` and `windowDidResize:`.
+		fn on_monitor_changed<F: Fn(MonitorInfo, Option<f32>) + 'static>(&mut self, callback: F) {
+			self.install_delegate();
+			super::set_monitor_handler(self.hwnd, Rc::new(callback));
+		}
+
+		/// Observe the distributed `AppleInterfaceThemeChangedNotification` with the window delegate.
+		fn on_theme_changed<F: Fn(Theme) + 'static>(&mut self, callback: F) {
+			if !super::set_theme_handler(self.hwnd, OsWindow::current_theme(), Rc::new(callback)) {
+				return;
+			}
+			self.install_delegate();
+			let cls = Class::get("NSDistributedNotificationCenter").expect("`NSDistributedNotificationCenter` is not registered.");
+			let name = NSString::from_str("AppleInterfaceThemeChangedNotification");
+			unsafe {
+				let delegate: *mut Object = msg_send!(self.window(), delegate);
+				let center: *mut Object = msg_send!(cls, defaultCenter);
+				let _: () = msg_send!(center, addObserver:delegate selector:sel!(themeChanged:) name:name object:0 as *mut Object);
+			}
+		}
+
+		/// Accept the files dropped from the Finder, replacing the Sciter drag-and-drop handling.
+		fn on_file_drop<F: Fn(Vec<PathBuf>) + 'static>(&mut self, callback: F) {
+			if !super::set_drop_handler(self.hwnd, Rc::new(callback)) {
+				return;
+			}
+			self.install_delegate();
+			// the window (and so its delegate) gets the drops which the views do not accept
+			let (view, wnd) = (self.view(), self.window());
+			let cls = Class::get("NSArray").expect("`NSArray` is not registered.");
+			unsafe {
+				let types: *mut Object = msg_send!(cls, arrayWithObject:NSFilenamesPboardType);
+				let _: () = msg_send!(view, unregisterDraggedTypes);
+				let _: () = msg_send!(wnd, registerForDraggedTypes:types);
+			}
+		}
+
+		/// Set native window title.
+		fn set_title(&mut self, title: &str) {
+			if let Some(wnd) = self.try_window() {
+				let s = NSString::from_str(title);
+				let _: () = unsafe { msg_send!(wnd, setTitle:s) };
+			}
+		}
+
+		/// Set the `accessibilityIdentifier` of the view.
+		fn set_automation_id(&mut self, id: &str) {
+			let s = NSString::from_str(id);
+			let _: () = unsafe { msg_send!(self.view(), setAccessibilityIdentifier:s) };
+		}
+
+		/// Set the `accessibilityLabel` of the view.
+		fn set_accessible_name(&mut self, name: &str) {
+			let s = NSString::from_str(name);
+			let _: () = unsafe { msg_send!(self.view(), setAccessibilityLabel:s) };
+		}
+
+		/// Get native window title, empty if the view is not in a window.
+		fn get_title(&self) -> String {
+			let wnd = match self.try_window() {
+				Some(wnd) => wnd,
+				None => return String::new(),
+			};
+			let title: *const NSString = unsafe { msg_send!(wnd, title) };
+			if title.is_null() {
+				return String::new();
+			}
+			let title = unsafe { &*title };
+			return title.as_str().to_owned();
+		}
+
+		/// Move the window to the specified screen position (in points).
+		fn move_to(&self, x: i32, y: i32) {
+			let rc = self.frame_rect();
+			self.set_frame_rect(RECT { left: x, top: y, right: x + rc.right - rc.left, bottom: y + rc.bottom - rc.top });
+		}
+
+		/// Resize the window (in points), keeping its top-left corner in place.
+		fn resize(&self, width: i32, height: i32) {
+			let rc = self.frame_rect();
+			self.set_frame_rect(RECT { left: rc.left, top: rc.top, right: rc.left + width, bottom: rc.top + height });
+		}
+
+		/// Move and resize the window (in points).
+		fn set_rect(&self, rc: RECT) {
+			self.set_frame_rect(rc);
+		}
+
+		/// Apply the changes at once, the frame is changed with a single `setFrame:display:`.
+		fn update_geometry(&self, changes: GeometryUpdate) {
+			super::update_geometry_in_steps(self, changes.state, || {
+				if changes.position.is_some() || changes.size.is_some() {
+					let rc = self.frame_rect();
+					let (x, y) = changes.position.unwrap_or((rc.left, rc.top));
+					let (width, height) = changes.size.unwrap_or((rc.right - rc.left, rc.bottom - rc.top));
+					self.set_frame_rect(RECT { left: x, top: y, right: x + width, bottom: y + height });
+				}
+				// ordering a hidden window shows it
+				let nil: *mut Object = ::std::ptr::null_mut();
+				match changes.z_order {
+					Some(ZOrder::Top) if self.is_visible() => { let _: () = unsafe { msg_send!(self.window(), orderFront:nil) }; },
+					Some(ZOrder::Bottom) if self.is_visible() => { let _: () = unsafe { msg_send!(self.window(), orderBack:nil) }; },
+					Some(ZOrder::Topmost) => self.set_topmost(true),
+					Some(ZOrder::NotTopmost) => self.set_topmost(false),
+					_ => {},
+				}
+			});
+		}
+
+		/// Get the window rectangle in screen coordinates (in points).
+		fn get_rect(&self) -> RECT {
+			self.frame_rect()
+		}
+
+		/// Set the minimum window size (in points), zero means no limit.
+		fn set_min_size(&self, width: i32, height: i32) {
+			let wnd = self.window();
+			let size = NSSize { width: width.max(0) as f64, height: height.max(0) as f64 };
+			let _: () = unsafe { msg_send!(wnd, setMinSize:size) };
+			self.set_frame_rect(self.frame_rect());
+		}
+
+		/// Set the maximum window size (in points), zero means no limit.
+		fn set_max_size(&self, width: i32, height: i32) {
+			const FLT_MAX: f64 = ::std::f32::MAX as f64;
+			let wnd = self.window();
+			let unlimited = |v: i32| if v > 0 { v as f64 } else { FLT_MAX };
+			let size = NSSize { width: unlimited(width), height: unlimited(height) };
+			let _: () = unsafe { msg_send!(wnd, setMaxSize:size) };
+			self.set_frame_rect(self.frame_rect());
+		}
+
+		/// The document minimal size from `SciterGetMinWidth` and `SciterGetMinHeight` plus the title bar.
+		fn sync_min_size_from_content(&self, track: bool) {
+			let content = super::content_min_size(self.hwnd);
+			let wnd = self.window();
+			let (frame, client): (NSRect, NSRect) = unsafe {
+				let frame: NSRect = msg_send!(wnd, frame);
+				(frame, msg_send!(wnd, contentRectForFrameRect:frame))
+			};
+			let extra = ((frame.size.width - client.size.width) as i32, (frame.size.height - client.size.height) as i32);
+			self.set_min_size(content.cx + extra.0, content.cy + extra.1);
+			super::track_content_min_size(self.hwnd, track);
+		}
+
+		/// Center the window in the visible frame (without the menu bar and dock) of its screen.
+		fn center(&self) {
+			let wnd = self.window();
+			unsafe {
+				let mut screen: *mut Object = msg_send!(wnd, screen);
+				if screen.is_null() {
+					let cls = Class::get("NSScreen").expect("`NSScreen` is not registered.");
+					screen = msg_send!(cls, mainScreen);
+				}
+				let work: NSRect = msg_send!(screen, visibleFrame);
+				let frame: NSRect = msg_send!(wnd, frame);
+				let origin = NSPoint {
+					x: work.origin.x + (work.size.width - frame.size.width) / 2.0,
+					y: work.origin.y + (work.size.height - frame.size.height) / 2.0,
+				};
+				let _: () = msg_send!(wnd, setFrameOrigin:origin);
+			}
+		}
+
+		/// The screen of the window or the main one if the window is off-screen.
+		fn current_monitor(&self) -> Option<MonitorInfo> {
+			if self.get_hwnd().is_null() {
+				return None;
+			}
+			let cls = Class::get("NSScreen").expect("`NSScreen` is not registered.");
+			let wnd = self.window();
+			unsafe {
+				let mut screen: *mut Object = msg_send!(wnd, screen);
+				if screen.is_null() {
+					screen = msg_send!(cls, mainScreen);
+				}
+				let screens: *mut Object = msg_send!(cls, screens);
+				let primary: *mut Object = msg_send!(screens, objectAtIndex:0usize);
+				return Some(OsWindow::monitor_info(screen, screen == primary));
+			}
+		}
+
+		/// The `CGDisplayMode` refresh rate of the window screen, zero for some built-in displays.
+		fn get_refresh_rate(&self) -> Option<f32> {
+			if self.get_hwnd().is_null() {
+				return None;
+			}
+			let key = NSString::from_str("NSScreenNumber");
+			let wnd = self.window();
+			unsafe {
+				let screen: *mut Object = msg_send!(wnd, screen);
+				if screen.is_null() {
+					return None;
+				}
+				let description: *mut Object = msg_send!(screen, deviceDescription);
+				let number: *mut Object = msg_send!(description, objectForKey:key);
+				if number.is_null() {
+					return None;
+				}
+				let display: UINT = msg_send!(number, unsignedIntValue);
+				let mode = CGDisplayCopyDisplayMode(display);
+				if mode.is_null() {
+					return None;
+				}
+				let rate = CGDisplayModeGetRefreshRate(mode);
+				CGDisplayModeRelease(mode);
+				return if rate > 0.0 { Some(rate as f32) } else { None };
 			}
-			if maximize {
-				let _: () = unsafe { msg_send!(wnd, performZoom:0) };
+		}
+
+		/// Save the current frame, zoomed windows keep their previous one by themselves.
+		fn save_placement(&self) -> WindowPlacement {
+			let rc = self.get_rect();
+			let m = self.current_monitor().map_or(RECT::default(), |monitor| monitor.rect);
+			return WindowPlacement {
+				position: (rc.left, rc.top),
+				size: (rc.width(), rc.height()),
+				maximized: self.get_state() == WindowState::Maximized,
+				monitor: (m.left, m.top, m.right, m.bottom),
+			};
+		}
+
+		/// Move the window to the clamped frame and zoom it if needed.
+		fn apply_placement(&self, placement: &WindowPlacement) {
+			let rc = super::placement_rect(placement, &OsWindow::enumerate_monitors());
+			self.update_geometry(GeometryUpdate {
+				position: Some((rc.left, rc.top)),
+				size: Some((rc.width(), rc.height())),
+				state: if placement.maximized { Some(WindowState::Maximized) } else { None },
+				..Default::default()
+			});
+		}
+
+		/// Enter or leave fullscreen.
+		fn set_fullscreen(&self, enable: bool) {
+			let wnd = self.window();
+			let mask: usize = unsafe { msg_send!(wnd, styleMask) };
+			if ((mask & NS_FULLSCREEN_WINDOW_MASK) != 0) != enable {
+				// Cocoa restores the previous geometry itself.
+				let nil: *mut Object = ::std::ptr::null_mut();
+				let _: () = unsafe { msg_send!(wnd, toggleFullScreen:nil) };
+			}
+		}
+
+		/// Switch between the borderless and the titled window style, keeping it resizable.
+		fn set_frameless(&self, enable: bool) {
+			// NSWindowStyleMaskTitled | NSWindowStyleMaskClosable | NSWindowStyleMaskMiniaturizable
+			const NS_TITLED_WINDOW_MASKS: usize = 1 | 1 << 1 | 1 << 2;
+			let wnd = self.window();
+			unsafe {
+				let mask: usize = msg_send!(wnd, styleMask);
+				let mask = if enable { mask & !NS_TITLED_WINDOW_MASKS } else { mask | NS_TITLED_WINDOW_MASKS };
+				let _: () = msg_send!(wnd, setStyleMask:mask);
+				let _: () = msg_send!(wnd, setMovableByWindowBackground:if enable { YES } else { NO });
+			}
+		}
+
+		/// Toggle `NSWindowStyleMaskResizable`, the frame stays the same.
+		fn set_resizable(&self, enable: bool) {
+			const NS_RESIZABLE_WINDOW_MASK: usize = 1 << 3;
+			let wnd = self.window();
+			unsafe {
+				let mask: usize = msg_send!(wnd, styleMask);
+				let mask = if enable { mask | NS_RESIZABLE_WINDOW_MASK } else { mask & !NS_RESIZABLE_WINDOW_MASK };
+				let _: () = msg_send!(wnd, setStyleMask:mask);
+			}
+		}
+
+		/// Whether the window has `NSWindowStyleMaskResizable`.
+		fn is_resizable(&self) -> bool {
+			const NS_RESIZABLE_WINDOW_MASK: usize = 1 << 3;
+			let wnd = self.window();
+			let mask: usize = unsafe { msg_send!(wnd, styleMask) };
+			return (mask & NS_RESIZABLE_WINDOW_MASK) != 0;
+		}
+
+		/// Set `hasShadow` of the window, AppKit recalculates the shadow by itself.
+		fn set_shadow(&self, enable: bool) {
+			let wnd = self.window();
+			let _: () = unsafe { msg_send!(wnd, setHasShadow:if enable { YES } else { NO }) };
+		}
+
+		/// Put the Sciter view of the top-level window into an `NSVisualEffectView` as the window content.
+		fn set_backdrop(&self, kind: BackdropKind) {
+			const NS_VISUAL_EFFECT_BLENDING_MODE_BEHIND_WINDOW: isize = 0;
+			const NS_VISUAL_EFFECT_STATE_ACTIVE: isize = 1;
+			const NS_VIEW_WIDTH_SIZABLE: usize = 2;
+			const NS_VIEW_HEIGHT_SIZABLE: usize = 16;
+			let material: isize = match kind {
+				BackdropKind::None => 0,
+				BackdropKind::Blur => 21, // under window background
+				BackdropKind::Acrylic => 13, // HUD window
+				BackdropKind::Mica => 12, // window background
+				BackdropKind::Tabbed => 3, // titlebar
+			};
+			// macOS 10.10+
+			let cls = match Class::get("NSVisualEffectView") {
+				Some(cls) => cls,
+				None => return,
+			};
+			let (view, wnd) = (self.view(), self.window());
+			unsafe {
+				let superview: *mut Object = msg_send!(view, superview);
+				let wrapped: ::objc::runtime::BOOL = if superview.is_null() { NO } else { msg_send!(superview, isKindOfClass:cls) };
+				if kind == BackdropKind::None {
+					if wrapped != NO {
+						let _: *mut Object = msg_send!(view, retain);
+						let _: () = msg_send!(view, removeFromSuperview);
+						let _: () = msg_send!(wnd, setContentView:view);
+						let _: () = msg_send!(view, release);
+						let _: ::objc::runtime::BOOL = msg_send!(wnd, makeFirstResponder:view);
+					}
+					return;
+				}
+				let effect = if wrapped != NO {
+					superview
+				} else {
+					let content: *mut Object = msg_send!(wnd, contentView);
+					if content != view {
+						// a child view, the host owns the window
+						return;
+					}
+					let frame: NSRect = msg_send!(view, frame);
+					let effect: *mut Object = msg_send!(cls, alloc);
+					let effect: *mut Object = msg_send!(effect, initWithFrame:frame);
+					let _: () = msg_send!(effect, setBlendingMode:NS_VISUAL_EFFECT_BLENDING_MODE_BEHIND_WINDOW);
+					let _: () = msg_send!(effect, setState:NS_VISUAL_EFFECT_STATE_ACTIVE);
+					// the window holds the only reference of the view
+					let _: *mut Object = msg_send!(view, retain);
+					let _: () = msg_send!(wnd, setContentView:effect);
+					let _: () = msg_send!(effect, release);
+					let bounds: NSRect = msg_send!(effect, bounds);
+					let _: () = msg_send!(view, setFrame:bounds);
+					let _: () = msg_send!(view, setAutoresizingMask:NS_VIEW_WIDTH_SIZABLE | NS_VIEW_HEIGHT_SIZABLE);
+					let _: () = msg_send!(effect, addSubview:view);
+					let _: () = msg_send!(view, release);
+					let _: ::objc::runtime::BOOL = msg_send!(wnd, makeFirstResponder:view);
+					effect
+				};
+				let _: () = msg_send!(effect, setMaterial:material);
+			}
+		}
+
+		/// Set the `backgroundColor` of the `NSWindow`, which shows through until Sciter paints the document.
+		fn set_background_color(&mut self, color: u32) {
+			self.background = Some(color);
+			if self.hwnd.is_null() {
+				// applied by `try_create`
+				return;
+			}
+			let channel = |shift: u32| ((color >> shift) & 0xFF) as f64 / 255.0;
+			let wnd = self.window();
+			let cls = Class::get("NSColor").expect("`NSColor` is not registered.");
+			unsafe {
+				let background: *mut Object = msg_send!(cls, colorWithSRGBRed:channel(16) green:channel(8) blue:channel(0) alpha:channel(24));
+				let _: () = msg_send!(wnd, setBackgroundColor:background);
+			}
+		}
+
+		/// AppKit delivers the touch and pen input as the mouse events already, so it is a no-op.
+		fn enable_touch(&mut self, _enable: bool) {
+		}
+
+		/// Start moving the window with the mouse from the current mouse-down event.
+		fn begin_drag(&self) {
+			let wnd = self.window();
+			unsafe {
+				let event: *mut Object = msg_send!(OsWindow::get_app(), currentEvent);
+				let supported: ::objc::runtime::BOOL = msg_send!(wnd, respondsToSelector:sel!(performWindowDragWithEvent:));
+				if event.is_null() || supported == NO {
+					// before macOS 10.11
+					let _: () = msg_send!(wnd, setMovableByWindowBackground:YES);
+					return;
+				}
+				let _: () = msg_send!(wnd, performWindowDragWithEvent:event);
+			}
+		}
+
+		/// Install the local left mouse-down monitor of the app once, the caption presses drag or zoom the window.
+		///
+		/// The borders are left to AppKit, which resizes the windows with the resizable style mask itself.
+		fn set_hit_test_callback<F: Fn(i32, i32) -> HitTestResult + 'static>(&mut self, callback: F) {
+			const BLOCK_IS_GLOBAL: INT = 1 << 28;
+			const NS_EVENT_MASK_LEFT_MOUSE_DOWN: u64 = 1 << 1;
+			static MONITOR: Once = Once::new();
+
+			extern "C" fn on_mouse_down(_block: *mut EventHandlerBlock, event: *mut Object) -> *mut Object {
+				let wnd: *mut Object = unsafe { msg_send!(event, window) };
+				if wnd.is_null() {
+					return event;
+				}
+				let owned_by = |hwnd: &HWINDOW| {
+					let owner: *mut Object = unsafe { msg_send!(*hwnd as *mut Object, window) };
+					owner == wnd
+				};
+				let hwnd = match super::hit_test_windows().into_iter().find(owned_by) {
+					Some(hwnd) => hwnd,
+					None => return event,
+				};
+				// from the bottom-left corner of the window
+				let (location, frame): (NSPoint, NSRect) = unsafe { (msg_send!(event, locationInWindow), msg_send!(wnd, frame)) };
+				let (x, y) = (location.x as i32, (frame.size.height - location.y) as i32);
+				if super::hit_test(hwnd, x, y) == Some(HitTestResult::Caption) {
+					let nil: *mut Object = ::std::ptr::null_mut();
+					unsafe {
+						let clicks: isize = msg_send!(event, clickCount);
+						if clicks == 2 {
+							let _: () = msg_send!(wnd, performZoom:nil);
+						} else {
+							let _: () = msg_send!(wnd, performWindowDragWithEvent:event);
+						}
+					}
+					// swallow it
+					return ::std::ptr::null_mut();
+				}
+				return event;
+			}
+
+			super::set_hit_test_handler(self.hwnd, Rc::new(callback));
+			MONITOR.call_once(|| {
+				let descriptor = Box::new(BlockDescriptor { reserved: 0, size: ::std::mem::size_of::<EventHandlerBlock>() });
+				let block = Box::new(EventHandlerBlock {
+					isa: unsafe { &_NSConcreteGlobalBlock },
+					flags: BLOCK_IS_GLOBAL,
+					reserved: 0,
+					invoke: on_mouse_down,
+					descriptor: Box::into_raw(descriptor),
+				});
+				// both live as long as the app does
+				let block = Box::into_raw(block);
+				let cls = Class::get("NSEvent").expect("`NSEvent` is not registered.");
+				unsafe {
+					let monitor: *mut Object = msg_send!(cls, addLocalMonitorForEventsMatchingMask:NS_EVENT_MASK_LEFT_MOUSE_DOWN handler:block);
+					let _: *mut Object = msg_send!(monitor, retain);
+				}
+			});
+		}
+
+		/// Bounce the dock icon until the app is activated (or just once).
+		fn request_attention(&self, urgent: bool) {
+			const NS_CRITICAL_REQUEST: usize = 0;
+			const NS_INFORMATIONAL_REQUEST: usize = 10;
+			let app = OsWindow::get_app();
+			let active: ::objc::runtime::BOOL = unsafe { msg_send!(app, isActive) };
+			if active != NO {
+				return;
+			}
+			let kind = if urgent { NS_CRITICAL_REQUEST } else { NS_INFORMATIONAL_REQUEST };
+			let _: isize = unsafe { msg_send!(app, requestUserAttention:kind) };
+		}
+
+		/// Show the progress as the badge of the app dock tile, it is shared by all windows of the app.
+		fn set_progress(&self, state: ProgressState, value: f32) {
+			let label = match state {
+				ProgressState::None => None,
+				ProgressState::Normal => Some(format!("{}%", (value.clamp(0.0, 1.0) * 100.0).round())),
+				ProgressState::Paused => Some(format!("{}% \u{2016}", (value.clamp(0.0, 1.0) * 100.0).round())),
+				ProgressState::Error => Some("!".to_owned()),
+				ProgressState::Indeterminate => Some("\u{2026}".to_owned()),
+			};
+			let app = OsWindow::get_app();
+			unsafe {
+				let tile: *mut Object = msg_send!(app, dockTile);
+				match label {
+					Some(label) => {
+						let label = NSString::from_str(&label);
+						let _: () = msg_send!(tile, setBadgeLabel:label);
+					},
+					None => {
+						let nil: *mut Object = ::std::ptr::null_mut();
+						let _: () = msg_send!(tile, setBadgeLabel:nil);
+					},
+				}
+			}
+		}
+
+		/// Does nothing, the Dock makes its own previews.
+		fn set_thumbnail_bitmap(&self, _png: &[u8]) {
+		}
+
+		/// Does nothing, the Dock makes its own previews.
+		fn set_live_preview_rect(&self, _rc: RECT) {
+		}
+
+		/// Exclude the window from the window cycling and the Window menu.
+		fn set_skip_taskbar(&self, skip: bool) {
+			// NSWindowCollectionBehaviorIgnoresCycle
+			const NS_IGNORES_CYCLE: usize = 1 << 6;
+			let wnd = self.window();
+			unsafe {
+				let behavior: usize = msg_send!(wnd, collectionBehavior);
+				let behavior = if skip { behavior | NS_IGNORES_CYCLE } else { behavior & !NS_IGNORES_CYCLE };
+				let _: () = msg_send!(wnd, setCollectionBehavior:behavior);
+				let _: () = msg_send!(wnd, setExcludedFromWindowsMenu:if skip { YES } else { NO });
+			}
+		}
+
+		/// Does nothing, the Dock groups the windows by the app bundle.
+		fn set_app_id(&self, _app_id: &str) {
+		}
+
+		/// Capture the Sciter view as composited by the window server.
+		fn snapshot(&self) -> Result<Vec<u8>, ()> {
+			const CG_WINDOW_LIST_OPTION_INCLUDING_WINDOW: UINT = 1 << 3;
+			const CG_WINDOW_IMAGE_BOUNDS_IGNORE_FRAMING: UINT = 1 << 0;
+			const NS_BITMAP_IMAGE_FILE_TYPE_PNG: usize = 4;
+			if !self.is_visible() {
+				return Err(());
+			}
+			let (view, wnd) = (self.view(), self.window());
+			unsafe {
+				// view bounds in the screen coordinates with the top-left origin, as CoreGraphics wants
+				let bounds: NSRect = msg_send!(view, bounds);
+				let rect: NSRect = msg_send!(view, convertRect:bounds toView:0 as *mut Object);
+				let mut rect: NSRect = msg_send!(wnd, convertRectToScreen:rect);
+				if rect.size.width <= 0.0 || rect.size.height <= 0.0 {
+					return Err(());
+				}
+				rect.origin.y = OsWindow::primary_screen_height() - (rect.origin.y + rect.size.height);
+
+				let number: isize = msg_send!(wnd, windowNumber);
+				let image = CGWindowListCreateImage(rect, CG_WINDOW_LIST_OPTION_INCLUDING_WINDOW, number as UINT, CG_WINDOW_IMAGE_BOUNDS_IGNORE_FRAMING);
+				if image.is_null() {
+					return Err(());
+				}
+				let rep_cls = Class::get("NSBitmapImageRep").expect("`NSBitmapImageRep` is not registered.");
+				let dict_cls = Class::get("NSDictionary").expect("`NSDictionary` is not registered.");
+				let rep: *mut Object = msg_send!(rep_cls, alloc);
+				let rep: *mut Object = msg_send!(rep, initWithCGImage:image);
+				CGImageRelease(image);
+				if rep.is_null() {
+					return Err(());
+				}
+				let properties: *mut Object = msg_send!(dict_cls, dictionary);
+				let data: *mut Object = msg_send!(rep, representationUsingType:NS_BITMAP_IMAGE_FILE_TYPE_PNG properties:properties);
+				let png = if data.is_null() {
+					Err(())
+				} else {
+					let bytes: LPCBYTE = msg_send!(data, bytes);
+					let length: usize = msg_send!(data, length);
+					Ok(::std::slice::from_raw_parts(bytes, length).to_vec())
+				};
+				let _: () = msg_send!(rep, release);
+				return png;
+			}
+		}
+
+		/// Keep the window above the other ones.
+		fn set_topmost(&self, enable: bool) {
+			const NS_NORMAL_WINDOW_LEVEL: isize = 0;
+			const NS_FLOATING_WINDOW_LEVEL: isize = 3;
+			let level = if enable { NS_FLOATING_WINDOW_LEVEL } else { NS_NORMAL_WINDOW_LEVEL };
+			let wnd = self.window();
+			let _: () = unsafe { msg_send!(wnd, setLevel:level) };
+		}
+
+		/// Push the `NSCursor` over the current one, the previous override is popped.
+		fn set_cursor(&self, cursor: CursorKind) {
+			let cls = Class::get("NSCursor").expect("`NSCursor` is not registered.");
+			let previous = super::set_cursor_override(self.hwnd, cursor).unwrap_or(CursorKind::Default);
+			unsafe {
+				match previous {
+					CursorKind::Default => {},
+					CursorKind::Hidden => { let _: () = msg_send!(cls, unhide); },
+					_ => { let _: () = msg_send!(cls, pop); },
+				}
+				let object: *mut Object = match cursor {
+					CursorKind::Default => return,
+					CursorKind::Hidden => {
+						let _: () = msg_send!(cls, hide);
+						return;
+					},
+					// there is no public busy cursor, the system shows one for the unresponsive apps
+					CursorKind::Arrow | CursorKind::Wait => msg_send!(cls, arrowCursor),
+					CursorKind::Hand => msg_send!(cls, pointingHandCursor),
+					CursorKind::Text => msg_send!(cls, IBeamCursor),
+					CursorKind::Crosshair => msg_send!(cls, crosshairCursor),
+					CursorKind::Move => msg_send!(cls, openHandCursor),
+					CursorKind::NotAllowed => msg_send!(cls, operationNotAllowedCursor),
+				};
+				let _: () = msg_send!(object, push);
+			}
+		}
+
+		/// Lock the cursor while the window is the key one, released on `windowDidResignKey:`
+		/// and locked again on `windowDidBecomeKey:`, see `clip_cursor`.
+		fn set_cursor_clip(&self, enable: bool) {
+			if super::set_cursor_clip_enabled(self.hwnd, enable) && self.is_focused() {
+				self.clip_cursor(enable);
+			}
+		}
+
+		/// `mouseLocation` is in the screen coordinates with the bottom-left origin, so it is flipped for the unflipped views.
+		fn cursor_position(&self) -> POINT {
+			let cls = Class::get("NSEvent").expect("`NSEvent` is not registered.");
+			let (view, wnd) = (self.view(), self.window());
+			unsafe {
+				let mouse: NSPoint = msg_send!(cls, mouseLocation);
+				let screen = NSRect { origin: mouse, size: NSSize { width: 0.0, height: 0.0 } };
+				let local: NSRect = msg_send!(wnd, convertRectFromScreen:screen);
+				let nil: *mut Object = ::std::ptr::null_mut();
+				let point: NSPoint = msg_send!(view, convertPoint:local.origin fromView:nil);
+				let flipped: ::objc::runtime::BOOL = msg_send!(view, isFlipped);
+				let y = if flipped != NO {
+					point.y
+				} else {
+					let bounds: NSRect = msg_send!(view, bounds);
+					bounds.size.height - point.y
+				};
+				return POINT { x: point.x.floor() as LONG, y: y.floor() as LONG };
+			}
+		}
+
+		/// `pressedMouseButtons` has the left button in bit 0, the right one in bit 1 and the others after them.
+		fn mouse_buttons(&self) -> MouseButtons {
+			let cls = Class::get("NSEvent").expect("`NSEvent` is not registered.");
+			let mask: usize = unsafe { msg_send!(cls, pressedMouseButtons) };
+			let mut buttons = MouseButtons::empty();
+			buttons.set(MouseButtons::LEFT, mask & (1 << 0) != 0);
+			buttons.set(MouseButtons::RIGHT, mask & (1 << 1) != 0);
+			buttons.set(MouseButtons::MIDDLE, mask & (1 << 2) != 0);
+			buttons.set(MouseButtons::X1, mask & (1 << 3) != 0);
+			buttons.set(MouseButtons::X2, mask & (1 << 4) != 0);
+			return buttons;
+		}
+
+		/// Install the local key-down monitor of the app once, it runs before the window gets the event.
+		fn register_accelerator<F: Fn() -> bool + 'static>(&mut self, accelerator: Accelerator, callback: F) {
+			const BLOCK_IS_GLOBAL: INT = 1 << 28;
+			const NS_EVENT_MASK_KEY_DOWN: u64 = 1 << 10;
+			static MONITOR: Once = Once::new();
+
+			extern "C" fn on_key_down(_block: *mut EventHandlerBlock, event: *mut Object) -> *mut Object {
+				let accelerator = match OsWindow::event_accelerator(event) {
+					Some(accelerator) => accelerator,
+					None => return event,
+				};
+				let wnd: *mut Object = unsafe { msg_send!(event, window) };
+				for hwnd in super::accelerator_windows() {
+					let owner: *mut Object = unsafe { msg_send!(hwnd as *mut Object, window) };
+					if owner == wnd && super::accelerator_pressed(hwnd, accelerator) {
+						// swallow it
+						return ::std::ptr::null_mut();
+					}
+				}
+				return event;
+			}
+
+			super::set_accelerator(self.hwnd, accelerator, Rc::new(callback));
+			MONITOR.call_once(|| {
+				let descriptor = Box::new(BlockDescriptor { reserved: 0, size: ::std::mem::size_of::<EventHandlerBlock>() });
+				let block = Box::new(EventHandlerBlock {
+					isa: unsafe { &_NSConcreteGlobalBlock },
+					flags: BLOCK_IS_GLOBAL,
+					reserved: 0,
+					invoke: on_key_down,
+					descriptor: Box::into_raw(descriptor),
+				});
+				// both live as long as the app does
+				let block = Box::into_raw(block);
+				let cls = Class::get("NSEvent").expect("`NSEvent` is not registered.");
+				unsafe {
+					let monitor: *mut Object = msg_send!(cls, addLocalMonitorForEventsMatchingMask:NS_EVENT_MASK_KEY_DOWN handler:block);
+					let _: *mut Object = msg_send!(monitor, retain);
+				}
+			});
+		}
+
+		/// Set the whole window opacity.
+		fn set_opacity(&self, alpha: f32) {
+			let alpha = alpha.clamp(0.0, 1.0) as f64;
+			let wnd = self.window();
+			let _: () = unsafe { msg_send!(wnd, setAlphaValue:alpha) };
+		}
+
+		/// Get the backing scale factor of the screen with the window.
+		fn get_dpi_scale(&self) -> f32 {
+			let wnd = self.window();
+			let scale: f64 = unsafe { msg_send!(wnd, backingScaleFactor) };
+			return scale as f32;
+		}
+
+		/// The zoom is the CSS `zoom` of the document root, see `apply_zoom`.
+		fn set_zoom(&self, factor: f32) {
+			super::set_zoom_factor(self.hwnd, factor);
+			super::apply_zoom(self.hwnd);
+		}
+
+		fn get_zoom(&self) -> f32 {
+			super::zoom_factor(self.hwnd)
+		}
+
+		/// Whether the window is on screen, miniaturized windows are not.
+		fn is_visible(&self) -> bool {
+			let wnd = self.window();
+			let visible: ::objc::runtime::BOOL = unsafe { msg_send!(wnd, isVisible) };
+			return visible != NO;
+		}
+
+		/// Whether the window is the key one, i.e. it gets the keyboard input.
+		fn is_focused(&self) -> bool {
+			let wnd = self.window();
+			let key: ::objc::runtime::BOOL = unsafe { msg_send!(wnd, isKeyWindow) };
+			return key != NO;
+		}
+
+		/// Put the text on the general pasteboard.
+		fn clipboard_set_text(&self, text: &str) {
+			let s = NSString::from_str(text);
+			unsafe {
+				let cls = Class::get("NSPasteboard").expect("`NSPasteboard` is not registered.");
+				let pasteboard: *mut Object = msg_send!(cls, generalPasteboard);
+				let _: isize = msg_send!(pasteboard, clearContents);
+				let _: ::objc::runtime::BOOL = msg_send!(pasteboard, setString:s forType:NSPasteboardTypeString);
+			}
+		}
+
+		/// Get the general pasteboard text.
+		fn clipboard_get_text(&self) -> Option<String> {
+			let text: *const NSString = unsafe {
+				let cls = Class::get("NSPasteboard").expect("`NSPasteboard` is not registered.");
+				let pasteboard: *mut Object = msg_send!(cls, generalPasteboard);
+				msg_send!(pasteboard, stringForType:NSPasteboardTypeString)
+			};
+			if text.is_null() {
+				return None;
+			}
+			let text = unsafe { &*text };
+			return Some(text.as_str().to_owned());
+		}
+
+		/// Time since the last keyboard or mouse event of the whole user session.
+		fn last_input_time(&self) -> Duration {
+			const COMBINED_SESSION_STATE: INT = 0;
+			const ANY_INPUT_EVENT_TYPE: UINT = !0;
+			let seconds = unsafe { CGEventSourceSecondsSinceLastEventType(COMBINED_SESSION_STATE, ANY_INPUT_EVENT_TYPE) };
+			return Duration::from_millis((seconds.max(0.0) * 1000.0) as u64);
+		}
+
+		/// Get the current window state.
+		fn get_state(&self) -> WindowState {
+			let wnd = self.window();
+			unsafe {
+				let mask: usize = msg_send!(wnd, styleMask);
+				let minimized: ::objc::runtime::BOOL = msg_send!(wnd, isMiniaturized);
+				let zoomed: ::objc::runtime::BOOL = msg_send!(wnd, isZoomed);
+				let visible: ::objc::runtime::BOOL = msg_send!(wnd, isVisible);
+				return if (mask & NS_FULLSCREEN_WINDOW_MASK) != 0 {
+					WindowState::Fullscreen
+				} else if minimized != NO {
+					// miniaturized windows are not visible
+					WindowState::Minimized
+				} else if visible == NO {
+					WindowState::Hidden
+				} else if zoomed != NO {
+					WindowState::Maximized
+				} else {
+					WindowState::Normal
+				};
+			}
+		}
+
+		/// Set the application icon (shown in the Dock) from PNG data.
+		fn set_icon(&mut self, image: &[u8]) {
+			// macOS windows do not have their own icons
+			let app = OsWindow::get_app();
+			unsafe {
+				let mut icon: *mut Object = ::std::ptr::null_mut();
+				if !image.is_empty() {
+					let data_cls = Class::get("NSData").expect("`NSData` is not registered.");
+					let image_cls = Class::get("NSImage").expect("`NSImage` is not registered.");
+					let data: *mut Object = msg_send!(data_cls, dataWithBytes:image.as_ptr() length:image.len());
+					icon = msg_send!(image_cls, alloc);
+					icon = msg_send!(icon, initWithData:data);
+				}
+				let _: () = msg_send!(app, setApplicationIconImage:icon);
+				if !icon.is_null() {
+					let _: () = msg_send!(icon, release);
+				}
+			}
+		}
+
+		/// Convert a point from the window client area to screen coordinates.
+		///
+		/// Sciter coordinates are in physical pixels while Cocoa ones are in points,
+		/// the result is in points relative to the top-left corner of the primary screen.
+		fn client_to_screen(&self, pt: POINT) -> POINT {
+			let view = self.view();
+			let wnd = self.window();
+			unsafe {
+				let scale: f64 = msg_send!(wnd, backingScaleFactor);
+				let local = NSRect {
+					origin: NSPoint { x: pt.x as f64 / scale, y: pt.y as f64 / scale },
+					size: NSSize::default(),
+				};
+				let nil: *mut Object = ::std::ptr::null_mut();
+				let in_window: NSRect = msg_send!(view, convertRect:local toView:nil);
+				let on_screen: NSRect = msg_send!(wnd, convertRectToScreen:in_window);
+
+				// Cocoa screen coordinates go from the bottom-left corner of the primary screen.
+				return POINT {
+					x: on_screen.origin.x as LONG,
+					y: (OsWindow::primary_screen_height() - on_screen.origin.y) as LONG,
+				};
+			}
+		}
+
+		/// Make the window background transparent before the document is painted.
+		fn set_transparent_background(&self) {
+			let wnd = self.window();
+			let cls = Class::get("NSColor").expect("`NSColor` is not registered.");
+			unsafe {
+				let clear: *mut Object = msg_send!(cls, clearColor);
+				let _: () = msg_send!(wnd, setOpaque:NO);
+				let _: () = msg_send!(wnd, setBackgroundColor:clear);
+			}
+		}
+
+		/// Run the main app message loop until window been closed.
+		fn run_app(&self) {
+			let app = OsWindow::get_app();
+			let _: () = unsafe { msg_send!(app, finishLaunching) };
+			let _: () = unsafe { msg_send!(app, run) };
+		}
+
+		/// Run the main app message loop with a run loop observer calling `idle` before the loop sleeps,
+		/// a timer wakes the loop up every `IDLE_INTERVAL`.
+		fn run_app_with<F: FnMut() + 'static>(&self, mut idle: F) {
+			const BEFORE_WAITING: usize = 1 << 5;
+
+			extern "C" fn on_before_waiting(_observer: LPVOID, _activity: usize, info: LPVOID) {
+				let idle = unsafe { &mut *(info as *mut &mut dyn FnMut()) };
+				idle();
+			}
+
+			extern "C" fn on_timer(_timer: LPVOID, _info: LPVOID) {
+				// nothing to do, the observer runs once the loop goes waiting again
+			}
+
+			let mut idle: &mut dyn FnMut() = &mut idle;
+			let mut context = CFRunLoopContext {
+				version: 0,
+				info: &mut idle as *mut &mut dyn FnMut() as LPVOID,
+				retain: ::std::ptr::null_mut(),
+				release: ::std::ptr::null_mut(),
+				copyDescription: ::std::ptr::null_mut(),
+			};
+			unsafe {
+				let null = ::std::ptr::null_mut();
+				let run_loop = CFRunLoopGetMain();
+				let observer = CFRunLoopObserverCreate(null, BEFORE_WAITING, true as u8, 0, on_before_waiting, &mut context);
+				CFRunLoopAddObserver(run_loop, observer, kCFRunLoopCommonModes);
+				let interval = super::IDLE_INTERVAL as f64 / 1000.0;
+				let timer = CFRunLoopTimerCreate(null, CFAbsoluteTimeGetCurrent() + interval, interval, 0, 0, on_timer, ::std::ptr::null_mut());
+				CFRunLoopAddTimer(run_loop, timer, kCFRunLoopCommonModes);
+
+				self.run_app();
+
+				CFRunLoopTimerInvalidate(timer);
+				CFRunLoopObserverInvalidate(observer);
+				CFRelease(timer);
+				CFRelease(observer);
+			}
+		}
+
+		/// Run `runModalForWindow:`, which blocks the other windows until the delegate stops it on `windowWillClose:`.
+		fn run_modal(&self, parent: &OsWindow) {
+			self.install_delegate();
+			let app = OsWindow::get_app();
+			let wnd = self.window();
+			unsafe {
+				let _: isize = msg_send!(app, runModalForWindow:wnd);
+				let owner = parent.ns_window();
+				if !owner.is_null() {
+					let nil: *mut Object = ::std::ptr::null_mut();
+					let _: () = msg_send!(owner, makeKeyAndOrderFront:nil);
+				}
+			}
+		}
+
+		/// Process the pending events without waiting, `false` means the app quit was requested.
+		fn pump_events(&self) -> bool {
+			const NS_EVENT_MASK_ANY: usize = !0;
+			static LAUNCHED: Once = Once::new();
+
+			let app = OsWindow::get_app();
+			LAUNCHED.call_once(|| {
+				let _: () = unsafe { msg_send!(app, finishLaunching) };
+			});
+			unsafe {
+				let nil: *mut Object = ::std::ptr::null_mut();
+				loop {
+					let event: *mut Object = msg_send!(app, nextEventMatchingMask:NS_EVENT_MASK_ANY untilDate:nil inMode:NSDefaultRunLoopMode dequeue:YES);
+					if event.is_null() {
+						break;
+					}
+					let _: () = msg_send!(app, sendEvent:event);
+				}
+				let _: () = msg_send!(app, updateWindows);
+			}
+			return !QUIT_POSTED.swap(false, Ordering::SeqCst);
+		}
+
+		/// Process the events until there are none for a while.
+		fn run_until_idle(&self, settle: Duration, timeout: Duration) -> bool {
+			const NS_EVENT_MASK_ANY: usize = !0;
+			let app = OsWindow::get_app();
+			let pending = || unsafe {
+				let nil: *mut Object = ::std::ptr::null_mut();
+				let event: *mut Object = msg_send!(app, nextEventMatchingMask:NS_EVENT_MASK_ANY untilDate:nil inMode:NSDefaultRunLoopMode dequeue:NO);
+				!event.is_null()
+			};
+			return super::pump_until_idle(settle, timeout, || self.pump_events(), pending);
+		}
+
+		/// Post app quit message.
+		fn quit_app(&self) {
+			QUIT_POSTED.store(true, Ordering::SeqCst);
+			let app = OsWindow::get_app();
+			let running: ::objc::runtime::BOOL = unsafe { msg_send!(app, isRunning) };
+			if running != NO {
+				let _: () = unsafe { msg_send!(app, terminate:app) };
+			}
+		}
+
+		/// Quit the app from any thread: `terminate:` is dispatched to the main queue.
+		fn post_quit(&self) {
+			extern "C" fn on_main(_context: LPVOID) {
+				OsWindow::new().quit_app();
+			}
+
+			unsafe {
+				let main_queue = &_dispatch_main_q as *const u8 as LPVOID;
+				dispatch_async_f(main_queue, ::std::ptr::null_mut(), on_main);
+			}
+		}
+
+		/// Run the closure on the main queue, the window may be closed already.
+		fn post_task(&self, task: UiTask) {
+			extern "C" fn on_main(context: LPVOID) {
+				let task = unsafe { Box::from_raw(context as *mut UiTask) };
+				task();
+			}
+
+			unsafe {
+				let main_queue = &_dispatch_main_q as *const u8 as LPVOID;
+				let context = Box::into_raw(Box::new(task));
+				dispatch_async_f(main_queue, context as LPVOID, on_main);
+			}
+		}
+	}
+
+	#[cfg(feature = "raw-window-handle")]
+	unsafe impl ::raw_window_handle::HasRawWindowHandle for OsWindow {
+		fn raw_window_handle(&self) -> ::raw_window_handle::RawWindowHandle {
+			let mut handle = ::raw_window_handle::AppKitHandle::empty();
+			if !self.hwnd.is_null() {
+				handle.ns_view = self.view() as LPVOID;
+				handle.ns_window = self.window() as LPVOID;
+			}
+			::raw_window_handle::RawWindowHandle::AppKit(handle)
+		}
+	}
+
+	#[cfg(test)]
+	mod tests {
+		use super::OsWindow;
+
+		// AppKit windows belong to the main thread, run it with `--ignored --test-threads=1`
+		#[test]
+		#[ignore]
+		fn shadow_toggles() {
+			use objc::runtime::{Class, Object, NO, YES, BOOL};
+			use super::super::BaseWindow;
+			use super::NSRect;
+
+			// a plain `NSWindow` is enough for the shadow
+			const NS_BORDERLESS_WINDOW_MASK: usize = 0;
+			const NS_BACKING_STORE_BUFFERED: usize = 2;
+			let cls = Class::get("NSWindow").unwrap();
+			let frame = NSRect::default();
+			let view: *mut Object = unsafe {
+				let wnd: *mut Object = msg_send!(cls, alloc);
+				let wnd: *mut Object = msg_send!(wnd, initWithContentRect:frame styleMask:NS_BORDERLESS_WINDOW_MASK backing:NS_BACKING_STORE_BUFFERED defer:YES);
+				msg_send!(wnd, contentView)
+			};
+			let wnd = OsWindow::from(view as _);
+			let ns_window = wnd.ns_window();
+
+			wnd.set_shadow(false);
+			let shadow: BOOL = unsafe { msg_send!(ns_window, hasShadow) };
+			assert_eq!(shadow, NO);
+
+			wnd.set_shadow(true);
+			let shadow: BOOL = unsafe { msg_send!(ns_window, hasShadow) };
+			assert_eq!(shadow, YES);
+
+			let _: () = unsafe { msg_send!(ns_window, close) };
+		}
+
+		#[test]
+		#[ignore]
+		fn detached_view_has_no_window() {
+			use objc::runtime::{Class, Object};
+			use super::super::BaseWindow;
+			use super::NSRect;
+
+			let cls = Class::get("NSView").unwrap();
+			let frame = NSRect::default();
+			let view: *mut Object = unsafe {
+				let view: *mut Object = msg_send!(cls, alloc);
+				msg_send!(view, initWithFrame:frame)
+			};
+			let mut wnd = OsWindow::from(view as _);
+			assert!(wnd.ns_window().is_null());
+
+			// no-ops until the view is added to a window
+			wnd.set_title("detached");
+			assert_eq!(wnd.get_title(), "");
+			wnd.expand(false);
+			wnd.collapse(true);
+			wnd.dismiss();
+
+			let _: () = unsafe { msg_send!(view, release) };
+		}
+	}
+}
+
+/// Shared message loop for several windows, which runs until the last of them is closed.
+///
+/// The windows should be created without the `SW_MAIN` flag,
+/// since closing a main window terminates the app anyway.
+///
+/// ```rust,no_run
+/// let app = sciter::App::new();
+/// let settings = sciter::WindowBuilder::with_flags(sciter::window::Flags::SW_TITLEBAR | sciter::window::Flags::SW_CONTROLS).create();
+/// let editor = sciter::WindowBuilder::with_flags(sciter::window::Flags::SW_TITLEBAR | sciter::window::Flags::SW_CONTROLS).create();
+/// app.register(&settings);
+/// app.register(&editor);
+/// app.run();
+/// ```
+pub struct App {
+	// the windows are tracked per thread
+	_not_send: ::std::marker::PhantomData<*mut ()>,
+}
+
+thread_local! {
+	/// Live windows registered in `App`.
+	static APP_WINDOWS: RefCell<HashSet<HWINDOW>> = Default::default();
+
+	/// Whether `App` stops the loop once the last registered window is closed.
+	static APP_QUITS_ON_LAST_CLOSE: Cell<bool> = const { Cell::new(true) };
+}
+
+impl App {
+	/// Initialize the app, must be called before creating the windows.
+	///
+	/// Panics if the app can not be initialized, see [`try_new`](#method.try_new).
+	#[allow(clippy::new_without_default)]
+	pub fn new() -> App {
+		match App::try_new() {
+			Ok(app) => app,
+			Err(error) => panic!("Failed to initialize the app: {}", error),
+		}
+	}
+
+	/// Initialize the app like [`new`](#method.new), but return an error instead of panicking.
+	///
+	/// On Windows it fails with `WindowError::OleInitFailed` if the thread has joined a multithreaded COM apartment
+	/// (`RPC_E_CHANGED_MODE`), since drag and drop and the clipboard need OLE.
+	pub fn try_new() -> Result<App, WindowError> {
+		OsWindow::init_app()?;
+		return Ok(App { _not_send: Default::default() });
+	}
+
+	/// Keep the message loop running while this window is alive.
+	pub fn register(&self, window: &Window) {
+		let hwnd = window.get_hwnd();
+		let added = APP_WINDOWS.with(|windows| windows.borrow_mut().insert(hwnd));
+		if added {
+			App::track_destroy(hwnd);
+		}
+	}
+
+	/// Choose whether closing the last registered window quits the app, which is the default.
+	///
+	/// When disabled, the loop keeps running without windows, e.g. to reopen one from a tray icon or the menu bar
+	/// as the macOS apps do, until `quit_app` is called.
+	pub fn set_quit_on_last_close(&mut self, enable: bool) {
+		APP_QUITS_ON_LAST_CLOSE.with(|quits| quits.set(enable));
+		#[cfg(target_os = "macos")]
+		OsWindow::install_app_delegate();
+	}
+
+	/// Whether closing the last registered window quits the app.
+	fn quits_on_last_close() -> bool {
+		APP_QUITS_ON_LAST_CLOSE.with(|quits| quits.get())
+	}
+
+	/// Run the message loop until all registered windows are closed,
+	/// or until `quit_app` is called if `set_quit_on_last_close(false)` is set.
+	///
+	/// On macOS `quit_app` terminates the process via `terminate:` without returning from `run`,
+	/// only closing the last registered window returns from it.
+	pub fn run(&self) {
+		if APP_WINDOWS.with(|windows| !windows.borrow().is_empty()) || !App::quits_on_last_close() {
+			OsWindow::new().run_app();
+		}
+	}
+
+	/// Stop the message loop after the last registered window is destroyed.
+	fn window_destroyed(hwnd: HWINDOW) {
+		let last = APP_WINDOWS.with(|windows| {
+			let mut windows = windows.borrow_mut();
+			windows.remove(&hwnd) && windows.is_empty()
+		});
+		if last && App::quits_on_last_close() {
+			#[cfg(target_os = "macos")]
+			OsWindow::stop_app();
+			#[cfg(not(target_os = "macos"))]
+			OsWindow::new().quit_app();
+		}
+	}
+
+	/// Windows created by `OsWindow` report `WM_DESTROY` by themselves.
+	#[cfg(windows)]
+	fn track_destroy(_hwnd: HWINDOW) {
+	}
+
+	/// Sciter detaches window event handlers when the window is destroyed.
+	#[cfg(not(windows))]
+	fn track_destroy(hwnd: HWINDOW) {
+		use capi::scbehavior::{EVENT_GROUPS, INITIALIZATION_EVENTS};
+		use capi::scdom::HELEMENT;
+		use _API;
+
+		extern "system" fn on_detach(tag: LPVOID, _he: HELEMENT, evtg: UINT, params: LPVOID) -> BOOL {
+			if evtg == EVENT_GROUPS::HANDLE_INITIALIZATION.bits() && unsafe { *(params as *const INITIALIZATION_EVENTS) } == INITIALIZATION_EVENTS::BEHAVIOR_DETACH {
+				App::window_destroyed(tag as HWINDOW);
 			}
+			return false as BOOL;
 		}
 
-		/// Close window.
-		fn dismiss(&self) {
-			let wnd = self.window();
-			let _: () = unsafe { msg_send!(wnd, close) };
+		(_API.SciterWindowAttachEventHandler)(hwnd, on_detach, hwnd as LPVOID, EVENT_GROUPS::HANDLE_INITIALIZATION.bits() as UINT);
+	}
+}
+
+/// An icon of the window in the notification area (the menu bar on macOS), e.g. to minimize the window to the tray.
+///
+/// It stays until it is removed or dropped. On Linux it is a `GtkStatusIcon`,
+/// which the desktops without the XEmbed tray (e.g. the plain GNOME Shell) do not show;
+/// if GTK is built without the deprecated `GtkStatusIcon`, the icon does nothing.
+///
+/// ```rust,no_run
+/// use std::rc::Rc;
+///
+/// let frame = Rc::new(sciter::WindowBuilder::main_window().create());
+/// let icon = std::fs::read("icon.png").unwrap();
+/// let mut tray = sciter::TrayIcon::add(&frame, &icon, "Restore the window");
+///
+/// // "minimize to tray": hide the window and bring it back on click
+/// let window = frame.clone();
+/// tray.on_click(move || window.activate());
+/// frame.collapse(true);
+///
+/// frame.run_app_with(|| {});
+/// tray.remove();
+/// ```
+pub struct TrayIcon {
+	id: u32,
+	icon: OsTrayIcon,
+}
+
+thread_local! {
+	/// `TrayIcon::on_click` callbacks by the icon ids.
+	static TRAY_HANDLERS: RefCell<HashMap<u32, Rc<dyn Fn()>>> = Default::default();
+
+	/// The id of the next `TrayIcon`.
+	static NEXT_TRAY_ID: Cell<u32> = const { Cell::new(1) };
+}
+
+impl TrayIcon {
+	/// Add the icon of the window with the `tooltip`, the `icon` is a PNG image (or ICO on Windows).
+	pub fn add(window: &Window, icon: &[u8], tooltip: &str) -> TrayIcon {
+		let id = NEXT_TRAY_ID.with(|next| next.replace(next.get() + 1));
+		TrayIcon { id: id, icon: OsTrayIcon::add(window.get_hwnd(), id, icon, tooltip) }
+	}
+
+	/// Call the callback when the icon is clicked (with the left button), e.g. to restore the window.
+	///
+	/// A new callback replaces the previous one.
+	pub fn on_click<F: Fn() + 'static>(&mut self, callback: F) {
+		TRAY_HANDLERS.with(|handlers| handlers.borrow_mut().insert(self.id, Rc::new(callback)));
+	}
+
+	/// Remove the icon, the same as dropping it.
+	pub fn remove(self) {
+	}
+
+	/// Call the `on_click` callback of the icon.
+	#[cfg_attr(all(windows, feature = "windowless"), allow(dead_code))]
+	fn clicked(id: u32) {
+		// the callback may remove the icon
+		let callback = TRAY_HANDLERS.with(|handlers| handlers.borrow().get(&id).cloned());
+		if let Some(callback) = callback {
+			callback();
 		}
+	}
+}
 
-		/// Set native window title.
-		fn set_title(&mut self, title: &str) {
-			let s = NSString::from_str(title);
-			let wnd = self.window();
-			let _: () = unsafe { msg_send!(wnd, setTitle:s) };
+impl Drop for TrayIcon {
+	fn drop(&mut self) {
+		self.icon.remove();
+		TRAY_HANDLERS.with(|handlers| handlers.borrow_mut().remove(&self.id));
+	}
+}
+
+type DropCallback = Rc<dyn Fn(Vec<PathBuf>)>;
+type GeometryCallback = Rc<dyn Fn(i32, i32)>;
+type DpiCallback = Rc<dyn Fn(f32)>;
+type MonitorCallback = Rc<dyn Fn(MonitorInfo, Option<f32>)>;
+type ThemeCallback = Rc<dyn Fn(Theme)>;
+
+/// `set_hit_test_callback` callback, gets the point relative to the window.
+type HitTestCallback = Rc<dyn Fn(i32, i32) -> HitTestResult>;
+
+/// `register_accelerator` callback, returns `true` if it has handled the key.
+type AcceleratorCallback = Rc<dyn Fn() -> bool>;
+
+/// `on_resize` and `on_move` callbacks of the window and the last geometry they know.
+struct GeometryHandlers {
+	on_resize: Option<GeometryCallback>,
+	on_move: Option<GeometryCallback>,
+	last: RECT,
+}
+
+/// `on_monitor_changed` callback of the window and the last monitor rect and refresh rate it knows.
+struct MonitorHandler {
+	callback: MonitorCallback,
+	last: (RECT, Option<f32>),
+}
+
+thread_local! {
+	/// Windows created by `OsWindow` and not destroyed yet.
+	static CREATED_WINDOWS: RefCell<HashSet<HWINDOW>> = Default::default();
+
+	/// `on_close` callbacks of the windows.
+	static CLOSE_HANDLERS: RefCell<HashMap<HWINDOW, Rc<dyn Fn() -> bool>>> = Default::default();
+
+	/// `on_file_drop` callbacks of the windows.
+	static DROP_HANDLERS: RefCell<HashMap<HWINDOW, DropCallback>> = Default::default();
+
+	/// Windows which opted out of `set_restore_focus`.
+	static FOCUS_NOT_RESTORED: RefCell<HashSet<HWINDOW>> = Default::default();
+
+	/// Focused elements of the inactive windows.
+	static SAVED_FOCUS: RefCell<HashMap<HWINDOW, Element>> = Default::default();
+
+	/// `on_resize` and `on_move` callbacks of the windows.
+	static GEOMETRY_HANDLERS: RefCell<HashMap<HWINDOW, GeometryHandlers>> = Default::default();
+
+	/// `on_dpi_changed` callbacks of the windows and the last scale they know.
+	static DPI_HANDLERS: RefCell<HashMap<HWINDOW, (DpiCallback, f32)>> = Default::default();
+
+	/// `on_monitor_changed` callbacks of the windows.
+	static MONITOR_HANDLERS: RefCell<HashMap<HWINDOW, MonitorHandler>> = Default::default();
+
+	/// `on_theme_changed` callbacks of the windows and the last theme they know.
+	static THEME_HANDLERS: RefCell<HashMap<HWINDOW, (ThemeCallback, Theme)>> = Default::default();
+
+	/// `set_hit_test_callback` callbacks of the windows.
+	static HIT_TEST_HANDLERS: RefCell<HashMap<HWINDOW, HitTestCallback>> = Default::default();
+
+	/// `set_cursor` overrides of the windows.
+	static CURSORS: RefCell<HashMap<HWINDOW, CursorKind>> = Default::default();
+
+	/// `set_zoom` factors of the windows.
+	static ZOOMS: RefCell<HashMap<HWINDOW, f32>> = Default::default();
+
+	/// Windows following the minimal size of their documents, see `track_content_min_size`.
+	static CONTENT_MIN_SIZES: RefCell<HashSet<HWINDOW>> = Default::default();
+
+	/// Windows with the `set_cursor_clip` enabled.
+	static CURSOR_CLIPS: RefCell<HashSet<HWINDOW>> = Default::default();
+
+	/// `register_accelerator` callbacks of the windows.
+	static ACCELERATORS: RefCell<HashMap<HWINDOW, HashMap<Accelerator, AcceleratorCallback>>> = Default::default();
+
+	/// When the windows have got the last keyboard or mouse input.
+	static LAST_INPUT: RefCell<HashMap<HWINDOW, Instant>> = Default::default();
+}
+
+/// Set the `on_close` callback of the window, returns `true` if it is the first one.
+fn set_close_handler(hwnd: HWINDOW, callback: Rc<dyn Fn() -> bool>) -> bool {
+	CLOSE_HANDLERS.with(|handlers| handlers.borrow_mut().insert(hwnd, callback).is_none())
+}
+
+/// Ask the `on_close` callback of the window whether it can be closed.
+fn close_allowed(hwnd: HWINDOW) -> bool {
+	// the callback may close other windows
+	let callback = CLOSE_HANDLERS.with(|handlers| handlers.borrow().get(&hwnd).cloned());
+	return match callback {
+		Some(callback) => callback(),
+		None => true,
+	};
+}
+
+/// Drop the `on_close` callback of the destroyed window.
+fn forget_close_handler(hwnd: HWINDOW) {
+	CLOSE_HANDLERS.with(|handlers| handlers.borrow_mut().remove(&hwnd));
+}
+
+/// Set the `on_file_drop` callback of the window, returns `true` if it is the first one.
+fn set_drop_handler(hwnd: HWINDOW, callback: DropCallback) -> bool {
+	DROP_HANDLERS.with(|handlers| handlers.borrow_mut().insert(hwnd, callback).is_none())
+}
+
+/// Whether the window accepts the dropped files.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn accepts_drop(hwnd: HWINDOW) -> bool {
+	DROP_HANDLERS.with(|handlers| handlers.borrow().contains_key(&hwnd))
+}
+
+/// Pass the dropped files to the `on_file_drop` callback, returns `false` if there is none.
+fn files_dropped(hwnd: HWINDOW, files: Vec<PathBuf>) -> bool {
+	let callback = DROP_HANDLERS.with(|handlers| handlers.borrow().get(&hwnd).cloned());
+	if let Some(callback) = callback {
+		callback(files);
+		return true;
+	}
+	return false;
+}
+
+/// Drop the `on_file_drop` callback of the destroyed window.
+fn forget_drop_handler(hwnd: HWINDOW) {
+	DROP_HANDLERS.with(|handlers| handlers.borrow_mut().remove(&hwnd));
+}
+
+/// Enable or disable restoring the focused element when the window is activated again.
+fn set_restore_focus(hwnd: HWINDOW, enable: bool) {
+	FOCUS_NOT_RESTORED.with(|windows| if enable {
+		windows.borrow_mut().remove(&hwnd);
+	} else {
+		windows.borrow_mut().insert(hwnd);
+	});
+	if !enable {
+		SAVED_FOCUS.with(|saved| saved.borrow_mut().remove(&hwnd));
+	}
+}
+
+/// Remember the focused element of the window being deactivated.
+#[cfg_attr(all(windows, feature = "windowless"), allow(dead_code))]
+fn save_focus(hwnd: HWINDOW) {
+	if FOCUS_NOT_RESTORED.with(|windows| windows.borrow().contains(&hwnd)) {
+		return;
+	}
+	let focus = Element::from_focus(hwnd).ok();
+	SAVED_FOCUS.with(|saved| match focus {
+		Some(focus) => saved.borrow_mut().insert(hwnd, focus),
+		None => saved.borrow_mut().remove(&hwnd),
+	});
+}
+
+/// Focus the remembered element of the activated window again.
+#[cfg_attr(all(windows, feature = "windowless"), allow(dead_code))]
+fn restore_focus(hwnd: HWINDOW) {
+	let saved = SAVED_FOCUS.with(|saved| saved.borrow_mut().remove(&hwnd));
+	if let Some(mut element) = saved {
+		// it may be removed from the document in the meantime
+		if element.get_hwnd(true) != hwnd || Element::from_focus(hwnd).ok().as_ref() == Some(&element) {
+			return;
 		}
+		element.set_state(ELEMENT_STATE_BITS::STATE_FOCUS, None, true).ok();
+	}
+}
 
-		/// Get native window title.
-		fn get_title(&self) -> String {
-			String::new()
+/// Drop the focus tracking state of the destroyed window.
+fn forget_focus(hwnd: HWINDOW) {
+	FOCUS_NOT_RESTORED.with(|windows| windows.borrow_mut().remove(&hwnd));
+	SAVED_FOCUS.with(|saved| saved.borrow_mut().remove(&hwnd));
+}
+
+/// Set the `on_resize` or `on_move` callback of the window, returns `true` if it is the first of them.
+fn set_geometry_handler<F: FnOnce(&mut GeometryHandlers)>(hwnd: HWINDOW, rc: RECT, set: F) -> bool {
+	GEOMETRY_HANDLERS.with(|handlers| {
+		let mut handlers = handlers.borrow_mut();
+		let first = !handlers.contains_key(&hwnd);
+		set(handlers.entry(hwnd).or_insert(GeometryHandlers { on_resize: None, on_move: None, last: rc }));
+		first
+	})
+}
+
+/// Set the `on_resize` callback of the window at the `rc` geometry.
+fn set_resize_handler(hwnd: HWINDOW, rc: RECT, callback: GeometryCallback) -> bool {
+	set_geometry_handler(hwnd, rc, |handlers| handlers.on_resize = Some(callback))
+}
+
+/// Set the `on_move` callback of the window at the `rc` geometry.
+fn set_move_handler(hwnd: HWINDOW, rc: RECT, callback: GeometryCallback) -> bool {
+	set_geometry_handler(hwnd, rc, |handlers| handlers.on_move = Some(callback))
+}
+
+/// Pass the new window geometry to the `on_resize` and `on_move` callbacks if it has changed.
+#[cfg_attr(all(windows, feature = "windowless"), allow(dead_code))]
+fn window_configured(hwnd: HWINDOW, rc: RECT) {
+	let (resized, moved) = GEOMETRY_HANDLERS.with(|handlers| match handlers.borrow_mut().get_mut(&hwnd) {
+		Some(handlers) => {
+			let last = ::std::mem::replace(&mut handlers.last, rc);
+			let resized = if (rc.width(), rc.height()) != (last.width(), last.height()) { handlers.on_resize.clone() } else { None };
+			let moved = if (rc.left, rc.top) != (last.left, last.top) { handlers.on_move.clone() } else { None };
+			(resized, moved)
+		},
+		None => (None, None),
+	});
+	// the callbacks may change the geometry again
+	if let Some(callback) = resized {
+		callback(rc.width(), rc.height());
+	}
+	if let Some(callback) = moved {
+		callback(rc.left, rc.top);
+	}
+}
+
+/// Drop the `on_resize` and `on_move` callbacks of the destroyed window.
+fn forget_geometry_handlers(hwnd: HWINDOW) {
+	GEOMETRY_HANDLERS.with(|handlers| handlers.borrow_mut().remove(&hwnd));
+}
+
+/// Remember the window created by `OsWindow`, its owner destroys it on drop.
+#[cfg_attr(feature = "windowless", allow(dead_code))]
+fn window_created(hwnd: HWINDOW) {
+	CREATED_WINDOWS.with(|windows| windows.borrow_mut().insert(hwnd));
+}
+
+/// Whether the window created by `OsWindow` is not destroyed yet.
+fn window_alive(hwnd: HWINDOW) -> bool {
+	CREATED_WINDOWS.with(|windows| windows.borrow().contains(&hwnd))
+}
+
+/// Forget the destroyed window.
+#[cfg_attr(feature = "windowless", allow(dead_code))]
+fn forget_window(hwnd: HWINDOW) {
+	CREATED_WINDOWS.with(|windows| windows.borrow_mut().remove(&hwnd));
+}
+
+/// Set the `on_dpi_changed` callback of the window at the `scale`, returns `true` if it is the first one.
+fn set_dpi_handler(hwnd: HWINDOW, scale: f32, callback: DpiCallback) -> bool {
+	DPI_HANDLERS.with(|handlers| handlers.borrow_mut().insert(hwnd, (callback, scale)).is_none())
+}
+
+/// Pass the new scale factor to the `on_dpi_changed` callback if it has changed.
+#[cfg_attr(all(windows, feature = "windowless"), allow(dead_code))]
+fn dpi_changed(hwnd: HWINDOW, scale: f32) {
+	let callback = DPI_HANDLERS.with(|handlers| match handlers.borrow_mut().get_mut(&hwnd) {
+		Some(&mut (ref callback, ref mut last)) if *last != scale => {
+			*last = scale;
+			Some(callback.clone())
+		},
+		_ => None,
+	});
+	if let Some(callback) = callback {
+		callback(scale);
+	}
+}
+
+/// Drop the `on_dpi_changed` callback of the destroyed window.
+fn forget_dpi_handler(hwnd: HWINDOW) {
+	DPI_HANDLERS.with(|handlers| handlers.borrow_mut().remove(&hwnd));
+}
+
+/// Set the `on_monitor_changed` callback of the window at its current monitor, returns `true` if it is the first one.
+fn set_monitor_handler(hwnd: HWINDOW, callback: MonitorCallback) -> bool {
+	let wnd = OsWindow::from(hwnd);
+	let last = (wnd.current_monitor().map_or(RECT::default(), |monitor| monitor.rect), wnd.get_refresh_rate());
+	MONITOR_HANDLERS.with(|handlers| handlers.borrow_mut().insert(hwnd, MonitorHandler { callback: callback, last: last }).is_none())
+}
+
+/// Pass the monitor of the window to the `on_monitor_changed` callback if it or its refresh rate has changed.
+#[cfg_attr(all(windows, feature = "windowless"), allow(dead_code))]
+fn monitor_changed(hwnd: HWINDOW) {
+	if !MONITOR_HANDLERS.with(|handlers| handlers.borrow().contains_key(&hwnd)) {
+		return;
+	}
+	let wnd = OsWindow::from(hwnd);
+	let monitor = match wnd.current_monitor() {
+		Some(monitor) => monitor,
+		None => return,
+	};
+	let current = (monitor.rect, wnd.get_refresh_rate());
+	let callback = MONITOR_HANDLERS.with(|handlers| match handlers.borrow_mut().get_mut(&hwnd) {
+		Some(handler) if handler.last != current => {
+			handler.last = current;
+			Some(handler.callback.clone())
+		},
+		_ => None,
+	});
+	if let Some(callback) = callback {
+		callback(monitor, current.1);
+	}
+}
+
+/// Drop the `on_monitor_changed` callback of the destroyed window.
+fn forget_monitor_handler(hwnd: HWINDOW) {
+	MONITOR_HANDLERS.with(|handlers| handlers.borrow_mut().remove(&hwnd));
+}
+
+/// Set the `on_theme_changed` callback of the window at the `theme`, returns `true` if it is the first one.
+fn set_theme_handler(hwnd: HWINDOW, theme: Theme, callback: ThemeCallback) -> bool {
+	THEME_HANDLERS.with(|handlers| handlers.borrow_mut().insert(hwnd, (callback, theme)).is_none())
+}
+
+/// Pass the new theme to the `on_theme_changed` callback if it has changed.
+#[cfg_attr(all(windows, feature = "windowless"), allow(dead_code))]
+fn theme_changed(hwnd: HWINDOW, theme: Theme) {
+	let callback = THEME_HANDLERS.with(|handlers| match handlers.borrow_mut().get_mut(&hwnd) {
+		Some(&mut (ref callback, ref mut last)) if *last != theme => {
+			*last = theme;
+			Some(callback.clone())
+		},
+		_ => None,
+	});
+	if let Some(callback) = callback {
+		callback(theme);
+	}
+}
+
+/// Drop the `on_theme_changed` callback of the destroyed window.
+fn forget_theme_handler(hwnd: HWINDOW) {
+	THEME_HANDLERS.with(|handlers| handlers.borrow_mut().remove(&hwnd));
+}
+
+/// Override the Sciter cursors of the window, returns the previous override if there is one.
+fn set_cursor_override(hwnd: HWINDOW, cursor: CursorKind) -> Option<CursorKind> {
+	CURSORS.with(|cursors| cursors.borrow_mut().insert(hwnd, cursor))
+}
+
+/// The cursor override of the window, `Default` means the Sciter ones.
+#[cfg_attr(any(target_os = "macos", all(windows, feature = "windowless")), allow(dead_code))]
+fn cursor_override(hwnd: HWINDOW) -> CursorKind {
+	CURSORS.with(|cursors| cursors.borrow().get(&hwnd).cloned().unwrap_or(CursorKind::Default))
+}
+
+/// Enable or disable the cursor clip of the window, returns `true` if it has changed.
+fn set_cursor_clip_enabled(hwnd: HWINDOW, enable: bool) -> bool {
+	CURSOR_CLIPS.with(|clips| if enable { clips.borrow_mut().insert(hwnd) } else { clips.borrow_mut().remove(&hwnd) })
+}
+
+/// Whether the cursor is to be clipped to the window while it has the focus.
+#[cfg_attr(all(windows, feature = "windowless"), allow(dead_code))]
+fn cursor_clip_enabled(hwnd: HWINDOW) -> bool {
+	CURSOR_CLIPS.with(|clips| clips.borrow().contains(&hwnd))
+}
+
+/// Drop the cursor override and clip of the destroyed window.
+fn forget_cursor(hwnd: HWINDOW) {
+	CURSORS.with(|cursors| cursors.borrow_mut().remove(&hwnd));
+	CURSOR_CLIPS.with(|clips| clips.borrow_mut().remove(&hwnd));
+}
+
+/// Store the clamped zoom factor of the window, returns `true` if it is the first one.
+fn set_zoom_factor(hwnd: HWINDOW, factor: f32) -> bool {
+	let factor = if factor.is_finite() { factor.clamp(0.25, 5.0) } else { 1.0 };
+	ZOOMS.with(|zooms| zooms.borrow_mut().insert(hwnd, factor).is_none())
+}
+
+/// The zoom factor of the window, `1.0` if it has not been set.
+fn zoom_factor(hwnd: HWINDOW) -> f32 {
+	ZOOMS.with(|zooms| zooms.borrow().get(&hwnd).cloned().unwrap_or(1.0))
+}
+
+/// Scale the window document by its zoom factor with the CSS `zoom` of the root element.
+///
+/// Sciter applies it on top of the monitor DPI, so the OS scale is left as is.
+fn apply_zoom(hwnd: HWINDOW) {
+	if let Ok(mut root) = Element::from_window(hwnd) {
+		root.set_style_attribute("zoom", &zoom_factor(hwnd).to_string()).ok();
+	}
+}
+
+/// Drop the zoom factor of the destroyed window.
+#[cfg_attr(all(windows, feature = "windowless"), allow(dead_code))]
+fn forget_zoom(hwnd: HWINDOW) {
+	ZOOMS.with(|zooms| zooms.borrow_mut().remove(&hwnd));
+}
+
+/// The minimal size of the window document: its minimal intrinsic width and the height at that width.
+fn content_min_size(hwnd: HWINDOW) -> SIZE {
+	use _API;
+	let width = (_API.SciterGetMinWidth)(hwnd);
+	let height = (_API.SciterGetMinHeight)(hwnd, width);
+	return SIZE { cx: width as i32, cy: height as i32 };
+}
+
+/// Call `sync_min_size_from_content` again when the document is loaded or its content changes, or stop it.
+fn track_content_min_size(hwnd: HWINDOW, track: bool) {
+	use capi::scbehavior::{BEHAVIOR_EVENTS, BEHAVIOR_EVENT_PARAMS, EVENT_GROUPS, INITIALIZATION_EVENTS, PHASE_MASK};
+	use capi::scdom::HELEMENT;
+	use _API;
+
+	extern "system" fn on_event(tag: LPVOID, _he: HELEMENT, evtg: UINT, params: LPVOID) -> BOOL {
+		let hwnd = tag as HWINDOW;
+		if evtg == EVENT_GROUPS::HANDLE_INITIALIZATION.bits() && unsafe { *(params as *const INITIALIZATION_EVENTS) } == INITIALIZATION_EVENTS::BEHAVIOR_DETACH {
+			CONTENT_MIN_SIZES.with(|windows| windows.borrow_mut().remove(&hwnd));
+		} else if evtg == EVENT_GROUPS::HANDLE_BEHAVIOR_EVENT.bits() {
+			// once per event, in the bubbling phase
+			let cmd = unsafe { (*(params as *const BEHAVIOR_EVENT_PARAMS)).cmd };
+			let code = BEHAVIOR_EVENTS::from_bits_truncate(cmd & 0x0_0FFF);
+			let sinking = (cmd & PHASE_MASK::SINKING.bits()) != 0;
+			if !sinking && (code == BEHAVIOR_EVENTS::CONTENT_CHANGED || code == BEHAVIOR_EVENTS::DOCUMENT_COMPLETE) {
+				OsWindow::from(hwnd).sync_min_size_from_content(true);
+			}
 		}
+		return false as BOOL;
+	}
 
-		/// Run the main app message loop until window been closed.
-		fn run_app(&self) {
-			let app = OsWindow::get_app();
-			let _: () = unsafe { msg_send!(app, finishLaunching) };
-			let _: () = unsafe { msg_send!(app, run) };
+	let changed = CONTENT_MIN_SIZES.with(|windows| if track { windows.borrow_mut().insert(hwnd) } else { windows.borrow_mut().remove(&hwnd) });
+	if !changed {
+		return;
+	}
+	if track {
+		let groups = EVENT_GROUPS::HANDLE_INITIALIZATION | EVENT_GROUPS::HANDLE_BEHAVIOR_EVENT;
+		(_API.SciterWindowAttachEventHandler)(hwnd, on_event, hwnd as LPVOID, groups.bits() as UINT);
+	} else {
+		(_API.SciterWindowDetachEventHandler)(hwnd, on_event, hwnd as LPVOID);
+	}
+}
+
+/// Pump the events until none arrive for `settle`, `false` means the app quit or `timeout` elapsed first.
+fn pump_until_idle<P: FnMut() -> bool, E: Fn() -> bool>(settle: Duration, timeout: Duration, mut pump: P, pending: E) -> bool {
+	let started = Instant::now();
+	let mut quiet_since = Instant::now();
+	while started.elapsed() < timeout {
+		// Sciter timers, animations and repaints post their own events, so it is busy while they keep coming
+		if pending() {
+			if !pump() {
+				return false;
+			}
+			quiet_since = Instant::now();
+		} else if quiet_since.elapsed() >= settle {
+			return true;
+		} else {
+			::std::thread::sleep(Duration::from_millis(1));
 		}
+	}
+	return false;
+}
 
-		/// Post app quit message.
-		fn quit_app(&self) {
-			let app = OsWindow::get_app();
-			let _: () = unsafe { msg_send!(app, terminate:app) };
+/// Where to restore the window placement: on its monitor if it is still connected,
+/// otherwise on the one it overlaps most or on the primary one, clamped into the work area.
+fn placement_rect(placement: &WindowPlacement, monitors: &[MonitorInfo]) -> RECT {
+	let ((x, y), (width, height)) = (placement.position, placement.size);
+	let rc = RECT { left: x, top: y, right: x + width, bottom: y + height };
+	let (left, top, right, bottom) = placement.monitor;
+	let saved = RECT { left: left, top: top, right: right, bottom: bottom };
+	let overlap = |monitor: &MonitorInfo| {
+		let area = monitor.work_area;
+		let w = (rc.right.min(area.right) - rc.left.max(area.left)).max(0);
+		let h = (rc.bottom.min(area.bottom) - rc.top.max(area.top)).max(0);
+		w as i64 * h as i64
+	};
+	let monitor = monitors.iter().find(|monitor| monitor.rect == saved)
+		.or_else(|| monitors.iter().filter(|&monitor| overlap(monitor) > 0).max_by_key(|&monitor| overlap(monitor)))
+		.or_else(|| monitors.iter().find(|monitor| monitor.is_primary))
+		.or_else(|| monitors.first());
+	let work = match monitor {
+		Some(monitor) => monitor.work_area,
+		None => return rc,
+	};
+	let (width, height) = (width.min(work.width()), height.min(work.height()));
+	let left = x.min(work.right - width).max(work.left);
+	let top = y.min(work.bottom - height).max(work.top);
+	return RECT { left: left, top: top, right: left + width, bottom: top + height };
+}
+
+/// Center the window of `size` in the work area of the `index` monitor, or of the primary one if there is no such monitor.
+///
+/// The `physical` size is scaled by the monitor DPI, it is clamped to the work area either way.
+fn monitor_centered_rect(monitors: &[MonitorInfo], index: usize, size: (i32, i32), physical: bool) -> RECT {
+	let monitor = monitors.get(index)
+		.or_else(|| monitors.iter().find(|monitor| monitor.is_primary))
+		.or_else(|| monitors.first());
+	let monitor = match monitor {
+		Some(monitor) => monitor,
+		None => return RECT { left: 0, top: 0, right: size.0, bottom: size.1 },
+	};
+	let scale = if physical { monitor.scale } else { 1.0 };
+	let work = monitor.work_area;
+	let width = ((size.0 as f32 * scale).round() as i32).min(work.width());
+	let height = ((size.1 as f32 * scale).round() as i32).min(work.height());
+	let left = work.left + (work.width() - width) / 2;
+	let top = work.top + (work.height() - height) / 2;
+	return RECT { left: left, top: top, right: left + width, bottom: top + height };
+}
+
+/// Apply `update_geometry` in steps: leave the fullscreen (it restores its own geometry), change the geometry, then the state.
+#[cfg_attr(windows, allow(dead_code))]
+fn update_geometry_in_steps<W: BaseWindow, F: FnOnce()>(window: &W, state: Option<WindowState>, change_geometry: F) {
+	if let Some(state) = state {
+		if state != WindowState::Fullscreen && window.get_state() == WindowState::Fullscreen {
+			window.set_fullscreen(false);
 		}
 	}
+	change_geometry();
+	match state {
+		Some(WindowState::Hidden) => window.collapse(true),
+		Some(WindowState::Minimized) => window.collapse(false),
+		Some(WindowState::Normal) => window.expand(false),
+		Some(WindowState::Maximized) => window.expand(true),
+		Some(WindowState::Fullscreen) => window.set_fullscreen(true),
+		None => {},
+	}
+}
+
+/// Set the callback of the window accelerator, returns `true` if it is a new accelerator.
+fn set_accelerator(hwnd: HWINDOW, accelerator: Accelerator, callback: AcceleratorCallback) -> bool {
+	ACCELERATORS.with(|accelerators| accelerators.borrow_mut().entry(hwnd).or_default().insert(accelerator, callback).is_none())
+}
+
+/// Call the callback of the pressed accelerator, returns `false` if there is none or it has not handled the key.
+fn accelerator_pressed(hwnd: HWINDOW, accelerator: Accelerator) -> bool {
+	// the callback may register the other ones
+	let callback = ACCELERATORS.with(|accelerators| accelerators.borrow().get(&hwnd).and_then(|keys| keys.get(&accelerator).cloned()));
+	return match callback {
+		Some(callback) => callback(),
+		None => false,
+	};
+}
+
+/// Set the `set_hit_test_callback` callback of the window, returns `true` if it is the first one.
+fn set_hit_test_handler(hwnd: HWINDOW, callback: HitTestCallback) -> bool {
+	HIT_TEST_HANDLERS.with(|handlers| handlers.borrow_mut().insert(hwnd, callback).is_none())
+}
+
+/// Ask the `set_hit_test_callback` callback of the window about the point, if there is one.
+#[cfg_attr(all(windows, feature = "windowless"), allow(dead_code))]
+fn hit_test(hwnd: HWINDOW, x: i32, y: i32) -> Option<HitTestResult> {
+	let callback = HIT_TEST_HANDLERS.with(|handlers| handlers.borrow().get(&hwnd).cloned());
+	callback.map(|callback| callback(x, y))
+}
+
+/// Windows with the `set_hit_test_callback` callbacks.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn hit_test_windows() -> Vec<HWINDOW> {
+	HIT_TEST_HANDLERS.with(|handlers| handlers.borrow().keys().cloned().collect())
+}
+
+/// Drop the `set_hit_test_callback` callback of the destroyed window.
+#[cfg_attr(all(windows, feature = "windowless"), allow(dead_code))]
+fn forget_hit_test_handler(hwnd: HWINDOW) {
+	HIT_TEST_HANDLERS.with(|handlers| handlers.borrow_mut().remove(&hwnd));
+}
+
+/// Windows with the accelerators.
+#[cfg_attr(not(target_os = "macos"), allow(dead_code))]
+fn accelerator_windows() -> Vec<HWINDOW> {
+	ACCELERATORS.with(|accelerators| accelerators.borrow().keys().cloned().collect())
+}
+
+/// Drop the accelerators of the destroyed window.
+#[cfg_attr(all(windows, feature = "windowless"), allow(dead_code))]
+fn forget_accelerators(hwnd: HWINDOW) {
+	ACCELERATORS.with(|accelerators| accelerators.borrow_mut().remove(&hwnd));
+}
+
+/// Remember the time of the keyboard or mouse input of the window.
+#[cfg_attr(any(target_os = "macos", all(windows, feature = "windowless")), allow(dead_code))]
+fn input_received(hwnd: HWINDOW) {
+	LAST_INPUT.with(|inputs| inputs.borrow_mut().insert(hwnd, Instant::now()));
+}
+
+/// Time since the last input of the window, zero if it has not been tracked.
+#[cfg_attr(target_os = "macos", allow(dead_code))]
+fn since_last_input(hwnd: HWINDOW) -> Duration {
+	LAST_INPUT.with(|inputs| inputs.borrow().get(&hwnd).map_or(Duration::from_secs(0), |time| time.elapsed()))
+}
+
+/// Drop the input time of the destroyed window.
+#[cfg_attr(any(target_os = "macos", all(windows, feature = "windowless")), allow(dead_code))]
+fn forget_input(hwnd: HWINDOW) {
+	LAST_INPUT.with(|inputs| inputs.borrow_mut().remove(&hwnd));
+}
+
+/// Encode the `BGRA` pixels of a window snapshot as PNG.
+#[cfg(not(target_os = "macos"))]
+fn encode_png(width: u32, height: u32, pixels: &[u8]) -> Result<Vec<u8>, ()> {
+	use graphics::{Image, SaveImageEncoding};
+	let image = Image::with_data((width, height), false, pixels).map_err(|_| ())?;
+	return image.save(SaveImageEncoding::Png).map_err(|_| ());
 }
 
 #[cfg(windows)]
 pub type OsWindow = windows::OsWindow;
+#[cfg(windows)]
+use self::windows::OsTrayIcon;
+#[cfg(all(windows, not(feature = "windowless")))]
+pub(crate) use self::windows::SetWindowLongPtrW;
 
 #[cfg(target_os = "linux")]
 pub type OsWindow = linux::OsWindow;
+#[cfg(target_os = "linux")]
+use self::linux::OsTrayIcon;
 
 #[cfg(target_os = "macos")]
 pub type OsWindow = macos::OsWindow;
+#[cfg(target_os = "macos")]
+use self::macos::OsTrayIcon;
+
+
+#[cfg(test)]
+mod tests {
+	use capi::sctypes::HWINDOW;
+	use std::time::Duration;
+
+	#[test]
+	fn idle_time_grows_since_input() {
+		let hwnd = 0x1000 as HWINDOW;
+		assert_eq!(super::since_last_input(hwnd), Duration::from_secs(0));
+
+		super::input_received(hwnd);
+		let before = super::since_last_input(hwnd);
+		::std::thread::sleep(Duration::from_millis(20));
+		let after = super::since_last_input(hwnd);
+		assert!(after >= before + Duration::from_millis(20));
+
+		// new input resets it
+		super::input_received(hwnd);
+		assert!(super::since_last_input(hwnd) < after);
+
+		super::forget_input(hwnd);
+		assert_eq!(super::since_last_input(hwnd), Duration::from_secs(0));
+	}
+
+	#[test]
+	fn placement_stays_on_its_monitor() {
+		use capi::sctypes::RECT;
+		use window::{MonitorInfo, WindowPlacement};
+
+		let primary = MonitorInfo {
+			rect: RECT { left: 0, top: 0, right: 1920, bottom: 1080 },
+			work_area: RECT { left: 0, top: 0, right: 1920, bottom: 1040 },
+			scale: 1.0,
+			is_primary: true,
+		};
+		let second = MonitorInfo {
+			rect: RECT { left: 1920, top: 0, right: 3840, bottom: 1080 },
+			work_area: RECT { left: 1920, top: 0, right: 3840, bottom: 1080 },
+			scale: 1.0,
+			is_primary: false,
+		};
+		let placement = WindowPlacement { position: (2000, 100), size: (800, 600), maximized: false, monitor: (1920, 0, 3840, 1080) };
+		let rc = super::placement_rect(&placement, &[primary, second]);
+		assert_eq!(rc, RECT { left: 2000, top: 100, right: 2800, bottom: 700 });
+
+		// partially off its monitor, it is moved into the monitor work area
+		let placement = WindowPlacement { position: (1500, 800), ..placement };
+		let rc = super::placement_rect(&placement, &[primary, second]);
+		assert_eq!(rc, RECT { left: 1920, top: 480, right: 2720, bottom: 1080 });
+
+		// the same, but its monitor is gone: the primary one is overlapped most
+		let placement = WindowPlacement { position: (1200, 800), monitor: (0, 0, 0, 0), ..placement };
+		let rc = super::placement_rect(&placement, &[primary, second]);
+		assert_eq!(rc, RECT { left: 1120, top: 440, right: 1920, bottom: 1040 });
+	}
+
+	#[test]
+	fn placement_of_disconnected_monitor_goes_to_primary() {
+		use capi::sctypes::RECT;
+		use window::{MonitorInfo, WindowPlacement};
+
+		let second = MonitorInfo {
+			rect: RECT { left: -1280, top: 0, right: 0, bottom: 1024 },
+			work_area: RECT { left: -1280, top: 0, right: 0, bottom: 1024 },
+			scale: 1.0,
+			is_primary: false,
+		};
+		let primary = MonitorInfo {
+			rect: RECT { left: 0, top: 0, right: 1280, bottom: 720 },
+			work_area: RECT { left: 0, top: 30, right: 1280, bottom: 720 },
+			scale: 1.0,
+			is_primary: true,
+		};
+		// it was on the right of the primary one, and bigger than it
+		let placement = WindowPlacement { position: (3000, 200), size: (1600, 900), maximized: true, monitor: (1920, 0, 3840, 1080) };
+		let rc = super::placement_rect(&placement, &[second, primary]);
+		assert_eq!(rc, primary.work_area);
+
+		// no monitors at all
+		let rc = super::placement_rect(&placement, &[]);
+		assert_eq!(rc, RECT { left: 3000, top: 200, right: 4600, bottom: 1100 });
+	}
+
+	#[test]
+	fn monitor_rect_is_centered_and_scaled() {
+		use capi::sctypes::RECT;
+		use window::MonitorInfo;
+
+		let primary = MonitorInfo {
+			rect: RECT { left: 0, top: 0, right: 1920, bottom: 1080 },
+			work_area: RECT { left: 0, top: 0, right: 1920, bottom: 1040 },
+			scale: 1.0,
+			is_primary: true,
+		};
+		let second = MonitorInfo {
+			rect: RECT { left: 1920, top: 0, right: 4480, bottom: 1440 },
+			work_area: RECT { left: 1920, top: 0, right: 4480, bottom: 1440 },
+			scale: 1.5,
+			is_primary: false,
+		};
+		let monitors = [second, primary];
+
+		let rc = super::monitor_centered_rect(&monitors, 0, (800, 600), true);
+		assert_eq!(rc, RECT { left: 2600, top: 270, right: 3800, bottom: 1170 });
+		let rc = super::monitor_centered_rect(&monitors, 0, (800, 600), false);
+		assert_eq!(rc, RECT { left: 2800, top: 420, right: 3600, bottom: 1020 });
+
+		// an unknown monitor is the primary one, and the size fits its work area
+		let rc = super::monitor_centered_rect(&monitors, 5, (2000, 600), true);
+		assert_eq!(rc, RECT { left: 0, top: 220, right: 1920, bottom: 820 });
+
+		// no monitors at all
+		let rc = super::monitor_centered_rect(&[], 0, (800, 600), true);
+		assert_eq!(rc, RECT { left: 0, top: 0, right: 800, bottom: 600 });
+	}
+
+	#[test]
+	fn accelerator_fires_until_forgotten() {
+		use capi::scbehavior::KEYBOARD_STATES;
+		use capi::scmsg::key_codes;
+		use std::cell::Cell;
+		use std::rc::Rc;
+		use window::Accelerator;
+
+		let hwnd = 0x5A5A as ::capi::sctypes::HWINDOW;
+		let save = Accelerator::new(KEYBOARD_STATES::CONTROL_KEY_PRESSED, key_codes::KB_S);
+		let calls = Rc::new(Cell::new(0));
+		let counter = calls.clone();
+		assert!(super::set_accelerator(hwnd, save, Rc::new(move || { counter.set(counter.get() + 1); true })));
+		assert!(super::accelerator_pressed(hwnd, save));
+		assert_eq!(calls.get(), 1);
+
+		// other modifiers or keys are not it
+		assert!(!super::accelerator_pressed(hwnd, Accelerator::new(0, key_codes::KB_S)));
+		assert!(!super::accelerator_pressed(hwnd, Accelerator::new(KEYBOARD_STATES::CONTROL_KEY_PRESSED, key_codes::KB_D)));
+		assert_eq!(calls.get(), 1);
+
+		// a callback can let the key through
+		assert!(!super::set_accelerator(hwnd, save, Rc::new(|| false)));
+		assert!(!super::accelerator_pressed(hwnd, save));
+
+		super::forget_accelerators(hwnd);
+		assert!(super::accelerator_windows().is_empty());
+		assert!(!super::accelerator_pressed(hwnd, save));
+	}
+
+	#[test]
+	fn dpi_callback_fires_on_change() {
+		use std::cell::Cell;
+		use std::rc::Rc;
+
+		let hwnd = 0x2000 as HWINDOW;
+		let calls = Rc::new(Cell::new(0));
+		let counter = calls.clone();
+		assert!(super::set_dpi_handler(hwnd, 1.0, Rc::new(move |scale| {
+			assert_eq!(scale, 2.0);
+			counter.set(counter.get() + 1);
+		})));
+
+		super::dpi_changed(hwnd, 1.0);
+		assert_eq!(calls.get(), 0);
+		super::dpi_changed(hwnd, 2.0);
+		super::dpi_changed(hwnd, 2.0);
+		assert_eq!(calls.get(), 1);
+
+		super::forget_dpi_handler(hwnd);
+		super::dpi_changed(hwnd, 1.0);
+		assert_eq!(calls.get(), 1);
+	}
+
+	#[test]
+	fn theme_callback_fires_on_change() {
+		use std::cell::Cell;
+		use std::rc::Rc;
+		use window::Theme;
+
+		let hwnd = 0x4000 as HWINDOW;
+		let calls = Rc::new(Cell::new(0));
+		let counter = calls.clone();
+		assert!(super::set_theme_handler(hwnd, Theme::Light, Rc::new(move |theme| {
+			assert_eq!(theme, Theme::Dark);
+			counter.set(counter.get() + 1);
+		})));
+
+		super::theme_changed(hwnd, Theme::Light);
+		assert_eq!(calls.get(), 0);
+		super::theme_changed(hwnd, Theme::Dark);
+		super::theme_changed(hwnd, Theme::Dark);
+		assert_eq!(calls.get(), 1);
+
+		super::forget_theme_handler(hwnd);
+		super::theme_changed(hwnd, Theme::Light);
+		assert_eq!(calls.get(), 1);
+	}
+
+	#[test]
+	fn current_theme_is_known() {
+		use window::Theme;
+		let theme = super::OsWindow::current_theme();
+		assert!(theme == Theme::Light || theme == Theme::Dark);
+	}
+
+	#[test]
+	fn zoom_is_clamped() {
+		let hwnd = 0x3000 as HWINDOW;
+		assert_eq!(super::zoom_factor(hwnd), 1.0);
+
+		assert!(super::set_zoom_factor(hwnd, 1.5));
+		assert_eq!(super::zoom_factor(hwnd), 1.5);
+		assert!(!super::set_zoom_factor(hwnd, 10.0));
+		assert_eq!(super::zoom_factor(hwnd), 5.0);
+		super::set_zoom_factor(hwnd, 0.0);
+		assert_eq!(super::zoom_factor(hwnd), 0.25);
+		super::set_zoom_factor(hwnd, ::std::f32::NAN);
+		assert_eq!(super::zoom_factor(hwnd), 1.0);
+
+		super::forget_zoom(hwnd);
+		assert_eq!(super::zoom_factor(hwnd), 1.0);
+	}
+}
@@ -0,0 +1,243 @@
+/*! System sounds playback.
+
+Audible feedback (errors, warnings, task completion) without bundling an audio engine.
+
+All functions here are fire-and-forget: they start playback and return immediately.
+If there is no sound device (or no sound service on Linux), they silently do nothing.
+
+```rust,no_run
+use sciter::sound::{self, SystemSound};
+
+sound::play(SystemSound::Error);
+sound::play_file("assets/done.wav");
+```
+
+Platform backends:
+
+* Windows: `MessageBeep` and `PlaySound` (`winmm.dll`).
+* macOS: `NSBeep` and `NSSound`.
+* Linux: [libcanberra](http://0pointer.de/lennart/projects/libcanberra/) which is loaded at runtime,
+  so it is not a build dependency.
+
+*/
+
+use std::path::Path;
+
+
+/// Predefined system sounds.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum SystemSound {
+	/// Default beep.
+	Default,
+	/// Critical error (e.g. "Critical Stop" on Windows).
+	Error,
+	/// Warning (e.g. "Exclamation" on Windows).
+	Warning,
+	/// Question (e.g. "Question" on Windows).
+	Question,
+	/// Information (e.g. "Asterisk" on Windows).
+	Info,
+}
+
+/// Play a predefined system sound.
+pub fn play(sound: SystemSound) {
+	imp::play(sound)
+}
+
+/// Play a sound file (e.g. `.wav`).
+///
+/// Supported formats depend on the platform: Windows accepts only `.wav` files,
+/// macOS and Linux accept any format known to the system sound service.
+pub fn play_file<P: AsRef<Path>>(path: P) {
+	imp::play_file(path.as_ref())
+}
+
+
+#[cfg(windows)]
+mod imp {
+	use std::os::windows::ffi::OsStrExt;
+	use std::path::Path;
+	use capi::sctypes::*;
+	use super::SystemSound;
+
+	#[link(name = "user32")]
+	extern "system" {
+		fn MessageBeep(kind: UINT) -> BOOL;
+	}
+
+	#[link(name = "winmm")]
+	extern "system" {
+		fn PlaySoundW(sound: LPCWSTR, module: LPVOID, flags: UINT) -> BOOL;
+	}
+
+	const SND_ASYNC: UINT = 0x0001;
+	const SND_NODEFAULT: UINT = 0x0002;
+	const SND_FILENAME: UINT = 0x0002_0000;
+
+	pub fn play(sound: SystemSound) {
+		let kind = match sound {
+			SystemSound::Default => 0x0000_0000,  // MB_OK
+			SystemSound::Error => 0x0000_0010,    // MB_ICONHAND
+			SystemSound::Question => 0x0000_0020, // MB_ICONQUESTION
+			SystemSound::Warning => 0x0000_0030,  // MB_ICONEXCLAMATION
+			SystemSound::Info => 0x0000_0040,     // MB_ICONASTERISK
+		};
+		unsafe { MessageBeep(kind) };
+	}
+
+	pub fn play_file(path: &Path) {
+		let mut wide: Vec<u16> = path.as_os_str().encode_wide().collect();
+		wide.push(0);
+		unsafe { PlaySoundW(wide.as_ptr(), ::std::ptr::null_mut(), SND_FILENAME | SND_ASYNC | SND_NODEFAULT) };
+	}
+}
+
+
+#[cfg(target_os = "macos")]
+mod imp {
+	extern crate objc_foundation;
+
+	use self::objc_foundation::{INSString, NSString};
+	use objc::runtime::{Class, Object, BOOL, YES};
+	use std::path::Path;
+	use super::SystemSound;
+
+	#[link(name = "AppKit", kind = "framework")]
+	extern "C" {
+		fn NSBeep();
+	}
+
+	pub fn play(sound: SystemSound) {
+		let name = match sound {
+			SystemSound::Default => {
+				unsafe { NSBeep() };
+				return;
+			},
+			SystemSound::Error => "Basso",
+			SystemSound::Warning => "Funk",
+			SystemSound::Question => "Purr",
+			SystemSound::Info => "Glass",
+		};
+		let cls = Class::get("NSSound").expect("`NSSound` is not registered.");
+		let name = NSString::from_str(name);
+		let sound: *mut Object = unsafe { msg_send!(cls, soundNamed:name) };
+		if !sound.is_null() {
+			let _: BOOL = unsafe { msg_send!(sound, play) };
+		}
+	}
+
+	pub fn play_file(path: &Path) {
+		let cls = Class::get("NSSound").expect("`NSSound` is not registered.");
+		let path = NSString::from_str(&path.to_string_lossy());
+		unsafe {
+			let sound: *mut Object = msg_send!(cls, alloc);
+			let sound: *mut Object = msg_send!(sound, initWithContentsOfFile:path byReference:YES);
+			if !sound.is_null() {
+				// Note: the instance is not released since `NSSound` stops the playback on deallocation.
+				let _: BOOL = msg_send!(sound, play);
+			}
+		}
+	}
+}
+
+
+#[cfg(target_os = "linux")]
+mod imp {
+	extern crate libc;
+
+	use std::ffi::CString;
+	use std::os::unix::ffi::OsStrExt;
+	use std::path::Path;
+	use capi::sctypes::*;
+	use super::SystemSound;
+
+	#[allow(non_camel_case_types)]
+	type ca_context = LPVOID;
+	#[allow(non_camel_case_types)]
+	type ca_proplist = LPVOID;
+
+	type ContextCreate = extern "C" fn(c: *mut ca_context) -> INT;
+	type ContextPlayFull = extern "C" fn(c: ca_context, id: UINT, p: ca_proplist, cb: LPVOID, userdata: LPVOID) -> INT;
+	type ProplistCreate = extern "C" fn(p: *mut ca_proplist) -> INT;
+	type ProplistDestroy = extern "C" fn(p: ca_proplist) -> INT;
+	type ProplistSets = extern "C" fn(p: ca_proplist, key: LPCSTR, value: LPCSTR) -> INT;
+
+	struct Canberra {
+		ca_context: ca_context,
+		ca_proplist_create: ProplistCreate,
+		ca_proplist_destroy: ProplistDestroy,
+		ca_proplist_sets: ProplistSets,
+		ca_context_play_full: ContextPlayFull,
+	}
+
+	// libcanberra is thread-safe.
+	unsafe impl Send for Canberra {}
+	unsafe impl Sync for Canberra {}
+
+	lazy_static! {
+		static ref CANBERRA: Option<Canberra> = unsafe { load() };
+	}
+
+	unsafe fn load() -> Option<Canberra> {
+		let dll = libc::dlopen(b"libcanberra.so.0\0".as_ptr() as LPCSTR, libc::RTLD_LOCAL | libc::RTLD_LAZY);
+		if dll.is_null() {
+			return None;
+		}
+
+		macro_rules! sym {
+			($name:ident: $t:ty) => {{
+				let sym = libc::dlsym(dll, concat!(stringify!($name), "\0").as_ptr() as LPCSTR);
+				if sym.is_null() {
+					return None;
+				}
+				::std::mem::transmute::<LPVOID, $t>(sym)
+			}};
+		}
+
+		let ca_context_create = sym!(ca_context_create: ContextCreate);
+		let mut context: ca_context = ::std::ptr::null_mut();
+		if ca_context_create(&mut context) != 0 || context.is_null() {
+			return None;
+		}
+
+		Some(Canberra {
+			ca_context: context,
+			ca_proplist_create: sym!(ca_proplist_create: ProplistCreate),
+			ca_proplist_destroy: sym!(ca_proplist_destroy: ProplistDestroy),
+			ca_proplist_sets: sym!(ca_proplist_sets: ProplistSets),
+			ca_context_play_full: sym!(ca_context_play_full: ContextPlayFull),
+		})
+	}
+
+	fn play_with(key: &[u8], value: CString) {
+		let ca = match CANBERRA.as_ref() {
+			Some(ca) => ca,
+			None => return,
+		};
+		let mut props: ca_proplist = ::std::ptr::null_mut();
+		if (ca.ca_proplist_create)(&mut props) != 0 {
+			return;
+		}
+		(ca.ca_proplist_sets)(props, key.as_ptr() as LPCSTR, value.as_ptr());
+		(ca.ca_context_play_full)(ca.ca_context, 0, props, ::std::ptr::null_mut(), ::std::ptr::null_mut());
+		(ca.ca_proplist_destroy)(props);
+	}
+
+	pub fn play(sound: SystemSound) {
+		// https://specifications.freedesktop.org/sound-naming-spec/latest/
+		let name = match sound {
+			SystemSound::Default => "bell",
+			SystemSound::Error => "dialog-error",
+			SystemSound::Warning => "dialog-warning",
+			SystemSound::Question => "dialog-question",
+			SystemSound::Info => "dialog-information",
+		};
+		play_with(b"event.id\0", CString::new(name).unwrap());
+	}
+
+	pub fn play_file(path: &Path) {
+		if let Ok(path) = CString::new(path.as_os_str().as_bytes()) {
+			play_with(b"media.filename\0", path);
+		}
+	}
+}
@@ -57,7 +57,7 @@ fn towcs(utf: &[u8], outbuf: &mut Vec<u16>) -> bool
 
 		} else if ((b & 0xf8) == 0xf0) {
 			// 4-BYTE sequence: 11101110wwwwzzzzyy + 110111yyyyxxxxxx = 11110uuu 10uuzzzz 10yyyyyy 10xxxxxx
-			if(pc >= last - 2) { outbuf.push(errc); break; }
+			if(pc >= last - 2) { outbuf.push(errc); num_errors += 1; break; }
 
 			b = (b & 0x07) << 18;
 			b |= (u32::from(utf[pc]) & 0x3f) << 12; pc += 1;
@@ -72,8 +72,10 @@ fn towcs(utf: &[u8], outbuf: &mut Vec<u16>) -> bool
 				continue;
 			}
 
+			// the surrogate pair is the whole character
 			outbuf.push( (0xd7c0 + (b >> 10)) as u16 );
 			outbuf.push( (0xdc00 | (b & 0x3ff)) as u16 );
+			continue;
 
 		} else {
 			num_errors += 1;
@@ -95,7 +97,19 @@ fn fromwcs(wcs: &[u16], outbuf: &mut Vec<u8>) -> bool
 	let last = wcs.len();
 	let mut pc = 0;
 	while (pc < last) {
-		let c = u32::from(wcs[pc]);
+		let mut c = u32::from(wcs[pc]);
+		if ((0xD800..=0xDFFF).contains(&c)) {
+			// a high surrogate followed by a low one, the unpaired ones are replaced with U+FFFD
+			let low = if (pc + 1 < last) { u32::from(wcs[pc + 1]) } else { 0 };
+			if (c <= 0xDBFF && (0xDC00..=0xDFFF).contains(&low)) {
+				c = 0x10000 + ((c - 0xD800) << 10) + (low - 0xDC00);
+				pc += 1;
+			} else {
+				c = 0xFFFD;
+				num_errors += 1;
+			}
+		}
+
 		if (c < (1 << 7)) {
 			outbuf.push(c as u8);
 
@@ -169,6 +183,8 @@ pub fn w2s(sz: LPCWSTR) -> String
 }
 
 /// UTF-16 to Rust string conversion. See also [`s2w!`](../macro.s2w.html).
+///
+/// The surrogate pairs are decoded as a unit, the unpaired surrogates are replaced with U+FFFD.
 pub fn w2sn(sz: LPCWSTR, len: usize) -> String
 {
 	if sz.is_null() || len == 0 {
@@ -237,7 +253,7 @@ mod tests {
 
 	use std::ffi::{CStr, CString};
 	use capi::sctypes::{LPCWSTR, LPCSTR};
-	use super::{wcslen, u2s, w2s, s2vec};
+	use super::{wcslen, u2s, w2s, w2sn, s2vec, fromwcs};
 
 	#[test]
 	fn test_wcslen() {
@@ -285,4 +301,28 @@ mod tests {
 		assert_eq!(n, 0);
 		assert_eq!(cs, []);
 	}
+
+	#[test]
+	fn surrogate_pairs() {
+		let title = "\u{1F680} App";
+		let expected: Vec<u16> = title.encode_utf16().chain(Some(0)).collect();
+		let v = s2vec(title);
+		assert_eq!(v, expected);
+		assert_eq!(w2s(v.as_ptr()), title);
+
+		let mut out = Vec::new();
+		assert!(fromwcs(&expected[..expected.len() - 1], &mut out));
+		assert_eq!(out, title.as_bytes());
+	}
+
+	#[test]
+	fn unpaired_surrogates() {
+		// a lone high, a lone low and a high at the end
+		let v = [0xD83D, 'a' as u16, 0xDE80, 'b' as u16, 0xD83D];
+		assert_eq!(w2sn(v.as_ptr(), v.len()), "\u{FFFD}a\u{FFFD}b\u{FFFD}");
+
+		let mut out = Vec::new();
+		assert!(!fromwcs(&v, &mut out));
+		assert_eq!(String::from_utf8(out).unwrap(), "\u{FFFD}a\u{FFFD}b\u{FFFD}");
+	}
 }
@@ -223,6 +223,23 @@ impl Value {
 	}
 
 	/// Make Sciter [error](https://sciter.com/docs/content/script/Error.htm) value.
+	///
+	/// When returned from a native function (or from a [script call handler](../dom/event/trait.EventHandler.html#method.on_script_call)),
+	/// the engine throws it as a script exception:
+	///
+	/// ```rust,no_run
+	/// # use sciter::Value;
+	/// fn divide(args: &[Value]) -> Value {
+	///   match (args[0].to_int(), args[1].to_int()) {
+	///     (Some(_), Some(0)) => Value::error("division by zero"),
+	///     (Some(a), Some(b)) => Value::from(a / b),
+	///     _ => Value::error("divide(a, b) expects two integers"),
+	///   }
+	/// }
+	/// ```
+	///
+	/// Returning `Err` from a function that returns `Result` does the same, see `From<Result<T, E>>`.
+	#[doc(alias = "make_error")]
 	pub fn error(val: &str) -> Value {
 		let mut me = Value::new();
 		me.assign_str(val, VALUE_UNIT_TYPE_STRING::ERROR);
@@ -736,7 +753,14 @@ impl Value {
 		self.is_function() || self.is_object_function() || self.is_native_function()
 	}
 	#[allow(missing_docs)]
+	#[deprecated(note="Use `Value::is_error()` instead.")]
 	pub const fn is_verror(&self) -> bool {
+		self.is_error()
+	}
+
+	/// Returns `true` for error values: made by [`Value::error`](#method.error) or script `Error` objects.
+	#[doc(alias = "is_verror")]
+	pub const fn is_error(&self) -> bool {
 		self.is_error_string() || self.is_object_error()
 	}
 
@@ -34,6 +34,7 @@ use host::{Host, HostHandler};
 use dom::{self, event::{EventHandler}};
 use crate::Value;
 
+use std::path::PathBuf;
 use std::rc::Rc;
 
 
@@ -41,6 +42,353 @@ use std::rc::Rc;
 pub type Flags = SCITER_CREATE_WINDOW_FLAGS;
 
 pub use capi::scdef::{SCITER_CREATE_WINDOW_FLAGS};
+pub use capi::scbehavior::KEYBOARD_STATES;
+pub use capi::scmsg::key_codes;
+
+
+/// Why a window can not be created, see [`Window::try_create()`](struct.Window.html#method.try_create).
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum WindowError {
+	/// The Sciter library can not be loaded, with the loader message.
+	LibraryNotFound(String),
+	/// `OleInitialize` has failed with this `HRESULT` (Windows only),
+	/// e.g. `RPC_E_CHANGED_MODE` if the thread has joined a multithreaded COM apartment already.
+	OleInitFailed(i32),
+	/// `SciterCreateWindow` has returned a null handle, e.g. because of invalid flags.
+	CreateFailed,
+}
+
+impl ::std::error::Error for WindowError {}
+
+impl ::std::fmt::Display for WindowError {
+	fn fmt(&self, f: &mut ::std::fmt::Formatter) -> ::std::fmt::Result {
+		match *self {
+			WindowError::LibraryNotFound(ref message) => write!(f, "{}", message),
+			WindowError::OleInitFailed(hr) if hr as u32 == 0x8001_0106 => write!(f, "OleInitialize failed: COM is initialized in a multithreaded apartment (RPC_E_CHANGED_MODE)"),
+			WindowError::OleInitFailed(hr) => write!(f, "OleInitialize failed with 0x{:08X}", hr),
+			WindowError::CreateFailed => write!(f, "SciterCreateWindow returned a null handle"),
+		}
+	}
+}
+
+
+/// Window state, see [`Window::get_state()`](struct.Window.html#method.get_state).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum WindowState {
+	/// Shown, neither minimized nor maximized.
+	Normal,
+	/// Minimized (iconified).
+	Minimized,
+	/// Maximized (zoomed on macOS).
+	Maximized,
+	/// Hidden or closed.
+	Hidden,
+	/// Fullscreen, see [`Window::set_fullscreen()`](struct.Window.html#method.set_fullscreen).
+	Fullscreen,
+}
+
+
+/// Mouse cursor over the window, see [`Window::set_cursor()`](struct.Window.html#method.set_cursor).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CursorKind {
+	/// The cursors of the document, as its CSS `cursor` properties define.
+	Default,
+	/// The standard arrow.
+	Arrow,
+	/// Busy, e.g. during a long operation. It is the arrow on macOS, which has no public busy cursor.
+	Wait,
+	/// The pointing hand of the links.
+	Hand,
+	/// The I-beam of the text selection.
+	Text,
+	/// Crosshair for the precise selection.
+	Crosshair,
+	/// Moving something, an open hand on macOS.
+	Move,
+	/// The action is not allowed.
+	NotAllowed,
+	/// No cursor over the window.
+	Hidden,
+}
+
+
+bitflags! {
+	/// Pressed mouse buttons, see [`Window::mouse_buttons()`](struct.Window.html#method.mouse_buttons).
+	#[derive(Default)]
+	pub struct MouseButtons: u32 {
+		/// The primary button.
+		const LEFT = 0x01;
+		/// The secondary button.
+		const RIGHT = 0x02;
+		/// The wheel button.
+		const MIDDLE = 0x04;
+		/// The first extra button, usually "back". Not reported on Linux.
+		const X1 = 0x08;
+		/// The second extra button, usually "forward". Not reported on Linux.
+		const X2 = 0x10;
+	}
+}
+
+
+/// Part of the window under the mouse, see [`Window::set_hit_test_callback()`](struct.Window.html#method.set_hit_test_callback).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum HitTestResult {
+	/// The document, it gets the mouse events as usual.
+	Client,
+	/// The titlebar: dragging moves the window and double-clicking maximizes it.
+	Caption,
+	/// The left resize border.
+	Left,
+	/// The right resize border.
+	Right,
+	/// The top resize border.
+	Top,
+	/// The bottom resize border.
+	Bottom,
+	/// The top-left resize corner.
+	TopLeft,
+	/// The top-right resize corner.
+	TopRight,
+	/// The bottom-left resize corner.
+	BottomLeft,
+	/// The bottom-right resize corner.
+	BottomRight,
+}
+
+
+/// Connected monitor, see [`enumerate_monitors()`](fn.enumerate_monitors.html).
+///
+/// The rectangles are in the screen coordinates, the same ones as of [`Window::get_rect()`](struct.Window.html#method.get_rect).
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct MonitorInfo {
+	/// The whole monitor area.
+	pub rect: RECT,
+	/// The monitor area without the taskbar, docks and panels.
+	pub work_area: RECT,
+	/// The scale factor of the monitor, `1.0` means 96 DPI.
+	pub scale: f32,
+	/// Whether this monitor is the primary one; exactly one monitor is.
+	pub is_primary: bool,
+}
+
+/// List the connected monitors, e.g. to place a window on a specific one.
+///
+/// The list is empty on Linux with GTK older than 3.22, which has no `GdkMonitor`.
+pub fn enumerate_monitors() -> Vec<MonitorInfo> {
+	OsWindow::enumerate_monitors()
+}
+
+
+/// Translucent material behind the window, see [`Window::set_backdrop()`](struct.Window.html#method.set_backdrop).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum BackdropKind {
+	/// The document background only.
+	None,
+	/// Blurred content behind the window.
+	Blur,
+	/// Acrylic, the blur of the transient windows like menus and popups.
+	Acrylic,
+	/// Mica, the wallpaper tinted material of the main windows.
+	Mica,
+	/// The Mica variant of the tabbed windows.
+	Tabbed,
+}
+
+
+/// System appearance, see [`current_theme()`](fn.current_theme.html).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Theme {
+	/// Dark text on the light backgrounds.
+	Light,
+	/// Light text on the dark backgrounds.
+	Dark,
+}
+
+/// The current system appearance for the apps, e.g. to pick the initial stylesheet;
+/// [`Window::on_theme_changed()`](struct.Window.html#method.on_theme_changed) reports the changes.
+pub fn current_theme() -> Theme {
+	OsWindow::current_theme()
+}
+
+
+#[cfg(windows)]
+bitflags! {
+	/// Win32 window styles for [`Window::modify_style()`](struct.Window.html#method.modify_style):
+	/// the `WS_*` ones in the low 32 bits and the `WS_EX_*` ones in the high 32 bits.
+	pub struct WindowStyle: u64 {
+		/// `WS_POPUP`
+		const POPUP = 0x8000_0000;
+		/// `WS_CHILD`
+		const CHILD = 0x4000_0000;
+		/// `WS_DISABLED`
+		const DISABLED = 0x0800_0000;
+		/// `WS_CLIPSIBLINGS`
+		const CLIPSIBLINGS = 0x0400_0000;
+		/// `WS_CLIPCHILDREN`
+		const CLIPCHILDREN = 0x0200_0000;
+		/// `WS_CAPTION`, the title bar (with the border).
+		const CAPTION = 0x00C0_0000;
+		/// `WS_BORDER`, a thin border.
+		const BORDER = 0x0080_0000;
+		/// `WS_DLGFRAME`, a dialog border.
+		const DLGFRAME = 0x0040_0000;
+		/// `WS_SYSMENU`, the window menu and the close button.
+		const SYSMENU = 0x0008_0000;
+		/// `WS_THICKFRAME`, the sizing border.
+		const THICKFRAME = 0x0004_0000;
+		/// `WS_MINIMIZEBOX`
+		const MINIMIZEBOX = 0x0002_0000;
+		/// `WS_MAXIMIZEBOX`
+		const MAXIMIZEBOX = 0x0001_0000;
+
+		/// `WS_EX_DLGMODALFRAME`
+		const EX_DLGMODALFRAME = 0x0000_0001 << 32;
+		/// `WS_EX_TOPMOST`, above all non-topmost windows.
+		const EX_TOPMOST = 0x0000_0008 << 32;
+		/// `WS_EX_ACCEPTFILES`
+		const EX_ACCEPTFILES = 0x0000_0010 << 32;
+		/// `WS_EX_TRANSPARENT`, the mouse input goes through the window.
+		const EX_TRANSPARENT = 0x0000_0020 << 32;
+		/// `WS_EX_TOOLWINDOW`, off the taskbar and the Alt+Tab list.
+		const EX_TOOLWINDOW = 0x0000_0080 << 32;
+		/// `WS_EX_WINDOWEDGE`
+		const EX_WINDOWEDGE = 0x0000_0100 << 32;
+		/// `WS_EX_CLIENTEDGE`
+		const EX_CLIENTEDGE = 0x0000_0200 << 32;
+		/// `WS_EX_APPWINDOW`, on the taskbar when visible.
+		const EX_APPWINDOW = 0x0004_0000 << 32;
+		/// `WS_EX_LAYERED`
+		const EX_LAYERED = 0x0008_0000 << 32;
+		/// `WS_EX_NOREDIRECTIONBITMAP`
+		const EX_NOREDIRECTIONBITMAP = 0x0020_0000 << 32;
+		/// `WS_EX_COMPOSITED`
+		const EX_COMPOSITED = 0x0200_0000 << 32;
+		/// `WS_EX_NOACTIVATE`, the window is not activated by clicks.
+		const EX_NOACTIVATE = 0x0800_0000 << 32;
+	}
+}
+
+
+/// Win32 parameters of a new window, see [`Builder::with_create_hook()`](struct.Builder.html#method.with_create_hook).
+///
+/// `SciterCreateWindow` registers and picks the window class itself and takes no styles,
+/// so the parameters are applied to the new window before it is shown:
+/// the styles with `SetWindowLongPtr`, the class ones with `SetClassLongPtr` and the menu with `SetMenu`.
+/// The window class and its procedure stay the Sciter ones.
+#[cfg(windows)]
+#[derive(Debug, Default, Clone)]
+pub struct WindowCreateParams {
+	/// A registered window class (of the app or a system one) to take the class styles and the icons from.
+	pub class_name: Option<String>,
+	/// `CS_*` class styles to add, e.g. `CS_DBLCLKS`.
+	///
+	/// Note that the class is shared with the other Sciter windows, so these styles leak into every Sciter window
+	/// created afterwards. And since they are set after `SciterCreateWindow`, the styles that are read
+	/// only at the window creation (e.g. `CS_OWNDC` or `CS_CLASSDC`) have no effect on this window.
+	pub class_style: u32,
+	/// `WS_*` styles replacing the ones made of the Sciter flags.
+	pub style: Option<u32>,
+	/// `WS_EX_*` styles replacing the ones made of the Sciter flags.
+	pub ex_style: Option<u32>,
+	/// `HMENU` of the menu bar.
+	pub menu: Option<LPVOID>,
+}
+
+#[cfg(windows)]
+pub(crate) type CreateHook = Box<dyn FnOnce(&mut WindowCreateParams)>;
+
+
+/// Taskbar progress indicator state, see [`Window::set_progress()`](struct.Window.html#method.set_progress).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ProgressState {
+	/// No progress indicator.
+	None,
+	/// The progress of a running operation.
+	Normal,
+	/// The progress of a paused operation.
+	Paused,
+	/// The progress of a failed operation.
+	Error,
+	/// An operation of unknown length, the value is ignored.
+	Indeterminate,
+}
+
+
+/// A keyboard shortcut of the window, see [`Window::register_accelerator()`](struct.Window.html#method.register_accelerator).
+///
+/// ```
+/// use sciter::window::{Accelerator, KEYBOARD_STATES, key_codes};
+/// let save = Accelerator::new(KEYBOARD_STATES::CONTROL_KEY_PRESSED, key_codes::KB_S);
+/// let fullscreen = Accelerator::new(0, key_codes::KB_F11);
+/// # assert_ne!(save, fullscreen);
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub struct Accelerator {
+	/// The [`KEYBOARD_STATES`](struct.KEYBOARD_STATES.html) flags of the modifiers to hold:
+	/// control, shift, alt and command (the Windows key elsewhere).
+	pub modifiers: u32,
+	/// The [key code](key_codes/index.html) of the key: a letter, a digit, space or a function key.
+	pub key: u32,
+}
+
+impl Accelerator {
+	/// The `key` with the `modifiers` held.
+	pub fn new(modifiers: u32, key: u32) -> Accelerator {
+		Accelerator { modifiers: modifiers, key: key }
+	}
+}
+
+
+/// Window geometry to restore the next time the app runs,
+/// see [`Window::save_placement()`](struct.Window.html#method.save_placement).
+///
+/// Implements `serde::Serialize` and `serde::Deserialize` with the `serde` feature, e.g. to keep it in a config file.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(::serde::Serialize, ::serde::Deserialize))]
+pub struct WindowPlacement {
+	/// The top-left corner of the window in the screen coordinates.
+	pub position: (i32, i32),
+	/// The window size; on Windows the one before maximizing for the maximized windows.
+	pub size: (i32, i32),
+	/// Whether the window is maximized.
+	pub maximized: bool,
+	/// The `(left, top, right, bottom)` rect of the monitor which the window was on, it identifies the monitor.
+	pub monitor: (i32, i32, i32, i32),
+}
+
+
+/// Position of the window among the other windows, see [`GeometryUpdate`](struct.GeometryUpdate.html).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ZOrder {
+	/// Above the other windows of its level.
+	Top,
+	/// Below all other windows.
+	Bottom,
+	/// Above all the non-topmost windows, see [`Window::set_topmost()`](struct.Window.html#method.set_topmost).
+	Topmost,
+	/// Back among the non-topmost windows.
+	NotTopmost,
+}
+
+/// Window geometry changes applied at once, see [`Window::update_geometry()`](struct.Window.html#method.update_geometry).
+///
+/// The missing fields are left as they are:
+///
+/// ```rust
+/// # use sciter::window::{GeometryUpdate, ZOrder};
+/// let changes = GeometryUpdate { position: Some((100, 100)), z_order: Some(ZOrder::Top), ..Default::default() };
+/// ```
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Default)]
+pub struct GeometryUpdate {
+	/// The new top-left corner of the window in the screen coordinates.
+	pub position: Option<(i32, i32)>,
+	/// The new window size.
+	pub size: Option<(i32, i32)>,
+	/// The new position in the z-order.
+	pub z_order: Option<ZOrder>,
+	/// The new window state; with `Minimized` and `Maximized` the position and size are the ones to restore to.
+	pub state: Option<WindowState>,
+}
 
 
 /// Per-window Sciter engine options.
@@ -82,6 +430,11 @@ pub enum Options {
 
 
 /// Sciter window.
+///
+/// Dropping a window created by one of the `create` functions (or by [`attach_child`](#method.attach_child)) destroys it
+/// unless it is closed already; an [attached](#method.attach) one is left intact.
+/// So a secondary window created in an event handler has to be kept somewhere,
+/// or released with [`into_hwnd`](#method.into_hwnd) to stay open on its own.
 pub struct Window
 {
 	base: OsWindow,
@@ -100,6 +453,8 @@ impl Window {
 	}
 
 	/// Create a new window with the specified position, flags and an optional parent window.
+	///
+	/// Panics if the window can not be created, see [`try_create`](#method.try_create).
 	#[cfg_attr(feature = "windowless", deprecated = "Sciter.Lite doesn't have OS windows in windowless mode.")]
 	pub fn create(rect: RECT, flags: Flags, parent: Option<HWINDOW>) -> Window {
 		if cfg!(feature = "windowless")
@@ -107,12 +462,139 @@ impl Window {
 			panic!("Sciter.Lite doesn't have OS windows in windowless mode!");
 		}
 
+		#[allow(deprecated)]
+		let wnd = Window::try_create(rect, flags, parent);
+		match wnd {
+			Ok(wnd) => wnd,
+			Err(error) => panic!("Failed to create window: {}", error),
+		}
+	}
+
+	/// Create a new window like [`create`](#method.create), but return an error instead of panicking.
+	///
+	/// It allows to show an error message or to fall back to other window flags.
+	#[cfg_attr(feature = "windowless", deprecated = "Sciter.Lite doesn't have OS windows in windowless mode.")]
+	pub fn try_create(rect: RECT, flags: Flags, parent: Option<HWINDOW>) -> Result<Window, WindowError> {
+		return Window::try_create_from(OsWindow::new(), rect, flags, parent);
+	}
+
+	/// Create the window of the prepared `base`, e.g. with a creation hook.
+	fn try_create_from(mut base: OsWindow, rect: RECT, flags: Flags, parent: Option<HWINDOW>) -> Result<Window, WindowError> {
+		if cfg!(feature = "windowless")
+		{
+			return Err(WindowError::CreateFailed);
+		}
+
+		let hwnd = base.try_create(rect, flags.bits() as UINT, parent.unwrap_or(0 as HWINDOW))?;
+
+		let wnd = Window { base: base, host: Rc::new(Host::attach(hwnd))};
+		return Ok(wnd);
+	}
+
+	/// Create a new window and show it in the given state, e.g. maximized.
+	///
+	/// Unlike [`create`](#method.create) followed by [`expand`](#method.expand),
+	/// the state is applied before the window is painted for the first time, so it does not flash in the normal state.
+	/// With `WindowState::Hidden` it is the same as [`create`](#method.create).
+	#[cfg_attr(feature = "windowless", deprecated = "Sciter.Lite doesn't have OS windows in windowless mode.")]
+	pub fn create_with_state(rect: RECT, flags: Flags, parent: Option<HWINDOW>, state: WindowState) -> Window {
+		if cfg!(feature = "windowless")
+		{
+			panic!("Sciter.Lite doesn't have OS windows in windowless mode!");
+		}
+
 		let mut base = OsWindow::new();
-		let hwnd = base.create(rect, flags.bits() as UINT, parent.unwrap_or(0 as HWINDOW));
+		let hwnd = base.create_with_state(rect, flags.bits() as UINT, parent.unwrap_or(0 as HWINDOW), state);
 		assert!(!hwnd.is_null());
 
-		let wnd = Window { base: base, host: Rc::new(Host::attach(hwnd))};
-		return wnd;
+		Window { base: base, host: Rc::new(Host::attach(hwnd)) }
+	}
+
+	/// Create a new window centered in the work area of the monitor at `monitor_index`
+	/// in the [`enumerate_monitors()`](fn.enumerate_monitors.html) list.
+	///
+	/// The `size` is in the logical pixels, it is scaled by the monitor DPI and clamped to its work area.
+	/// If there is no such monitor (e.g. it has been disconnected), the window goes to the primary one,
+	/// see [`current_monitor`](#method.current_monitor).
+	///
+	/// ```rust,no_run
+	/// # use sciter::window::{self, Window, Flags};
+	/// let last = window::enumerate_monitors().len().saturating_sub(1);
+	/// let flags = Flags::SW_MAIN | Flags::SW_TITLEBAR | Flags::SW_RESIZEABLE;
+	/// let frame = Window::create_on_monitor(last, (800, 600), flags, None);
+	/// ```
+	#[cfg_attr(feature = "windowless", deprecated = "Sciter.Lite doesn't have OS windows in windowless mode.")]
+	pub fn create_on_monitor(monitor_index: usize, size: (i32, i32), flags: Flags, parent: Option<HWINDOW>) -> Window {
+		if cfg!(feature = "windowless")
+		{
+			panic!("Sciter.Lite doesn't have OS windows in windowless mode!");
+		}
+
+		let mut base = OsWindow::new();
+		let hwnd = base.create_on_monitor(monitor_index, size, flags.bits() as UINT, parent.unwrap_or(0 as HWINDOW));
+		assert!(!hwnd.is_null());
+
+		Window { base: base, host: Rc::new(Host::attach(hwnd)) }
+	}
+
+	/// Create a new window which stays hidden until it is shown, e.g. for a tray-only app.
+	///
+	/// The window gets the default size and is kept off the taskbar
+	/// (see [`set_skip_taskbar`](#method.set_skip_taskbar)), even after it is shown.
+	/// [`run_app`](#method.run_app) keeps running the event loop as usual while it is hidden,
+	/// so the app can sit in the tray until [`show`](#method.show) opens the window.
+	#[cfg_attr(feature = "windowless", deprecated = "Sciter.Lite doesn't have OS windows in windowless mode.")]
+	pub fn create_hidden(flags: Flags, parent: Option<HWINDOW>) -> Window {
+		if cfg!(feature = "windowless")
+		{
+			panic!("Sciter.Lite doesn't have OS windows in windowless mode!");
+		}
+
+		let mut base = OsWindow::new();
+		let hwnd = base.create_hidden(flags.bits() as UINT, parent.unwrap_or(0 as HWINDOW));
+		assert!(!hwnd.is_null());
+
+		Window { base: base, host: Rc::new(Host::attach(hwnd)) }
+	}
+
+	/// Create a window that runs the layout and scripts but never becomes visible, e.g. for automated UI tests.
+	///
+	/// It is a message-only window on Windows, a Sciter child in a `GtkOffscreenWindow` on Linux
+	/// and a window outside of the screens on macOS.
+	/// Load a document and drive it with [`pump_events`](#method.pump_events) as usual;
+	/// [`expand`](#method.expand) has no effect on Windows and Linux.
+	///
+	/// Panics if the window can not be created, see [`try_create_offscreen`](#method.try_create_offscreen).
+	#[cfg_attr(feature = "windowless", deprecated = "Sciter.Lite doesn't have OS windows in windowless mode.")]
+	pub fn create_offscreen(size: (i32, i32)) -> Window {
+		if cfg!(feature = "windowless")
+		{
+			panic!("Sciter.Lite doesn't have OS windows in windowless mode!");
+		}
+
+		#[allow(deprecated)]
+		let wnd = Window::try_create_offscreen(size);
+		match wnd {
+			Ok(wnd) => wnd,
+			Err(error) => panic!("Failed to create window: {}", error),
+		}
+	}
+
+	/// Create an offscreen window like [`create_offscreen`](#method.create_offscreen), but return an error instead of panicking.
+	///
+	/// On Linux it fails with `WindowError::CreateFailed` when GTK can not be loaded or initialized, e.g. without a display,
+	/// so a headless test run can skip the UI tests.
+	#[cfg_attr(feature = "windowless", deprecated = "Sciter.Lite doesn't have OS windows in windowless mode.")]
+	pub fn try_create_offscreen(size: (i32, i32)) -> Result<Window, WindowError> {
+		if cfg!(feature = "windowless")
+		{
+			return Err(WindowError::CreateFailed);
+		}
+
+		let mut base = OsWindow::new();
+		let hwnd = base.try_create_offscreen(size)?;
+
+		return Ok(Window { base: base, host: Rc::new(Host::attach(hwnd)) });
 	}
 
 	/// Attach Sciter to an existing native window.
@@ -120,6 +602,10 @@ impl Window {
 	/// Most likely, there is no need for [`run_app`](#method.run_app) or [`run_loop`](#method.run_loop) after that.
 	/// However, to get UI working, you have to route window events
 	/// to Sciter - see the [blog article](https://sciter.com/developers/embedding-principles/).
+	///
+	/// On macOS the view may be not added to a window yet:
+	/// until it is, [`set_title`](#method.set_title), [`expand`](#method.expand), [`collapse`](#method.collapse)
+	/// and [`dismiss`](#method.dismiss) do nothing.
 	pub fn attach(hwnd: HWINDOW) -> Window {
 		// suppress warnings about unused method when compiled as "windowless"
 		let _ = &OsWindow::new;
@@ -128,6 +614,18 @@ impl Window {
 		Window { base: OsWindow::from(hwnd), host: Rc::new(Host::attach(hwnd)) }
 	}
 
+	/// Create a Sciter child window inside of an existing native window, e.g. of another GUI toolkit.
+	///
+	/// The child is created with the `SW_CHILD | SW_CONTROLS` flags at `rect` in the `parent` client coordinates.
+	/// The `parent` window stays owned by its creator:
+	/// [`dismiss`](#method.dismiss) destroys only the Sciter child.
+	#[cfg_attr(feature = "windowless", deprecated = "Sciter.Lite doesn't have OS windows in windowless mode.")]
+	pub fn attach_child(parent: HWINDOW, rect: RECT) -> Window {
+		let base = OsWindow::attach(parent, rect);
+		let hwnd = base.get_hwnd();
+		Window { base: base, host: Rc::new(Host::attach(hwnd)) }
+	}
+
 	/// Attach Sciter to an existing native window and intercept its messages.
 	///
 	/// This will automatically intercept specific messages needed by Sciter
@@ -136,28 +634,14 @@ impl Window {
 	pub fn attach_intercepted(hwnd: HWINDOW) -> Window {
 		assert!(!hwnd.is_null());
 
-		#[cfg(target_pointer_width = "64")]
-		#[link(name="user32")]
-		extern "system"
-		{
-			fn SetWindowLongPtrW(hwnd: HWINDOW, index: i32, new_data: WndProc) -> WndProc;
-			fn CallWindowProcW(prev: WndProc, hwnd: HWINDOW, msg: UINT, wp: WPARAM, lp: LPARAM) -> LRESULT;
-		}
+		use platform::SetWindowLongPtrW;
 
-		#[cfg(target_pointer_width = "32")]
 		#[link(name="user32")]
 		extern "system"
 		{
-			fn SetWindowLongW(hwnd: HWINDOW, index: i32, new_data: WndProc) -> WndProc;
 			fn CallWindowProcW(prev: WndProc, hwnd: HWINDOW, msg: UINT, wp: WPARAM, lp: LPARAM) -> LRESULT;
 		}
 
-		#[cfg(target_pointer_width = "64")]
-		let set_window_proc = SetWindowLongPtrW;
-
-		#[cfg(target_pointer_width = "32")]
-		let set_window_proc = SetWindowLongW;
-
 		type WndProc = extern "system" fn (hwnd: HWINDOW, msg: UINT, wp: WPARAM, lp: LPARAM) -> LRESULT;
 		type PrevProcs = std::collections::HashMap<HWINDOW, WndProc>;
 
@@ -189,125 +673,728 @@ impl Window {
 
 		// Subclass the window in order to receive its messages.
 		const GWLP_WNDPROC: i32 = -4;
-		let prev_proc = unsafe { set_window_proc(hwnd, GWLP_WNDPROC, wnd_proc) };
+		let prev_proc = unsafe { ::std::mem::transmute::<isize, WndProc>(SetWindowLongPtrW(hwnd, GWLP_WNDPROC, wnd_proc as WndProc as usize as isize)) };
 		PREV_PROC.with(|procs| {
 			procs.borrow_mut().insert(hwnd, prev_proc);
 		});
 
-		Window { base: OsWindow::from(hwnd), host: Rc::new(Host::attach(hwnd)) }
+		Window { base: OsWindow::from(hwnd), host: Rc::new(Host::attach(hwnd)) }
+	}
+
+	/// Obtain a reference to [`Host`](../host/struct.Host.html) which offers some advanced control over the Sciter engine instance.
+	pub fn get_host(&self) -> Rc<Host> {
+		self.host.clone()
+	}
+
+	/// Set a [callback](../host/trait.HostHandler.html) for Sciter engine events.
+	pub fn sciter_handler<Callback: HostHandler + Sized>(&mut self, handler: Callback) {
+		self.host.setup_callback(handler);
+	}
+
+	/// Attach [`dom::EventHandler`](../dom/event/trait.EventHandler.html) to the Sciter window.
+	///
+	/// You should install a window event handler only once - it will survive all document reloads.
+	/// Also it can be registered on an empty window before the document is loaded.
+	pub fn event_handler<Handler: EventHandler>(&mut self, handler: Handler) {
+		self.host.attach_handler(handler);
+	}
+
+  /// Register an archive produced by `packfolder` tool.
+	///
+	/// The resources can be accessed via the `this://app/` URL.
+	///
+	/// See documentation of the [`Archive`](../host/struct.Archive.html).
+	///
+  pub fn archive_handler(&mut self, resource: &[u8]) -> Result<(), ()> {
+    self.host.register_archive(resource)
+  }
+
+	/// Register a native event handler for the specified behavior name.
+	///
+	/// Behavior is a named event handler which is created for a particular DOM element.
+	/// In Sciter’s sense, it is a function that is called for different UI events on the DOM element.
+	/// Essentially it is an analog of the [WindowProc](https://en.wikipedia.org/wiki/WindowProc) in Windows.
+	///
+	/// In HTML, there is a `behavior` CSS property that defines the name of a native module
+	/// that is responsible for the initialization and event handling on the element.
+	/// For example, by defining `div { behavior:button; }` you are asking all `<div>` elements in your markup
+	/// to behave as buttons: generate [`BUTTON_CLICK`](../dom/event/enum.BEHAVIOR_EVENTS.html#variant.BUTTON_CLICK)
+	/// DOM events when the user clicks on that element, and be focusable.
+	///
+	/// When the engine discovers an element having `behavior: xyz;` defined in its style,
+	/// it sends the [`SC_ATTACH_BEHAVIOR`](../host/trait.HostHandler.html#method.on_attach_behavior) host notification
+	/// with the name `"xyz"` and an element handle to the application.
+	/// You can consume the notification and respond to it yourself,
+	/// or the default handler walks through the list of registered behavior factories
+	/// and creates an instance of the corresponding [`dom::EventHandler`](../dom/event/trait.EventHandler.html).
+	///
+	/// ## Example:
+	///
+	/// ```rust,no_run
+	/// struct Button;
+	///
+	/// impl sciter::EventHandler for Button {}
+	///
+	/// let mut frame = sciter::Window::new();
+	///
+	/// // register a factory method that creates a new event handler
+	/// // for each element that has "custom-button" behavior:
+	/// frame.register_behavior("custom-button", || { Box::new(Button) });
+	/// ```
+	///
+	/// And in HTML it can be used as:
+	///
+	/// ```html
+	/// <button style="behavior: custom-button">Rusty button</button>
+	/// ```
+	pub fn register_behavior<Factory>(&mut self, name: &str, factory: Factory)
+	where
+		Factory: Fn() -> Box<dyn EventHandler> + 'static
+	{
+		self.host.register_behavior(name, factory);
+	}
+
+	/// Load an HTML document from file.
+	///
+	/// The specified `uri` should be either an absolute file path
+	/// or a full URL to the HTML to load.
+	///
+	/// Supported URL schemes are: `http://`, `file://`; `this://app/` (when used with [`archive_handler`](#method.archive_handler)).
+	///
+	/// ZIP archives [are also supported](https://sciter.com/zip-resource-packaging-in-sciter/).
+	pub fn load_file(&mut self, uri: &str) -> bool {
+		let ok = self.host.load_file(uri);
+		self.keep_zoom();
+		ok
+	}
+
+	/// Load an HTML document from memory.
+	///
+	/// For example, HTML can be loaded from a file in compile time
+	/// via [`include_bytes!`](https://doc.rust-lang.org/nightly/std/macro.include_bytes.html).
+	pub fn load_html(&mut self, html: &[u8], uri: Option<&str>) -> bool {
+		let ok = self.host.load_html(html, uri);
+		self.keep_zoom();
+		ok
+	}
+
+	/// Apply the `set_zoom` factor to the new document.
+	fn keep_zoom(&self) {
+		let zoom = self.base.get_zoom();
+		if zoom != 1.0 {
+			self.base.set_zoom(zoom);
+		}
+	}
+
+	/// Get a native window handle.
+	pub fn get_hwnd(&self) -> HWINDOW {
+		self.base.get_hwnd()
+	}
+
+	/// Give up the ownership of the window and return its native handle, the window stays open after this.
+	///
+	/// [`attach`](#method.attach) can wrap the handle again later, e.g. to close the window.
+	pub fn into_hwnd(mut self) -> HWINDOW {
+		self.base.release();
+		self.get_hwnd()
+	}
+
+	/// Move the live window into another `parent` window (a container widget on Linux, a view on macOS),
+	/// e.g. to dock it; a null `parent` detaches it into a new top-level window.
+	///
+	/// The window is moved rather than recreated, so the loaded document keeps its DOM and script state.
+	/// The former top-level window of a main or popup window is hidden on Linux and macOS.
+	pub fn set_parent(&mut self, parent: HWINDOW) {
+		self.base.set_parent(parent)
+	}
+
+	/// The Win32 `HWND` of the window, the same as [`get_hwnd`](#method.get_hwnd).
+	#[cfg(windows)]
+	pub fn win32_hwnd(&self) -> HWINDOW {
+		self.base.win32_hwnd()
+	}
+
+	/// Add and remove the Win32 window styles, then let the window recalculate its frame.
+	///
+	/// `WindowStyle::EX_TOPMOST` goes through `SetWindowPos` like [`set_topmost`](#method.set_topmost),
+	/// the rest is written back with `SetWindowLongPtr`.
+	#[cfg(windows)]
+	pub fn modify_style(&self, add: WindowStyle, remove: WindowStyle) {
+		self.base.modify_style(add, remove)
+	}
+
+	/// The current Win32 window styles, including the ones unknown to [`WindowStyle`](struct.WindowStyle.html).
+	#[cfg(windows)]
+	pub fn style(&self) -> WindowStyle {
+		self.base.style()
+	}
+
+	/// Intercept the Win32 messages of the window before Sciter, e.g. for a custom hit-testing.
+	///
+	/// `Some(result)` is the result of the message and skips its default processing,
+	/// `None` passes it on to the original window procedure.
+	/// Another call replaces the closure, which is dropped when the window is destroyed.
+	#[cfg(windows)]
+	pub fn subclass<F: Fn(HWINDOW, UINT, WPARAM, LPARAM) -> Option<LRESULT> + 'static>(&mut self, proc: F) {
+		self.base.subclass(proc)
+	}
+
+	/// The toplevel `GtkWindow*` of the window.
+	///
+	/// Unlike [`get_hwnd`](#method.get_hwnd), which is the Sciter widget, it is the one to pass to GTK window functions.
+	/// Null if the widget is not in a toplevel window yet.
+	#[cfg(target_os = "linux")]
+	pub fn gtk_window(&self) -> LPVOID {
+		self.base.gtk_window()
+	}
+
+	/// The `NSWindow` of the window.
+	///
+	/// Note that [`get_hwnd`](#method.get_hwnd) is its content `NSView` on macOS.
+	/// Null if the window is not created.
+	#[cfg(target_os = "macos")]
+	pub fn ns_window(&self) -> *mut ::objc::runtime::Object {
+		self.base.ns_window()
+	}
+
+	/// Minimize or hide the window.
+	///
+	/// `collapse(true)` hides the window, `collapse(false)` minimizes it (on all platforms),
+	/// the same as [`hide`](#method.hide) and [`minimize`](#method.minimize).
+	pub fn collapse(&self, hide: bool) {
+		self.base.collapse(hide)
+	}
+
+	/// Show or maximize the window.
+	///
+	/// `expand(true)` is [`maximize`](#method.maximize); `expand(false)` shows the window
+	/// and also restores a maximized one on Windows (unlike [`show`](#method.show)).
+	pub fn expand(&self, maximize: bool) {
+		self.base.expand(maximize)
+	}
+
+	/// Show the hidden window in the state it had, see [`restore`](#method.restore) to get it back to normal.
+	pub fn show(&self) {
+		self.base.show()
+	}
+
+	/// Hide the window, it stays alive and can be shown again.
+	pub fn hide(&self) {
+		self.base.hide()
+	}
+
+	/// Minimize the window to the taskbar or the Dock.
+	pub fn minimize(&self) {
+		self.base.minimize()
+	}
+
+	/// Show and maximize the window (zoom it on macOS).
+	pub fn maximize(&self) {
+		self.base.maximize()
+	}
+
+	/// Get the window back to the normal state from the minimized, maximized or fullscreen one, and show it.
+	///
+	/// Note that the window managers on Linux apply it asynchronously.
+	pub fn restore(&self) {
+		self.base.restore()
+	}
+
+	/// Bring the window to the foreground and focus it, restoring it first if it is minimized,
+	/// e.g. on a tray icon click or when a second instance of the app is launched.
+	///
+	/// Unlike [`show`](#method.show) it works around the focus stealing prevention of the system
+	/// (Linux window managers may still only flash the window).
+	pub fn activate(&self) {
+		self.base.activate()
+	}
+
+	/// Close the window unconditionally.
+	///
+	/// The document can not prevent it, see [`request_close`](#method.request_close) for that.
+	pub fn dismiss(&self) {
+		self.base.dismiss()
+	}
+
+	/// Ask the window to close, as if the user clicked its close button.
+	///
+	/// Unlike [`dismiss`](#method.dismiss), the closing can be cancelled,
+	/// e.g. by the document (a `closerequest` event handler) to ask about unsaved changes.
+	pub fn request_close(&self) {
+		self.base.request_close()
+	}
+
+	/// Set a callback to be called when the window is asked to close, e.g. via its close button.
+	///
+	/// Returning `false` cancels the closing, e.g. to ask about unsaved changes first;
+	/// [`dismiss`](#method.dismiss) closes the window without asking.
+	/// A new callback replaces the previous one.
+	///
+	/// The callback is called on the UI thread from the message dispatching,
+	/// so it should not close this window by itself (return `true` instead).
+	pub fn on_close<F: Fn() -> bool + 'static>(&mut self, callback: F) {
+		self.base.on_close(callback)
+	}
+
+	/// Set a callback to receive the files dropped onto the window from the file manager.
+	///
+	/// The callback is called once per drop with the absolute paths of all the dropped files and folders.
+	/// A new callback replaces the previous one.
+	///
+	/// Note that it replaces the drag-and-drop handling of the document for the drops from other apps,
+	/// so the HTML `drop` events do not see them anymore.
+	///
+	/// ```rust,no_run
+	/// let mut frame = sciter::Window::new();
+	/// frame.on_file_drop(|files| for file in files {
+	///   println!("dropped {}", file.display());
+	/// });
+	/// ```
+	pub fn on_file_drop<F: Fn(Vec<PathBuf>) + 'static>(&mut self, callback: F) {
+		self.base.on_file_drop(callback)
+	}
+
+	/// Whether to focus the previously focused element again when the window is reactivated, enabled by default.
+	///
+	/// Otherwise the focus after switching back to the window is up to Sciter,
+	/// e.g. an input being edited can lose its caret.
+	pub fn set_restore_focus(&mut self, enable: bool) {
+		self.base.set_restore_focus(enable)
+	}
+
+	/// Call the callback with the new `(width, height)` when the window is resized, by the user or programmatically.
+	///
+	/// The size is measured as [`get_rect`](#method.get_rect) does. A new callback replaces the previous one,
+	/// it is not called after the window is dismissed.
+	pub fn on_resize<F: Fn(i32, i32) + 'static>(&mut self, callback: F) {
+		self.base.on_resize(callback)
+	}
+
+	/// Call the callback with the new `(x, y)` screen position when the window is moved.
+	///
+	/// The position is the one of [`get_rect`](#method.get_rect). A new callback replaces the previous one,
+	/// it is not called after the window is dismissed.
+	pub fn on_move<F: Fn(i32, i32) + 'static>(&mut self, callback: F) {
+		self.base.on_move(callback)
+	}
+
+	/// Call the callback with the new DPI scale factor when it changes, e.g. to reload the bitmaps at the new density.
+	///
+	/// It happens when the window is moved to a monitor with another scale, see also [`get_dpi_scale`](#method.get_dpi_scale).
+	/// On Windows the window is also resized to the rect suggested by the system.
+	/// A new callback replaces the previous one.
+	pub fn on_dpi_changed<F: Fn(f32) + 'static>(&mut self, callback: F) {
+		self.base.on_dpi_changed(callback)
+	}
+
+	/// Call the callback with the new monitor and its refresh rate when the window moves to another monitor
+	/// or the refresh rate of its monitor changes.
+	///
+	/// See also [`current_monitor`](#method.current_monitor) and [`get_refresh_rate`](#method.get_refresh_rate).
+	/// A new callback replaces the previous one.
+	pub fn on_monitor_changed<F: Fn(MonitorInfo, Option<f32>) + 'static>(&mut self, callback: F) {
+		self.base.on_monitor_changed(callback)
+	}
+
+	/// Call the callback with the new system theme when the user switches between the light and dark appearance.
+	///
+	/// Windows reports the `AppsUseLightTheme` setting, macOS the `AppleInterfaceStyle` one,
+	/// and on Linux it is the dark variant of the GTK theme (`gtk-application-prefer-dark-theme` or a `-dark` theme name).
+	/// A new callback replaces the previous one.
+	pub fn on_theme_changed<F: Fn(Theme) + 'static>(&mut self, callback: F) {
+		self.base.on_theme_changed(callback)
+	}
+
+	/// Move the window to the specified screen position (of its top-left corner).
+	///
+	/// Coordinates are in screen pixels (in points on macOS), as in [`create`](#method.create).
+	pub fn move_to(&self, x: i32, y: i32) {
+		self.base.move_to(x, y)
+	}
+
+	/// Resize the window, keeping its top-left corner in place.
+	///
+	/// Note that on Linux the size does not include the window decorations (if any).
+	pub fn resize(&self, width: i32, height: i32) {
+		self.base.resize(width, height)
+	}
+
+	/// Move and resize the window at once, see [`move_to`](#method.move_to) and [`resize`](#method.resize).
+	pub fn set_rect(&self, rc: RECT) {
+		self.base.set_rect(rc)
+	}
+
+	/// Change the window position, size, z-order and state at once, e.g. to restore a saved layout without flicker.
+	///
+	/// On Windows it is a single `SetWindowPos` (or `SetWindowPlacement` to minimize, maximize or restore the window),
+	/// on macOS the frame is changed with a single `setFrame:display:`
+	/// and on Linux GTK sends the move and the resize as one configure request.
+	///
+	/// ```rust,no_run
+	/// # use sciter::window::{GeometryUpdate, WindowState};
+	/// # let frame = sciter::Window::new();
+	/// frame.update_geometry(GeometryUpdate {
+	///   position: Some((2000, 100)),
+	///   size: Some((800, 600)),
+	///   state: Some(WindowState::Maximized),
+	///   ..Default::default()
+	/// });
+	/// ```
+	pub fn update_geometry(&self, changes: GeometryUpdate) {
+		self.base.update_geometry(changes)
+	}
+
+	/// Get the window rectangle in screen coordinates, e.g. to restore the window geometry on the next run.
+	///
+	/// The rectangle can be passed back to [`set_rect`](#method.set_rect).
+	/// On Linux its size does not include the window decorations (see [`resize`](#method.resize)).
+	pub fn get_rect(&self) -> RECT {
+		self.base.get_rect()
+	}
+
+	/// Limit how small the user (or [`resize`](#method.resize)) can make the window.
+	///
+	/// The size is in the same units as [`resize`](#method.resize) uses, `0` for a dimension means no limit.
+	pub fn set_min_size(&self, width: i32, height: i32) {
+		self.base.set_min_size(width, height)
+	}
+
+	/// Limit how large the user (or [`resize`](#method.resize)) can make the window.
+	///
+	/// The size is in the same units as [`resize`](#method.resize) uses, `0` for a dimension means no limit.
+	pub fn set_max_size(&self, width: i32, height: i32) {
+		self.base.set_max_size(width, height)
+	}
+
+	/// Set the [minimum size](#method.set_min_size) to what the loaded document needs, e.g. its CSS `min-width`,
+	/// so the window can not be shrunk to break the layout.
+	///
+	/// It is the minimal intrinsic width of the document and its height at that width, plus the window frame.
+	/// With `track` it is synced again whenever the document is loaded or its content changes, until called without it.
+	pub fn sync_min_size_from_content(&self, track: bool) {
+		self.base.sync_min_size_from_content(track)
+	}
+
+	/// Center the window on the monitor it overlaps most, excluding the taskbar or dock area.
+	///
+	/// Can be called before the window is shown.
+	pub fn center(&self) {
+		self.base.center()
+	}
+
+	/// The monitor containing the largest part of the window, see also [`enumerate_monitors()`](fn.enumerate_monitors.html).
+	///
+	/// Returns `None` if the window has no native handle, or on Linux with GTK older than 3.22.
+	pub fn current_monitor(&self) -> Option<MonitorInfo> {
+		self.base.current_monitor()
+	}
+
+	/// The refresh rate of the [current monitor](#method.current_monitor) in Hz, e.g. to pace a custom render loop.
+	///
+	/// Returns `None` if the system does not report it, see also [`on_monitor_changed`](#method.on_monitor_changed).
+	pub fn get_refresh_rate(&self) -> Option<f32> {
+		self.base.get_refresh_rate()
+	}
+
+	/// Get the window geometry to restore it later with [`apply_placement`](#method.apply_placement).
+	pub fn save_placement(&self) -> WindowPlacement {
+		self.base.save_placement()
+	}
+
+	/// Restore the window geometry saved by [`save_placement`](#method.save_placement).
+	///
+	/// If the monitor of the placement is disconnected, the window goes to the one it overlaps most or to the primary one;
+	/// it is also moved into the monitor work area if it does not fit there.
+	/// A maximized placement shows the window.
+	pub fn apply_placement(&self, placement: &WindowPlacement) {
+		self.base.apply_placement(placement)
+	}
+
+	/// Remove (or restore) the native window decorations, e.g. for an HTML-drawn titlebar.
+	///
+	/// The window stays movable and resizable via the Sciter `window-frame` CSS attributes
+	/// (`role="window-caption"` and others) of the document.
+	pub fn set_frameless(&self, enable: bool) {
+		self.base.set_frameless(enable)
+	}
+
+	/// Allow or forbid resizing the window by the user, e.g. during a modal operation.
+	///
+	/// Unlike the `SW_RESIZEABLE` creation flag, it can be changed at any time; the window keeps its current size.
+	/// On Windows it also removes the maximize button.
+	pub fn set_resizable(&self, enable: bool) {
+		self.base.set_resizable(enable)
+	}
+
+	/// Whether the window can be resized by the user, see [`set_resizable`](#method.set_resizable).
+	pub fn is_resizable(&self) -> bool {
+		self.base.is_resizable()
+	}
+
+	/// Enable or disable the OS drop shadow, e.g. of a [frameless](#method.set_frameless) window with its own HTML chrome.
+	///
+	/// On Windows it turns off the DWM frame rendering and the `CS_DROPSHADOW` style of the Sciter window class,
+	/// i.e. of the windows created afterwards as well. On macOS it sets `hasShadow`.
+	/// GTK does not control the shadow, so it does nothing on Linux.
+	pub fn set_shadow(&self, enable: bool) {
+		self.base.set_shadow(enable)
+	}
+
+	/// Show a native translucent material behind the document, e.g. of a chromeless window with its own HTML frame.
+	///
+	/// The material shows through the transparent parts of the document only, so the window has to be created
+	/// [`alpha`](struct.WindowBuilder.html#method.alpha) (with `SW_ALPHA`) and its HTML must have a transparent background.
+	///
+	/// Windows 11 (22H2+) has all the kinds as the system backdrops, the older versions get `DwmEnableBlurBehindWindow` instead.
+	/// On macOS it is an `NSVisualEffectView` behind the Sciter view of a top-level window.
+	/// On Linux it asks KWin for the blur behind the window, any kind is `Blur` there and the other compositors ignore it.
+	/// Where the kind is not supported, there is no backdrop.
+	pub fn set_backdrop(&self, kind: BackdropKind) {
+		self.base.set_backdrop(kind)
+	}
+
+	/// Set the native background color of the window as `0xAARRGGBB`, shown until the document is painted.
+	///
+	/// It avoids the white flash of a dark themed app between the window creation and the first frame,
+	/// so call it before [`expand`](#method.expand) or use [`WindowBuilder::with_background_color`](struct.WindowBuilder.html#method.with_background_color).
+	/// The alpha is ignored on Windows.
+	pub fn set_background_color(&mut self, color: u32) {
+		self.base.set_background_color(color)
+	}
+
+	/// Route the touch and pen input to the document as the mouse input, e.g. for a touch-first kiosk app.
+	///
+	/// On Windows it registers the window for `WM_TOUCH`: the first finger on the screen drives the mouse
+	/// until it is lifted, the others are ignored meanwhile, and a pen needs some pressure to press the button.
+	/// It is off by default, so the mouse apps get the system touch handling as usual.
+	/// GTK and AppKit deliver the touch input as the pointer events already, it is a no-op there.
+	pub fn enable_touch(&mut self, enable: bool) {
+		self.base.enable_touch(enable)
+	}
+
+	/// Start moving the window with the mouse, e.g. from a custom titlebar of a [frameless](#method.set_frameless) window.
+	///
+	/// Must be called while the (left) mouse button is pressed, i.e. from a `mousedown` handler,
+	/// otherwise it does nothing.
+	pub fn begin_drag(&self) {
+		self.base.begin_drag()
+	}
+
+	/// Tell which part of a [frameless](#method.set_frameless) window is at the `(x, y)` point of the window,
+	/// so the system can move and resize it as if it had a native frame.
+	///
+	/// The coordinates are relative to the top-left corner of the window.
+	/// It is `WM_NCHITTEST` on Windows; on Linux and macOS the mouse presses off the client area
+	/// start the move or resize drags. The borders resize the window on macOS only if it is [resizable](#method.set_resizable).
+	/// A new callback replaces the previous one.
+	pub fn set_hit_test_callback<F: Fn(i32, i32) -> HitTestResult + 'static>(&mut self, callback: F) {
+		self.base.set_hit_test_callback(callback)
+	}
+
+	/// Flash the window in the taskbar (or bounce the dock icon), e.g. on a new message.
+	///
+	/// `urgent` keeps doing it until the window is activated, otherwise it is a brief notification
+	/// (Linux does not distinguish them). Does nothing if the window is active already.
+	pub fn request_attention(&self, urgent: bool) {
+		self.base.request_attention(urgent)
 	}
 
-	/// Obtain a reference to [`Host`](../host/struct.Host.html) which offers some advanced control over the Sciter engine instance.
-	pub fn get_host(&self) -> Rc<Host> {
-		self.host.clone()
+	/// Show the progress of a long operation, `value` is from `0.0` to `1.0`.
+	///
+	/// It is the taskbar button progress on Windows and the dock tile badge of the app on macOS.
+	/// Linux has no common progress indicator, so it does nothing there.
+	pub fn set_progress(&self, state: ProgressState, value: f32) {
+		self.base.set_progress(state, value)
 	}
 
-	/// Set a [callback](../host/trait.HostHandler.html) for Sciter engine events.
-	pub fn sciter_handler<Callback: HostHandler + Sized>(&mut self, handler: Callback) {
-		self.host.setup_callback(handler);
+	/// Show this PNG image as the taskbar thumbnail of the window instead of its content, e.g. for a player cover.
+	///
+	/// It enables the DWM iconic representation, which also needs a live preview, see [`set_live_preview_rect`](#method.set_live_preview_rect).
+	/// Windows only, it does nothing on the other platforms.
+	pub fn set_thumbnail_bitmap(&self, png: &[u8]) {
+		self.base.set_thumbnail_bitmap(png)
 	}
 
-	/// Attach [`dom::EventHandler`](../dom/event/trait.EventHandler.html) to the Sciter window.
+	/// Show only this part of the client area as the taskbar live preview ("peek") of the window.
 	///
-	/// You should install a window event handler only once - it will survive all document reloads.
-	/// Also it can be registered on an empty window before the document is loaded.
-	pub fn event_handler<Handler: EventHandler>(&mut self, handler: Handler) {
-		self.host.attach_handler(handler);
+	/// The preview is made of the window snapshot when DWM asks for it, as is the thumbnail
+	/// until [`set_thumbnail_bitmap`](#method.set_thumbnail_bitmap) sets one.
+	/// Windows only, it does nothing on the other platforms.
+	pub fn set_live_preview_rect(&self, rc: RECT) {
+		self.base.set_live_preview_rect(rc)
 	}
 
-  /// Register an archive produced by `packfolder` tool.
+	/// Keep the window out of the taskbar and the Alt+Tab list, e.g. for tool palettes.
 	///
-	/// The resources can be accessed via the `this://app/` URL.
+	/// On macOS it is excluded from the window cycling and the Window menu,
+	/// since the Dock icon belongs to the whole app.
+	pub fn set_skip_taskbar(&self, skip: bool) {
+		self.base.set_skip_taskbar(skip)
+	}
+
+	/// Set the `AppUserModelID` of the app, which Windows uses to group the taskbar buttons and for jump lists.
 	///
-	/// See documentation of the [`Archive`](../host/struct.Archive.html).
+	/// It applies to the whole process, so it must be called early, before the first window is shown.
+	/// Does nothing on Linux and macOS, so it can be called unconditionally.
+	pub fn set_app_id(&self, app_id: &str) {
+		self.base.set_app_id(app_id)
+	}
+
+	/// Keep the window on top of the other (non-topmost) windows, e.g. for overlays.
 	///
-  pub fn archive_handler(&mut self, resource: &[u8]) -> Result<(), ()> {
-    self.host.register_archive(resource)
-  }
+	/// The setting survives [`collapse`](#method.collapse) and [`expand`](#method.expand) calls.
+	pub fn set_topmost(&self, enable: bool) {
+		self.base.set_topmost(enable)
+	}
 
-	/// Register a native event handler for the specified behavior name.
+	/// Set the whole window opacity, from `0.0` (fully transparent) to `1.0` (opaque).
 	///
-	/// Behavior is a named event handler which is created for a particular DOM element.
-	/// In Sciter’s sense, it is a function that is called for different UI events on the DOM element.
-	/// Essentially it is an analog of the [WindowProc](https://en.wikipedia.org/wiki/WindowProc) in Windows.
+	/// Values outside of this range are clamped.
+	/// Unlike [`Builder::alpha`](struct.Builder.html#method.alpha) windows (where the document defines
+	/// the transparency of each pixel), the window is blended as a whole, e.g. to fade it in or out.
 	///
-	/// In HTML, there is a `behavior` CSS property that defines the name of a native module
-	/// that is responsible for the initialization and event handling on the element.
-	/// For example, by defining `div { behavior:button; }` you are asking all `<div>` elements in your markup
-	/// to behave as buttons: generate [`BUTTON_CLICK`](../dom/event/enum.BEHAVIOR_EVENTS.html#variant.BUTTON_CLICK)
-	/// DOM events when the user clicks on that element, and be focusable.
+	/// Note that on Windows it makes the window layered, so it is not intended for `alpha()` windows,
+	/// and on Linux it requires a compositing window manager.
+	pub fn set_opacity(&self, alpha: f32) {
+		self.base.set_opacity(alpha)
+	}
+
+	/// Force the mouse cursor over the window regardless of the document cursors, e.g. the busy one during a long operation.
 	///
-	/// When the engine discovers an element having `behavior: xyz;` defined in its style,
-	/// it sends the [`SC_ATTACH_BEHAVIOR`](../host/trait.HostHandler.html#method.on_attach_behavior) host notification
-	/// with the name `"xyz"` and an element handle to the application.
-	/// You can consume the notification and respond to it yourself,
-	/// or the default handler walks through the list of registered behavior factories
-	/// and creates an instance of the corresponding [`dom::EventHandler`](../dom/event/trait.EventHandler.html).
+	/// `CursorKind::Default` gives the cursor back to the document.
+	/// On macOS the cursor is pushed over the current one and applies until the document changes it.
+	pub fn set_cursor(&self, cursor: CursorKind) {
+		self.base.set_cursor(cursor)
+	}
+
+	/// Confine the mouse cursor to the window client area, e.g. for games and canvas editors.
 	///
-	/// ## Example:
+	/// The clip is released when the window loses the focus and applied again when it regains it, until it is disabled.
+	/// On Linux it is a pointer grab confined to the window, which works on X11 only;
+	/// macOS has no confinement, so the cursor is locked in place over the window and only the mouse deltas are reported.
+	pub fn set_cursor_clip(&self, enable: bool) {
+		self.base.set_cursor_clip(enable)
+	}
+
+	/// The current mouse cursor position relative to the window client area, e.g. to place a context menu outside of the event handlers.
 	///
-	/// ```rust,no_run
-	/// struct Button;
+	/// The point is outside of the client area if the mouse is not over the window.
+	/// It is in logical pixels on macOS and Linux, in physical ones on Windows, as the window geometry is.
+	pub fn cursor_position(&self) -> POINT {
+		self.base.cursor_position()
+	}
+
+	/// The mouse buttons held down right now, whether the mouse is over the window or not.
+	pub fn mouse_buttons(&self) -> MouseButtons {
+		self.base.mouse_buttons()
+	}
+
+	/// Call the `callback` when the keyboard shortcut is pressed in the window,
+	/// before the document gets the key, even if a focused input would handle it.
 	///
-	/// impl sciter::EventHandler for Button {}
+	/// The callback returns `true` if it has handled the key; otherwise the document gets it as usual.
+	/// Registering the same accelerator again replaces its callback.
 	///
-	/// let mut frame = sciter::Window::new();
+	/// On Windows the main loops of the crate (`run_app`, `run_app_with`, `run_modal` and `pump_events`) check the accelerators;
+	/// on Linux they go to a `GtkAccelGroup` of the toplevel window, and a local `NSEvent` monitor checks them on macOS.
+	pub fn register_accelerator<F: Fn() -> bool + 'static>(&mut self, accelerator: Accelerator, callback: F) {
+		self.base.register_accelerator(accelerator, callback)
+	}
+
+	/// Capture what the window currently renders as a PNG image.
 	///
-	/// // register a factory method that creates a new event handler
-	/// // for each element that has "custom-button" behavior:
-	/// frame.register_behavior("custom-button", || { Box::new(Button) });
+	/// Only the client area is captured, in physical pixels (see [`get_dpi_scale`](#method.get_dpi_scale)).
+	/// Fails if the window is not shown or its area is empty.
+	///
+	/// ```rust,no_run
+	/// # let frame = sciter::Window::new();
+	/// let png = frame.snapshot().expect("the window is not shown");
+	/// std::fs::write("screenshot.png", png).unwrap();
 	/// ```
+	pub fn snapshot(&self) -> Result<Vec<u8>, ()> {
+		self.base.snapshot()
+	}
+
+	/// Get the current window state.
 	///
-	/// And in HTML it can be used as:
+	/// For example, a toolbar button can decide whether to maximize or to restore the window:
 	///
-	/// ```html
-	/// <button style="behavior: custom-button">Rusty button</button>
+	/// ```rust,no_run
+	/// # use sciter::window::WindowState;
+	/// # let frame = sciter::Window::new();
+	/// if frame.get_state() == WindowState::Maximized {
+	///   frame.expand(false);
+	/// } else {
+	///   frame.expand(true);
+	/// }
 	/// ```
-	pub fn register_behavior<Factory>(&mut self, name: &str, factory: Factory)
-	where
-		Factory: Fn() -> Box<dyn EventHandler> + 'static
-	{
-		self.host.register_behavior(name, factory);
+	pub fn get_state(&self) -> WindowState {
+		self.base.get_state()
 	}
 
-	/// Load an HTML document from file.
+	/// Whether the window is shown, a cheaper check than [`get_state`](#method.get_state).
 	///
-	/// The specified `uri` should be either an absolute file path
-	/// or a full URL to the HTML to load.
+	/// Minimized windows are visible on Windows and Linux, but not on macOS.
+	pub fn is_visible(&self) -> bool {
+		self.base.is_visible()
+	}
+
+	/// Whether the window has the keyboard focus, e.g. to throttle the rendering while it is in the background.
 	///
-	/// Supported URL schemes are: `http://`, `file://`; `this://app/` (when used with [`archive_handler`](#method.archive_handler)).
+	/// It is the foreground window on Windows (or a child window with the focus in it), the active one on Linux
+	/// and the key window on macOS.
+	pub fn is_focused(&self) -> bool {
+		self.base.is_focused()
+	}
+
+	/// Get the DPI scale factor of the monitor the window is on, `1.0` means 96 DPI.
 	///
-	/// ZIP archives [are also supported](https://sciter.com/zip-resource-packaging-in-sciter/).
-	pub fn load_file(&mut self, uri: &str) -> bool {
-		self.host.load_file(uri)
+	/// On Linux and macOS it is the integer scale of the desktop (`2.0` for Retina displays).
+	pub fn get_dpi_scale(&self) -> f32 {
+		self.base.get_dpi_scale()
 	}
 
-	/// Load an HTML document from memory.
+	/// Scale the whole document, e.g. for an accessibility "zoom" control, without changing the OS DPI.
 	///
-	/// For example, HTML can be loaded from a file in compile time
-	/// via [`include_bytes!`](https://doc.rust-lang.org/nightly/std/macro.include_bytes.html).
-	pub fn load_html(&mut self, html: &[u8], uri: Option<&str>) -> bool {
-		self.host.load_html(html, uri)
+	/// The `factor` is clamped to `0.25..=5.0` and multiplies the monitor scale reported by `get_dpi_scale`.
+	/// It goes to the CSS `zoom` of the document root and is kept by `load_file` and `load_html`.
+	pub fn set_zoom(&self, factor: f32) {
+		self.base.set_zoom(factor)
 	}
 
-	/// Get a native window handle.
-	pub fn get_hwnd(&self) -> HWINDOW {
-		self.base.get_hwnd()
+	/// The zoom factor set by `set_zoom`, `1.0` by default.
+	pub fn get_zoom(&self) -> f32 {
+		self.base.get_zoom()
 	}
 
-	/// Minimize or hide the window.
-	pub fn collapse(&self, hide: bool) {
-		self.base.collapse(hide)
+	/// Time since the last keyboard or mouse input, e.g. for idle detection.
+	///
+	/// On Windows and Linux it is the input of this window (counted since its creation),
+	/// on macOS it is system-wide (the whole user session).
+	pub fn last_input_time(&self) -> ::std::time::Duration {
+		self.base.last_input_time()
 	}
 
-	/// Show or maximize the window.
-	pub fn expand(&self, maximize: bool) {
-		self.base.expand(maximize)
+	/// Put the text on the system clipboard, e.g. for a "copy" button handled in Rust.
+	pub fn clipboard_set_text(&self, text: &str) {
+		self.base.clipboard_set_text(text)
 	}
 
-	/// Close the window.
-	pub fn dismiss(&self) {
-		self.base.dismiss()
+	/// Get the text of the system clipboard, `None` if it holds no text.
+	///
+	/// On Linux it waits for the clipboard owner to respond, running the main loop meanwhile.
+	pub fn clipboard_get_text(&self) -> Option<String> {
+		self.base.clipboard_get_text()
+	}
+
+	/// Enter or leave fullscreen mode on the current monitor.
+	///
+	/// Unlike [`expand(true)`](#method.expand), the window covers the whole monitor without any decorations.
+	/// Leaving fullscreen restores the previous window geometry.
+	///
+	/// Note that on macOS it is an animated transition to a separate space.
+	pub fn set_fullscreen(&self, enable: bool) {
+		self.base.set_fullscreen(enable)
 	}
 
 	/// Set a new window title.
@@ -320,6 +1407,34 @@ impl Window {
 		self.base.get_title()
 	}
 
+	/// Set a stable identifier of the native window for the UI automation tools.
+	///
+	/// On Windows it is the `AutomationId` of the window client,
+	/// it is also stored as a global atom in the `SciterAutomationId` window property.
+	/// On macOS it is the `accessibilityIdentifier` of the view and on Linux the ATK accessible id (ATK 2.34+).
+	pub fn set_automation_id(&mut self, id: &str) {
+		self.base.set_automation_id(id)
+	}
+
+	/// Set the accessible name of the native window, which the screen readers announce instead of the title.
+	///
+	/// On Windows it is also stored as a global atom in the `SciterAccessibleName` window property.
+	pub fn set_accessible_name(&mut self, name: &str) {
+		self.base.set_accessible_name(name)
+	}
+
+	/// Set the window icon (in the titlebar and taskbar) from PNG data, an empty slice clears the icon.
+	///
+	/// ```rust,no_run
+	/// let mut frame = sciter::Window::new();
+	/// frame.set_icon(include_bytes!("../examples/icon.png"));
+	/// ```
+	///
+	/// Note that on macOS windows do not have icons, so the application icon in the Dock is changed instead.
+	pub fn set_icon(&mut self, image: &[u8]) {
+		self.base.set_icon(image)
+	}
+
 	/// Set various Sciter engine options, see the [`Options`](enum.Options.html).
 	pub fn set_options(&self, options: Options) -> Result<(), ()> {
 		use capi::scdef::SCITER_RT_OPTIONS::*;
@@ -377,6 +1492,114 @@ impl Window {
 		}
 	}
 
+	/// Wait until an element matching the CSS `selector` appears in the document.
+	///
+	/// The message loop is running meanwhile, so the document can be populated by (asynchronous) script.
+	/// The document is searched again on its mutations (`CONTENT_CHANGED` events) and every 50 ms,
+	/// since changes of the attributes, classes or states (e.g. waiting for `.ready`) raise no such events.
+	///
+	/// Returns `Ok(None)` if the `timeout` expires first. The timeout is not an error here:
+	/// `Err` carries the `SCDOM_RESULT` of the DOM calls, which has no code for it,
+	/// and waiting for an element that never appears is a plain way to let the document settle.
+	///
+	/// It is mostly useful for UI integration tests:
+	///
+	/// ```rust,no_run
+	/// # use std::time::Duration;
+	/// let mut frame = sciter::Window::new();
+	/// frame.load_html(b"<html><script>setTimeout(() => document.body.innerHTML = '<b#done/>', 100)</script></html>", None);
+	/// let done = frame.wait_for_selector("#done", Duration::from_secs(1)).unwrap();
+	/// assert!(done.is_some());
+	/// ```
+	pub fn wait_for_selector(&self, selector: &str, timeout: ::std::time::Duration) -> dom::Result<Option<dom::Element>> {
+		use std::cell::Cell;
+		use std::time::Instant;
+		use capi::scdef::SCITER_APP_CMD;
+		use dom::HELEMENT;
+		use dom::event::{EVENT_GROUPS, BEHAVIOR_EVENTS, PHASE_MASK, EventReason};
+
+		// wakes up the message loop to check the timeout and to search again
+		const WAIT_TIMER: u64 = 0x5741_4954;
+
+		struct Watcher {
+			recheck: Rc<Cell<bool>>,
+		}
+
+		impl EventHandler for Watcher {
+			fn get_subscription(&mut self) -> Option<EVENT_GROUPS> {
+				Some(EVENT_GROUPS::HANDLE_BEHAVIOR_EVENT | EVENT_GROUPS::HANDLE_TIMER)
+			}
+
+			fn on_event(&mut self, _: HELEMENT, _: HELEMENT, _: HELEMENT, code: BEHAVIOR_EVENTS, _: PHASE_MASK, _: EventReason) -> bool {
+				if code == BEHAVIOR_EVENTS::CONTENT_CHANGED {
+					self.recheck.set(true);
+				}
+				false
+			}
+
+			fn on_timer(&mut self, _root: HELEMENT, timer_id: u64) -> bool {
+				if timer_id == WAIT_TIMER {
+					self.recheck.set(true);
+					return true;
+				}
+				false
+			}
+		}
+
+		let mut root = dom::Element::from_window(self.get_hwnd())?;
+		if let Some(found) = root.find_first(selector)? {
+			return Ok(Some(found));
+		}
+
+		let recheck = Rc::new(Cell::new(false));
+		let token = root.attach_handler(Watcher { recheck: recheck.clone() })?;
+		root.start_timer(50, WAIT_TIMER)?;
+
+		let deadline = Instant::now() + timeout;
+		let mut result = Ok(None);
+		while Instant::now() < deadline {
+			(_API.SciterExec)(SCITER_APP_CMD::SCITER_APP_LOOP_ITERATION.bits(), 0, 0);
+			if recheck.replace(false) {
+				result = root.find_first(selector);
+				if !matches!(result, Ok(None)) {
+					break;
+				}
+			}
+		}
+
+		root.stop_timer(WAIT_TIMER).ok();
+		root.detach_handler::<Watcher>(token).ok();
+		return result;
+	}
+
+	/// Convert a point from the window client area to the local space of the `element`.
+	///
+	/// The local space origin is the top-left corner of the element border box,
+	/// shifted by the element own scroll position, so the result is in the coordinates of the element content.
+	/// The scroll positions of the containers and the element translations are already included
+	/// in its view-relative location (see [`Element::rect_in`](../dom/struct.Element.html#method.rect_in)).
+	///
+	/// It is the inverse of [`element_to_client`](#method.element_to_client).
+	pub fn client_to_element(&self, element: &dom::Element, point: POINT) -> dom::Result<POINT> {
+		let origin = Self::element_origin(element)?;
+		Ok(POINT { x: point.x - origin.x, y: point.y - origin.y })
+	}
+
+	/// Convert a point from the local space of the `element` to the window client area.
+	///
+	/// See [`client_to_element`](#method.client_to_element) for the local space definition.
+	pub fn element_to_client(&self, element: &dom::Element, point: POINT) -> dom::Result<POINT> {
+		let origin = Self::element_origin(element)?;
+		Ok(POINT { x: point.x + origin.x, y: point.y + origin.y })
+	}
+
+	/// Window position of the element content origin.
+	fn element_origin(element: &dom::Element) -> dom::Result<POINT> {
+		let rc = element.rect_in(dom::CoordSpace::Window)?;
+		let (scroll, _, _) = element.scroll_info()?;
+		Ok(POINT { x: rc.left - scroll.x, y: rc.top - scroll.y })
+	}
+
 	/// Show window and run the main app message loop until the main window is closed.
 	pub fn run_app(self) {
 		self.base.expand(false);
@@ -388,10 +1611,141 @@ impl Window {
 		self.base.run_app();
 	}
 
+	/// Show window and run the main app message loop, calling `idle` whenever the loop would wait for the events.
+	///
+	/// `idle` is called periodically (about 60 times per second) while there are no events,
+	/// e.g. to drain a channel of the worker threads and update the document on the UI thread
+	/// without another thread to post the updates.
+	pub fn run_app_with<F: FnMut() + 'static>(&self, idle: F) {
+		self.base.expand(false);
+		self.base.run_app_with(idle);
+	}
+
+	/// Show the window as a modal dialog of the `parent` and wait until it is closed.
+	///
+	/// The parent does not get the user input meanwhile: it is disabled on Windows,
+	/// the window is modal and transient for it on Linux and it runs `runModalForWindow:` on macOS.
+	/// The parent is enabled and activated again however the dialog is closed, including the system close button.
+	/// If the app quits meanwhile, it returns with the dialog still open and the quit goes to the outer loop.
+	pub fn run_modal(&self, parent: &Window) {
+		self.base.run_modal(&parent.base)
+	}
+
+	/// Process all pending UI events without blocking, for apps which drive their own event loop
+	/// instead of [`run_app`](#method.run_app).
+	///
+	/// Returns `false` once [`quit_app`](#method.quit_app) has been called, the event loop should stop then.
+	pub fn pump_events(&self) -> bool {
+		self.base.pump_events()
+	}
+
+	/// Process the events until the window settles down, handy for tests.
+	///
+	/// Returns once no events (including the Sciter timers, animations and repaints) arrived for 100 ms,
+	/// or `false` if the `timeout` elapsed or the app quit first.
+	///
+	/// Sciter does not report its pending timers, so a timer due later than that is not waited for,
+	/// see [`run_until_idle_with`](#method.run_until_idle_with) for longer ones.
+	pub fn run_until_idle(&self, timeout: ::std::time::Duration) -> bool {
+		self.run_until_idle_with(::std::time::Duration::from_millis(100), timeout)
+	}
+
+	/// Process the events until none arrived for the `settle` period, see [`run_until_idle`](#method.run_until_idle).
+	///
+	/// The `settle` period should be longer than the script timers to wait for.
+	pub fn run_until_idle_with(&self, settle: ::std::time::Duration, timeout: ::std::time::Duration) -> bool {
+		self.base.run_until_idle(settle, timeout)
+	}
+
 	/// Post a quit message for the app.
+	///
+	/// Must be called on the UI thread, see [`quit_handle`](#method.quit_handle) for the other threads.
 	pub fn quit_app(&self) {
 		self.base.quit_app()
 	}
+
+	/// Quit the app from the UI thread asynchronously, like [`QuitHandle::post_quit`](struct.QuitHandle.html#method.post_quit) does.
+	pub fn post_quit(&self) {
+		self.base.post_quit()
+	}
+
+	/// Get a handle to quit the app from any thread, e.g. when a background job fails.
+	pub fn quit_handle(&self) -> QuitHandle {
+		QuitHandle { hwnd: self.get_hwnd() as usize }
+	}
+
+	/// Get a handle to run closures on the UI thread from any thread, e.g. to show a result of a background job.
+	pub fn proxy(&self) -> UiProxy {
+		UiProxy { hwnd: self.get_hwnd() as usize }
+	}
+}
+
+
+/// Quits the app from any thread, see [`Window::quit_handle()`](struct.Window.html#method.quit_handle).
+///
+/// ```rust,no_run
+/// let frame = sciter::Window::new();
+/// let quit = frame.quit_handle();
+/// std::thread::spawn(move || {
+///   // ... some long job
+///   quit.post_quit();
+/// });
+/// frame.run_app();
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct QuitHandle {
+	// `HWINDOW` is not `Send`, but it is used only to post a message to the UI thread.
+	hwnd: usize,
+}
+
+impl QuitHandle {
+	/// Ask the UI thread to quit the app; [`run_app`](struct.Window.html#method.run_app) returns then.
+	///
+	/// Does not wait for it.
+	pub fn post_quit(&self) {
+		OsWindow::from(self.hwnd as HWINDOW).post_quit()
+	}
+}
+
+/// Runs closures on the UI thread from any thread, see [`Window::proxy()`](struct.Window.html#method.proxy).
+///
+/// ```rust,no_run
+/// let frame = sciter::Window::new();
+/// let proxy = frame.proxy();
+/// std::thread::spawn(move || {
+///   let answer = 42; // ... some long job
+///   proxy.post(move || {
+///     // the DOM and `Value` calls are allowed here
+///     println!("the answer is {}", answer);
+///   });
+/// });
+/// frame.run_app();
+/// ```
+#[derive(Clone, Copy, Debug)]
+pub struct UiProxy {
+	// `HWINDOW` is not `Send`, but it is used only to post a message to the UI thread.
+	hwnd: usize,
+}
+
+impl UiProxy {
+	/// Run the closure on the UI thread once the event loop gets to it.
+	///
+	/// Does not wait for it. The closure runs exactly once, closures posted before the window is closed still run.
+	/// On Windows, the ones posted after that are dropped without running;
+	/// on Linux and macOS they run anyway, as the event loop does not depend on the window.
+	pub fn post<F: FnOnce() + Send + 'static>(&self, f: F) {
+		OsWindow::from(self.hwnd as HWINDOW).post_task(Box::new(f))
+	}
+}
+
+/// Native window handle for the other toolkits, e.g. to render via `wgpu` into a Sciter window.
+///
+/// Requires the `raw-window-handle` feature.
+#[cfg(feature = "raw-window-handle")]
+unsafe impl ::raw_window_handle::HasRawWindowHandle for Window {
+	fn raw_window_handle(&self) -> ::raw_window_handle::RawWindowHandle {
+		::raw_window_handle::HasRawWindowHandle::raw_window_handle(&self.base)
+	}
 }
 
 
@@ -417,11 +1771,29 @@ pub struct Rectangle {
 ///   .glassy()
 ///   .create();
 /// ```
+///
+/// or without the style methods:
+///
+/// ```rust,no_run
+/// let mut frame = sciter::window::Builder::main_window()
+///   .with_size((400, 300))
+///   .title("Settings")
+///   .resizable(false)
+///   .create();
+/// ```
 #[derive(Default)]
 pub struct Builder {
 	flags: Flags,
 	rect: RECT,
 	parent: Option<HWINDOW>,
+	title: Option<String>,
+	center: bool,
+	transparent: bool,
+	frameless: bool,
+	state: Option<WindowState>,
+	background: Option<u32>,
+	#[cfg(windows)]
+	create_hook: Option<CreateHook>,
 }
 
 // Note: https://rust-lang-nursery.github.io/api-guidelines/type-safety.html#non-consuming-builders-preferred
@@ -429,11 +1801,14 @@ impl Builder {
 
 	/// Main application window (resizeable with min/max buttons and title).
 	/// Will terminate the app on close.
+	///
+	/// It is centered on the screen unless a position is specified.
 	pub fn main_window() -> Self {
 		Builder::main()
 			.resizeable()
 			.closeable()
 			.with_title()
+			.centered()
 	}
 
 	/// Popup window (with min/max buttons and title).
@@ -492,6 +1867,7 @@ impl Builder {
 
 	/// Specify the precise window position in `(X, Y)` form.
 	pub fn with_pos(mut self, position: (i32, i32)) -> Self {
+		self.center = false;
 		let size = self.rect.size();
 		self.rect.left = position.0;
 		self.rect.top = position.1;
@@ -502,6 +1878,7 @@ impl Builder {
 
 	/// Specify the exact window rectangle in `(X, Y, W, H)` form.
 	pub fn with_rect(mut self, rect: Rectangle) -> Self {
+		self.center = false;
 		self.rect = RECT {
 			left: rect.x,
 			top: rect.y,
@@ -511,11 +1888,33 @@ impl Builder {
 		self
 	}
 
+	/// Center the window on the screen, see [`Window::center()`](struct.Window.html#method.center).
+	pub fn centered(mut self) -> Self {
+		self.center = true;
+		self
+	}
+
+	/// Set the window title text, see [`Window::set_title()`](struct.Window.html#method.set_title).
+	pub fn title(mut self, title: &str) -> Self {
+		self.title = Some(title.to_owned());
+		self
+	}
+
 	/// Top level window, has titlebar.
 	pub fn with_title(self) -> Self {
 		self.or(SCITER_CREATE_WINDOW_FLAGS::SW_TITLEBAR)
 	}
 
+	/// Whether the window has a titlebar, like [`with_title()`](#method.with_title).
+	pub fn with_titlebar(self, enable: bool) -> Self {
+		if enable { self.or(SCITER_CREATE_WINDOW_FLAGS::SW_TITLEBAR) } else { self.and(SCITER_CREATE_WINDOW_FLAGS::SW_TITLEBAR) }
+	}
+
+	/// Whether the window can be resized, like [`resizeable()`](#method.resizeable) or [`fixed()`](#method.fixed).
+	pub fn resizable(self, enable: bool) -> Self {
+		if enable { self.resizeable() } else { self.fixed() }
+	}
+
 	/// Can be resized.
 	pub fn resizeable(self) -> Self {
 		self.or(SCITER_CREATE_WINDOW_FLAGS::SW_RESIZEABLE)
@@ -546,6 +1945,52 @@ impl Builder {
 		self.or(SCITER_CREATE_WINDOW_FLAGS::SW_ENABLE_DEBUG)
 	}
 
+	/// Transparent window which stays transparent until the document is painted.
+	///
+	/// Implies [`alpha()`](#method.alpha), but also prepares the native window for per-pixel alpha up front
+	/// and loads a blank transparent document, so there is no opaque flash before the actual document is loaded.
+	///
+	/// Note that the document defines the final background: the root element must have
+	/// a transparent (or translucent) CSS `background`, e.g. `html { background: transparent; }`,
+	/// otherwise its background is painted as usual.
+	pub fn with_transparent_background(mut self) -> Self {
+		self.transparent = true;
+		self.alpha()
+	}
+
+	/// Window without the native decorations, see [`Window::set_frameless()`](struct.Window.html#method.set_frameless).
+	pub fn frameless(mut self) -> Self {
+		self.frameless = true;
+		self
+	}
+
+	/// Native background color as `0xAARRGGBB` until the document is painted, see [`Window::set_background_color()`](struct.Window.html#method.set_background_color).
+	pub fn with_background_color(mut self, color: u32) -> Self {
+		self.background = Some(color);
+		self
+	}
+
+	/// Adjust the Win32 parameters of the window, e.g. to add a class style:
+	/// the `hook` runs right before `SciterCreateWindow`, see [`WindowCreateParams`](struct.WindowCreateParams.html) for how they are applied.
+	///
+	/// ```rust,no_run
+	/// const CS_DBLCLKS: u32 = 0x0008;
+	/// let frame = sciter::WindowBuilder::main_window()
+	///   .with_create_hook(|params| params.class_style |= CS_DBLCLKS)
+	///   .create();
+	/// ```
+	#[cfg(windows)]
+	pub fn with_create_hook<F: FnOnce(&mut WindowCreateParams) + 'static>(mut self, hook: F) -> Self {
+		self.create_hook = Some(Box::new(hook));
+		self
+	}
+
+	/// Show the created window in the given state right away, see [`Window::create_with_state()`](struct.Window.html#method.create_with_state).
+	pub fn with_state(mut self, state: WindowState) -> Self {
+		self.state = Some(state);
+		self
+	}
+
 	fn or(mut self, flag: Flags) -> Self {
 		self.flags = self.flags | flag;
 		self
@@ -560,6 +2005,77 @@ impl Builder {
 	/// Consume the builder and call [`Window::create()`](struct.Window.html#method.create) with built parameters.
 	#[cfg_attr(feature = "windowless", deprecated = "Sciter.Lite doesn't have OS windows in windowless mode.")]
 	pub fn create(self) -> Window {
-		Window::create(self.rect, self.flags, self.parent)
+		#[allow(deprecated)]
+		let wnd = self.try_create();
+		match wnd {
+			Ok(wnd) => wnd,
+			Err(error) => panic!("Failed to create window: {}", error),
+		}
+	}
+
+	/// Consume the builder and call [`Window::try_create()`](struct.Window.html#method.try_create) with built parameters.
+	#[cfg_attr(feature = "windowless", deprecated = "Sciter.Lite doesn't have OS windows in windowless mode.")]
+	pub fn try_create(self) -> Result<Window, WindowError> {
+		#[allow(unused_mut)]
+		let mut base = OsWindow::new();
+		#[cfg(windows)]
+		{
+			if let Some(hook) = self.create_hook {
+				base.set_create_hook(hook);
+			}
+		}
+		let mut wnd = Window::try_create_from(base, self.rect, self.flags, self.parent)?;
+		if let Some(color) = self.background {
+			// the window is still hidden
+			wnd.set_background_color(color);
+		}
+		if let Some(title) = self.title {
+			wnd.set_title(&title);
+		}
+		if self.center && !self.flags.contains(SCITER_CREATE_WINDOW_FLAGS::SW_CHILD) {
+			wnd.center();
+		}
+		if self.transparent {
+			wnd.base.set_transparent_background();
+			wnd.set_options(Options::AlphaWindow(true)).ok();
+			wnd.load_html(b"<html style='background: transparent'></html>", None);
+		}
+		if self.frameless {
+			wnd.base.set_frameless(true);
+		}
+		if let Some(state) = self.state {
+			// after the styles, so the window is shown with them already
+			wnd.base.show_in_state(state);
+		}
+		Ok(wnd)
+	}
+}
+
+
+#[cfg(test)]
+mod tests {
+	use super::{Builder, Flags};
+
+	#[test]
+	fn main_window_defaults() {
+		let builder = Builder::main_window();
+		assert_eq!(builder.flags, Flags::SW_MAIN | Flags::SW_RESIZEABLE | Flags::SW_CONTROLS | Flags::SW_TITLEBAR);
+		assert!(builder.center);
+		assert!(builder.title.is_none());
+
+		// an explicit position wins
+		assert!(!Builder::main_window().with_pos((10, 10)).center);
+	}
+
+	#[test]
+	fn builder_toggles() {
+		let builder = Builder::main_window().resizable(false).title("Settings");
+		assert!(!builder.flags.contains(Flags::SW_RESIZEABLE));
+		assert!(builder.flags.contains(Flags::SW_TITLEBAR));
+		assert_eq!(builder.title.as_ref().map(String::as_str), Some("Settings"));
+
+		let builder = builder.resizable(true).with_titlebar(false);
+		assert!(builder.flags.contains(Flags::SW_RESIZEABLE));
+		assert!(!builder.flags.contains(Flags::SW_TITLEBAR));
 	}
 }
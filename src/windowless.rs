@@ -11,8 +11,13 @@ the difference between the desktop and the windowless Sciter engine versions.
 use ::{_API};
 use capi::scdef::{GFX_LAYER};
 use capi::scdom::HELEMENT;
-use capi::sctypes::{HWINDOW, POINT, UINT, BOOL, RECT, LPCBYTE, LPVOID, INT};
+use capi::sctypes::{HWINDOW, POINT, SIZE, UINT, BOOL, RECT, LPCBYTE, LPVOID, INT};
 use capi::scmsg::*;
+use capi::scgraphics::GRAPHIN_RESULT;
+use graphics::{self, Graphics, Image};
+use std::cell::RefCell;
+use std::collections::HashMap;
+use std::rc::Rc;
 
 pub use capi::scmsg::key_codes;
 pub use capi::scbehavior::{MOUSE_BUTTONS, MOUSE_EVENTS, KEYBOARD_STATES, KEY_EVENTS};
@@ -78,6 +83,22 @@ pub enum Message {
 	Keyboard(KeyboardEvent),
 }
 
+/// Host input events to forward to Sciter, see [`handle_input`](fn.handle_input.html).
+#[derive(Debug)]
+pub enum InputEvent {
+	/// Mouse input.
+	Mouse(MouseEvent),
+
+	/// Keyboard input.
+	Keyboard(KeyboardEvent),
+
+	/// The rendering surface has got or lost the input focus.
+	Focus {
+		/// Whether the focus is got.
+		enter: bool,
+	},
+}
+
 /// Events describing the mouse input.
 #[derive(Debug)]
 pub struct MouseEvent {
@@ -155,11 +176,80 @@ pub struct DxgiRenderEvent {
 }
 
 
+/// `on_invalidate` callback, gets the invalidated area.
+type InvalidateCallback = Rc<dyn Fn(&RECT)>;
+
+/// Redraw state of an engine instance.
+struct Invalidation {
+	/// Whether it is invalidated since the last painting.
+	dirty: bool,
+	callback: Option<InvalidateCallback>,
+}
+
+thread_local! {
+	/// Redraw states of the engine instances, see `needs_redraw`.
+	static INVALIDATIONS: RefCell<HashMap<HWINDOW, Invalidation>> = Default::default();
+}
+
+/// Whether Sciter wants the document to be repainted, since it was painted last time.
+///
+/// It is set by the `SC_INVALIDATE_RECT` notifications, which need a [`Host`](../host/struct.Host.html) attached to `wnd`,
+/// and reset by the painting messages and [`render_frame`](fn.render_frame.html).
+/// Running animations and transitions keep invalidating the document on each [`Heartbit`](enum.Message.html#variant.Heartbit),
+/// so the embedder can skip the frames while it is `false`.
+/// A new instance needs to be painted.
+pub fn needs_redraw(wnd: HWINDOW) -> bool {
+	INVALIDATIONS.with(|states| match states.borrow().get(&wnd) {
+		Some(state) => state.dirty,
+		None => true,
+	})
+}
+
+/// Call the callback with the invalidated area whenever Sciter wants to repaint the document,
+/// e.g. to wake up the render loop of an idle UI. See also [`needs_redraw`](fn.needs_redraw.html).
+///
+/// A new callback replaces the previous one, the callback is dropped with [`Destroy`](enum.Message.html#variant.Destroy).
+pub fn on_invalidate<F: Fn(&RECT) + 'static>(wnd: HWINDOW, callback: F) {
+	INVALIDATIONS.with(|states| {
+		states.borrow_mut().entry(wnd).or_insert(Invalidation { dirty: true, callback: None }).callback = Some(Rc::new(callback));
+	});
+}
+
+/// Mark the instance as invalidated on `SC_INVALIDATE_RECT`.
+pub(crate) fn invalidated(wnd: HWINDOW, area: &RECT) {
+	let callback = INVALIDATIONS.with(|states| {
+		let mut states = states.borrow_mut();
+		let state = states.entry(wnd).or_insert(Invalidation { dirty: true, callback: None });
+		state.dirty = true;
+		state.callback.clone()
+	});
+	// it may paint right away
+	if let Some(callback) = callback {
+		callback(area);
+	}
+}
+
+/// Reset the invalidation after the instance is painted.
+fn painted(wnd: HWINDOW) {
+	INVALIDATIONS.with(|states| {
+		states.borrow_mut().entry(wnd).or_insert(Invalidation { dirty: false, callback: None }).dirty = false;
+	});
+}
+
+
 /// Notify Sciter about UI-specific events.
 ///
 /// `wnd` here is not a window handle but rather a window instance (pointer).
 pub fn handle_message(wnd: HWINDOW, event: Message) -> bool
 {
+	let paints = match event {
+		Message::Redraw | Message::Paint(_) | Message::RenderTo(_) => true,
+		#[cfg(windows)]
+		Message::RenderToDxgiSurface(_) => true,
+		_ => false,
+	};
+	let destroys = matches!(event, Message::Destroy);
+
 	let ok = match event {
 		Message::Create { backend, transparent } => {
 			let msg = SCITER_X_MSG_CREATE {
@@ -326,5 +416,55 @@ pub fn handle_message(wnd: HWINDOW, event: Message) -> bool
 
 	};
 
+	if ok != 0 && paints {
+		painted(wnd);
+	}
+	if destroys {
+		INVALIDATIONS.with(|states| states.borrow_mut().remove(&wnd));
+	}
 	ok != 0
 }
+
+
+/// Forward an input event of the embedding renderer (e.g. a game engine) to Sciter.
+///
+/// A shortcut for the corresponding [`Message`](enum.Message.html) variants.
+pub fn handle_input(wnd: HWINDOW, event: InputEvent) -> bool
+{
+	let message = match event {
+		InputEvent::Mouse(params) => Message::Mouse(params),
+		InputEvent::Keyboard(params) => Message::Keyboard(params),
+		InputEvent::Focus { enter } => Message::Focus { enter },
+	};
+	handle_message(wnd, message)
+}
+
+/// Render the whole document onto the `gfx` surface, stretched to the `rc` area.
+///
+/// Meant to be called on every frame of the embedding renderer
+/// after the [`Size`](enum.Message.html#variant.Size) and [`Heartbit`](enum.Message.html#variant.Heartbit) messages.
+pub fn render_frame(wnd: HWINDOW, gfx: &mut Graphics, rc: RECT) -> graphics::Result<()>
+{
+	let frame = Rc::new(RefCell::new(None));
+	let sink = frame.clone();
+	let paint = RenderEvent {
+		layer: None,
+		callback: Box::new(move |area: &RECT, bitmap: &[u8]| {
+			*sink.borrow_mut() = Some((area.width() as u32, area.height() as u32, bitmap.to_vec()));
+		}),
+	};
+
+	if !handle_message(wnd, Message::RenderTo(paint)) {
+		return Err(GRAPHIN_RESULT::FAILURE);
+	}
+
+	let frame = frame.borrow_mut().take();
+	let (width, height, bitmap) = frame.ok_or(GRAPHIN_RESULT::FAILURE)?;
+	let image = Image::with_data((width, height), true, &bitmap)?;
+
+	let dst_pos = (rc.left as f32, rc.top as f32);
+	let dst_size = (rc.width() as f32, rc.height() as f32);
+	let src_size = SIZE { cx: width as INT, cy: height as INT };
+	gfx.draw_image_part(&image, dst_pos, dst_size, POINT { x: 0, y: 0 }, src_size)?;
+	Ok(())
+}
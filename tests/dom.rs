@@ -0,0 +1,75 @@
+// Note: these tests need the Sciter library and a desktop session, so they are ignored by default.
+
+extern crate sciter;
+
+use sciter::dom::{CoordSpace, Element, SCDOM_RESULT};
+
+fn window_with(html: &str) -> sciter::Window {
+  let mut frame = sciter::WindowBuilder::main_window()
+    .with_pos((100, 100))
+    .with_size((400, 300))
+    .create();
+  frame.load_html(html.as_bytes(), Some("example://dom.htm"));
+  frame
+}
+
+#[test]
+#[ignore]
+fn rect_in_places_popup_over_button() {
+  let frame = window_with("<html><body><button#ok>OK</button></body></html>");
+  frame.expand(false);
+
+  let root = Element::from_window(frame.get_hwnd()).unwrap();
+  let button = root.find_first("button#ok").unwrap().expect("the button is missing");
+
+  let own = button.rect_in(CoordSpace::Element).unwrap();
+  let view = button.rect_in(CoordSpace::Window).unwrap();
+  let screen = button.rect_in(CoordSpace::Screen).unwrap();
+
+  assert_eq!((own.left, own.top), (0, 0));
+  assert_eq!(own.right - own.left, view.right - view.left);
+  assert!(view.right > view.left && view.bottom > view.top);
+  assert!(screen.right > screen.left && screen.bottom > screen.top);
+
+  // A native popup right below the button.
+  let popup = sciter::WindowBuilder::popup_window()
+    .with_parent(frame.get_hwnd())
+    .with_pos((screen.left, screen.bottom))
+    .with_size((screen.right - screen.left, 50))
+    .create();
+  assert!(!popup.get_hwnd().is_null());
+  popup.dismiss();
+}
+
+#[test]
+#[ignore]
+fn move_before_after_reorders_items() {
+  let frame = window_with("<html><body><ul><li#a>a</li><li#b>b</li><li#c>c</li></ul></body></html>");
+
+  let root = Element::from_window(frame.get_hwnd()).unwrap();
+  let list = root.find_first("ul").unwrap().unwrap();
+  let mut a = root.find_first("#a").unwrap().unwrap();
+  let mut c = root.find_first("#c").unwrap().unwrap();
+  let b = root.find_first("#b").unwrap().unwrap();
+
+  let order = || list.children().map(|li| li.get_text()).collect::<Vec<_>>().join("");
+
+  c.move_before(&a).unwrap();
+  assert_eq!(order(), "cab");
+
+  a.move_after(&b).unwrap();
+  assert_eq!(order(), "cba");
+
+  // no-op moves
+  a.move_after(&b).unwrap();
+  a.move_before(&a.clone()).unwrap();
+  assert_eq!(order(), "cba");
+
+  c.move_after(&a).unwrap();
+  assert_eq!(order(), "bac");
+
+  // the list can not be moved into itself
+  let mut moved = list.clone();
+  assert_eq!(moved.move_before(&a), Err(SCDOM_RESULT::INVALID_PARAMETER));
+  assert_eq!(order(), "bac");
+}
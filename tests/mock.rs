@@ -0,0 +1,117 @@
+//! Tests of the stub API, run them with `cargo test --features mock-api`.
+
+#![cfg(feature = "mock-api")]
+
+extern crate sciter;
+
+use sciter::mock;
+use sciter::{Value, RuntimeOptions};
+
+#[test]
+fn records_calls() {
+  mock::reset();
+  let v = Value::from(42);
+  assert_eq!(v.to_int(), Some(42));
+  assert!(mock::was_called("ValueIntDataSet"));
+  assert!(mock::was_called("ValueIntData"));
+  assert!(!mock::was_called("ValueFloatDataSet"));
+}
+
+#[test]
+fn canned_results() {
+  mock::reset();
+  assert!(sciter::set_options(RuntimeOptions::DebugMode(true)).is_ok());
+
+  mock::set_result("SciterSetOption", 0);
+  assert!(sciter::set_options(RuntimeOptions::DebugMode(true)).is_err());
+  assert_eq!(mock::calls().iter().filter(|&&name| name == "SciterSetOption").count(), 2);
+
+  mock::set_result("SciterVersion", 0x0405_0102);
+  assert_eq!(sciter::version(), "4.5.1.2");
+}
+
+#[test]
+fn strings_roundtrip() {
+  mock::reset();
+  let v = Value::from("Hello, мир!");
+  assert_eq!(v.as_string(), Some("Hello, мир!".to_owned()));
+  assert_eq!(v.to_string(), "\"Hello, мир!\"");
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn collapse_window_state() {
+  use sciter::types::{HWINDOW, SCITER_WINDOW_CMD, SCITER_WINDOW_STATE};
+
+  mock::reset();
+  let hwnd = 0x1000 as HWINDOW;
+  let frame = sciter::Window::attach(hwnd);
+  let state = || (sciter::SciterAPI().SciterWindowExec)(hwnd, SCITER_WINDOW_CMD::SCITER_WINDOW_GET_STATE.bits(), 0, 0) as u32;
+
+  frame.collapse(true);
+  assert_eq!(state(), SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_HIDDEN.bits());
+
+  frame.collapse(false);
+  assert_eq!(state(), SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_MINIMIZED.bits());
+
+  frame.expand(false);
+  assert_eq!(state(), SCITER_WINDOW_STATE::SCITER_WINDOW_STATE_SHOWN.bits());
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn window_state_follows_commands() {
+  use sciter::types::HWINDOW;
+  use sciter::window::WindowState;
+
+  mock::reset();
+  let frame = sciter::Window::attach(0x2000 as HWINDOW);
+  assert_eq!(frame.get_state(), WindowState::Normal);
+
+  frame.expand(true);
+  assert_eq!(frame.get_state(), WindowState::Maximized);
+
+  frame.collapse(false);
+  assert_eq!(frame.get_state(), WindowState::Minimized);
+
+  frame.collapse(true);
+  assert_eq!(frame.get_state(), WindowState::Hidden);
+}
+
+#[test]
+#[cfg(target_os = "linux")]
+fn pump_events_stops_after_quit() {
+  use sciter::types::HWINDOW;
+
+  mock::reset();
+  let frame = sciter::Window::attach(0x3000 as HWINDOW);
+
+  assert!(frame.pump_events());
+  frame.quit_app();
+  assert!(!frame.pump_events());
+  // the quit is consumed, like `WM_QUIT` is
+  assert!(frame.pump_events());
+}
+
+#[test]
+fn try_create_reports_null_handle() {
+  use sciter::window::{Flags, WindowError};
+  use sciter::types::RECT;
+
+  mock::reset();
+  // the stub returns a null handle as the real one does for a child without a parent
+  let rect = RECT { left: 0, top: 0, right: 100, bottom: 100 };
+  let result = sciter::Window::try_create(rect, Flags::SW_CHILD, None);
+  assert_eq!(result.err(), Some(WindowError::CreateFailed));
+  assert!(mock::was_called("SciterCreateWindow"));
+}
+
+#[test]
+fn runtime_version_without_window() {
+  mock::reset();
+  assert!(sciter::is_api_available());
+
+  mock::set_result("SciterVersion", 0x0405_0102);
+  assert_eq!(sciter::runtime_version(), Some((4, 5)));
+  assert!(!mock::was_called("SciterCreateWindow"));
+}
@@ -0,0 +1,1321 @@
+// Note: these tests need the Sciter library and a desktop session, so they are ignored by default.
+
+extern crate sciter;
+#[cfg(feature = "raw-window-handle")]
+extern crate raw_window_handle;
+
+use sciter::dom::Element;
+
+#[test]
+#[ignore]
+fn transparent_background_first_frame() {
+  let frame = sciter::WindowBuilder::main_window()
+    .with_size((200, 100))
+    .with_transparent_background()
+    .create();
+
+  // The blank document shown before the actual one is loaded.
+  let root = Element::from_window(frame.get_hwnd()).unwrap();
+  let background = root.get_style_attribute("background-color");
+  assert!(background == "transparent" || background.replace(' ', "").ends_with(",0)"), "opaque background: {}", background);
+}
+
+#[test]
+#[ignore]
+fn wait_for_selector_finds_late_element() {
+  use std::time::Duration;
+
+  let mut frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  frame.load_html(b"<html><body><script>setTimeout(() => document.body.innerHTML = '<p#late>here</p>', 200)</script></body></html>", None);
+
+  let nothing = frame.wait_for_selector("#never", Duration::from_millis(100)).unwrap();
+  assert!(nothing.is_none());
+
+  let late = frame.wait_for_selector("#late", Duration::from_secs(5)).unwrap();
+  assert_eq!(late.expect("timeout").get_text(), "here");
+
+  // a class change is not a content mutation
+  frame.load_html(b"<html><body><p#late>here</p><script>setTimeout(() => document.$('#late').classList.add('ready'), 200)</script></body></html>", None);
+  let ready = frame.wait_for_selector(".ready", Duration::from_secs(5)).unwrap();
+  assert_eq!(ready.expect("timeout").get_text(), "here");
+}
+
+#[test]
+#[ignore]
+fn client_to_element_scrolled_translated() {
+  use sciter::types::POINT;
+
+  let mut frame = sciter::WindowBuilder::main_window().with_pos((100, 100)).with_size((400, 300)).create();
+  frame.load_html(br#"<html><body style="margin: 0">
+    <div#list style="position: absolute; left: 10px; top: 20px; width: 100px; height: 100px; overflow: scroll; transform: translate(30px, 40px)">
+      <div style="height: 1000px"/>
+    </div>
+  </body></html>"#, None);
+  frame.expand(false);
+
+  let root = Element::from_window(frame.get_hwnd()).unwrap();
+  let list = root.find_first("#list").unwrap().expect("the list is missing");
+  list.eval_script("this.scrollTo(0, 50, false)").unwrap();
+
+  let rc = list.rect_in(sciter::dom::CoordSpace::Window).unwrap();
+  let (scroll, _, _) = list.scroll_info().unwrap();
+  assert_eq!((scroll.x, scroll.y), (0, 50));
+
+  let local = frame.client_to_element(&list, POINT { x: rc.left + 5, y: rc.top + 5 }).unwrap();
+  assert_eq!((local.x, local.y), (5, 55));
+
+  let client = frame.element_to_client(&list, local).unwrap();
+  assert_eq!((client.x, client.y), (rc.left + 5, rc.top + 5));
+}
+
+#[test]
+#[ignore]
+fn native_error_is_thrown_to_script() {
+  use sciter::Value;
+
+  let mut frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  frame.load_html(b"<html><body></body></html>", None);
+
+  let fail = |_: &[Value]| Value::error("native failure");
+  frame.set_variable("nativeFail", Value::from(fail)).unwrap();
+
+  let root = Element::from_window(frame.get_hwnd()).unwrap();
+  let rv = root.eval_script("(function() { try { nativeFail(); return 'not thrown'; } catch (e) { return 'caught: ' + e; } })()").unwrap();
+  let rv = rv.as_string().unwrap();
+  assert!(rv.starts_with("caught: ") && rv.contains("native failure"), "unexpected result: {}", rv);
+}
+
+#[test]
+#[ignore]
+#[cfg(any(target_os = "linux", target_os = "macos"))]
+fn title_roundtrip() {
+  let mut frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+
+  let title = "Заголовок — 标题 ✓";
+  frame.set_title(title);
+  assert_eq!(frame.get_title(), title);
+}
+
+#[test]
+#[ignore]
+fn emoji_title_roundtrip() {
+  let mut frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+
+  // outside of the BMP, a surrogate pair in UTF-16
+  let title = "\u{1F680} App";
+  frame.set_title(title);
+  assert_eq!(frame.get_title().as_bytes(), title.as_bytes());
+}
+
+#[test]
+#[ignore]
+fn move_and_resize() {
+  use sciter::dom::CoordSpace;
+  use std::time::Duration;
+
+  let frame = sciter::WindowBuilder::main_window().with_pos((100, 100)).with_size((400, 300)).create();
+  frame.expand(false);
+
+  // let the window manager apply the geometry
+  let settle = || { frame.wait_for_selector("#never", Duration::from_millis(200)).unwrap(); };
+  let client = || Element::from_window(frame.get_hwnd()).unwrap().rect_in(CoordSpace::Screen).unwrap();
+  settle();
+  let before = client();
+
+  frame.move_to(150, 120);
+  settle();
+  let moved = client();
+  assert_eq!((moved.left - before.left, moved.top - before.top), (50, 20));
+
+  frame.resize(500, 350);
+  settle();
+  let resized = client();
+  assert_eq!((resized.left, resized.top), (moved.left, moved.top));
+  assert_eq!((resized.right - resized.left) - (moved.right - moved.left), 100);
+  assert_eq!((resized.bottom - resized.top) - (moved.bottom - moved.top), 50);
+}
+
+#[test]
+#[ignore]
+fn get_rect_matches_created() {
+  use std::time::Duration;
+
+  let frame = sciter::WindowBuilder::main_window().with_pos((100, 100)).with_size((400, 300)).create();
+  frame.expand(false);
+  frame.wait_for_selector("#never", Duration::from_millis(200)).unwrap();
+
+  let rc = frame.get_rect();
+
+  // window managers may shift the window by its decorations
+  let near = |a: i32, b: i32| (a - b).abs() <= 50;
+  assert!(near(rc.left, 100) && near(rc.top, 100), "unexpected position: {:?}", rc);
+  assert!(near(rc.right - rc.left, 400) && near(rc.bottom - rc.top, 300), "unexpected size: {:?}", rc);
+
+  frame.set_rect(rc);
+  frame.wait_for_selector("#never", Duration::from_millis(200)).unwrap();
+  assert_eq!(frame.get_rect(), rc);
+}
+
+#[test]
+#[ignore]
+fn fullscreen_and_back() {
+  use std::time::Duration;
+
+  let frame = sciter::WindowBuilder::main_window().with_pos((100, 100)).with_size((400, 300)).create();
+  frame.expand(false);
+  let settle = || { frame.wait_for_selector("#never", Duration::from_millis(500)).unwrap(); };
+  settle();
+  let windowed = frame.get_rect();
+
+  frame.set_fullscreen(true);
+  settle();
+  // the whole monitor, not just its work area as maximized
+  let monitor = frame.current_monitor().expect("no monitor for the window");
+  assert_eq!(frame.get_rect(), monitor.rect);
+  assert_eq!(frame.get_state(), sciter::window::WindowState::Fullscreen);
+
+  frame.set_fullscreen(false);
+  settle();
+  assert_eq!(frame.get_rect(), windowed);
+  assert_eq!(frame.get_state(), sciter::window::WindowState::Normal);
+}
+
+#[test]
+#[ignore]
+fn window_state_roundtrip() {
+  use sciter::window::WindowState;
+  use std::time::Duration;
+
+  let frame = sciter::WindowBuilder::main_window().with_size((400, 300)).create();
+  let settle = || { frame.wait_for_selector("#never", Duration::from_millis(500)).unwrap(); };
+
+  frame.expand(false);
+  settle();
+  assert_eq!(frame.get_state(), WindowState::Normal);
+
+  frame.expand(true);
+  settle();
+  assert_eq!(frame.get_state(), WindowState::Maximized);
+
+  frame.collapse(false);
+  settle();
+  assert_eq!(frame.get_state(), WindowState::Minimized);
+
+  frame.collapse(true);
+  settle();
+  assert_eq!(frame.get_state(), WindowState::Hidden);
+}
+
+#[test]
+#[ignore]
+fn set_icon_smoke() {
+  let mut frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  frame.set_icon(include_bytes!("../examples/icon.png"));
+  frame.set_icon(b"not a png");
+  frame.set_icon(&[]);
+}
+
+#[test]
+#[ignore]
+fn topmost_toggle() {
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  frame.expand(false);
+
+  #[cfg(windows)]
+  let is_topmost = || {
+    #[link(name = "user32")]
+    extern "system" {
+      fn GetWindowLongW(hwnd: sciter::types::HWINDOW, index: i32) -> i32;
+    }
+    const GWL_EXSTYLE: i32 = -20;
+    const WS_EX_TOPMOST: i32 = 0x0008;
+    unsafe { GetWindowLongW(frame.get_hwnd(), GWL_EXSTYLE) & WS_EX_TOPMOST != 0 }
+  };
+
+  frame.set_topmost(true);
+  frame.collapse(false);
+  frame.expand(false);
+  #[cfg(windows)]
+  assert!(is_topmost());
+
+  frame.set_topmost(false);
+  #[cfg(windows)]
+  assert!(!is_topmost());
+}
+
+#[test]
+#[ignore]
+fn opacity_saturates() {
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  frame.expand(false);
+  for &alpha in &[0.0, 0.5, 1.0, -1.0, 2.0] {
+    frame.set_opacity(alpha);
+  }
+}
+
+#[test]
+#[ignore]
+fn request_close_is_cancellable_dismiss_is_not() {
+  use std::time::Duration;
+
+  let mut frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  frame.load_html(br#"<html><body><script type="text/tiscript">
+    var requests = 0;
+    view.on("closerequest", function(evt) { requests += 1; evt.cancel = true; });
+  </script></body></html>"#, None);
+  frame.expand(false);
+  let settle = || { frame.wait_for_selector("#never", Duration::from_millis(300)).unwrap(); };
+
+  // cancellable: the document keeps the window open
+  frame.request_close();
+  settle();
+  let root = Element::from_window(frame.get_hwnd()).expect("the window is closed");
+  assert_eq!(root.eval_script("requests").unwrap().to_int(), Some(1));
+
+  // forced: the window is gone
+  frame.dismiss();
+  settle();
+  assert!(Element::from_window(frame.get_hwnd()).is_err());
+}
+
+#[test]
+#[ignore]
+#[cfg(feature = "raw-window-handle")]
+fn raw_window_handle_matches_hwnd() {
+  use raw_window_handle::{HasRawWindowHandle, RawWindowHandle};
+
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  frame.expand(false);
+
+  match frame.raw_window_handle() {
+    #[cfg(windows)]
+    RawWindowHandle::Win32(handle) => {
+      assert_eq!(handle.hwnd, frame.get_hwnd() as *mut _);
+      assert!(!handle.hinstance.is_null());
+    },
+    #[cfg(target_os = "macos")]
+    RawWindowHandle::AppKit(handle) => {
+      assert_eq!(handle.ns_view, frame.get_hwnd() as *mut _);
+      assert!(!handle.ns_window.is_null());
+    },
+    #[cfg(target_os = "linux")]
+    RawWindowHandle::Xlib(handle) => {
+      assert_ne!(handle.window, 0);
+      assert!(!handle.display.is_null());
+    },
+    #[cfg(target_os = "linux")]
+    RawWindowHandle::Wayland(handle) => {
+      assert!(!handle.surface.is_null());
+      assert!(!handle.display.is_null());
+    },
+    other => panic!("unexpected handle {:?}", other),
+  }
+}
+
+#[test]
+#[ignore]
+#[cfg(windows)]
+fn attach_child_to_foreign_window() {
+  use sciter::types::{HWINDOW, LPCWSTR, LPVOID, RECT, UINT};
+
+  #[link(name = "user32")]
+  extern "system" {
+    fn CreateWindowExW(ex_style: UINT, class: LPCWSTR, title: LPCWSTR, style: UINT,
+      x: i32, y: i32, width: i32, height: i32, parent: HWINDOW, menu: LPVOID, instance: LPVOID, param: LPVOID) -> HWINDOW;
+    fn DestroyWindow(hwnd: HWINDOW) -> i32;
+    fn GetParent(hwnd: HWINDOW) -> HWINDOW;
+    fn IsWindow(hwnd: HWINDOW) -> i32;
+  }
+
+  const WS_OVERLAPPEDWINDOW: UINT = 0x00CF_0000;
+  let class = sciter::utf::s2vec("STATIC");
+  let host = unsafe {
+    CreateWindowExW(0, class.as_ptr(), class.as_ptr(), WS_OVERLAPPEDWINDOW,
+      100, 100, 400, 300, std::ptr::null_mut(), std::ptr::null_mut(), std::ptr::null_mut(), std::ptr::null_mut())
+  };
+  assert!(!host.is_null());
+
+  let child = sciter::Window::attach_child(host, RECT { left: 0, top: 0, right: 200, bottom: 100 });
+  assert_eq!(unsafe { GetParent(child.get_hwnd()) }, host);
+
+  // the host outlives its Sciter child
+  child.dismiss();
+  assert_ne!(unsafe { IsWindow(host) }, 0);
+  unsafe { DestroyWindow(host) };
+}
+
+#[test]
+#[ignore]
+#[cfg(windows)]
+fn dropped_windows_are_destroyed() {
+  use sciter::types::{HWINDOW, LPVOID, UINT};
+
+  #[link(name = "user32")]
+  extern "system" {
+    fn GetGuiResources(process: LPVOID, flags: UINT) -> UINT;
+    fn IsWindow(hwnd: HWINDOW) -> i32;
+  }
+  #[link(name = "kernel32")]
+  extern "system" {
+    fn GetCurrentProcess() -> LPVOID;
+  }
+
+  const GR_USEROBJECTS: UINT = 1;
+  let user_objects = || unsafe { GetGuiResources(GetCurrentProcess(), GR_USEROBJECTS) };
+
+  // the first one loads the engine
+  drop(sciter::Window::create_offscreen((100, 100)));
+  let before = user_objects();
+  for _ in 0..50 {
+    let mut frame = sciter::Window::create_offscreen((100, 100));
+    frame.load_html(b"<html><body>transient</body></html>", Some("example://transient.htm"));
+  }
+  // some slack for the objects Sciter caches
+  assert!(user_objects() <= before + 5, "{} user objects, {} before", user_objects(), before);
+
+  // the borrowed handle stays alive
+  let frame = sciter::Window::create_offscreen((100, 100));
+  drop(sciter::Window::attach(frame.get_hwnd()));
+  assert_ne!(unsafe { IsWindow(frame.get_hwnd()) }, 0);
+
+  // and so does the released one
+  let released = sciter::Window::create_offscreen((100, 100)).into_hwnd();
+  assert_ne!(unsafe { IsWindow(released) }, 0);
+  sciter::Window::attach(released).dismiss();
+}
+
+#[test]
+#[ignore]
+fn pump_events_until_quit() {
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  frame.expand(false);
+
+  assert!(frame.pump_events());
+  frame.quit_app();
+  assert!(!frame.pump_events());
+}
+
+#[test]
+#[ignore]
+fn min_max_size_clamp_resize() {
+  use std::time::Duration;
+
+  let frame = sciter::WindowBuilder::main_window().with_pos((100, 100)).with_size((400, 300)).create();
+  frame.expand(false);
+  let settle = || { frame.wait_for_selector("#never", Duration::from_millis(200)).unwrap(); };
+  let size = || { let rc = frame.get_rect(); (rc.right - rc.left, rc.bottom - rc.top) };
+
+  frame.set_min_size(300, 200);
+  frame.resize(100, 100);
+  settle();
+  assert_eq!(size(), (300, 200));
+
+  // only the height is limited
+  frame.set_max_size(0, 250);
+  frame.resize(600, 400);
+  settle();
+  assert_eq!(size(), (600, 250));
+}
+
+#[test]
+#[ignore]
+fn center_on_work_area() {
+  use std::time::Duration;
+
+  let frame = sciter::WindowBuilder::main_window().with_pos((10, 10)).with_size((400, 300)).create();
+  frame.center();
+  frame.expand(false);
+  frame.wait_for_selector("#never", Duration::from_millis(200)).unwrap();
+
+  let root = Element::from_window(frame.get_hwnd()).unwrap();
+  let work = |side: &str| root.eval_script(&format!("view.screenBox(#workarea, #{})", side)).unwrap().to_int().unwrap();
+  let (cx, cy) = ((work("left") + work("right")) / 2, (work("top") + work("bottom")) / 2);
+
+  let rc = frame.get_rect();
+  // GTK does not count the window decorations
+  let tolerance = if cfg!(target_os = "linux") { 20 } else { 2 };
+  assert!(((rc.left + rc.right) / 2 - cx).abs() <= tolerance);
+  assert!(((rc.top + rc.bottom) / 2 - cy).abs() <= tolerance);
+}
+
+#[test]
+#[ignore]
+fn app_runs_until_last_window() {
+  use sciter::window::Flags;
+  use std::time::{Duration, Instant};
+
+  let app = sciter::App::new();
+  let flags = Flags::SW_TITLEBAR | Flags::SW_CONTROLS;
+  let first = sciter::WindowBuilder::with_flags(flags).with_size((200, 100)).create();
+  let mut second = sciter::WindowBuilder::with_flags(flags).with_size((200, 100)).create();
+  second.load_html(br#"<html><body><script type="text/tiscript">
+    self.timer(300ms, function() { view.close(); });
+  </script></body></html>"#, None);
+  app.register(&first);
+  app.register(&second);
+  first.expand(false);
+  second.expand(false);
+
+  // closing one window keeps the loop running until the other one closes itself
+  let started = Instant::now();
+  first.dismiss();
+  app.run();
+  assert!(started.elapsed() >= Duration::from_millis(250));
+}
+
+#[test]
+#[ignore]
+fn dpi_scale_is_sane() {
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  let scale = frame.get_dpi_scale();
+  assert!(scale.is_finite() && scale >= 1.0, "scale {}", scale);
+}
+
+#[test]
+#[ignore]
+fn on_close_cancels_first_close() {
+  use std::cell::Cell;
+  use std::rc::Rc;
+  use std::time::Duration;
+
+  let mut frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  frame.expand(false);
+
+  let asked = Rc::new(Cell::new(0));
+  let counter = asked.clone();
+  frame.on_close(move || {
+    counter.set(counter.get() + 1);
+    counter.get() > 1
+  });
+  let settle = || { frame.wait_for_selector("#never", Duration::from_millis(300)).unwrap(); };
+
+  frame.request_close();
+  settle();
+  assert_eq!(asked.get(), 1);
+  assert!(Element::from_window(frame.get_hwnd()).is_ok());
+
+  frame.request_close();
+  settle();
+  assert_eq!(asked.get(), 2);
+  assert!(Element::from_window(frame.get_hwnd()).is_err());
+}
+
+#[test]
+#[ignore]
+fn frameless_has_no_titlebar() {
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).frameless().create();
+  frame.expand(false);
+
+  #[cfg(windows)]
+  let has_frame = || {
+    #[link(name = "user32")]
+    extern "system" {
+      fn GetWindowLongW(hwnd: sciter::types::HWINDOW, index: i32) -> i32;
+    }
+    const GWL_STYLE: i32 = -16;
+    const WS_CAPTION: i32 = 0x00C0_0000;
+    const WS_THICKFRAME: i32 = 0x0004_0000;
+    unsafe { GetWindowLongW(frame.get_hwnd(), GWL_STYLE) & (WS_CAPTION | WS_THICKFRAME) != 0 }
+  };
+
+  #[cfg(windows)]
+  assert!(!has_frame());
+
+  frame.set_frameless(false);
+  #[cfg(windows)]
+  assert!(has_frame());
+}
+
+#[test]
+#[ignore]
+fn begin_drag_without_mouse_down() {
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).frameless().create();
+  frame.expand(false);
+  let before = frame.get_rect();
+
+  // no button is pressed, so nothing happens
+  frame.begin_drag();
+  frame.pump_events();
+  let after = frame.get_rect();
+  assert_eq!((before.left, before.top), (after.left, after.top));
+}
+
+#[test]
+#[ignore]
+fn post_quit_from_thread() {
+  use std::time::Duration;
+
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  let quit = frame.quit_handle();
+  let worker = std::thread::spawn(move || {
+    std::thread::sleep(Duration::from_millis(200));
+    quit.post_quit();
+  });
+
+  // returns once the worker quits the app
+  frame.run_app();
+  worker.join().unwrap();
+}
+
+#[test]
+#[ignore]
+fn visibility_flips() {
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  assert!(!frame.is_visible());
+
+  frame.expand(false);
+  assert!(frame.is_visible());
+
+  frame.collapse(true);
+  assert!(!frame.is_visible());
+}
+
+#[test]
+#[ignore]
+fn shown_window_gets_focus() {
+  use std::time::{Duration, Instant};
+
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  assert!(!frame.is_focused());
+
+  frame.expand(false);
+  // the window manager activates it asynchronously
+  let started = Instant::now();
+  while !frame.is_focused() && started.elapsed() < Duration::from_secs(2) {
+    frame.pump_events();
+    std::thread::sleep(Duration::from_millis(20));
+  }
+  assert!(frame.is_focused());
+}
+
+#[test]
+#[ignore]
+fn request_attention_smoke() {
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  frame.expand(false);
+  frame.request_attention(false);
+  frame.request_attention(true);
+}
+
+#[test]
+#[ignore]
+fn skip_taskbar_toggle() {
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  frame.expand(false);
+
+  #[cfg(windows)]
+  let is_tool_window = || {
+    #[link(name = "user32")]
+    extern "system" {
+      fn GetWindowLongW(hwnd: sciter::types::HWINDOW, index: i32) -> i32;
+    }
+    const GWL_EXSTYLE: i32 = -20;
+    const WS_EX_TOOLWINDOW: i32 = 0x0080;
+    unsafe { GetWindowLongW(frame.get_hwnd(), GWL_EXSTYLE) & WS_EX_TOOLWINDOW != 0 }
+  };
+
+  frame.set_skip_taskbar(true);
+  assert!(frame.is_visible());
+  #[cfg(windows)]
+  assert!(is_tool_window());
+
+  frame.set_skip_taskbar(false);
+  #[cfg(windows)]
+  assert!(!is_tool_window());
+}
+
+#[test]
+#[ignore]
+fn snapshot_renders_document() {
+  use sciter::dom::CoordSpace;
+  use sciter::graphics::{Image, SaveImageEncoding};
+  use std::time::Duration;
+
+  let mut frame = sciter::WindowBuilder::main_window().with_size((300, 200)).create();
+  frame.load_html(b"<html><body style='margin:0; background:#f00'></body></html>", None);
+  assert!(frame.snapshot().is_err(), "the window is not shown yet");
+
+  frame.expand(false);
+  frame.wait_for_selector("#never", Duration::from_millis(300)).unwrap();
+
+  let png = frame.snapshot().unwrap();
+  let image = Image::load(&png).unwrap();
+  let root = Element::from_window(frame.get_hwnd()).unwrap();
+  let view = root.rect_in(CoordSpace::Window).unwrap();
+  let (width, height) = image.dimensions().unwrap();
+  assert_eq!((width as i32, height as i32), (view.right - view.left, view.bottom - view.top));
+
+  // the document is rendered, not an empty frame: `[a,b,g,r]` in the middle
+  let raw = image.save(SaveImageEncoding::Raw).unwrap();
+  let middle = ((height / 2 * width + width / 2) * 4) as usize;
+  let pixel = &raw[middle..middle + 4];
+  assert!(pixel[3] > 200 && pixel[1] < 50 && pixel[2] < 50, "pixel {:?}", pixel);
+}
+
+#[test]
+#[ignore]
+fn monitors_have_one_primary() {
+  let monitors = sciter::window::enumerate_monitors();
+  assert!(!monitors.is_empty());
+  assert_eq!(monitors.iter().filter(|m| m.is_primary).count(), 1, "{:?}", monitors);
+
+  for m in &monitors {
+    assert!(m.rect.right > m.rect.left && m.rect.bottom > m.rect.top);
+    assert!(m.work_area.left >= m.rect.left && m.work_area.right <= m.rect.right);
+    assert!(m.scale >= 1.0);
+  }
+}
+
+#[test]
+#[ignore]
+#[cfg(windows)]
+fn file_drop_receives_paths() {
+  use std::cell::RefCell;
+  use std::path::PathBuf;
+  use std::rc::Rc;
+
+  #[link(name = "kernel32")]
+  extern "system" {
+    fn GlobalAlloc(flags: u32, size: usize) -> *mut u8;
+    fn GlobalLock(mem: *mut u8) -> *mut u8;
+    fn GlobalUnlock(mem: *mut u8) -> i32;
+  }
+  #[link(name = "user32")]
+  extern "system" {
+    fn SendMessageW(hwnd: sciter::types::HWINDOW, msg: u32, w: usize, l: isize) -> isize;
+  }
+  const GHND: u32 = 0x0042; // GMEM_MOVEABLE | GMEM_ZEROINIT
+  const WM_DROPFILES: u32 = 0x0233;
+
+  let mut frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  let dropped = Rc::new(RefCell::new(Vec::new()));
+  let sink = dropped.clone();
+  frame.on_file_drop(move |files| sink.borrow_mut().push(files));
+
+  // `DROPFILES` followed by the double NUL terminated list of wide strings
+  let expected = vec![PathBuf::from(r"C:\projects\one.txt"), PathBuf::from(r"C:\projects\two")];
+  let mut list: Vec<u16> = Vec::new();
+  for file in &expected {
+    list.extend(file.to_str().unwrap().encode_utf16());
+    list.push(0);
+  }
+  list.push(0);
+  #[repr(C)]
+  struct DropFiles { files: u32, x: i32, y: i32, nc: i32, wide: i32 }
+  let header = DropFiles { files: std::mem::size_of::<DropFiles>() as u32, x: 0, y: 0, nc: 0, wide: 1 };
+  unsafe {
+    let mem = GlobalAlloc(GHND, std::mem::size_of::<DropFiles>() + list.len() * 2);
+    let ptr = GlobalLock(mem);
+    std::ptr::write(ptr as *mut DropFiles, header);
+    std::ptr::copy_nonoverlapping(list.as_ptr(), ptr.add(std::mem::size_of::<DropFiles>()) as *mut u16, list.len());
+    GlobalUnlock(mem);
+    // the window frees the memory via `DragFinish`
+    SendMessageW(frame.get_hwnd(), WM_DROPFILES, mem as usize, 0);
+  }
+
+  assert_eq!(*dropped.borrow(), vec![expected]);
+}
+
+#[test]
+#[ignore]
+fn create_maximized_without_normal_state() {
+  use sciter::window::WindowState;
+  use std::time::{Duration, Instant};
+
+  let frame = sciter::WindowBuilder::main_window().with_size((300, 200)).with_state(WindowState::Maximized).create();
+  assert_eq!(frame.get_state(), WindowState::Maximized);
+
+  // the state is kept while the window gets mapped and painted
+  let started = Instant::now();
+  while started.elapsed() < Duration::from_millis(500) {
+    frame.pump_events();
+    let state = frame.get_state();
+    assert_eq!(state, WindowState::Maximized, "after {:?}", started.elapsed());
+  }
+}
+
+#[test]
+#[ignore]
+fn focus_restored_after_reactivation() {
+  use sciter::dom::ELEMENT_STATE_BITS;
+  use std::time::Duration;
+
+  let mut frame = sciter::WindowBuilder::main_window().with_size((300, 200)).create();
+  frame.load_html(b"<html><body><input#first /><input#second /></body></html>", None);
+  frame.expand(false);
+  let settle = |frame: &sciter::Window| { frame.wait_for_selector("#never", Duration::from_millis(300)).unwrap(); };
+  settle(&frame);
+
+  let root = Element::from_window(frame.get_hwnd()).unwrap();
+  let mut second = root.find_first("#second").unwrap().unwrap();
+  second.set_state(ELEMENT_STATE_BITS::STATE_FOCUS, None, true).unwrap();
+  settle(&frame);
+
+  // another window takes the activation and gives it back on close
+  let other = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  other.expand(false);
+  settle(&other);
+  other.dismiss();
+  settle(&frame);
+
+  assert_eq!(Element::from_focus(frame.get_hwnd()).ok(), Some(second));
+}
+
+#[test]
+#[ignore]
+fn proxy_runs_closure_on_loop_thread() {
+  use std::sync::mpsc;
+  use std::thread;
+
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  let proxy = frame.proxy();
+  let quit = frame.quit_handle();
+  let (tx, rx) = mpsc::channel();
+
+  let worker = thread::spawn(move || {
+    proxy.post(move || {
+      tx.send(thread::current().id()).unwrap();
+      quit.post_quit();
+    });
+  });
+  worker.join().unwrap();
+
+  frame.run_app();
+  assert_eq!(rx.try_recv(), Ok(thread::current().id()));
+}
+
+#[test]
+#[ignore]
+fn clipboard_text_roundtrip() {
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+
+  let text = "Привет, 世界! 🦀";
+  frame.clipboard_set_text(text);
+  assert_eq!(frame.clipboard_get_text().as_ref().map(String::as_str), Some(text));
+}
+
+#[test]
+#[ignore]
+fn resize_callback_gets_new_size() {
+  use std::cell::Cell;
+  use std::rc::Rc;
+  use std::time::Duration;
+
+  let mut frame = sciter::WindowBuilder::main_window().with_pos((100, 100)).with_size((300, 200)).create();
+  frame.expand(false);
+
+  let size = Rc::new(Cell::new((0, 0)));
+  let sink = size.clone();
+  frame.on_resize(move |width, height| sink.set((width, height)));
+
+  frame.resize(400, 250);
+  // window managers apply it asynchronously
+  frame.wait_for_selector("#never", Duration::from_millis(500)).unwrap();
+  assert_eq!(size.get(), (400, 250));
+}
+
+#[test]
+#[ignore]
+fn runtime_version_is_reported() {
+  assert!(sciter::is_api_available());
+  let (major, _minor) = sciter::runtime_version().expect("the Sciter library is missing");
+  assert!(major >= 4);
+  assert_eq!(major, sciter::version_num() >> 24);
+}
+
+#[test]
+#[ignore]
+fn current_monitor_contains_window() {
+  let frame = sciter::WindowBuilder::main_window()
+    .with_size((200, 100))
+    .create();
+
+  let monitor = frame.current_monitor().expect("no monitor for the window");
+  assert!(monitor.rect.right > monitor.rect.left && monitor.rect.bottom > monitor.rect.top);
+  assert!(monitor.scale >= 1.0);
+  assert!(sciter::window::enumerate_monitors().iter().any(|m| m.rect == monitor.rect));
+}
+
+#[test]
+#[ignore]
+fn refresh_rate_is_plausible() {
+  let mut frame = sciter::WindowBuilder::main_window()
+    .with_size((200, 100))
+    .create();
+  frame.on_monitor_changed(|_monitor, _rate| {});
+
+  // some virtual displays do not report it
+  if let Some(rate) = frame.get_refresh_rate() {
+    assert!(rate >= 24.0 && rate <= 360.0, "refresh rate {}", rate);
+  }
+  frame.dismiss();
+}
+
+#[test]
+#[ignore]
+fn offscreen_window_runs_document() {
+  let mut frame = sciter::Window::create_offscreen((300, 200));
+
+  frame.load_html(b"<html><body><p#text>offscreen</p></body></html>", Some("example://offscreen.htm"));
+  frame.pump_events();
+
+  let root = Element::from_window(frame.get_hwnd()).unwrap();
+  let text = root.find_first("#text").unwrap().expect("the paragraph is missing");
+  assert_eq!(text.get_text(), "offscreen");
+  frame.dismiss();
+}
+
+#[test]
+#[ignore]
+fn native_toplevel_handle_is_set() {
+  let frame = sciter::WindowBuilder::main_window()
+    .with_size((200, 100))
+    .create();
+
+  #[cfg(windows)]
+  assert_eq!(frame.win32_hwnd(), frame.get_hwnd());
+
+  #[cfg(target_os = "linux")]
+  assert!(!frame.gtk_window().is_null());
+
+  #[cfg(target_os = "macos")]
+  {
+    let window = frame.ns_window();
+    assert!(!window.is_null());
+    // the handle is the content view, not the window
+    assert_ne!(window as sciter::types::HWINDOW, frame.get_hwnd());
+  }
+}
+
+#[test]
+#[ignore]
+#[cfg(windows)]
+fn modal_dialog_disables_parent() {
+  use sciter::types::{BOOL, HWINDOW, LPARAM, UINT, WPARAM};
+  use std::sync::atomic::{AtomicBool, Ordering};
+
+  #[link(name = "user32")]
+  extern "system" {
+    fn IsWindowEnabled(hwnd: HWINDOW) -> BOOL;
+    fn PostMessageW(hwnd: HWINDOW, msg: UINT, w: WPARAM, l: LPARAM) -> BOOL;
+  }
+  const WM_CLOSE: UINT = 0x0010;
+  static DISABLED_DURING: AtomicBool = AtomicBool::new(false);
+
+  let parent = sciter::WindowBuilder::main_window()
+    .with_size((400, 300))
+    .create();
+  parent.expand(false);
+  let dialog = sciter::WindowBuilder::popup_window()
+    .with_parent(parent.get_hwnd())
+    .with_size((200, 100))
+    .create();
+
+  let (parent_hwnd, dialog_hwnd) = (parent.get_hwnd() as usize, dialog.get_hwnd() as usize);
+  // runs inside of the modal loop and closes the dialog like its close button does
+  dialog.proxy().post(move || unsafe {
+    DISABLED_DURING.store(IsWindowEnabled(parent_hwnd as HWINDOW) == 0, Ordering::SeqCst);
+    PostMessageW(dialog_hwnd as HWINDOW, WM_CLOSE, 0, 0);
+  });
+  dialog.run_modal(&parent);
+
+  assert!(DISABLED_DURING.load(Ordering::SeqCst));
+  assert_ne!(unsafe { IsWindowEnabled(parent.get_hwnd()) }, 0);
+}
+
+#[test]
+#[ignore]
+fn wait_cursor_is_restored() {
+  use sciter::window::CursorKind;
+
+  let frame = sciter::WindowBuilder::main_window()
+    .with_size((200, 100))
+    .create();
+  frame.expand(false);
+
+  frame.set_cursor(CursorKind::Wait);
+  frame.pump_events();
+  frame.set_cursor(CursorKind::Hidden);
+  frame.pump_events();
+  frame.set_cursor(CursorKind::Default);
+  frame.pump_events();
+  frame.dismiss();
+}
+
+#[test]
+#[ignore]
+fn placement_is_restored() {
+  let frame = sciter::WindowBuilder::main_window()
+    .with_pos((100, 100))
+    .with_size((400, 300))
+    .create();
+  frame.expand(false);
+
+  let placement = frame.save_placement();
+  assert_eq!(placement.size, (400, 300));
+  assert!(!placement.maximized);
+
+  frame.move_to(150, 180);
+  frame.resize(300, 200);
+  frame.apply_placement(&placement);
+  assert_eq!(frame.save_placement(), placement);
+}
+
+#[test]
+#[ignore]
+fn idle_runs_before_quit() {
+  use std::cell::Cell;
+  use std::rc::Rc;
+  use std::sync::mpsc;
+  use std::time::Duration;
+
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  let quit = frame.quit_handle();
+  let (sender, receiver) = mpsc::channel();
+  let worker = std::thread::spawn(move || {
+    sender.send(()).unwrap();
+    std::thread::sleep(Duration::from_millis(200));
+    quit.post_quit();
+  });
+
+  let calls = Rc::new(Cell::new(0));
+  let received = Rc::new(Cell::new(false));
+  {
+    let calls = calls.clone();
+    let received = received.clone();
+    frame.run_app_with(move || {
+      calls.set(calls.get() + 1);
+      if receiver.try_recv().is_ok() {
+        received.set(true);
+      }
+    });
+  }
+  worker.join().unwrap();
+  assert!(calls.get() >= 1);
+  assert!(received.get());
+}
+
+#[test]
+#[ignore]
+#[cfg(windows)]
+fn ctrl_s_accelerator_fires() {
+  use sciter::window::{Accelerator, KEYBOARD_STATES, key_codes};
+  use std::cell::Cell;
+  use std::rc::Rc;
+
+  #[link(name = "user32")]
+  extern "system" {
+    fn keybd_event(vk: u8, scan: u8, flags: u32, extra: usize);
+    fn SetForegroundWindow(hwnd: sciter::types::HWINDOW) -> i32;
+  }
+  const VK_CONTROL: u8 = 0x11;
+  const KEYEVENTF_KEYUP: u32 = 0x0002;
+
+  let mut frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  frame.load_html(b"<html><body><input|text /></body></html>", None);
+  frame.expand(false);
+
+  let fired = Rc::new(Cell::new(0));
+  let counter = fired.clone();
+  frame.register_accelerator(Accelerator::new(KEYBOARD_STATES::CONTROL_KEY_PRESSED, key_codes::KB_S), move || {
+    counter.set(counter.get() + 1);
+    true
+  });
+
+  unsafe {
+    SetForegroundWindow(frame.get_hwnd());
+    keybd_event(VK_CONTROL, 0, 0, 0);
+    keybd_event(b'S', 0, 0, 0);
+    keybd_event(b'S', 0, KEYEVENTF_KEYUP, 0);
+    keybd_event(VK_CONTROL, 0, KEYEVENTF_KEYUP, 0);
+  }
+  for _ in 0..10 {
+    frame.pump_events();
+    std::thread::sleep(std::time::Duration::from_millis(20));
+  }
+  assert_eq!(fired.get(), 1);
+}
+
+#[test]
+#[ignore]
+fn state_transitions_are_explicit() {
+  use sciter::window::WindowState;
+  use std::time::{Duration, Instant};
+
+  // the window managers change the states asynchronously
+  fn wait_for(frame: &sciter::Window, state: WindowState) -> WindowState {
+    let started = Instant::now();
+    while frame.get_state() != state && started.elapsed() < Duration::from_secs(2) {
+      frame.pump_events();
+      std::thread::sleep(Duration::from_millis(20));
+    }
+    frame.get_state()
+  }
+
+  let frame = sciter::WindowBuilder::main_window().with_size((300, 200)).create();
+  assert_eq!(frame.get_state(), WindowState::Hidden);
+
+  frame.show();
+  assert_eq!(wait_for(&frame, WindowState::Normal), WindowState::Normal);
+
+  frame.maximize();
+  assert_eq!(wait_for(&frame, WindowState::Maximized), WindowState::Maximized);
+
+  frame.restore();
+  assert_eq!(wait_for(&frame, WindowState::Normal), WindowState::Normal);
+
+  frame.minimize();
+  assert_eq!(wait_for(&frame, WindowState::Minimized), WindowState::Minimized);
+
+  frame.restore();
+  assert_eq!(wait_for(&frame, WindowState::Normal), WindowState::Normal);
+
+  frame.hide();
+  assert_eq!(wait_for(&frame, WindowState::Hidden), WindowState::Hidden);
+}
+
+#[test]
+#[ignore]
+fn zoom_scales_layout() {
+  use sciter::dom::{CoordSpace, Element};
+
+  // not shown, Sciter lays out the document anyway
+  let mut frame = sciter::WindowBuilder::main_window().with_size((400, 300)).create();
+  frame.load_html(b"<html><body><div#box style='width: 100px; height: 50px'></div></body></html>", Some("example://zoom.htm"));
+  assert_eq!(frame.get_zoom(), 1.0);
+
+  let root = Element::from_window(frame.get_hwnd()).unwrap();
+  let div = root.find_first("#box").unwrap().expect("the box is missing");
+  let width = |div: &Element| {
+    root.update(true).unwrap();
+    let rc = div.rect_in(CoordSpace::Element).unwrap();
+    rc.right - rc.left
+  };
+  let before = width(&div);
+
+  frame.set_zoom(1.5);
+  assert_eq!(frame.get_zoom(), 1.5);
+  let after = width(&div);
+  assert!((after as f32 - before as f32 * 1.5).abs() <= 1.0, "{} is not 1.5 times {}", after, before);
+
+  // clamped to a sane range
+  frame.set_zoom(100.0);
+  assert_eq!(frame.get_zoom(), 5.0);
+}
+
+#[test]
+#[ignore]
+fn min_size_follows_content() {
+  let mut frame = sciter::WindowBuilder::main_window().with_size((800, 600)).create();
+  frame.load_html(b"<html><body style='margin: 0'><div style='min-width: 500px; height: 300px'></div></body></html>", Some("example://min.htm"));
+  frame.expand(false);
+
+  frame.sync_min_size_from_content(false);
+  frame.resize(100, 100);
+  let rect = frame.get_rect();
+  // the window frame goes on top of the content on Windows and macOS
+  assert!(rect.width() >= 500 && rect.width() < 600, "{:?}", rect);
+  assert!(rect.height() >= 300 && rect.height() < 400, "{:?}", rect);
+
+  // tracked, the wider content widens the window
+  frame.sync_min_size_from_content(true);
+  let root = sciter::Element::from_window(frame.get_hwnd()).unwrap();
+  let mut div = root.find_first("div").unwrap().unwrap();
+  div.set_html(b"<p style='width: 700px'>wide</p>", None).unwrap();
+  root.update(true).unwrap();
+  frame.pump_events();
+  assert!(frame.get_rect().width() >= 700);
+}
+
+#[test]
+#[ignore]
+fn run_until_idle_settles_timers() {
+  use std::time::Duration;
+
+  let mut frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  frame.load_html(b"<html><body><p#status>loading</p><script>setTimeout(() => document.$('#status').textContent = 'done', 20)</script></body></html>", None);
+
+  assert!(frame.run_until_idle(Duration::from_secs(5)));
+  let root = Element::from_window(frame.get_hwnd()).unwrap();
+  let status = root.find_first("#status").unwrap().unwrap();
+  assert_eq!(status.get_text(), "done");
+
+  // a timer longer than the default settle period
+  frame.load_html(b"<html><body><p#status>loading</p><script>setTimeout(() => document.$('#status').textContent = 'late', 300)</script></body></html>", None);
+  assert!(frame.run_until_idle_with(Duration::from_millis(500), Duration::from_secs(5)));
+  let root = Element::from_window(frame.get_hwnd()).unwrap();
+  let status = root.find_first("#status").unwrap().unwrap();
+  assert_eq!(status.get_text(), "late");
+}
+
+#[test]
+#[ignore]
+fn hidden_window_shows_on_demand() {
+  use sciter::window::Flags;
+
+  let frame = sciter::Window::create_hidden(Flags::SW_MAIN | Flags::SW_TITLEBAR | Flags::SW_CONTROLS, None);
+  frame.pump_events();
+  assert!(!frame.is_visible());
+
+  frame.show();
+  frame.pump_events();
+  assert!(frame.is_visible());
+}
+
+#[test]
+#[ignore]
+fn activate_restores_and_focuses() {
+  use sciter::window::WindowState;
+  use std::time::Duration;
+
+  let frame = sciter::WindowBuilder::main_window().with_size((300, 200)).create();
+  frame.expand(false);
+  frame.minimize();
+  frame.run_until_idle(Duration::from_secs(2));
+  assert_eq!(frame.get_state(), WindowState::Minimized);
+
+  frame.activate();
+  // the window managers apply it asynchronously
+  frame.run_until_idle(Duration::from_secs(2));
+  assert_eq!(frame.get_state(), WindowState::Normal);
+  assert!(frame.is_focused());
+}
+
+#[test]
+#[ignore]
+fn app_keeps_running_without_windows() {
+  use sciter::window::Flags;
+  use std::time::{Duration, Instant};
+
+  let mut app = sciter::App::new();
+  app.set_quit_on_last_close(false);
+  let flags = Flags::SW_TITLEBAR | Flags::SW_CONTROLS;
+  let mut frame = sciter::WindowBuilder::with_flags(flags).with_size((200, 100)).create();
+  frame.load_html(br#"<html><body><script type="text/tiscript">
+    self.timer(100ms, function() { view.close(); });
+  </script></body></html>"#, None);
+  app.register(&frame);
+  frame.expand(false);
+
+  // an unregistered hidden window stands for the tray icon, which quits the app later
+  let keeper = sciter::WindowBuilder::with_flags(flags).with_size((100, 100)).create();
+  let quit = keeper.quit_handle();
+  std::thread::spawn(move || {
+    std::thread::sleep(Duration::from_millis(600));
+    quit.post_quit();
+  });
+
+  let started = Instant::now();
+  app.run();
+  assert!(started.elapsed() >= Duration::from_millis(550));
+}
+
+#[test]
+#[ignore]
+fn cursor_position_is_inside_when_hovered() {
+  use std::time::Duration;
+
+  let frame = sciter::WindowBuilder::main_window().with_pos((100, 100)).with_size((300, 200)).create();
+  frame.expand(false);
+  frame.run_until_idle(Duration::from_secs(1));
+
+  // move the window under the pointer, wherever it is
+  let rc = frame.get_rect();
+  let pt = frame.cursor_position();
+  frame.move_to(rc.left + pt.x - 150, rc.top + pt.y - 100);
+  frame.run_until_idle(Duration::from_secs(1));
+
+  let rc = frame.get_rect();
+  let pt = frame.cursor_position();
+  if (pt.x, pt.y) == (0, 0) {
+    // no pointer in headless sessions
+    return;
+  }
+  assert!(pt.x >= 0 && pt.x < rc.right - rc.left, "x {}", pt.x);
+  assert!(pt.y >= 0 && pt.y < rc.bottom - rc.top, "y {}", pt.y);
+}
+
+#[test]
+#[ignore]
+#[cfg(windows)]
+fn create_hook_enables_double_clicks() {
+  use sciter::types::{HWINDOW, UINT};
+  use std::cell::Cell;
+  use std::rc::Rc;
+  use std::time::Duration;
+
+  #[link(name = "user32")]
+  extern "system" {
+    #[cfg_attr(target_pointer_width = "32", link_name = "GetClassLongW")]
+    fn GetClassLongPtrW(hwnd: HWINDOW, index: i32) -> isize;
+    fn SetCursorPos(x: i32, y: i32) -> i32;
+    fn mouse_event(flags: UINT, dx: UINT, dy: UINT, data: UINT, extra: usize);
+  }
+
+  const GCL_STYLE: i32 = -26;
+  const CS_DBLCLKS: u32 = 0x0008;
+  const WM_LBUTTONDBLCLK: UINT = 0x0203;
+  const MOUSEEVENTF_LEFTDOWN: UINT = 0x0002;
+  const MOUSEEVENTF_LEFTUP: UINT = 0x0004;
+
+  let mut frame = sciter::WindowBuilder::main_window()
+    .with_pos((100, 100))
+    .with_size((300, 200))
+    .with_create_hook(|params| params.class_style |= CS_DBLCLKS)
+    .create();
+  let style = unsafe { GetClassLongPtrW(frame.get_hwnd(), GCL_STYLE) } as u32;
+  assert_ne!(style & CS_DBLCLKS, 0);
+
+  let double_clicked = Rc::new(Cell::new(false));
+  let seen = double_clicked.clone();
+  frame.subclass(move |_hwnd, msg, _wp, _lp| {
+    if msg == WM_LBUTTONDBLCLK {
+      seen.set(true);
+    }
+    None
+  });
+  frame.expand(false);
+  frame.activate();
+  frame.run_until_idle(Duration::from_secs(1));
+
+  let rc = frame.get_rect();
+  unsafe {
+    SetCursorPos((rc.left + rc.right) / 2, (rc.top + rc.bottom) / 2);
+    for _ in 0..2 {
+      mouse_event(MOUSEEVENTF_LEFTDOWN, 0, 0, 0, 0);
+      mouse_event(MOUSEEVENTF_LEFTUP, 0, 0, 0, 0);
+    }
+  }
+  frame.run_until_idle(Duration::from_secs(1));
+  assert!(double_clicked.get());
+}
+
+#[test]
+#[ignore]
+fn tray_icon_is_added_and_removed() {
+  let frame = sciter::WindowBuilder::main_window().with_size((200, 100)).create();
+  let mut tray = sciter::TrayIcon::add(&frame, &[], "sciter-rs test");
+  tray.on_click(|| {});
+  frame.collapse(true);
+  frame.run_until_idle(std::time::Duration::from_millis(300));
+  tray.remove();
+
+  // dropping removes it as well
+  drop(sciter::TrayIcon::add(&frame, &[], "sciter-rs test"));
+}
+
+#[test]
+#[ignore]
+fn window_is_created_on_last_monitor() {
+  let monitors = sciter::window::enumerate_monitors();
+  let last = monitors.len() - 1;
+  let flags = sciter::window::Flags::SW_MAIN | sciter::window::Flags::SW_TITLEBAR;
+  let frame = sciter::Window::create_on_monitor(last, (300, 200), flags, None);
+
+  let monitor = frame.current_monitor().expect("no monitor for the window");
+  assert_eq!(monitor, monitors[last]);
+
+  // an unknown one is the primary monitor
+  let frame = sciter::Window::create_on_monitor(monitors.len(), (300, 200), flags, None);
+  assert!(frame.current_monitor().expect("no monitor for the window").is_primary);
+}
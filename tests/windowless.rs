@@ -0,0 +1,71 @@
+// Note: these tests need the Sciter.Lite library, so they are ignored by default.
+// Run them with `cargo test --features windowless -- --ignored`.
+
+#![cfg(feature = "windowless")]
+
+extern crate sciter;
+
+use sciter::graphics::{Image, SaveImageEncoding};
+use sciter::types::{GFX_LAYER, HWINDOW, RECT};
+use sciter::windowless::{handle_input, handle_message, render_frame, InputEvent, Message};
+
+#[test]
+#[ignore]
+fn render_frame_into_image() {
+  let engine = Box::new(0u8);
+  let scwnd = &*engine as *const _ as HWINDOW;
+
+  assert!(handle_message(scwnd, Message::Create { backend: GFX_LAYER::SKIA_CPU, transparent: false }));
+  assert!(handle_message(scwnd, Message::Size { width: 100, height: 100 }));
+  sciter::Host::attach(scwnd).load_html(b"<html><body style='background: red'></body></html>", None);
+  assert!(handle_input(scwnd, InputEvent::Focus { enter: true }));
+
+  let mut image = Image::create((100, 100), false).unwrap();
+  let area = RECT { left: 0, top: 0, right: 100, bottom: 100 };
+  assert_eq!(image.paint(|gfx, _| render_frame(scwnd, gfx, area)), Ok(()));
+
+  let png = image.save(SaveImageEncoding::Png).unwrap();
+  assert!(!png.is_empty());
+
+  handle_message(scwnd, Message::Destroy);
+}
+
+#[test]
+#[ignore]
+fn needs_redraw_follows_animations() {
+  use sciter::windowless::needs_redraw;
+
+  fn run_frames(scwnd: HWINDOW, started: u32, frames: u32) -> bool {
+    let mut image = Image::create((100, 100), false).unwrap();
+    let area = RECT { left: 0, top: 0, right: 100, bottom: 100 };
+    for frame in 0..frames {
+      handle_message(scwnd, Message::Heartbit { milliseconds: started + frame * 16 });
+      if needs_redraw(scwnd) {
+        image.paint(|gfx, _| render_frame(scwnd, gfx, area)).unwrap();
+      }
+    }
+    // whether the last heartbit has invalidated it again
+    handle_message(scwnd, Message::Heartbit { milliseconds: started + frames * 16 });
+    needs_redraw(scwnd)
+  }
+
+  let engine = Box::new(0u8);
+  let scwnd = &*engine as *const _ as HWINDOW;
+  assert!(handle_message(scwnd, Message::Create { backend: GFX_LAYER::SKIA_CPU, transparent: false }));
+  assert!(handle_message(scwnd, Message::Size { width: 100, height: 100 }));
+  let host = sciter::Host::attach(scwnd);
+  assert!(needs_redraw(scwnd));
+
+  // a static document settles after the first frames
+  host.load_html(b"<html><body style='background: red'></body></html>", None);
+  assert!(!run_frames(scwnd, 0, 30));
+
+  // an endless animation keeps invalidating it
+  host.load_html(b"<html><head><style>
+    @keyframes spin { from { transform: rotate(0deg); } to { transform: rotate(360deg); } }
+    div { size: 50px; background: blue; animation: spin 1s linear infinite; }
+  </style></head><body><div/></body></html>", None);
+  assert!(run_frames(scwnd, 1000, 30));
+
+  handle_message(scwnd, Message::Destroy);
+}